@@ -7,7 +7,7 @@ use bsp::hal::clock::v2 as clock;
 use dwt_systick_monotonic::DwtSystick;
 use dwt_systick_monotonic::ExtU32 as _;
 // TODO: Any reason this cannot be in a HAL's prelude?
-use hal::ehal::digital::v2::StatefulOutputPin as _;
+use hal::ehal_02::digital::v2::StatefulOutputPin as _;
 use hal::prelude::*;
 use panic_rtt_target as _;
 use rtt_target::{rprintln, rtt_init_print};
@@ -15,8 +15,8 @@ use atsamd_hal::{
         rtcosc::RtcOsc,
         xosc32k::{ControlGainMode, Xosc1k, Xosc32k, Xosc32kBase},
     },
-    ehal::serial::Read as _,
-    ehal::serial::Write,
+    ehal_02::serial::Read as _,
+    ehal_02::serial::Write,
     gpio::{Pins, PA04, PA05},
     prelude::*,
     rtc::{ClockMode, Rtc},
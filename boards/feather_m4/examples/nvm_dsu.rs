@@ -2,7 +2,7 @@
 #![no_main]
 #![allow(clippy::bool_comparison)]
 
-use bsp::ehal;
+use bsp::ehal_02;
 use bsp::hal;
 use feather_m4 as bsp;
 
@@ -12,7 +12,7 @@ use panic_halt as _;
 use panic_semihosting as _;
 
 use bsp::entry;
-use ehal::digital::v2::ToggleableOutputPin;
+use ehal_02::digital::v2::ToggleableOutputPin;
 use hal::clock::GenericClockController;
 use hal::dsu::Dsu;
 use hal::nvm::{retrieve_bank_size, Bank, Nvm, WriteGranularity, BLOCKSIZE};
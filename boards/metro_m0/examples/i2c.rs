@@ -19,7 +19,7 @@ use pac::Peripherals;
 
 use hal::clock::GenericClockController;
 use hal::dmac::{DmaController, PriorityLevel};
-use hal::ehal::blocking::i2c::WriteRead;
+use hal::ehal_02::blocking::i2c::WriteRead;
 use hal::prelude::*;
 use hal::sercom::i2c;
 
@@ -5,6 +5,7 @@ pub use cortex_m_rt::entry;
 
 pub use atsamd_hal as hal;
 pub use hal::ehal;
+pub use hal::ehal_02;
 pub use hal::pac;
 
 use hal::clock::GenericClockController;
@@ -205,7 +206,7 @@ pub type SpiPads = spi::Pads<Sercom4, Miso, Mosi, Sclk>;
 
 /// SPI master for the labelled SPI peripheral
 ///
-/// This type implements [`FullDuplex<u8>`](ehal::spi::FullDuplex).
+/// This type implements [`FullDuplex<u8>`](ehal_02::spi::FullDuplex).
 pub type Spi = spi::Spi<spi::Config<SpiPads>, spi::Duplex>;
 
 /// Convenience for setting up the 2x3 header block for SPI.
@@ -240,7 +241,7 @@ pub type FlashPads = spi::Pads<Sercom5, FlashMiso, FlashMosi, FlashSclk>;
 
 /// SPI master for the labelled SPI peripheral
 ///
-/// This type implements [`FullDuplex<u8>`](ehal::spi::FullDuplex).
+/// This type implements [`FullDuplex<u8>`](ehal_02::spi::FullDuplex).
 pub type FlashSpi = (spi::Spi<spi::Config<FlashPads>, spi::Duplex>, FlashCs);
 
 /// Convenience for accessing the on-board SPI Flash device.
@@ -277,9 +278,9 @@ pub type I2cPads = i2c::Pads<Sercom3, Sda, Scl>;
 
 /// I2C master for the labelled I2C peripheral
 ///
-/// This type implements [`Read`](ehal::blocking::i2c::Read),
-/// [`Write`](ehal::blocking::i2c::Write) and
-/// [`WriteRead`](ehal::blocking::i2c::WriteRead).
+/// This type implements [`Read`](ehal_02::blocking::i2c::Read),
+/// [`Write`](ehal_02::blocking::i2c::Write) and
+/// [`WriteRead`](ehal_02::blocking::i2c::WriteRead).
 pub type I2c = i2c::I2c<i2c::Config<I2cPads>>;
 
 /// Convenience for setting up the labelled SDA, SCL pins to
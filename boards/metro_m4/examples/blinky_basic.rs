@@ -3,7 +3,7 @@
 
 use metro_m4 as bsp;
 
-use bsp::ehal;
+use bsp::ehal_02;
 use bsp::hal;
 use bsp::pac;
 
@@ -17,7 +17,7 @@ use hal::clock::GenericClockController;
 use hal::prelude::*;
 use pac::{CorePeripherals, Peripherals};
 
-use ehal::blocking::delay::DelayMs;
+use ehal_02::blocking::delay::DelayMs;
 use hal::delay::Delay;
 
 #[entry]
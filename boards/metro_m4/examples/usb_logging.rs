@@ -3,13 +3,13 @@
 
 use metro_m4 as bsp;
 
-use bsp::ehal;
+use bsp::ehal_02;
 use bsp::hal;
 use bsp::pac;
 
 use cortex_m::asm::delay as cycle_delay;
 use cortex_m::peripheral::NVIC;
-use ehal::digital::v2::ToggleableOutputPin;
+use ehal_02::digital::v2::ToggleableOutputPin;
 use usb_device::bus::UsbBusAllocator;
 use usb_device::prelude::*;
 use usbd_serial::{SerialPort, USB_CLASS_CDC};
@@ -7,6 +7,7 @@
 
 pub use atsamd_hal as hal;
 pub use hal::ehal;
+pub use hal::ehal_02;
 pub use hal::pac;
 
 #[cfg(feature = "rt")]
@@ -242,7 +243,7 @@ pub type SpiPads = spi::Pads<SpiSercom, IoSet1, Miso, Mosi, Sclk>;
 
 /// SPI master for the labelled SPI peripheral
 ///
-/// This type implements [`FullDuplex<u8>`](ehal::spi::FullDuplex).
+/// This type implements [`FullDuplex<u8>`](ehal_02::spi::FullDuplex).
 pub type Spi = spi::Spi<spi::Config<SpiPads>, spi::Duplex>;
 
 /// Convenience for setting up the 2x3 header block for SPI.
@@ -301,9 +302,9 @@ pub type I2cPads = i2c::Pads<I2cSercom, IoSet6, Sda, Scl>;
 
 /// I2C master for the labelled I2C peripheral
 ///
-/// This type implements [`Read`](ehal::blocking::i2c::Read),
-/// [`Write`](ehal::blocking::i2c::Write) and
-/// [`WriteRead`](ehal::blocking::i2c::WriteRead).
+/// This type implements [`Read`](ehal_02::blocking::i2c::Read),
+/// [`Write`](ehal_02::blocking::i2c::Write) and
+/// [`WriteRead`](ehal_02::blocking::i2c::WriteRead).
 pub type I2c = i2c::I2c<i2c::Config<I2cPads>>;
 
 /// Convenience for setting up the labelled SDA, SCL pins to
@@ -7,6 +7,7 @@ pub use cortex_m_rt::entry;
 
 pub use atsamd_hal as hal;
 pub use hal::ehal;
+pub use hal::ehal_02;
 pub use hal::pac;
 
 use hal::clock::GenericClockController;
@@ -103,9 +104,9 @@ pub type I2cPads = i2c::Pads<Sercom0, Sda, Scl>;
 
 /// I2C master for the labelled I2C peripheral
 ///
-/// This type implements [`Read`](ehal::blocking::i2c::Read),
-/// [`Write`](ehal::blocking::i2c::Write) and
-/// [`WriteRead`](ehal::blocking::i2c::WriteRead).
+/// This type implements [`Read`](ehal_02::blocking::i2c::Read),
+/// [`Write`](ehal_02::blocking::i2c::Write) and
+/// [`WriteRead`](ehal_02::blocking::i2c::WriteRead).
 pub type I2c = i2c::I2c<i2c::Config<I2cPads>>;
 
 /// Convenience for setting up the labelled SDA, SCL pins to
@@ -1,9 +1,9 @@
 use atsamd_hal::{
     clock::GenericClockController,
     delay::Delay,
-    ehal::blocking::delay::DelayMs,
-    ehal::digital::v2::OutputPin,
-    ehal::serial::{Read, Write},
+    ehal_02::blocking::delay::DelayMs,
+    ehal_02::digital::v2::OutputPin,
+    ehal_02::serial::{Read, Write},
     pac::{interrupt, MCLK, SERCOM0},
     prelude::*,
     sercom::{uart, IoSet2, Sercom0},
@@ -0,0 +1,741 @@
+//! # Analog Comparator
+//!
+//! The Analog Comparator (AC) contains a pair of comparators, which compare
+//! the voltage of a positive input to the voltage of a negative input. The
+//! comparator outputs can be read from software, routed to I/O pins, generate
+//! interrupts and events, and can be combined in window mode to detect whether
+//! a voltage is inside or outside of a range.
+//!
+//! # Initializing
+//!
+//! Create an [`Ac`] with [`Ac::new`], which enables the AC APB clock and resets
+//! the peripheral. The AC generic clock, used by the comparators for
+//! single-shot measurements, filtering and synchronous outputs, must be
+//! configured separately.
+//!
+//! # Comparator configuration
+//!
+//! Each comparator is configured from a [`Config`] and identified at the type
+//! level by a [`CompId`]. Input and output pins are checked at compile time by
+//! [`Config::pos_pin`], [`Config::neg_pin`] and [`Config::output_pin`].
+//!
+//! ```no_run
+//! use atsamd_hal::ac::{Ac, Comp0, Config, Flags, Interrupt, NegInput};
+//! use atsamd_hal::gpio::{Pins, B};
+//! # let mut peripherals = atsamd_hal::pac::Peripherals::take().unwrap();
+//! # #[cfg(feature = "thumbv6")]
+//! # let mut apb = peripherals.PM;
+//! # #[cfg(feature = "thumbv7")]
+//! # let mut apb = peripherals.MCLK;
+//! let pins = Pins::new(peripherals.PORT);
+//! let sense = pins.pa04.into_alternate::<B>();
+//!
+//! let mut ac = Ac::new(peripherals.AC, &mut apb);
+//! // Trip when the current sense voltage rises above 3/4 of VDD
+//! ac.set_scaler::<Comp0>(47);
+//! let config = Config::<Comp0>::new()
+//!     .pos_pin(&sense)
+//!     .neg_input(NegInput::Vscale)
+//!     .interrupt(Interrupt::Rising);
+//! ac.configure(config);
+//! ac.enable_interrupts(Flags::COMP0);
+//! ac.enable();
+//! ```
+//!
+//! # Window mode
+//!
+//! In window mode, the positive inputs of both comparators are tied together.
+//! The negative input of comparator 0 is the upper limit and that of
+//! comparator 1 is the lower limit. Enable it with [`Ac::enable_window`] and
+//! read the result with [`Ac::window_state`].
+
+use core::marker::PhantomData;
+
+use bitflags::bitflags;
+
+use crate::gpio::*;
+use crate::pac::ac::compctrl::{FLEN_A, INTSEL_A, MUXNEG_A, MUXPOS_A, OUT_A};
+use crate::pac::ac::winctrl::WINTSEL0_A;
+use crate::pac::AC;
+use crate::typelevel::Sealed;
+
+#[cfg(feature = "thumbv6")]
+use crate::pac::ac::compctrl::SPEED_A;
+#[cfg(feature = "thumbv6")]
+use crate::pac::PM as APB_CLK_CTRL;
+
+#[cfg(feature = "thumbv7")]
+use crate::pac::ac::compctrl::HYST_A;
+#[cfg(feature = "thumbv7")]
+use crate::pac::MCLK as APB_CLK_CTRL;
+
+//==============================================================================
+// Ids
+//==============================================================================
+
+/// Type-level comparator number
+pub trait CompId: Sealed {
+    const NUM: usize;
+}
+
+/// Type-level comparator 0
+pub enum Comp0 {}
+impl Sealed for Comp0 {}
+impl CompId for Comp0 {
+    const NUM: usize = 0;
+}
+
+/// Type-level comparator 1
+pub enum Comp1 {}
+impl Sealed for Comp1 {}
+impl CompId for Comp1 {
+    const NUM: usize = 1;
+}
+
+//==============================================================================
+// Pins
+//==============================================================================
+
+/// [`PinId`]s that can be used as a comparator input
+///
+/// Input pins must be in [`AlternateB`] mode.
+pub trait InputPin: PinId {
+    /// Index of the `AIN` input
+    const INDEX: u8;
+}
+
+/// [`PinId`]s that can be used as a comparator output
+pub trait OutputPin: PinId {
+    /// Comparator driving this pin
+    type Comp: CompId;
+    /// Alternate function of the comparator output
+    type Mode: PinMode;
+}
+
+macro_rules! input_pins {
+    ($( $( #[$cfg:meta] )? $PinId:ident = $index:literal ),+ $(,)?) => {
+        $(
+            $( #[$cfg] )?
+            impl InputPin for $PinId {
+                const INDEX: u8 = $index;
+            }
+        )+
+    };
+}
+
+input_pins!(
+    PA04 = 0,
+    PA05 = 1,
+    #[cfg(feature = "pins-24")]
+    PA06 = 2,
+    #[cfg(feature = "pins-24")]
+    PA07 = 3,
+);
+
+#[cfg(any(feature = "samd21", feature = "thumbv7"))]
+macro_rules! output_pins {
+    ($( $( #[$cfg:meta] )? $PinId:ident = $Comp:ident ),+ $(,)?) => {
+        $(
+            $( #[$cfg] )?
+            impl OutputPin for $PinId {
+                type Comp = $Comp;
+                #[cfg(feature = "samd21")]
+                type Mode = AlternateH;
+                #[cfg(feature = "thumbv7")]
+                type Mode = AlternateM;
+            }
+        )+
+    };
+}
+
+#[cfg(any(feature = "samd21", feature = "thumbv7"))]
+output_pins!(
+    #[cfg(feature = "pins-48")]
+    PA12 = Comp0,
+    #[cfg(feature = "pins-48")]
+    PA13 = Comp1,
+    #[cfg(feature = "pins-32")]
+    PA18 = Comp0,
+    #[cfg(feature = "pins-32")]
+    PA19 = Comp1,
+);
+
+//==============================================================================
+// Configuration
+//==============================================================================
+
+/// Positive input of a comparator
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PosInput {
+    /// `AIN` pin `n`, in the range `0..4`
+    ///
+    /// Prefer [`Config::pos_pin`], which checks the pin at compile time.
+    Pin(u8),
+    /// VDD scaler of the comparator
+    #[cfg(feature = "thumbv7")]
+    Vscale,
+}
+
+/// Negative input of a comparator
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum NegInput {
+    /// `AIN` pin `n`, in the range `0..4`
+    ///
+    /// Prefer [`Config::neg_pin`], which checks the pin at compile time.
+    Pin(u8),
+    /// Ground
+    Gnd,
+    /// VDD scaler of the comparator, see [`Ac::set_scaler`]
+    Vscale,
+    /// Internal bandgap reference
+    Bandgap,
+    /// DAC output
+    Dac,
+}
+
+/// Comparator measurement mode
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Mode {
+    /// The comparator output is continuously updated
+    Continuous,
+    /// A comparison is made when started with [`Ac::start`] or with an event
+    SingleShot,
+}
+
+/// Condition setting the comparator interrupt flag
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Interrupt {
+    /// Any change of the comparator output
+    Toggle,
+    /// Rising edge of the comparator output
+    Rising,
+    /// Falling edge of the comparator output
+    Falling,
+    /// End of a single-shot comparison
+    EndOfComparison,
+}
+
+/// Digital filter of the comparator output
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Filter {
+    /// No filtering
+    Off,
+    /// 3-bit majority filter
+    Majority3,
+    /// 5-bit majority filter
+    Majority5,
+}
+
+/// Routing of the comparator output to its I/O pin
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Output {
+    /// The output is not routed to the pin
+    Off,
+    /// Asynchronous comparator output
+    Async,
+    /// Filtered output, synchronized to the AC generic clock
+    Sync,
+}
+
+/// Comparator hysteresis
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Hysteresis {
+    /// No hysteresis
+    Disabled,
+    /// Hysteresis enabled
+    #[cfg(feature = "thumbv6")]
+    Enabled,
+    /// 50 mV hysteresis
+    #[cfg(feature = "thumbv7")]
+    Mv50,
+    /// 100 mV hysteresis
+    #[cfg(feature = "thumbv7")]
+    Mv100,
+    /// 150 mV hysteresis
+    #[cfg(feature = "thumbv7")]
+    Mv150,
+}
+
+/// Comparator speed
+#[cfg(feature = "thumbv6")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Speed {
+    /// Low speed, low power
+    Low,
+    /// High speed
+    High,
+}
+
+/// Condition setting the window interrupt flag
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum WindowInterrupt {
+    /// The signal goes above the window
+    Above,
+    /// The signal goes inside the window
+    Inside,
+    /// The signal goes below the window
+    Below,
+    /// The signal goes outside the window
+    Outside,
+}
+
+/// Position of the signal relative to the window
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum WindowState {
+    /// The signal is above the window
+    Above,
+    /// The signal is inside the window
+    Inside,
+    /// The signal is below the window
+    Below,
+}
+
+/// Configuration of a comparator
+///
+/// A new `Config` compares `AIN0` to `AIN1` continuously, with no filter,
+/// hysteresis or output, and sets the interrupt flag on any output change.
+/// Apply it with [`Ac::configure`].
+pub struct Config<Id: CompId> {
+    pos: PosInput,
+    neg: NegInput,
+    swap: bool,
+    mode: Mode,
+    interrupt: Interrupt,
+    filter: Filter,
+    output: Output,
+    hysteresis: Hysteresis,
+    #[cfg(feature = "thumbv6")]
+    speed: Speed,
+    #[cfg(feature = "thumbv7")]
+    run_standby: bool,
+    id: PhantomData<Id>,
+}
+
+impl<Id: CompId> Default for Config<Id> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Id: CompId> Config<Id> {
+    /// Create a new comparator configuration
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            pos: PosInput::Pin(0),
+            neg: NegInput::Pin(1),
+            swap: false,
+            mode: Mode::Continuous,
+            interrupt: Interrupt::Toggle,
+            filter: Filter::Off,
+            output: Output::Off,
+            hysteresis: Hysteresis::Disabled,
+            #[cfg(feature = "thumbv6")]
+            speed: Speed::High,
+            #[cfg(feature = "thumbv7")]
+            run_standby: false,
+            id: PhantomData,
+        }
+    }
+
+    /// Select the positive input
+    #[inline]
+    pub fn pos_input(mut self, input: PosInput) -> Self {
+        self.pos = input;
+        self
+    }
+
+    /// Use an I/O pin as the positive input
+    #[inline]
+    pub fn pos_pin<I: InputPin>(self, _pin: &Pin<I, AlternateB>) -> Self {
+        self.pos_input(PosInput::Pin(I::INDEX))
+    }
+
+    /// Select the negative input
+    #[inline]
+    pub fn neg_input(mut self, input: NegInput) -> Self {
+        self.neg = input;
+        self
+    }
+
+    /// Use an I/O pin as the negative input
+    #[inline]
+    pub fn neg_pin<I: InputPin>(self, _pin: &Pin<I, AlternateB>) -> Self {
+        self.neg_input(NegInput::Pin(I::INDEX))
+    }
+
+    /// Swap the positive and negative inputs, and invert the output
+    ///
+    /// Swapping the inputs can be used to cancel the comparator offset.
+    #[inline]
+    pub fn swap(mut self, swap: bool) -> Self {
+        self.swap = swap;
+        self
+    }
+
+    /// Select continuous or single-shot measurements
+    #[inline]
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Select the condition setting the interrupt flag and generating an event
+    #[inline]
+    pub fn interrupt(mut self, interrupt: Interrupt) -> Self {
+        self.interrupt = interrupt;
+        self
+    }
+
+    /// Select the digital filter
+    #[inline]
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Select the hysteresis
+    #[inline]
+    pub fn hysteresis(mut self, hysteresis: Hysteresis) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// Select the comparator speed
+    #[cfg(feature = "thumbv6")]
+    #[inline]
+    pub fn speed(mut self, speed: Speed) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Keep the comparator running in standby sleep mode
+    #[cfg(feature = "thumbv7")]
+    #[inline]
+    pub fn run_standby(mut self, run_standby: bool) -> Self {
+        self.run_standby = run_standby;
+        self
+    }
+
+    /// Route the comparator output to an I/O pin
+    #[cfg(any(feature = "samd21", feature = "thumbv7"))]
+    #[inline]
+    pub fn output_pin<I>(mut self, _pin: &Pin<I, I::Mode>, output: Output) -> Self
+    where
+        I: OutputPin<Comp = Id>,
+    {
+        self.output = output;
+        self
+    }
+}
+
+//==============================================================================
+// Flags
+//==============================================================================
+
+bitflags! {
+    /// Interrupt and event output flags
+    ///
+    /// The binary format of the underlying bits exactly matches the INTFLAG
+    /// bits, as well as the event output bits of the EVCTRL register.
+    pub struct Flags: u8 {
+        const COMP0 = 0x01;
+        const COMP1 = 0x02;
+        const WIN0 = 0x10;
+    }
+}
+
+//==============================================================================
+// Ac
+//==============================================================================
+
+/// Analog Comparator peripheral
+pub struct Ac {
+    ac: AC,
+}
+
+impl Ac {
+    /// Enable the AC APB clock and reset the peripheral
+    #[inline]
+    pub fn new(ac: AC, apb: &mut APB_CLK_CTRL) -> Self {
+        apb.apbcmask.modify(|_, w| w.ac_().set_bit());
+        let ac = Self { ac };
+        ac.ac.ctrla.write(|w| w.swrst().set_bit());
+        ac.sync();
+        ac
+    }
+
+    #[inline]
+    fn sync(&self) {
+        #[cfg(feature = "thumbv6")]
+        while self.ac.statusb.read().syncbusy().bit_is_set() {}
+        #[cfg(feature = "thumbv7")]
+        while self.ac.syncbusy.read().bits() != 0 {}
+    }
+
+    /// Enable the AC
+    ///
+    /// The comparators start comparing once both the AC and the comparators
+    /// are enabled.
+    #[inline]
+    pub fn enable(&mut self) {
+        self.ac.ctrla.modify(|_, w| w.enable().set_bit());
+        self.sync();
+    }
+
+    /// Disable the AC
+    #[inline]
+    pub fn disable(&mut self) {
+        self.ac.ctrla.modify(|_, w| w.enable().clear_bit());
+        self.sync();
+    }
+
+    /// Keep the AC running in standby sleep mode
+    #[cfg(feature = "thumbv6")]
+    #[inline]
+    pub fn run_standby(&mut self, run_standby: bool) {
+        self.ac.ctrla.modify(|_, w| w.runstdby().bit(run_standby));
+        self.sync();
+    }
+
+    /// Apply a [`Config`] and enable the comparator
+    ///
+    /// The comparator is disabled while it is configured.
+    #[inline]
+    pub fn configure<Id: CompId>(&mut self, config: Config<Id>) {
+        let compctrl = &self.ac.compctrl[Id::NUM];
+        compctrl.modify(|_, w| w.enable().clear_bit());
+        self.sync();
+        compctrl.write(|w| {
+            match config.pos {
+                PosInput::Pin(0) => w.muxpos().variant(MUXPOS_A::PIN0),
+                PosInput::Pin(1) => w.muxpos().variant(MUXPOS_A::PIN1),
+                PosInput::Pin(2) => w.muxpos().variant(MUXPOS_A::PIN2),
+                PosInput::Pin(3) => w.muxpos().variant(MUXPOS_A::PIN3),
+                PosInput::Pin(_) => panic!("Invalid AC input pin"),
+                #[cfg(feature = "thumbv7")]
+                PosInput::Vscale => w.muxpos().variant(MUXPOS_A::VSCALE),
+            };
+            w.muxneg().variant(match config.neg {
+                NegInput::Pin(0) => MUXNEG_A::PIN0,
+                NegInput::Pin(1) => MUXNEG_A::PIN1,
+                NegInput::Pin(2) => MUXNEG_A::PIN2,
+                NegInput::Pin(3) => MUXNEG_A::PIN3,
+                NegInput::Pin(_) => panic!("Invalid AC input pin"),
+                NegInput::Gnd => MUXNEG_A::GND,
+                NegInput::Vscale => MUXNEG_A::VSCALE,
+                NegInput::Bandgap => MUXNEG_A::BANDGAP,
+                NegInput::Dac => MUXNEG_A::DAC,
+            });
+            w.swap().bit(config.swap);
+            w.single().bit(config.mode == Mode::SingleShot);
+            w.intsel().variant(match config.interrupt {
+                Interrupt::Toggle => INTSEL_A::TOGGLE,
+                Interrupt::Rising => INTSEL_A::RISING,
+                Interrupt::Falling => INTSEL_A::FALLING,
+                Interrupt::EndOfComparison => INTSEL_A::EOC,
+            });
+            w.flen().variant(match config.filter {
+                Filter::Off => FLEN_A::OFF,
+                Filter::Majority3 => FLEN_A::MAJ3,
+                Filter::Majority5 => FLEN_A::MAJ5,
+            });
+            w.out().variant(match config.output {
+                Output::Off => OUT_A::OFF,
+                Output::Async => OUT_A::ASYNC,
+                Output::Sync => OUT_A::SYNC,
+            });
+            #[cfg(feature = "thumbv6")]
+            {
+                w.hyst().bit(config.hysteresis == Hysteresis::Enabled);
+                w.speed().variant(match config.speed {
+                    Speed::Low => SPEED_A::LOW,
+                    Speed::High => SPEED_A::HIGH,
+                });
+            }
+            #[cfg(feature = "thumbv7")]
+            {
+                match config.hysteresis {
+                    Hysteresis::Disabled => w.hysten().clear_bit(),
+                    Hysteresis::Mv50 => w.hysten().set_bit().hyst().variant(HYST_A::HYST50),
+                    Hysteresis::Mv100 => w.hysten().set_bit().hyst().variant(HYST_A::HYST100),
+                    Hysteresis::Mv150 => w.hysten().set_bit().hyst().variant(HYST_A::HYST150),
+                };
+                w.speed().high();
+                w.runstdby().bit(config.run_standby);
+            }
+            w
+        });
+        self.sync();
+        compctrl.modify(|_, w| w.enable().set_bit());
+        self.sync();
+    }
+
+    /// Disable a comparator
+    #[inline]
+    pub fn disable_comparator<Id: CompId>(&mut self) {
+        self.ac.compctrl[Id::NUM].modify(|_, w| w.enable().clear_bit());
+        self.sync();
+    }
+
+    /// Set the VDD scaler of a comparator
+    ///
+    /// The scaler output is `VDD * (value + 1) / 64`, for `value` in the range
+    /// `0..64`. The scaler is enabled when selected as a comparator input.
+    #[inline]
+    pub fn set_scaler<Id: CompId>(&mut self, value: u8) {
+        assert!(value < 64, "Invalid AC scaler value");
+        // Safety: The value is checked above
+        self.ac.scaler[Id::NUM].write(|w| unsafe { w.value().bits(value) });
+    }
+
+    /// Start a single-shot comparison
+    #[inline]
+    pub fn start<Id: CompId>(&mut self) {
+        // Safety: Writing a one to the START bit of a comparator starts it, and
+        // writing zeros has no effect
+        self.ac.ctrlb.write(|w| unsafe { w.bits(1 << Id::NUM) });
+    }
+
+    /// Return `true` if the comparator output is ready
+    ///
+    /// The output is not valid until the comparator has started up.
+    #[inline]
+    pub fn is_ready<Id: CompId>(&self) -> bool {
+        self.ac.statusb.read().bits() & (1 << Id::NUM) != 0
+    }
+
+    /// Return the current comparator output, `true` when the positive input is
+    /// above the negative input
+    #[inline]
+    pub fn state<Id: CompId>(&self) -> bool {
+        self.ac.statusa.read().bits() & (1 << Id::NUM) != 0
+    }
+
+    /// Enable window mode
+    ///
+    /// `interrupt` selects the condition setting [`Flags::WIN0`]. Both
+    /// comparators should be configured with the same positive input.
+    #[inline]
+    pub fn enable_window(&mut self, interrupt: WindowInterrupt) {
+        self.while_disabled(|ac| {
+            ac.winctrl.write(|w| {
+                w.wintsel0().variant(match interrupt {
+                    WindowInterrupt::Above => WINTSEL0_A::ABOVE,
+                    WindowInterrupt::Inside => WINTSEL0_A::INSIDE,
+                    WindowInterrupt::Below => WINTSEL0_A::BELOW,
+                    WindowInterrupt::Outside => WINTSEL0_A::OUTSIDE,
+                });
+                w.wen0().set_bit()
+            });
+        });
+    }
+
+    /// Disable window mode
+    #[inline]
+    pub fn disable_window(&mut self) {
+        self.while_disabled(|ac| ac.winctrl.write(|w| w.wen0().clear_bit()));
+    }
+
+    /// Return the position of the signal relative to the window
+    #[inline]
+    pub fn window_state(&self) -> WindowState {
+        match self.ac.statusa.read().wstate0().bits() {
+            0 => WindowState::Above,
+            1 => WindowState::Inside,
+            _ => WindowState::Below,
+        }
+    }
+
+    /// Enable interrupts for the specified flags
+    #[inline]
+    pub fn enable_interrupts(&mut self, flags: Flags) {
+        // Safety: `Flags` bits match the INTENSET bits
+        self.ac.intenset.write(|w| unsafe { w.bits(flags.bits()) });
+    }
+
+    /// Disable interrupts for the specified flags
+    #[inline]
+    pub fn disable_interrupts(&mut self, flags: Flags) {
+        // Safety: `Flags` bits match the INTENCLR bits
+        self.ac.intenclr.write(|w| unsafe { w.bits(flags.bits()) });
+    }
+
+    /// Read the interrupt flags
+    #[inline]
+    pub fn read_flags(&self) -> Flags {
+        Flags::from_bits_truncate(self.ac.intflag.read().bits())
+    }
+
+    /// Clear the specified interrupt flags
+    #[inline]
+    pub fn clear_flags(&mut self, flags: Flags) {
+        // Safety: `Flags` bits match the INTFLAG bits
+        self.ac.intflag.write(|w| unsafe { w.bits(flags.bits()) });
+    }
+
+    /// Enable event outputs for the specified flags, and disable the others
+    ///
+    /// Comparator events are generated on the condition selected by
+    /// [`Config::interrupt`], window events on the condition selected by
+    /// [`Ac::enable_window`].
+    #[inline]
+    pub fn set_event_outputs(&mut self, flags: Flags) {
+        self.while_disabled(|ac| {
+            ac.evctrl.modify(|r, w| {
+                let bits = (r.bits() & !0xFF) | u16::from(flags.bits());
+                // Safety: `Flags` bits match the event output bits of EVCTRL
+                unsafe { w.bits(bits) }
+            });
+        });
+    }
+
+    /// Start a single-shot comparison on the incoming event
+    #[inline]
+    pub fn set_event_input<Id: CompId>(&mut self, enable: bool) {
+        self.while_disabled(|ac| {
+            ac.evctrl.modify(|r, w| {
+                let bit = 1 << (8 + Id::NUM);
+                let bits = if enable {
+                    r.bits() | bit
+                } else {
+                    r.bits() & !bit
+                };
+                // Safety: Only the COMPEIn bit is modified
+                unsafe { w.bits(bits) }
+            });
+        });
+    }
+
+    /// Reset the AC and return the register block
+    #[inline]
+    pub fn free(self) -> AC {
+        self.ac.ctrla.write(|w| w.swrst().set_bit());
+        self.sync();
+        self.ac
+    }
+
+    /// The `EVCTRL` and, on thumbv7em targets, `WINCTRL` registers are
+    /// enable-protected, so disable the AC while they are written, then restore
+    /// its state
+    #[inline]
+    fn while_disabled(&mut self, f: impl FnOnce(&AC)) {
+        let enabled = self.ac.ctrla.read().enable().bit_is_set();
+        self.disable();
+        f(&self.ac);
+        self.sync();
+        if enabled {
+            self.enable();
+        }
+    }
+}
@@ -0,0 +1,883 @@
+//! # Input capture
+//!
+//! The TC and TCC compare/capture channels can record the value of the
+//! counter when an event is received from the [`evsys`](crate::evsys) module.
+//! Usually the event is generated by an [`eic`](crate::eic) pin, so the capture
+//! measures an external signal, such as a tachometer output, without polling
+//! the pin.
+//!
+//! # Modes
+//!
+//! Each instance is configured in one of the [`Mode`]s:
+//!
+//! * [`Mode::Timestamp`] captures the counter into `CC0` on each event. The
+//!   timestamps can be read one by one, or streamed to memory with DMA.
+//! * [`Mode::Ppw`] and [`Mode::Pwp`] restart the counter on each active edge of
+//!   the event, and capture both the period and the pulse width of the signal.
+//!   They only differ in the `CC` register used for each value. The result is
+//!   a [`Measurement`], which provides the period, pulse width, frequency and
+//!   duty cycle of the signal.
+//!
+//! # Initializing
+//!
+//! Create a [`TcCapture`] or a [`TccCapture`] from its PAC struct, the
+//! frequency of its generic clock and a [`Config`]. The generic clock must be
+//! configured separately. Then route the event generator to the capture
+//! event input of the instance:
+//!
+//! | Instance | [`Mode::Timestamp`] | [`Mode::Ppw`] and [`Mode::Pwp`] |
+//! |----------|---------------------|---------------------------------|
+//! | `TCn`    | `users::Tcn`        | `users::Tcn`                    |
+//! | `TCCn`   | `users::TccnMc0`    | `users::TccnEv1`                |
+//!
+//! ```no_run
+//! use atsamd_hal::capture::{Config, Mode, TcCapture};
+//! use atsamd_hal::evsys::{generators::EicExtInt3, users::Tc3, EventSystem};
+//! use atsamd_hal::fugit::RateExtU32;
+//! # let mut peripherals = atsamd_hal::pac::Peripherals::take().unwrap();
+//! # #[cfg(feature = "thumbv6")]
+//! # let mut apb = peripherals.PM;
+//! # #[cfg(feature = "thumbv7")]
+//! # let mut apb = peripherals.MCLK;
+//! // The EIC pin must have its event output enabled with `enable_event`
+//! let (_evsys, channels) = EventSystem::new(peripherals.EVSYS, &mut apb);
+//! let mut ch0 = channels.0.asynchronous::<EicExtInt3>();
+//! ch0.connect::<Tc3>();
+//!
+//! let config = Config {
+//!     mode: Mode::Ppw,
+//!     ..Default::default()
+//! };
+//! let mut capture = TcCapture::new(peripherals.TC3, &mut apb, 48_000_000.Hz(), config);
+//! capture.enable();
+//! let measurement = nb::block!(capture.read_measurement()).unwrap();
+//! let rpm = measurement.frequency().to_Hz() * 60;
+//! ```
+
+use core::ops::Deref;
+
+use crate::pac::tcc0;
+use crate::time::{Hertz, Nanoseconds};
+use crate::typelevel::Sealed;
+
+#[cfg(feature = "thumbv7")]
+use crate::pac::tc0 as tc;
+#[cfg(feature = "samd11")]
+use crate::pac::tc1 as tc;
+#[cfg(feature = "samd21")]
+use crate::pac::tc3 as tc;
+
+#[cfg(feature = "thumbv7")]
+use crate::pac::MCLK as APB_CLK_CTRL;
+#[cfg(feature = "thumbv6")]
+use crate::pac::PM as APB_CLK_CTRL;
+
+#[cfg(feature = "has-tc0")]
+use crate::pac::TC0;
+#[cfg(feature = "has-tc1")]
+use crate::pac::TC1;
+#[cfg(feature = "has-tc2")]
+use crate::pac::TC2;
+#[cfg(feature = "has-tc3")]
+use crate::pac::TC3;
+#[cfg(feature = "has-tc4")]
+use crate::pac::TC4;
+#[cfg(feature = "has-tc5")]
+use crate::pac::TC5;
+#[cfg(feature = "has-tc6")]
+use crate::pac::TC6;
+#[cfg(feature = "has-tc7")]
+use crate::pac::TC7;
+#[cfg(feature = "has-tcc0")]
+use crate::pac::TCC0;
+#[cfg(feature = "has-tcc1")]
+use crate::pac::TCC1;
+#[cfg(feature = "has-tcc2")]
+use crate::pac::TCC2;
+#[cfg(feature = "has-tcc3")]
+use crate::pac::TCC3;
+
+#[cfg(feature = "dma")]
+use crate::dmac::{
+    self,
+    channel::{AnyChannel, Busy, Channel, InterruptFlags, Ready},
+    Buffer, BufferPair, CallbackStatus, Transfer, TriggerAction, TriggerSource,
+};
+
+//==============================================================================
+// Instances
+//==============================================================================
+
+/// TC instance usable for input capture
+pub trait CaptureTc: Deref<Target = tc::RegisterBlock> + Sealed {
+    /// DMA trigger of the `CC0` capture
+    #[cfg(feature = "dma")]
+    const DMA_TRIGGER: TriggerSource;
+
+    #[doc(hidden)]
+    fn enable_apb(apb: &mut APB_CLK_CTRL);
+}
+
+/// TCC instance usable for input capture
+pub trait CaptureTcc: Deref<Target = tcc0::RegisterBlock> + Sealed {
+    /// DMA trigger of the `CC0` capture
+    #[cfg(feature = "dma")]
+    const DMA_TRIGGER: TriggerSource;
+
+    #[doc(hidden)]
+    fn enable_apb(apb: &mut APB_CLK_CTRL);
+}
+
+macro_rules! instances {
+    ($Trait:ident: $( $( #[$attr:meta] )* $Inst:ident: ($apbmask:ident, $apbbit:ident, $trigger:ident), )+) => {
+        $(
+            $( #[$attr] )*
+            impl Sealed for $Inst {}
+
+            $( #[$attr] )*
+            impl $Trait for $Inst {
+                #[cfg(feature = "dma")]
+                const DMA_TRIGGER: TriggerSource = TriggerSource::$trigger;
+
+                #[inline]
+                fn enable_apb(apb: &mut APB_CLK_CTRL) {
+                    apb.$apbmask.modify(|_, w| w.$apbbit().set_bit());
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(feature = "thumbv6")]
+instances!(CaptureTc:
+    #[cfg(feature = "has-tc1")]
+    TC1: (apbcmask, tc1_, TC1_MC0),
+    #[cfg(feature = "has-tc2")]
+    TC2: (apbcmask, tc2_, TC2_MC0),
+    #[cfg(feature = "has-tc3")]
+    TC3: (apbcmask, tc3_, TC3_MC0),
+    #[cfg(feature = "has-tc4")]
+    TC4: (apbcmask, tc4_, TC4_MC0),
+    #[cfg(feature = "has-tc5")]
+    TC5: (apbcmask, tc5_, TC5_MC0),
+    #[cfg(feature = "has-tc6")]
+    TC6: (apbcmask, tc6_, TC6_MC0),
+    #[cfg(feature = "has-tc7")]
+    TC7: (apbcmask, tc7_, TC7_MC0),
+);
+
+#[cfg(feature = "thumbv6")]
+instances!(CaptureTcc:
+    #[cfg(feature = "has-tcc0")]
+    TCC0: (apbcmask, tcc0_, TCC0_MC0),
+    #[cfg(feature = "has-tcc1")]
+    TCC1: (apbcmask, tcc1_, TCC1_MC0),
+    #[cfg(feature = "has-tcc2")]
+    TCC2: (apbcmask, tcc2_, TCC2_MC0),
+);
+
+#[cfg(feature = "thumbv7")]
+instances!(CaptureTc:
+    #[cfg(feature = "has-tc0")]
+    TC0: (apbamask, tc0_, TC0_MC_0),
+    #[cfg(feature = "has-tc1")]
+    TC1: (apbamask, tc1_, TC1_MC_0),
+    #[cfg(feature = "has-tc2")]
+    TC2: (apbbmask, tc2_, TC2_MC_0),
+    #[cfg(feature = "has-tc3")]
+    TC3: (apbbmask, tc3_, TC3_MC_0),
+    #[cfg(feature = "has-tc4")]
+    TC4: (apbcmask, tc4_, TC4_MC_0),
+    #[cfg(feature = "has-tc5")]
+    TC5: (apbcmask, tc5_, TC5_MC_0),
+    #[cfg(feature = "has-tc6")]
+    TC6: (apbdmask, tc6_, TC6_MC_0),
+    #[cfg(feature = "has-tc7")]
+    TC7: (apbdmask, tc7_, TC7_MC_0),
+);
+
+#[cfg(feature = "thumbv7")]
+instances!(CaptureTcc:
+    #[cfg(feature = "has-tcc0")]
+    TCC0: (apbbmask, tcc0_, TCC0_MC_0),
+    #[cfg(feature = "has-tcc1")]
+    TCC1: (apbbmask, tcc1_, TCC1_MC_0),
+    #[cfg(feature = "has-tcc2")]
+    TCC2: (apbcmask, tcc2_, TCC2_MC_0),
+    #[cfg(feature = "has-tcc3")]
+    TCC3: (apbcmask, tcc3_, TCC3_MC_0),
+);
+
+//==============================================================================
+// Configuration
+//==============================================================================
+
+/// Division of the generic clock to obtain the counter clock
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Prescaler {
+    #[default]
+    Div1,
+    Div2,
+    Div4,
+    Div8,
+    Div16,
+    Div64,
+    Div256,
+    Div1024,
+}
+
+impl Prescaler {
+    /// Division factor
+    #[inline]
+    pub fn divider(self) -> u32 {
+        match self {
+            Prescaler::Div1 => 1,
+            Prescaler::Div2 => 2,
+            Prescaler::Div4 => 4,
+            Prescaler::Div8 => 8,
+            Prescaler::Div16 => 16,
+            Prescaler::Div64 => 64,
+            Prescaler::Div256 => 256,
+            Prescaler::Div1024 => 1024,
+        }
+    }
+}
+
+/// Write the `PRESCALER` field of a TC or TCC `CTRLA` register
+macro_rules! prescaler {
+    ($w:ident, $prescaler:expr) => {
+        match $prescaler {
+            Prescaler::Div1 => $w.prescaler().div1(),
+            Prescaler::Div2 => $w.prescaler().div2(),
+            Prescaler::Div4 => $w.prescaler().div4(),
+            Prescaler::Div8 => $w.prescaler().div8(),
+            Prescaler::Div16 => $w.prescaler().div16(),
+            Prescaler::Div64 => $w.prescaler().div64(),
+            Prescaler::Div256 => $w.prescaler().div256(),
+            Prescaler::Div1024 => $w.prescaler().div1024(),
+        }
+    };
+}
+
+/// Capture mode
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Mode {
+    /// Capture the counter into `CC0` on each event
+    #[default]
+    Timestamp,
+    /// Period captured into `CC0`, pulse width into `CC1`
+    Ppw,
+    /// Pulse width captured into `CC0`, period into `CC1`
+    Pwp,
+}
+
+impl Mode {
+    /// Indices of the period and pulse width `CC` registers
+    #[inline]
+    fn channels(self) -> (usize, usize) {
+        match self {
+            Mode::Timestamp | Mode::Ppw => (0, 1),
+            Mode::Pwp => (1, 0),
+        }
+    }
+}
+
+/// Input capture configuration
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Config {
+    /// Division of the generic clock to obtain the counter clock
+    pub prescaler: Prescaler,
+    /// Capture mode
+    pub mode: Mode,
+    /// Invert the event input
+    ///
+    /// In [`Mode::Ppw`] and [`Mode::Pwp`], the period starts on the rising
+    /// edge of the event, and the pulse width is the time it stays high.
+    /// Inverting the event measures the low pulse width instead.
+    pub invert: bool,
+}
+
+/// Input capture error
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// The counter wrapped around before the capture, so the period is too
+    /// long to be measured with the current prescaler
+    Overflow,
+    /// A capture occurred before the previous one was read
+    Missed,
+}
+
+//==============================================================================
+// Measurement
+//==============================================================================
+
+/// Period and pulse width of a signal, in counter ticks
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Measurement {
+    period: u32,
+    pulse_width: u32,
+    freq: Hertz,
+}
+
+impl Measurement {
+    /// Create a measurement from captured values and the counter frequency
+    #[inline]
+    pub fn new(period: u32, pulse_width: u32, freq: Hertz) -> Self {
+        Self {
+            period,
+            pulse_width,
+            freq,
+        }
+    }
+
+    /// Period, in counter ticks
+    #[inline]
+    pub fn period_ticks(&self) -> u32 {
+        self.period
+    }
+
+    /// Pulse width, in counter ticks
+    #[inline]
+    pub fn pulse_width_ticks(&self) -> u32 {
+        self.pulse_width
+    }
+
+    /// Period of the signal
+    ///
+    /// Saturates at `u32::MAX` nanoseconds.
+    #[inline]
+    pub fn period(&self) -> Nanoseconds {
+        self.ticks_to_ns(self.period)
+    }
+
+    /// Pulse width of the signal
+    ///
+    /// Saturates at `u32::MAX` nanoseconds.
+    #[inline]
+    pub fn pulse_width(&self) -> Nanoseconds {
+        self.ticks_to_ns(self.pulse_width)
+    }
+
+    /// Frequency of the signal, or zero if the period is zero
+    #[inline]
+    pub fn frequency(&self) -> Hertz {
+        match self.period {
+            0 => Hertz::from_raw(0),
+            period => Hertz::from_raw(self.freq.to_Hz() / period),
+        }
+    }
+
+    /// Ratio of the pulse width to the period, between 0 and 1
+    #[inline]
+    pub fn duty_cycle(&self) -> f32 {
+        match self.period {
+            0 => 0.0,
+            period => self.pulse_width as f32 / period as f32,
+        }
+    }
+
+    #[inline]
+    fn ticks_to_ns(&self, ticks: u32) -> Nanoseconds {
+        let ns = ticks as u64 * 1_000_000_000 / self.freq.to_Hz() as u64;
+        Nanoseconds::from_ticks(ns.min(u32::MAX as u64) as u32)
+    }
+}
+
+//==============================================================================
+// TcCapture
+//==============================================================================
+
+/// Input capture on a TC instance, in 16-bit mode
+pub struct TcCapture<T: CaptureTc> {
+    tc: T,
+    freq: Hertz,
+    mode: Mode,
+}
+
+impl<T: CaptureTc> TcCapture<T> {
+    /// Enable the APB clock, reset and configure the TC
+    ///
+    /// `clock_freq` is the frequency of the generic clock of the TC. The TC is
+    /// left disabled.
+    #[inline]
+    pub fn new(tc: T, apb: &mut APB_CLK_CTRL, clock_freq: Hertz, config: Config) -> Self {
+        T::enable_apb(apb);
+        let capture = Self {
+            tc,
+            freq: clock_freq / config.prescaler.divider(),
+            mode: config.mode,
+        };
+        let count = capture.tc.count16();
+        let measure = config.mode != Mode::Timestamp;
+
+        count.ctrla.write(|w| w.swrst().set_bit());
+        // SWRST is bit 0, which is not readable through the PAC on all targets
+        while count.ctrla.read().bits() & 1 != 0 {}
+
+        #[cfg(feature = "thumbv6")]
+        {
+            count.ctrla.write(|w| {
+                w.mode().count16();
+                prescaler!(w, config.prescaler)
+            });
+            count
+                .ctrlc
+                .write(|w| w.cpten0().set_bit().cpten1().bit(measure));
+        }
+        #[cfg(feature = "thumbv7")]
+        count.ctrla.write(|w| {
+            w.mode().count16();
+            w.capten0().set_bit();
+            w.capten1().bit(measure);
+            prescaler!(w, config.prescaler)
+        });
+        capture.sync();
+
+        count.evctrl.write(|w| {
+            match config.mode {
+                #[cfg(feature = "thumbv6")]
+                Mode::Timestamp => w.evact().off(),
+                #[cfg(feature = "thumbv7")]
+                Mode::Timestamp => w.evact().stamp(),
+                Mode::Ppw => w.evact().ppw(),
+                Mode::Pwp => w.evact().pwp(),
+            };
+            w.tcinv().bit(config.invert);
+            w.tcei().set_bit()
+        });
+        capture
+    }
+
+    #[inline]
+    fn sync(&self) {
+        #[cfg(feature = "thumbv6")]
+        while self.tc.count16().status.read().syncbusy().bit_is_set() {}
+        #[cfg(feature = "thumbv7")]
+        while self.tc.count16().syncbusy.read().bits() != 0 {}
+    }
+
+    /// Frequency of the counter
+    #[inline]
+    pub fn tick_freq(&self) -> Hertz {
+        self.freq
+    }
+
+    /// Start capturing
+    #[inline]
+    pub fn enable(&mut self) {
+        self.tc.count16().ctrla.modify(|_, w| w.enable().set_bit());
+        self.sync();
+    }
+
+    /// Stop capturing
+    #[inline]
+    pub fn disable(&mut self) {
+        self.tc
+            .count16()
+            .ctrla
+            .modify(|_, w| w.enable().clear_bit());
+        self.sync();
+    }
+
+    /// Check and clear the error flags
+    #[inline]
+    fn check_errors(&mut self) -> Result<(), Error> {
+        let count = self.tc.count16();
+        let flags = count.intflag.read();
+        if flags.err().bit_is_set() {
+            count.intflag.write(|w| w.err().set_bit());
+            Err(Error::Missed)
+        } else if flags.ovf().bit_is_set() {
+            count.intflag.write(|w| w.ovf().set_bit());
+            Err(Error::Overflow)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Read the next timestamp, in [`Mode::Timestamp`]
+    ///
+    /// Returns [`Error::Overflow`] once each time the counter wraps around,
+    /// which can be used to extend the timestamps in software.
+    #[inline]
+    pub fn read_timestamp(&mut self) -> nb::Result<u16, Error> {
+        self.check_errors()?;
+        let count = self.tc.count16();
+        if count.intflag.read().mc0().bit_is_set() {
+            // Reading CC0 clears the MC0 flag
+            Ok(count.cc[0].read().cc().bits())
+        } else {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+
+    /// Read the next period and pulse width, in [`Mode::Ppw`] or [`Mode::Pwp`]
+    ///
+    /// The measurement is available once the period is captured, at the end
+    /// of each period of the signal.
+    #[inline]
+    pub fn read_measurement(&mut self) -> nb::Result<Measurement, Error> {
+        self.check_errors()?;
+        let count = self.tc.count16();
+        let (period, pulse_width) = self.mode.channels();
+        if count.intflag.read().bits() & (0x10 << period) != 0 {
+            let pulse_width = count.cc[pulse_width].read().cc().bits();
+            let period = count.cc[period].read().cc().bits();
+            Ok(Measurement::new(
+                period.into(),
+                pulse_width.into(),
+                self.freq,
+            ))
+        } else {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+
+    /// Enable the interrupt fired when a timestamp or a measurement is ready
+    #[inline]
+    pub fn enable_interrupt(&mut self) {
+        let (period, _) = self.mode.channels();
+        // Safety: MC0 and MC1 are bits 4 and 5, and writing zeros has no effect
+        self.tc
+            .count16()
+            .intenset
+            .write(|w| unsafe { w.bits(0x10 << period) });
+    }
+
+    /// Disable the interrupt fired when a timestamp or a measurement is ready
+    #[inline]
+    pub fn disable_interrupt(&mut self) {
+        let (period, _) = self.mode.channels();
+        // Safety: MC0 and MC1 are bits 4 and 5, and writing zeros has no effect
+        self.tc
+            .count16()
+            .intenclr
+            .write(|w| unsafe { w.bits(0x10 << period) });
+    }
+
+    /// Reset the TC and return it
+    #[inline]
+    pub fn free(self) -> T {
+        let count = self.tc.count16();
+        count.ctrla.write(|w| w.swrst().set_bit());
+        while count.ctrla.read().bits() & 1 != 0 {}
+        self.tc
+    }
+}
+
+//==============================================================================
+// TccCapture
+//==============================================================================
+
+/// Input capture on a TCC instance
+pub struct TccCapture<T: CaptureTcc> {
+    tcc: T,
+    freq: Hertz,
+    mode: Mode,
+}
+
+impl<T: CaptureTcc> TccCapture<T> {
+    /// Enable the APB clock, reset and configure the TCC
+    ///
+    /// `clock_freq` is the frequency of the generic clock of the TCC. The TCC
+    /// is left disabled.
+    #[inline]
+    pub fn new(tcc: T, apb: &mut APB_CLK_CTRL, clock_freq: Hertz, config: Config) -> Self {
+        T::enable_apb(apb);
+        let capture = Self {
+            tcc,
+            freq: clock_freq / config.prescaler.divider(),
+            mode: config.mode,
+        };
+        let tcc = &capture.tcc;
+        let measure = config.mode != Mode::Timestamp;
+
+        tcc.ctrla.write(|w| w.swrst().set_bit());
+        while tcc.syncbusy.read().swrst().bit_is_set() {}
+
+        tcc.ctrla.write(|w| {
+            w.cpten0().set_bit();
+            w.cpten1().bit(measure);
+            prescaler!(w, config.prescaler)
+        });
+
+        tcc.evctrl.write(|w| {
+            match config.mode {
+                Mode::Timestamp => {
+                    w.mcei0().set_bit();
+                }
+                Mode::Ppw => {
+                    w.evact1().ppw();
+                    w.tcinv1().bit(config.invert);
+                    w.tcei1().set_bit();
+                }
+                Mode::Pwp => {
+                    w.evact1().pwp();
+                    w.tcinv1().bit(config.invert);
+                    w.tcei1().set_bit();
+                }
+            }
+            w
+        });
+        capture
+    }
+
+    /// Frequency of the counter
+    #[inline]
+    pub fn tick_freq(&self) -> Hertz {
+        self.freq
+    }
+
+    /// Start capturing
+    #[inline]
+    pub fn enable(&mut self) {
+        self.tcc.ctrla.modify(|_, w| w.enable().set_bit());
+        while self.tcc.syncbusy.read().enable().bit_is_set() {}
+    }
+
+    /// Stop capturing
+    #[inline]
+    pub fn disable(&mut self) {
+        self.tcc.ctrla.modify(|_, w| w.enable().clear_bit());
+        while self.tcc.syncbusy.read().enable().bit_is_set() {}
+    }
+
+    /// Check and clear the error flags
+    #[inline]
+    fn check_errors(&mut self) -> Result<(), Error> {
+        let flags = self.tcc.intflag.read();
+        if flags.err().bit_is_set() {
+            self.tcc.intflag.write(|w| w.err().set_bit());
+            Err(Error::Missed)
+        } else if flags.ovf().bit_is_set() {
+            self.tcc.intflag.write(|w| w.ovf().set_bit());
+            Err(Error::Overflow)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Read the next timestamp, in [`Mode::Timestamp`]
+    ///
+    /// Returns [`Error::Overflow`] once each time the counter wraps around,
+    /// which can be used to extend the timestamps in software.
+    #[inline]
+    pub fn read_timestamp(&mut self) -> nb::Result<u32, Error> {
+        self.check_errors()?;
+        if self.tcc.intflag.read().mc0().bit_is_set() {
+            // Reading CC0 clears the MC0 flag
+            Ok(self.tcc.cc()[0].read().cc().bits())
+        } else {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+
+    /// Read the next period and pulse width, in [`Mode::Ppw`] or [`Mode::Pwp`]
+    ///
+    /// The measurement is available once the period is captured, at the end
+    /// of each period of the signal.
+    #[inline]
+    pub fn read_measurement(&mut self) -> nb::Result<Measurement, Error> {
+        self.check_errors()?;
+        let (period, pulse_width) = self.mode.channels();
+        if self.tcc.intflag.read().bits() & (1 << (16 + period)) != 0 {
+            let cc = self.tcc.cc();
+            let pulse_width = cc[pulse_width].read().cc().bits();
+            let period = cc[period].read().cc().bits();
+            Ok(Measurement::new(period, pulse_width, self.freq))
+        } else {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+
+    /// Enable the interrupt fired when a timestamp or a measurement is ready
+    #[inline]
+    pub fn enable_interrupt(&mut self) {
+        let (period, _) = self.mode.channels();
+        // Safety: MCx are bits 16 and up, and writing zeros has no effect
+        self.tcc
+            .intenset
+            .write(|w| unsafe { w.bits(1 << (16 + period)) });
+    }
+
+    /// Disable the interrupt fired when a timestamp or a measurement is ready
+    #[inline]
+    pub fn disable_interrupt(&mut self) {
+        let (period, _) = self.mode.channels();
+        // Safety: MCx are bits 16 and up, and writing zeros has no effect
+        self.tcc
+            .intenclr
+            .write(|w| unsafe { w.bits(1 << (16 + period)) });
+    }
+
+    /// Reset the TCC and return it
+    #[inline]
+    pub fn free(self) -> T {
+        self.tcc.ctrla.write(|w| w.swrst().set_bit());
+        while self.tcc.syncbusy.read().swrst().bit_is_set() {}
+        self.tcc
+    }
+}
+
+//==============================================================================
+// DMA
+//==============================================================================
+
+/// [`TcCapture`] used as the source of a DMA transfer
+///
+/// Created by [`TcCapture::receive_with_dma`]. It points to the `CC0`
+/// register. Call [`free`](DmaTcCapture::free) to get the [`TcCapture`] back
+/// once the transfer is complete.
+#[cfg(feature = "dma")]
+pub struct DmaTcCapture<T: CaptureTc> {
+    capture: TcCapture<T>,
+}
+
+#[cfg(feature = "dma")]
+impl<T: CaptureTc> DmaTcCapture<T> {
+    /// Return the [`TcCapture`]
+    #[inline]
+    pub fn free(self) -> TcCapture<T> {
+        self.capture
+    }
+}
+
+#[cfg(feature = "dma")]
+unsafe impl<T: CaptureTc> Buffer for DmaTcCapture<T> {
+    type Beat = u16;
+
+    #[inline]
+    fn dma_ptr(&mut self) -> *mut Self::Beat {
+        self.capture.tc.count16().cc[0].as_ptr() as *mut _
+    }
+
+    #[inline]
+    fn incrementing(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn buffer_len(&self) -> usize {
+        1
+    }
+}
+
+/// [`TccCapture`] used as the source of a DMA transfer
+///
+/// Created by [`TccCapture::receive_with_dma`]. It points to the `CC0`
+/// register. Call [`free`](DmaTccCapture::free) to get the [`TccCapture`] back
+/// once the transfer is complete.
+#[cfg(feature = "dma")]
+pub struct DmaTccCapture<T: CaptureTcc> {
+    capture: TccCapture<T>,
+}
+
+#[cfg(feature = "dma")]
+impl<T: CaptureTcc> DmaTccCapture<T> {
+    /// Return the [`TccCapture`]
+    #[inline]
+    pub fn free(self) -> TccCapture<T> {
+        self.capture
+    }
+}
+
+#[cfg(feature = "dma")]
+unsafe impl<T: CaptureTcc> Buffer for DmaTccCapture<T> {
+    type Beat = u32;
+
+    #[inline]
+    fn dma_ptr(&mut self) -> *mut Self::Beat {
+        self.capture.tcc.cc()[0].as_ptr() as *mut _
+    }
+
+    #[inline]
+    fn incrementing(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn buffer_len(&self) -> usize {
+        1
+    }
+}
+
+/// Implement `receive_with_dma` for a capture type
+macro_rules! receive_with_dma {
+    ($Capture:ident<$Trait:ident>, $Dma:ident, $Beat:ty) => {
+        #[cfg(feature = "dma")]
+        impl<T: $Trait> $Capture<T> {
+            /// Transform the capture into a DMA [`Transfer`] and start copying
+            /// the `CC0` captures to `buf`
+            ///
+            /// The transfer is triggered by each `CC0` capture, so in
+            /// [`Mode::Timestamp`] it records a timestamp per event. If
+            /// `circular` is `true`, the buffer is overwritten until the
+            /// transfer is stopped.
+            #[inline]
+            pub fn receive_with_dma<Ch, B, W>(
+                self,
+                buf: B,
+                mut channel: Ch,
+                circular: bool,
+                waker: W,
+            ) -> Transfer<Channel<Ch::Id, Busy>, BufferPair<$Dma<T>, B>, W>
+            where
+                Ch: AnyChannel<Status = Ready>,
+                B: Buffer<Beat = $Beat> + 'static,
+                W: FnOnce(CallbackStatus) + 'static,
+            {
+                channel
+                    .as_mut()
+                    .enable_interrupts(InterruptFlags::new().with_tcmpl(true));
+
+                let source = $Dma { capture: self };
+
+                #[cfg(feature = "thumbv7")]
+                let trigger_action = TriggerAction::BURST;
+
+                #[cfg(feature = "thumbv6")]
+                let trigger_action = TriggerAction::BEAT;
+
+                // SAFETY: This is safe because the of the `'static` bound check
+                // for `B`, and the fact that the buffer length of the source is
+                // always 1.
+                let xfer = unsafe { dmac::Transfer::new_unchecked(channel, source, buf, circular) };
+                xfer.with_waker(waker).begin(T::DMA_TRIGGER, trigger_action)
+            }
+        }
+    };
+}
+
+receive_with_dma!(TcCapture<CaptureTc>, DmaTcCapture, u16);
+receive_with_dma!(TccCapture<CaptureTcc>, DmaTccCapture, u32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measurement_conversions() {
+        // 1 kHz signal at 25% duty cycle, measured with a 1 MHz counter
+        let measurement = Measurement::new(1000, 250, Hertz::from_raw(1_000_000));
+        assert_eq!(measurement.period(), Nanoseconds::from_ticks(1_000_000));
+        assert_eq!(measurement.pulse_width(), Nanoseconds::from_ticks(250_000));
+        assert_eq!(measurement.frequency(), Hertz::from_raw(1000));
+        assert_eq!(measurement.duty_cycle(), 0.25);
+    }
+
+    #[test]
+    fn measurement_saturates() {
+        let measurement = Measurement::new(0xff_ffff, 0, Hertz::from_raw(1000));
+        assert_eq!(measurement.period(), Nanoseconds::from_ticks(u32::MAX));
+        assert_eq!(measurement.duty_cycle(), 0.0);
+
+        let measurement = Measurement::new(0, 0, Hertz::from_raw(1000));
+        assert_eq!(measurement.frequency(), Hertz::from_raw(0));
+        assert_eq!(measurement.duty_cycle(), 0.0);
+    }
+}
@@ -0,0 +1,640 @@
+//! # Digital-to-Analog Converter
+//!
+//! The DAC converts digital values to an analog voltage on its `VOUT` pins.
+#![cfg_attr(
+    feature = "thumbv6",
+    doc = "SAMD11/SAMD21 targets have a single 10-bit channel."
+)]
+#![cfg_attr(
+    feature = "thumbv7",
+    doc = "SAMD5x/E5x targets have two 12-bit channels, which can be used
+independently or as a differential pair."
+)]
+//!
+//! # Initializing
+//!
+//! Create a [`Dac`] with [`Dac::new`], which enables the DAC APB clock, resets
+//! the peripheral and selects the voltage reference. The DAC generic clock must
+//! be configured separately. Then enable the output of each channel with
+//! [`Dac::enable_output`], which checks the `VOUT` pin at compile time, and
+//! enable the DAC with [`Dac::enable`].
+//!
+//! ```no_run
+//! use atsamd_hal::dac::{Ch0, Dac, Reference};
+//! use atsamd_hal::gpio::{Pins, B};
+//! # let mut peripherals = atsamd_hal::pac::Peripherals::take().unwrap();
+//! # #[cfg(feature = "thumbv6")]
+//! # let mut apb = peripherals.PM;
+//! # #[cfg(feature = "thumbv7")]
+//! # let mut apb = peripherals.MCLK;
+//! # #[cfg(feature = "thumbv6")]
+//! # let reference = Reference::Avcc;
+//! # #[cfg(feature = "thumbv7")]
+//! # let reference = Reference::Vddana;
+//! let pins = Pins::new(peripherals.PORT);
+//! let vout = pins.pa02.into_alternate::<B>();
+//!
+//! let mut dac = Dac::new(peripherals.DAC, &mut apb, reference);
+//! dac.enable_output::<Ch0>(&vout);
+//! dac.enable();
+//! dac.write::<Ch0>(0x100);
+//! ```
+//!
+//! # Waveform output
+//!
+//! A sample table can be streamed to a channel with
+//! [`Dac::send_with_dma`]. The DMA transfer writes the `DATABUF` register
+//! whenever it is empty, and the buffered value is only converted on the DAC
+//! start event. Connect the start event to a TC overflow, or any other event
+//! generator, with the [`evsys`](crate::evsys) module and
+//! [`Dac::set_event_input`] to output the samples at a fixed rate.
+
+use bitflags::bitflags;
+
+use crate::gpio::*;
+use crate::pac::dac::ctrlb::REFSEL_A;
+use crate::pac::DAC;
+use crate::typelevel::Sealed;
+
+#[cfg(feature = "thumbv6")]
+use crate::pac::PM as APB_CLK_CTRL;
+
+#[cfg(feature = "thumbv7")]
+use crate::pac::dac::dacctrl::{CCTRL_A, OSR_A};
+#[cfg(feature = "thumbv7")]
+use crate::pac::MCLK as APB_CLK_CTRL;
+
+#[cfg(feature = "dma")]
+use crate::dmac::{
+    self,
+    channel::{AnyChannel, Busy, CallbackStatus, Channel, InterruptFlags, Ready},
+    transfer::BufferPair,
+    Buffer, Transfer, TriggerAction, TriggerSource,
+};
+
+//==============================================================================
+// Ids
+//==============================================================================
+
+/// Type-level DAC channel number
+pub trait ChId: Sealed {
+    const NUM: usize;
+    /// `VOUT` pin of the channel
+    type Pin: PinId;
+    /// DMA trigger of the data buffer empty condition
+    #[cfg(feature = "dma")]
+    const DMA_TRIGGER: TriggerSource;
+}
+
+/// Type-level DAC channel 0
+pub enum Ch0 {}
+impl Sealed for Ch0 {}
+impl ChId for Ch0 {
+    const NUM: usize = 0;
+    type Pin = PA02;
+    #[cfg(all(feature = "dma", feature = "thumbv6"))]
+    const DMA_TRIGGER: TriggerSource = TriggerSource::DAC_EMPTY;
+    #[cfg(all(feature = "dma", feature = "thumbv7"))]
+    const DMA_TRIGGER: TriggerSource = TriggerSource::DAC_EMPTY_0;
+}
+
+/// Type-level DAC channel 1
+#[cfg(feature = "thumbv7")]
+pub enum Ch1 {}
+#[cfg(feature = "thumbv7")]
+impl Sealed for Ch1 {}
+#[cfg(feature = "thumbv7")]
+impl ChId for Ch1 {
+    const NUM: usize = 1;
+    type Pin = PA05;
+    #[cfg(feature = "dma")]
+    const DMA_TRIGGER: TriggerSource = TriggerSource::DAC_EMPTY_1;
+}
+
+//==============================================================================
+// Configuration
+//==============================================================================
+
+/// DAC voltage reference
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Reference {
+    /// Internal 1.0 V reference
+    #[cfg(feature = "thumbv6")]
+    Int1v,
+    /// Analog supply
+    #[cfg(feature = "thumbv6")]
+    Avcc,
+    /// External reference on the `VREFA` pin
+    #[cfg(feature = "thumbv6")]
+    VrefA,
+    /// Unbuffered external reference on the `VREFA` pin
+    #[cfg(feature = "thumbv7")]
+    VrefAUnbuffered,
+    /// Analog supply
+    #[cfg(feature = "thumbv7")]
+    Vddana,
+    /// Buffered external reference on the `VREFA` pin
+    #[cfg(feature = "thumbv7")]
+    VrefABuffered,
+    /// Internal bandgap reference
+    #[cfg(feature = "thumbv7")]
+    Internal,
+}
+
+impl From<Reference> for REFSEL_A {
+    #[inline]
+    fn from(reference: Reference) -> Self {
+        match reference {
+            #[cfg(feature = "thumbv6")]
+            Reference::Int1v => REFSEL_A::INT1V,
+            #[cfg(feature = "thumbv6")]
+            Reference::Avcc => REFSEL_A::AVCC,
+            #[cfg(feature = "thumbv6")]
+            Reference::VrefA => REFSEL_A::VREFP,
+            #[cfg(feature = "thumbv7")]
+            Reference::VrefAUnbuffered => REFSEL_A::VREFPU,
+            #[cfg(feature = "thumbv7")]
+            Reference::Vddana => REFSEL_A::VDDANA,
+            #[cfg(feature = "thumbv7")]
+            Reference::VrefABuffered => REFSEL_A::VREFPB,
+            #[cfg(feature = "thumbv7")]
+            Reference::Internal => REFSEL_A::INTREF,
+        }
+    }
+}
+
+/// Current control of a DAC channel, to be selected according to the DAC
+/// generic clock frequency
+#[cfg(feature = "thumbv7")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CurrentControl {
+    /// GCLK_DAC up to 1.2 MHz
+    Cc100k,
+    /// GCLK_DAC up to 6 MHz
+    Cc1m,
+    /// GCLK_DAC up to 12 MHz
+    Cc12m,
+}
+
+/// Oversampling ratio of the interpolation filter
+#[cfg(feature = "thumbv7")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Oversampling {
+    Osr1,
+    Osr2,
+    Osr4,
+    Osr8,
+    Osr16,
+    Osr32,
+}
+
+/// Configuration of a DAC channel
+///
+/// Apply it with [`Dac::configure`].
+#[cfg(feature = "thumbv7")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChannelConfig {
+    /// Left-adjust the data written to `DATA` and `DATABUF`
+    pub left_adjust: bool,
+    /// Current control
+    pub current: CurrentControl,
+    /// Refresh period, in units of 30 µs, in the range `0..16`
+    ///
+    /// The output is refreshed periodically to compensate for leakage when it
+    /// is not updated. Zero disables the refresh.
+    pub refresh: u8,
+    /// Dither the output with the four least significant bits of the data
+    pub dither: bool,
+    /// Use the external filter mode, in which the output is not buffered
+    pub external_filter: bool,
+    /// Oversampling ratio of the interpolation filter
+    pub oversampling: Oversampling,
+    /// Keep the channel running in standby sleep mode
+    pub run_standby: bool,
+}
+
+#[cfg(feature = "thumbv7")]
+impl Default for ChannelConfig {
+    fn default() -> Self {
+        Self {
+            left_adjust: false,
+            current: CurrentControl::Cc12m,
+            refresh: 0,
+            dither: false,
+            external_filter: false,
+            oversampling: Oversampling::Osr1,
+            run_standby: false,
+        }
+    }
+}
+
+//==============================================================================
+// Flags
+//==============================================================================
+
+#[cfg(feature = "thumbv6")]
+bitflags! {
+    /// Interrupt bit flags
+    ///
+    /// The binary format of the underlying bits exactly matches the INTFLAG
+    /// bits.
+    pub struct Flags: u8 {
+        const UNDERRUN = 0x01;
+        const EMPTY = 0x02;
+        const SYNCRDY = 0x04;
+    }
+}
+
+#[cfg(feature = "thumbv7")]
+bitflags! {
+    /// Interrupt bit flags
+    ///
+    /// The binary format of the underlying bits exactly matches the INTFLAG
+    /// bits.
+    pub struct Flags: u8 {
+        const UNDERRUN0 = 0x01;
+        const UNDERRUN1 = 0x02;
+        const EMPTY0 = 0x04;
+        const EMPTY1 = 0x08;
+        const RESRDY0 = 0x10;
+        const RESRDY1 = 0x20;
+        const OVERRUN0 = 0x40;
+        const OVERRUN1 = 0x80;
+    }
+}
+
+//==============================================================================
+// Dac
+//==============================================================================
+
+/// Digital-to-Analog Converter peripheral
+pub struct Dac {
+    dac: DAC,
+}
+
+impl Dac {
+    /// Enable the DAC APB clock, reset the peripheral and select the voltage
+    /// reference
+    #[inline]
+    pub fn new(dac: DAC, apb: &mut APB_CLK_CTRL, reference: Reference) -> Self {
+        #[cfg(feature = "thumbv6")]
+        apb.apbcmask.modify(|_, w| w.dac_().set_bit());
+        #[cfg(feature = "thumbv7")]
+        apb.apbdmask.modify(|_, w| w.dac_().set_bit());
+
+        let dac = Self { dac };
+        dac.dac.ctrla.write(|w| w.swrst().set_bit());
+        dac.sync();
+        dac.dac
+            .ctrlb
+            .write(|w| w.refsel().variant(reference.into()));
+        dac
+    }
+
+    #[inline]
+    fn sync(&self) {
+        #[cfg(feature = "thumbv6")]
+        while self.dac.status.read().syncbusy().bit_is_set() {}
+        #[cfg(feature = "thumbv7")]
+        while self.dac.syncbusy.read().bits() != 0 {}
+    }
+
+    /// Enable the DAC
+    #[inline]
+    pub fn enable(&mut self) {
+        self.dac.ctrla.modify(|_, w| w.enable().set_bit());
+        self.sync();
+    }
+
+    /// Disable the DAC
+    #[inline]
+    pub fn disable(&mut self) {
+        self.dac.ctrla.modify(|_, w| w.enable().clear_bit());
+        self.sync();
+    }
+
+    /// Enable the output of a channel on its `VOUT` pin
+    #[inline]
+    pub fn enable_output<Id: ChId>(&mut self, _pin: &Pin<Id::Pin, AlternateB>) {
+        self.while_disabled(|dac| {
+            #[cfg(feature = "thumbv6")]
+            dac.ctrlb.modify(|_, w| w.eoen().set_bit());
+            #[cfg(feature = "thumbv7")]
+            dac.dacctrl[Id::NUM].modify(|_, w| w.enable().set_bit());
+        });
+    }
+
+    /// Disable the output of a channel
+    #[inline]
+    pub fn disable_output<Id: ChId>(&mut self) {
+        self.while_disabled(|dac| {
+            #[cfg(feature = "thumbv6")]
+            dac.ctrlb.modify(|_, w| w.eoen().clear_bit());
+            #[cfg(feature = "thumbv7")]
+            dac.dacctrl[Id::NUM].modify(|_, w| w.enable().clear_bit());
+        });
+    }
+
+    /// Route the DAC output to the analog comparator and ADC
+    #[cfg(feature = "thumbv6")]
+    #[inline]
+    pub fn internal_output(&mut self, enable: bool) {
+        self.while_disabled(|dac| dac.ctrlb.modify(|_, w| w.ioen().bit(enable)));
+    }
+
+    /// Left-adjust the data written to `DATA` and `DATABUF`
+    #[cfg(feature = "thumbv6")]
+    #[inline]
+    pub fn left_adjust(&mut self, enable: bool) {
+        self.while_disabled(|dac| dac.ctrlb.modify(|_, w| w.leftadj().bit(enable)));
+    }
+
+    /// Disable the voltage pump, which can be done when VDDANA is above 2.5 V
+    #[cfg(feature = "thumbv6")]
+    #[inline]
+    pub fn disable_voltage_pump(&mut self, disable: bool) {
+        self.while_disabled(|dac| dac.ctrlb.modify(|_, w| w.vpd().bit(disable)));
+    }
+
+    /// Keep the DAC running in standby sleep mode
+    #[cfg(feature = "thumbv6")]
+    #[inline]
+    pub fn run_standby(&mut self, run_standby: bool) {
+        self.dac.ctrla.modify(|_, w| w.runstdby().bit(run_standby));
+        self.sync();
+    }
+
+    /// Apply a [`ChannelConfig`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if the refresh period is out of range.
+    #[cfg(feature = "thumbv7")]
+    #[inline]
+    pub fn configure<Id: ChId>(&mut self, config: ChannelConfig) {
+        assert!(config.refresh < 16, "Invalid DAC refresh period");
+        self.while_disabled(|dac| {
+            dac.dacctrl[Id::NUM].modify(|_, w| {
+                w.leftadj().bit(config.left_adjust);
+                w.cctrl().variant(match config.current {
+                    CurrentControl::Cc100k => CCTRL_A::CC100K,
+                    CurrentControl::Cc1m => CCTRL_A::CC1M,
+                    CurrentControl::Cc12m => CCTRL_A::CC12M,
+                });
+                w.refresh().bits(config.refresh);
+                w.dither().bit(config.dither);
+                w.fext().bit(config.external_filter);
+                w.osr().variant(match config.oversampling {
+                    Oversampling::Osr1 => OSR_A::OSR_1,
+                    Oversampling::Osr2 => OSR_A::OSR_2,
+                    Oversampling::Osr4 => OSR_A::OSR_4,
+                    Oversampling::Osr8 => OSR_A::OSR_8,
+                    Oversampling::Osr16 => OSR_A::OSR_16,
+                    Oversampling::Osr32 => OSR_A::OSR_32,
+                });
+                w.runstdby().bit(config.run_standby)
+            });
+        });
+    }
+
+    /// Use both channels as a differential pair, with channel 0 as the
+    /// positive output and channel 1 as the negative output
+    #[cfg(feature = "thumbv7")]
+    #[inline]
+    pub fn differential(&mut self, enable: bool) {
+        self.while_disabled(|dac| dac.ctrlb.modify(|_, w| w.diff().bit(enable)));
+    }
+
+    /// Return `true` if the channel has started up and is ready to convert
+    #[cfg(feature = "thumbv7")]
+    #[inline]
+    pub fn is_ready<Id: ChId>(&self) -> bool {
+        self.dac.status.read().bits() & (1 << Id::NUM) != 0
+    }
+
+    /// Write a value to be converted immediately
+    #[inline]
+    pub fn write<Id: ChId>(&mut self, value: u16) {
+        // Safety: The DAC ignores the bits beyond its resolution
+        #[cfg(feature = "thumbv6")]
+        self.dac.data.write(|w| unsafe { w.data().bits(value) });
+        #[cfg(feature = "thumbv7")]
+        self.dac.data[Id::NUM].write(|w| unsafe { w.data().bits(value) });
+        self.sync();
+    }
+
+    /// Write a value to be converted on the next start event
+    #[inline]
+    pub fn write_buffered<Id: ChId>(&mut self, value: u16) {
+        // Safety: The DAC ignores the bits beyond its resolution
+        #[cfg(feature = "thumbv6")]
+        self.dac
+            .databuf
+            .write(|w| unsafe { w.databuf().bits(value) });
+        #[cfg(feature = "thumbv7")]
+        self.dac.databuf[Id::NUM].write(|w| unsafe { w.databuf().bits(value) });
+        self.sync();
+    }
+
+    /// Enable the start event input, which converts the value in `DATABUF`
+    #[inline]
+    pub fn set_event_input<Id: ChId>(&mut self, enable: bool) {
+        self.while_disabled(|dac| {
+            // Safety: Only the STARTEIn bit is modified
+            dac.evctrl.modify(|r, w| unsafe {
+                w.bits(if enable {
+                    r.bits() | (1 << Id::NUM)
+                } else {
+                    r.bits() & !(1 << Id::NUM)
+                })
+            });
+        });
+    }
+
+    /// Invert the start event input
+    #[cfg(feature = "thumbv7")]
+    #[inline]
+    pub fn invert_event_input<Id: ChId>(&mut self, invert: bool) {
+        self.while_disabled(|dac| {
+            let bit = 1 << (4 + Id::NUM);
+            // Safety: Only the INVEIn bit is modified
+            dac.evctrl.modify(|r, w| unsafe {
+                w.bits(if invert {
+                    r.bits() | bit
+                } else {
+                    r.bits() & !bit
+                })
+            });
+        });
+    }
+
+    /// Enable the data buffer empty event output
+    #[inline]
+    pub fn set_event_output<Id: ChId>(&mut self, enable: bool) {
+        self.while_disabled(|dac| {
+            #[cfg(feature = "thumbv6")]
+            dac.evctrl.modify(|_, w| w.emptyeo().bit(enable));
+            #[cfg(feature = "thumbv7")]
+            {
+                let bit = 1 << (2 + Id::NUM);
+                // Safety: Only the EMPTYEOn bit is modified
+                dac.evctrl.modify(|r, w| unsafe {
+                    w.bits(if enable {
+                        r.bits() | bit
+                    } else {
+                        r.bits() & !bit
+                    })
+                });
+            }
+        });
+    }
+
+    /// Enable interrupts for the specified flags
+    #[inline]
+    pub fn enable_interrupts(&mut self, flags: Flags) {
+        // Safety: `Flags` bits match the INTENSET bits
+        self.dac.intenset.write(|w| unsafe { w.bits(flags.bits()) });
+    }
+
+    /// Disable interrupts for the specified flags
+    #[inline]
+    pub fn disable_interrupts(&mut self, flags: Flags) {
+        // Safety: `Flags` bits match the INTENCLR bits
+        self.dac.intenclr.write(|w| unsafe { w.bits(flags.bits()) });
+    }
+
+    /// Read the interrupt flags
+    #[inline]
+    pub fn read_flags(&self) -> Flags {
+        Flags::from_bits_truncate(self.dac.intflag.read().bits())
+    }
+
+    /// Clear the specified interrupt flags
+    #[inline]
+    pub fn clear_flags(&mut self, flags: Flags) {
+        // Safety: `Flags` bits match the INTFLAG bits
+        self.dac.intflag.write(|w| unsafe { w.bits(flags.bits()) });
+    }
+
+    /// Reset the DAC and return the register block
+    #[inline]
+    pub fn free(self) -> DAC {
+        self.dac.ctrla.write(|w| w.swrst().set_bit());
+        self.sync();
+        self.dac
+    }
+
+    /// Most of the DAC configuration is enable-protected, so disable the DAC
+    /// while it is written, then restore its state
+    #[inline]
+    fn while_disabled(&mut self, f: impl FnOnce(&DAC)) {
+        let enabled = self.dac.ctrla.read().enable().bit_is_set();
+        self.disable();
+        f(&self.dac);
+        if enabled {
+            self.enable();
+        }
+    }
+}
+
+//==============================================================================
+// DMA
+//==============================================================================
+
+/// [`Dac`] used as the destination of a DMA transfer
+///
+/// Created by [`Dac::send_with_dma`]. It points to the `DATABUF` register of
+/// channel `Id`. Call [`free`](DmaDac::free) to get the [`Dac`] back once the
+/// transfer is complete.
+#[cfg(feature = "dma")]
+pub struct DmaDac<Id: ChId> {
+    dac: Dac,
+    id: core::marker::PhantomData<Id>,
+}
+
+#[cfg(feature = "dma")]
+impl<Id: ChId> DmaDac<Id> {
+    /// Return the [`Dac`]
+    #[inline]
+    pub fn free(self) -> Dac {
+        self.dac
+    }
+}
+
+#[cfg(feature = "dma")]
+unsafe impl<Id: ChId> Buffer for DmaDac<Id> {
+    type Beat = u16;
+
+    #[inline]
+    fn dma_ptr(&mut self) -> *mut Self::Beat {
+        #[cfg(feature = "thumbv6")]
+        return self.dac.dac.databuf.as_ptr();
+        #[cfg(feature = "thumbv7")]
+        return self.dac.dac.databuf[Id::NUM].as_ptr();
+    }
+
+    #[inline]
+    fn incrementing(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn buffer_len(&self) -> usize {
+        1
+    }
+}
+
+/// DMA [`Transfer`] streaming a buffer to channel `Id` of the [`Dac`]
+///
+/// Returned by [`Dac::send_with_dma`]. `C` is the id of the DMA channel.
+#[cfg(feature = "dma")]
+pub type DacTransfer<C, Id, B, W> = Transfer<Channel<C, Busy>, BufferPair<B, DmaDac<Id>>, W>;
+
+#[cfg(feature = "dma")]
+impl Dac {
+    /// Transform a [`Dac`] into a DMA [`Transfer`] and start streaming `buf` to
+    /// the `DATABUF` register of channel `Id`
+    ///
+    /// The transfer is triggered each time the data buffer is emptied by a
+    /// start event, see [`Dac::set_event_input`]. If `circular` is `true`, the
+    /// buffer is repeated until the transfer is stopped.
+    #[inline]
+    pub fn send_with_dma<Id, Ch, B, W>(
+        self,
+        buf: B,
+        mut channel: Ch,
+        circular: bool,
+        waker: W,
+    ) -> DacTransfer<Ch::Id, Id, B, W>
+    where
+        Id: ChId,
+        Ch: AnyChannel<Status = Ready>,
+        B: Buffer<Beat = u16> + 'static,
+        W: FnOnce(CallbackStatus) + 'static,
+    {
+        channel
+            .as_mut()
+            .enable_interrupts(InterruptFlags::new().with_tcmpl(true));
+
+        let dest = DmaDac {
+            dac: self,
+            id: core::marker::PhantomData,
+        };
+
+        #[cfg(feature = "thumbv7")]
+        let trigger_action = TriggerAction::BURST;
+
+        #[cfg(feature = "thumbv6")]
+        let trigger_action = TriggerAction::BEAT;
+
+        // SAFETY: This is safe because the of the `'static` bound check
+        // for `B`, and the fact that the buffer length of a `DmaDac` is always 1.
+        let xfer = unsafe { dmac::Transfer::new_unchecked(channel, buf, dest, circular) };
+        xfer.with_waker(waker)
+            .begin(Id::DMA_TRIGGER, trigger_action)
+    }
+}
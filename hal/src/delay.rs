@@ -4,7 +4,9 @@ use cortex_m::peripheral::syst::SystClkSource;
 use cortex_m::peripheral::SYST;
 
 use crate::clock::GenericClockController;
-use crate::ehal::blocking::delay::{DelayMs, DelayUs};
+use crate::ehal_1::delay::DelayNs;
+#[cfg(feature = "ehal-02")]
+use crate::ehal_02::blocking::delay::{DelayMs, DelayUs};
 use crate::time::Hertz;
 
 /// System timer (SysTick) as a delay provider
@@ -28,62 +30,90 @@ impl Delay {
     pub fn free(self) -> SYST {
         self.syst
     }
+
+    /// Delay for the given number of SysTick cycles
+    fn delay_cycles(&mut self, cycles: u64) {
+        // The SysTick Reload Value register supports values between 1 and 0x00FFFFFF.
+        const MAX_RVR: u64 = 0x00FF_FFFF;
+
+        let mut total_rvr = cycles;
+
+        while total_rvr != 0 {
+            let current_rvr = total_rvr.min(MAX_RVR);
+
+            self.syst.set_reload(current_rvr as u32);
+            self.syst.clear_current();
+            self.syst.enable_counter();
+
+            // Update the tracking variable while we are waiting...
+            total_rvr -= current_rvr;
+
+            while !self.syst.has_wrapped() {}
+
+            self.syst.disable_counter();
+        }
+    }
+
+    /// Number of SysTick cycles in the given number of time units, where
+    /// `scale` is the number of units per second
+    #[inline]
+    fn cycles(&self, units: u32, scale: u64) -> u64 {
+        (units as u64 * self.sysclock.to_Hz() as u64).div_ceil(scale)
+    }
 }
 
+impl DelayNs for Delay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.delay_cycles(self.cycles(ns, 1_000_000_000));
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        self.delay_cycles(self.cycles(us, 1_000_000));
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.delay_cycles(self.cycles(ms, 1_000));
+    }
+}
+
+#[cfg(feature = "ehal-02")]
 impl DelayMs<u32> for Delay {
     fn delay_ms(&mut self, ms: u32) {
-        self.delay_us(ms * 1_000);
+        DelayNs::delay_ms(self, ms);
     }
 }
 
+#[cfg(feature = "ehal-02")]
 impl DelayMs<u16> for Delay {
     fn delay_ms(&mut self, ms: u16) {
-        self.delay_ms(ms as u32);
+        DelayNs::delay_ms(self, ms as u32);
     }
 }
 
+#[cfg(feature = "ehal-02")]
 impl DelayMs<u8> for Delay {
     fn delay_ms(&mut self, ms: u8) {
-        self.delay_ms(ms as u32);
+        DelayNs::delay_ms(self, ms as u32);
     }
 }
 
+#[cfg(feature = "ehal-02")]
 impl DelayUs<u32> for Delay {
     fn delay_us(&mut self, us: u32) {
-        // The SysTick Reload Value register supports values between 1 and 0x00FFFFFF.
-        const MAX_RVR: u32 = 0x00FF_FFFF;
-
-        let mut total_rvr = us * (self.sysclock.to_Hz() / 1_000_000);
-
-        while total_rvr != 0 {
-            let current_rvr = if total_rvr <= MAX_RVR {
-                total_rvr
-            } else {
-                MAX_RVR
-            };
-
-            self.syst.set_reload(current_rvr);
-            self.syst.clear_current();
-            self.syst.enable_counter();
-
-            // Update the tracking variable while we are waiting...
-            total_rvr -= current_rvr;
-
-            while !self.syst.has_wrapped() {}
-
-            self.syst.disable_counter();
-        }
+        DelayNs::delay_us(self, us);
     }
 }
 
+#[cfg(feature = "ehal-02")]
 impl DelayUs<u16> for Delay {
     fn delay_us(&mut self, us: u16) {
-        self.delay_us(us as u32)
+        DelayNs::delay_us(self, us as u32);
     }
 }
 
+#[cfg(feature = "ehal-02")]
 impl DelayUs<u8> for Delay {
     fn delay_us(&mut self, us: u8) {
-        self.delay_us(us as u32)
+        DelayNs::delay_us(self, us as u32);
     }
 }
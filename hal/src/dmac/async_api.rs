@@ -0,0 +1,128 @@
+//! # `async` support for DMA channels
+//!
+//! With the `async` feature enabled, a `Ready` [`Channel`] can run a transfer
+//! as a [`Future`](core::future::Future) through
+//! [`Channel::transfer_future`]. This works with any `async` executor, such as
+//! [Embassy](https://embassy.dev) or [RTIC](https://rtic.rs) v2.
+//!
+//! # Interrupts
+//!
+//! Each channel future is woken from the DMAC interrupt through a per-channel
+//! waker. [`on_interrupt`] must be called from the DMAC interrupt handler(s),
+//! and the DMAC interrupt(s) must be unmasked in the NVIC. On SAMD11/SAMD21,
+//! there is a single `DMAC` interrupt. On SAMD51/SAME5x, the `DMAC_0` to
+//! `DMAC_3` interrupts are dedicated to channels 0 to 3, and `DMAC_OTHER` is
+//! shared by all other channels. [`on_interrupt`] services every channel, so
+//! it can be called from any of them.
+//!
+//! [`on_interrupt`] only touches the channels running a
+//! [`transfer_future`](Channel::transfer_future). Other channels, like those
+//! running a blocking or callback-based
+//! [`Transfer`](super::transfer::Transfer), must still be serviced by the
+//! application.
+//!
+//! ```
+//! #[interrupt]
+//! fn DMAC() {
+//!     atsamd_hal::dmac::on_interrupt();
+//! }
+//!
+//! async fn copy(chan: &mut Channel<Ch0, Ready>, src: &mut [u8; 64], dst: &mut [u8; 64]) {
+//!     chan.transfer_future(src, dst, TriggerSource::DISABLE, TriggerAction::BLOCK)
+//!         .await
+//!         .unwrap();
+//! }
+//! ```
+
+use super::{
+    channel::{Channel, Ready},
+    dma_controller::ChId,
+    NUM_CHANNELS,
+};
+use crate::pac::DMAC;
+use core::sync::atomic::{AtomicBool, Ordering};
+use embassy_sync::waitqueue::AtomicWaker;
+
+#[allow(clippy::declare_interior_mutable_const)]
+const NEW_WAKER: AtomicWaker = AtomicWaker::new();
+
+/// Wakers for the futures of every DMA channel
+pub(super) static WAKERS: [AtomicWaker; NUM_CHANNELS] = [NEW_WAKER; NUM_CHANNELS];
+
+#[allow(clippy::declare_interior_mutable_const)]
+const NEW_FLAG: AtomicBool = AtomicBool::new(false);
+
+/// Channels currently running a transfer future
+///
+/// Each flag is only written by the owner of the corresponding channel, so
+/// plain loads and stores are enough, even on thumbv6m targets.
+static ASYNC_CHANNELS: [AtomicBool; NUM_CHANNELS] = [NEW_FLAG; NUM_CHANNELS];
+
+/// DMAC interrupt handler for `async` channel transfers.
+///
+/// This function disables the interrupts of every channel running a transfer
+/// future with a pending interrupt, then wakes the corresponding future. The
+/// interrupt flags are left untouched, so that the future can check them when
+/// it is polled. Channels used by other transfers are left untouched.
+#[inline]
+pub fn on_interrupt() {
+    // SAFETY: We only read the interrupt status, and only write to the
+    // interrupt enable register of async channels which have a pending
+    // interrupt. The channels re-enable their interrupts when they are polled.
+    let dmac = unsafe { &*DMAC::ptr() };
+    let pending = dmac.intstatus.read().bits();
+
+    for (id, waker) in WAKERS.iter().enumerate() {
+        if pending & (1 << id) == 0 || !ASYNC_CHANNELS[id].load(Ordering::Acquire) {
+            continue;
+        }
+
+        #[cfg(feature = "thumbv6")]
+        {
+            // Restore the old CHID value, in case the interrupt preempted a
+            // context accessing another channel.
+            let old_id = dmac.chid.read().id().bits();
+            dmac.chid.write(|w| unsafe { w.id().bits(id as u8) });
+            dmac.chintenclr.write(|w| unsafe { w.bits(0x07) });
+            dmac.chid.write(|w| unsafe { w.id().bits(old_id) });
+        }
+
+        #[cfg(feature = "thumbv7")]
+        dmac.channel[id]
+            .chintenclr
+            .write(|w| unsafe { w.bits(0x07) });
+
+        waker.wake();
+    }
+}
+
+/// Stops a channel when dropped, so that a cancelled transfer future never
+/// leaves the DMAC accessing borrowed buffers.
+pub(super) struct StopGuard<'a, Id: ChId> {
+    chan: &'a mut Channel<Id, Ready>,
+}
+
+impl<'a, Id: ChId> StopGuard<'a, Id> {
+    /// Mark the channel as running a transfer future
+    ///
+    /// This must be called before the transfer is started, so that
+    /// [`on_interrupt`] services its interrupts.
+    #[inline]
+    pub(super) fn new(chan: &'a mut Channel<Id, Ready>) -> Self {
+        ASYNC_CHANNELS[Id::USIZE].store(true, Ordering::Release);
+        Self { chan }
+    }
+
+    #[inline]
+    pub(super) fn channel(&mut self) -> &mut Channel<Id, Ready> {
+        self.chan
+    }
+}
+
+impl<Id: ChId> Drop for StopGuard<'_, Id> {
+    #[inline]
+    fn drop(&mut self) {
+        self.chan.stop_async();
+        ASYNC_CHANNELS[Id::USIZE].store(false, Ordering::Release);
+    }
+}
@@ -201,6 +201,41 @@ impl<Id: ChId, S: Status> Channel<Id, S> {
     fn _trigger_private(&mut self) {
         self.regs.swtrigctrl.set_bit();
     }
+
+    #[inline]
+    fn _start_private(&mut self, trig_src: TriggerSource, trig_act: TriggerAction) {
+        // Configure the trigger source and trigger action
+        #[cfg(feature = "thumbv6")]
+        self.regs.chctrlb.modify(|_, w| {
+            w.trigsrc().variant(trig_src);
+            w.trigact().variant(trig_act)
+        });
+
+        #[cfg(feature = "thumbv7")]
+        self.regs.chctrla.modify(|_, w| {
+            w.trigsrc().variant(trig_src);
+            w.trigact().variant(trig_act)
+        });
+
+        // Start channel
+        self.regs.chctrla.modify(|_, w| w.enable().set_bit());
+
+        // If trigger source is DISABLE, manually trigger transfer
+        if trig_src == TriggerSource::DISABLE {
+            self._trigger_private();
+        }
+    }
+
+    #[inline]
+    fn _enabled_private(&mut self) -> bool {
+        self.regs.chctrla.read().enable().bit_is_set()
+    }
+
+    #[inline]
+    fn _stop_private(&mut self) {
+        self.regs.chctrla.modify(|_, w| w.enable().clear_bit());
+        while self._enabled_private() {}
+    }
 }
 
 /// These methods may only be used on a `Ready` DMA channel
@@ -238,6 +273,40 @@ impl<Id: ChId> Channel<Id, Ready> {
             .modify(|_, w| w.burstlen().bits(burst_length as u8));
     }
 
+    /// Set the beat count of the channel's write-back descriptor
+    ///
+    /// The DMAC only fetches the descriptor on the first trigger. Setting the
+    /// count to the transfer length before starting it keeps
+    /// [`remaining_beats`](Self::remaining_beats) correct if the transfer is
+    /// stopped before the first trigger.
+    #[inline]
+    pub(crate) fn set_remaining_beats(&mut self, beats: usize) {
+        // SAFETY: The channel is not running, so the DMAC does not access its
+        // write-back descriptor. We write the field without creating a
+        // reference to the static.
+        unsafe {
+            core::ptr::write_volatile(
+                core::ptr::addr_of_mut!(super::WRITEBACK[Id::USIZE].btcnt),
+                beats as u16,
+            );
+        }
+    }
+
+    /// Return the number of beats the last transfer did not complete
+    ///
+    /// The DMAC updates the write-back descriptor when the channel is
+    /// disabled, so this is only meaningful once the transfer has stopped.
+    #[inline]
+    pub(crate) fn remaining_beats(&self) -> usize {
+        // SAFETY: The channel is not running, so the DMAC does not write to its
+        // write-back descriptor. We read the field without creating a
+        // reference to the static.
+        let beats = unsafe {
+            core::ptr::read_volatile(core::ptr::addr_of!(super::WRITEBACK[Id::USIZE].btcnt))
+        };
+        beats as usize
+    }
+
     /// Start transfer on channel using the specified trigger source.
     ///
     /// # Return
@@ -249,26 +318,7 @@ impl<Id: ChId> Channel<Id, Ready> {
         trig_src: TriggerSource,
         trig_act: TriggerAction,
     ) -> Channel<Id, Busy> {
-        // Configure the trigger source and trigger action
-        #[cfg(feature = "thumbv6")]
-        self.regs.chctrlb.modify(|_, w| {
-            w.trigsrc().variant(trig_src);
-            w.trigact().variant(trig_act)
-        });
-
-        #[cfg(feature = "thumbv7")]
-        self.regs.chctrla.modify(|_, w| {
-            w.trigsrc().variant(trig_src);
-            w.trigact().variant(trig_act)
-        });
-
-        // Start channel
-        self.regs.chctrla.modify(|_, w| w.enable().set_bit());
-
-        // If trigger source is DISABLE, manually trigger transfer
-        if trig_src == TriggerSource::DISABLE {
-            self._trigger_private();
-        }
+        self._start_private(trig_src, trig_act);
 
         Channel {
             regs: self.regs,
@@ -277,6 +327,112 @@ impl<Id: ChId> Channel<Id, Ready> {
     }
 }
 
+#[cfg(feature = "async")]
+impl<Id: ChId> Channel<Id, Ready> {
+    /// Begin a DMA transfer and return a [`Future`](core::future::Future)
+    /// that resolves when the transfer is complete.
+    ///
+    /// The channel's transfer complete and transfer error interrupts are used
+    /// to wake the future, so the DMAC interrupt(s) must be unmasked in the
+    /// NVIC, and [`on_interrupt`](super::async_api::on_interrupt) must be
+    /// called from the interrupt handler(s).
+    ///
+    /// Unlike a [`Transfer`](super::transfer::Transfer), the buffers are only
+    /// borrowed for the duration of the transfer. The future is cancel-safe:
+    /// dropping it stops the channel, after which the buffers may safely be
+    /// reused. The future must not be leaked (for example, with
+    /// [`mem::forget`](core::mem::forget)) while the transfer is running.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::LengthMismatch`](super::Error::LengthMismatch) if the
+    /// buffers are not of compatible lengths, and
+    /// [`Error::TransferError`](super::Error::TransferError) if the DMAC
+    /// reports a bus error during the transfer.
+    pub async fn transfer_future<S, D>(
+        &mut self,
+        mut source: S,
+        mut destination: D,
+        trig_src: TriggerSource,
+        trig_act: TriggerAction,
+    ) -> super::Result<()>
+    where
+        S: super::transfer::Buffer,
+        D: super::transfer::Buffer<Beat = S::Beat>,
+    {
+        use core::{sync::atomic, task::Poll};
+
+        super::transfer::check_buffer_pair(&source, &destination)?;
+
+        // SAFETY: We own the channel, and it is not running. The guard below
+        // makes sure the channel is stopped before the buffers are released.
+        unsafe {
+            // The future is woken by the transfer complete interrupt
+            super::transfer::fill_descriptor(
+                Id::USIZE,
+                &mut source,
+                &mut destination,
+                false,
+                super::transfer::BlockAction::Int,
+            );
+        }
+
+        let flags = InterruptFlags::new().with_tcmpl(true).with_terr(true);
+        self.check_and_clear_interrupts(flags);
+
+        // Stop the channel when the future completes or is dropped, and let
+        // `on_interrupt` service it in the meantime
+        let mut guard = super::async_api::StopGuard::new(self);
+
+        // Memory barrier to prevent the compiler/CPU from re-ordering read/write
+        // operations beyond this fence.
+        // (see https://docs.rust-embedded.org/embedonomicon/dma.html#compiler-misoptimizations)
+        atomic::fence(atomic::Ordering::Release); //  ▲
+        guard.channel()._start_private(trig_src, trig_act);
+
+        let result = core::future::poll_fn(|cx| {
+            let chan = guard.channel();
+            super::async_api::WAKERS[Id::USIZE].register(cx.waker());
+
+            let status = chan.check_and_clear_interrupts(flags);
+            if status.terr() {
+                return Poll::Ready(Err(super::Error::TransferError));
+            }
+            if !chan._enabled_private() {
+                return Poll::Ready(Ok(()));
+            }
+
+            // The interrupt handler disables the channel interrupts before waking
+            // the task, so they must be enabled again.
+            chan.enable_interrupts(flags);
+            Poll::Pending
+        })
+        .await;
+
+        drop(guard);
+        atomic::fence(atomic::Ordering::Acquire); // ▼
+
+        // Keep the buffers alive until the transfer has been stopped
+        drop(source);
+        drop(destination);
+        result
+    }
+}
+
+#[cfg(feature = "async")]
+impl<Id: ChId> Channel<Id, Ready> {
+    #[inline]
+    pub(super) fn stop_async(&mut self) {
+        self.disable_interrupts(
+            InterruptFlags::new()
+                .with_tcmpl(true)
+                .with_terr(true)
+                .with_susp(true),
+        );
+        self._stop_private();
+    }
+}
+
 /// These methods may only be used on a `Busy` DMA channel
 impl<Id: ChId> Channel<Id, Busy> {
     /// Issue a software trigger to the channel
@@ -288,7 +444,7 @@ impl<Id: ChId> Channel<Id, Busy> {
     /// Returns whether or not the transfer is complete.
     #[inline]
     pub(crate) fn xfer_complete(&mut self) -> bool {
-        !self.regs.chctrla.read().enable().bit_is_set()
+        !self._enabled_private()
     }
 
     /// Stop transfer on channel whether or not the transfer has completed
@@ -299,8 +455,7 @@ impl<Id: ChId> Channel<Id, Busy> {
     /// [`Transfer`](super::transfer::Transfer)
     #[inline]
     pub(crate) fn free(mut self) -> Channel<Id, Ready> {
-        self.regs.chctrla.modify(|_, w| w.enable().clear_bit());
-        while !self.xfer_complete() {}
+        self._stop_private();
         Channel {
             regs: self.regs,
             _status: PhantomData,
@@ -334,6 +489,20 @@ impl<Id: ChId> Channel<Id, Busy> {
     pub(crate) fn restart(&mut self) {
         self.regs.chctrla.modify(|_, w| w.enable().set_bit());
     }
+
+    /// Resume a suspended transfer. If the channel was suspended because it
+    /// fetched an invalid descriptor, the descriptor is fetched again.
+    #[inline]
+    pub(crate) fn resume(&mut self) {
+        self.regs.chctrlb.modify(|_, w| w.cmd().resume());
+    }
+
+    /// Returns whether the channel was suspended after fetching an invalid
+    /// descriptor.
+    #[inline]
+    pub(crate) fn fetch_error(&mut self) -> bool {
+        self.regs.chstatus.read().ferr().bit_is_set()
+    }
 }
 
 impl<Id: ChId> From<Channel<Id, Ready>> for Channel<Id, Uninitialized> {
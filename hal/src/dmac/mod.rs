@@ -10,9 +10,8 @@
 //! functions, including memory-to-memory,
 //! memory-to-peripheral, peripheral-to-memory,
 //! and peripheral-to-peripheral transfers.
-//! One-shot and circular transfers are supported. More complex
-//! transfer configurations, including multi-buffer
-//! (linked-list descriptor) transfers, are not currently supported.
+//! One-shot and circular transfers are supported, as well as multi-buffer
+//! (linked-list descriptor) transfers through [`LinkedTransfer`].
 //!
 //! Transfers are supported for `i8`, `u8`, `i16`, `u16`, `i32`, `u32` and `f32`
 //! beat sizes.
@@ -89,6 +88,12 @@
 //! the NVIC. You will be responsible for clearing the interrupt flags in the
 //! ISR.
 //!
+//! The exception is the `async` API, enabled with the `async` feature, where
+//! [`Channel::transfer_future`] relies on the DMAC interrupts to wake the
+//! transfer futures. See the [`async_api`] module for more information. The
+//! application must provide a `critical-section` implementation, for example by
+//! enabling the `critical-section-single-core` feature of `cortex-m`.
+//!
 //! # About static lifetimes
 //!
 //! The safe API this driver offers requires all buffers (source and
@@ -167,6 +172,46 @@
 //! let (old_source, old_dest) = xfer.recycle(new_source, new_destination).unwrap();
 //! ```
 //!
+//! # Linked-list transfers
+//!
+//! A [`LinkedTransfer`] chains several blocks into a single DMA transaction,
+//! using one descriptor per block. Each side of the transfer is a
+//! [`BlockBuffers`]: either an array holding one [`Buffer`] per block, or a
+//! single buffer wrapped in [`Repeat`] that is reused for every block (for
+//! example, a peripheral data register). The descriptors for every block must
+//! live in a `'static` [`LinkedDescriptors`] for the duration of the transfer.
+//!
+//! ```
+//! static mut DESCRIPTORS: LinkedDescriptors<3> = LinkedDescriptors::new();
+//!
+//! // Assume `spi` is a fully configured `Spi`, and `header`, `body` and
+//! // `footer` are `&'static mut [u8]` slices
+//! let descriptors = unsafe { &mut DESCRIPTORS };
+//! let xfer = LinkedTransfer::new(
+//!     chan0,
+//!     [header, body, footer],
+//!     Repeat(spi),
+//!     descriptors,
+//!     false,
+//! )
+//! .unwrap()
+//! .begin(Sercom0::DMA_TX_TRIGGER, TriggerAction::BURST);
+//!
+//! let (chan0, [header, body, footer], Repeat(spi), descriptors) = xfer.wait();
+//! ```
+//!
+//! By default, the transfer complete interrupt is only raised at the end of the
+//! last block. With [`LinkedTransfer::block_interrupts`], it is raised at the
+//! end of every block instead, and the [`LinkedTransfer`]'s waker is called
+//! once per block. [`LinkedTransfer::active_block`] reports which block
+//! the DMAC is currently working on.
+//!
+//! # Ping-pong transfers
+//!
+//! A [`PingPongTransfer`] is a circular, two-block [`LinkedTransfer`] that
+//! hands each completed half to the application while the DMAC fills the
+//! other one. See the [`ping_pong`] module for more information.
+//!
 //! # Waker operation
 //!
 //! A [`Transfer`] can also accept a function or closure that will be called on
@@ -253,8 +298,11 @@
 
 use modular_bitfield::prelude::*;
 
+#[cfg(feature = "async")]
+pub use async_api::*;
 pub use channel::*;
 pub use dma_controller::*;
+pub use ping_pong::*;
 pub use transfer::*;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
@@ -272,6 +320,10 @@ pub enum Error {
 
     /// Operation is not valid in the current state of the object.
     InvalidState,
+
+    /// The DMAC reported a transfer error, ie a bus error while accessing
+    /// the source or destination of the transfer.
+    TransferError,
 }
 
 /// Result for DMAC operations
@@ -345,8 +397,9 @@ pub struct BlockTransferControl {
     valid: bool,
     #[allow(dead_code)]
     evosel: B2,
+    #[bits = 2]
     #[allow(dead_code)]
-    blockact: B2,
+    blockact: BlockAction,
     #[skip]
     _reserved: B3,
     #[bits = 2]
@@ -392,6 +445,9 @@ static mut WRITEBACK: [DmacDescriptor; NUM_CHANNELS] = [DEFAULT_DESCRIPTOR; NUM_
 #[doc(hidden)]
 static mut DESCRIPTOR_SECTION: [DmacDescriptor; NUM_CHANNELS] = [DEFAULT_DESCRIPTOR; NUM_CHANNELS];
 
+#[cfg(feature = "async")]
+pub mod async_api;
 pub mod channel;
 pub mod dma_controller;
+pub mod ping_pong;
 pub mod transfer;
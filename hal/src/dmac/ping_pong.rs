@@ -0,0 +1,420 @@
+//! # Double-buffered (ping-pong) DMA transfers
+//!
+//! A [`PingPongTransfer`] is a circular [`LinkedTransfer`] made of two blocks,
+//! or halves. While the DMAC is working on one half, the application owns the
+//! other one. This is typically used for continuous peripheral-to-memory
+//! transfers, such as audio capture or UART receive logging, where the
+//! application must process the received data without stopping the DMAC.
+//!
+//! # Operation
+//!
+//! Every time the DMAC finishes a half, it raises the transfer complete
+//! (TCMPL) interrupt, and [`PingPongTransfer::service`] must be called from
+//! the DMAC interrupt handler. Transfers with a waker call
+//! [`PingPongTransfer::callback`] instead, which also calls the waker. The
+//! completed half is then handed to the application, which can access it
+//! through [`ready_destination`](PingPongTransfer::ready_destination) (or
+//! [`ready_source`](PingPongTransfer::ready_source) for memory-to-peripheral
+//! transfers), then gives it back to the DMAC with
+//! [`release`](PingPongTransfer::release).
+//!
+//! While a half is held by the application, its descriptor is marked as
+//! invalid. If the DMAC finishes the other half before the application
+//! releases it, the DMAC fetches an invalid descriptor and suspends the
+//! channel instead of overwriting data the application is still using. This
+//! raises the suspend (SUSP) interrupt, and is reported by
+//! [`overrun`](PingPongTransfer::overrun). The transfer resumes as soon as
+//! the half is released.
+//!
+//! ```
+//! static mut DESCRIPTORS: LinkedDescriptors<2> = LinkedDescriptors::new();
+//!
+//! // Assume `uart` is a fully configured `Uart`, and `ping` and `pong` are
+//! // `&'static mut [u8; 64]` arrays
+//! let descriptors = unsafe { &mut DESCRIPTORS };
+//! let mut xfer = PingPongTransfer::new(chan0, Repeat(uart), [ping, pong], descriptors)
+//!     .unwrap()
+//!     .begin(Sercom0::DMA_RX_TRIGGER, TriggerAction::BURST);
+//!
+//! // In the DMAC interrupt handler
+//! xfer.service();
+//!
+//! // In the application
+//! if let Some((half, buffer)) = xfer.ready_destination() {
+//!     log(half, buffer);
+//!     xfer.release().unwrap();
+//! }
+//! ```
+
+use super::{
+    channel::{AnyChannel, Busy, CallbackStatus, Channel, ChannelId, InterruptFlags, Ready},
+    dma_controller::{TriggerAction, TriggerSource},
+    transfer::{BlockBuffers, Buffer, LinkedDescriptors, LinkedTransfer, Repeat},
+    Error, Result,
+};
+
+/// One half of a [`PingPongTransfer`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Half {
+    /// First half, ie the first block of the transfer
+    First,
+    /// Second half, ie the second block of the transfer
+    Second,
+}
+
+impl Half {
+    #[inline]
+    fn index(self) -> usize {
+        match self {
+            Half::First => 0,
+            Half::Second => 1,
+        }
+    }
+
+    #[inline]
+    fn from_index(index: usize) -> Self {
+        if index == 0 {
+            Half::First
+        } else {
+            Half::Second
+        }
+    }
+}
+
+/// Double-buffered circular DMA transfer.
+///
+/// See the [module-level documentation](self) for more information.
+pub struct PingPongTransfer<Chan, S, D, W = ()>
+where
+    Chan: AnyChannel,
+    S: BlockBuffers<2>,
+    D: BlockBuffers<2, Beat = S::Beat>,
+{
+    xfer: LinkedTransfer<Chan, S, D, 2>,
+    waker: Option<W>,
+    /// Halves currently held by the application
+    held: [bool; 2],
+    /// Index of the half following the most recently completed one
+    next: usize,
+    overrun: bool,
+    error: bool,
+}
+
+impl<C, S, D> PingPongTransfer<C, S, D>
+where
+    S: BlockBuffers<2> + 'static,
+    D: BlockBuffers<2, Beat = S::Beat> + 'static,
+    C: AnyChannel<Status = Ready>,
+{
+    /// Safely construct a new `PingPongTransfer`. To guarantee memory safety,
+    /// both sides of the transfer are required to be `'static`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::LengthMismatch`] if, for either half, the source and
+    /// destination buffers both have a length > 1 and are not of equal length.
+    #[allow(clippy::new_ret_no_self)]
+    #[inline]
+    pub fn new(
+        chan: C,
+        source: S,
+        destination: D,
+        descriptors: &'static mut LinkedDescriptors<2>,
+    ) -> Result<Self> {
+        let xfer = LinkedTransfer::new(chan, source, destination, descriptors, true)?;
+        Ok(Self::from_linked(xfer))
+    }
+}
+
+impl<C, S, D> PingPongTransfer<C, S, D>
+where
+    S: BlockBuffers<2>,
+    D: BlockBuffers<2, Beat = S::Beat>,
+    C: AnyChannel<Status = Ready>,
+{
+    /// Construct a new `PingPongTransfer` without checking for memory safety.
+    ///
+    /// # Safety
+    ///
+    /// The same invariants as for [`LinkedTransfer::new_unchecked`] must be
+    /// upheld.
+    #[inline]
+    pub unsafe fn new_unchecked(
+        chan: C,
+        source: S,
+        destination: D,
+        descriptors: &'static mut LinkedDescriptors<2>,
+    ) -> Self {
+        let xfer = LinkedTransfer::new_unchecked(chan, source, destination, descriptors, true);
+        Self::from_linked(xfer)
+    }
+
+    #[inline]
+    fn from_linked(xfer: LinkedTransfer<C, S, D, 2>) -> Self {
+        Self {
+            xfer: xfer.block_interrupts(true),
+            waker: None,
+            held: [false; 2],
+            next: 0,
+            overrun: false,
+            error: false,
+        }
+    }
+
+    /// Append a waker to the transfer. This will be called every time the
+    /// DMAC interrupt is serviced by [`callback`](PingPongTransfer::callback).
+    #[inline]
+    pub fn with_waker<W: FnMut(CallbackStatus) + 'static>(
+        self,
+        waker: W,
+    ) -> PingPongTransfer<C, S, D, W> {
+        PingPongTransfer {
+            xfer: self.xfer,
+            waker: Some(waker),
+            held: self.held,
+            next: self.next,
+            overrun: self.overrun,
+            error: self.error,
+        }
+    }
+}
+
+impl<C, S, D, W> PingPongTransfer<C, S, D, W>
+where
+    S: BlockBuffers<2>,
+    D: BlockBuffers<2, Beat = S::Beat>,
+    C: AnyChannel<Status = Ready>,
+{
+    /// Begin DMA transfer. The transfer complete and suspend interrupts are
+    /// enabled for the channel, as they drive the hand-over of each half.
+    #[inline]
+    pub fn begin(
+        mut self,
+        trig_src: TriggerSource,
+        trig_act: TriggerAction,
+    ) -> PingPongTransfer<Channel<ChannelId<C>, Busy>, S, D, W> {
+        self.xfer.chan.as_mut().enable_interrupts(
+            InterruptFlags::new()
+                .with_tcmpl(true)
+                .with_susp(true)
+                .with_terr(true),
+        );
+
+        PingPongTransfer {
+            xfer: self.xfer.begin(trig_src, trig_act),
+            waker: self.waker,
+            held: [false; 2],
+            next: 0,
+            overrun: false,
+            error: false,
+        }
+    }
+}
+
+impl<C, S, D, W> PingPongTransfer<C, S, D, W>
+where
+    S: BlockBuffers<2>,
+    D: BlockBuffers<2, Beat = S::Beat>,
+    C: AnyChannel<Status = Busy>,
+{
+    /// Issue a software trigger request to the corresponding channel.
+    #[inline]
+    pub fn software_trigger(&mut self) {
+        self.xfer.software_trigger();
+    }
+
+    /// Service the DMAC interrupt flags of the channel. This function should
+    /// be put inside the DMAC interrupt handler.
+    ///
+    /// Returns the half that was completed, if any.
+    #[inline]
+    pub fn service(&mut self) -> Option<Half> {
+        let flags = self.xfer.chan.as_mut().check_and_clear_interrupts(
+            InterruptFlags::new()
+                .with_tcmpl(true)
+                .with_susp(true)
+                .with_terr(true),
+        );
+
+        let stalled = self.xfer.chan.as_mut().fetch_error();
+        if flags.susp() && stalled {
+            self.overrun = true;
+        }
+
+        if flags.terr() {
+            self.error = true;
+        }
+
+        if !flags.tcmpl() {
+            return None;
+        }
+
+        let index = if stalled {
+            // The DMAC completed a half, then stalled on fetching the other
+            // one, which is held by the application.
+            self.held.iter().position(|held| !held)?
+        } else {
+            // The DMAC already moved on to the other half. If the interrupt was
+            // serviced late, this is the most recently completed half.
+            (self.xfer.active_block()? + 1) % 2
+        };
+
+        if self.held[index] {
+            return None;
+        }
+
+        self.hold(index);
+        self.next = (index + 1) % 2;
+        Some(Half::from_index(index))
+    }
+
+    /// Return the oldest half completed by the DMAC and not yet released by
+    /// the application, if any.
+    #[inline]
+    pub fn ready(&self) -> Option<Half> {
+        // When both halves are held, the next half the DMAC would complete is
+        // the oldest one.
+        if self.held[self.next] {
+            Some(Half::from_index(self.next))
+        } else if self.held[(self.next + 1) % 2] {
+            Some(Half::from_index((self.next + 1) % 2))
+        } else {
+            None
+        }
+    }
+
+    /// Give the [`ready`](PingPongTransfer::ready) half back to the DMAC. If
+    /// the DMAC was stalled waiting for this half, the transfer is resumed.
+    ///
+    /// Returns [`Error::InvalidState`] if the application holds no half.
+    #[inline]
+    pub fn release(&mut self) -> Result<()> {
+        let index = self.ready().ok_or(Error::InvalidState)?.index();
+        self.held[index] = false;
+
+        let descriptor = &mut self.xfer.descriptors.descriptors[index];
+        descriptor.btctrl.set_valid(true);
+
+        // Make sure the DMAC sees the valid descriptor before resuming
+        core::sync::atomic::fence(core::sync::atomic::Ordering::Release);
+
+        let chan = self.xfer.chan.as_mut();
+        if chan.fetch_error() {
+            chan.resume();
+        }
+        Ok(())
+    }
+
+    /// Check whether the DMAC was stalled since the last call, because the
+    /// application did not release a half in time. Data arriving from the
+    /// peripheral during the stall may have been lost.
+    #[inline]
+    pub fn overrun(&mut self) -> bool {
+        core::mem::replace(&mut self.overrun, false)
+    }
+
+    /// Check whether the DMAC reported a transfer error (TERR) since the last
+    /// call. The DMAC disables the channel on a transfer error, so the
+    /// transfer should then be [`stop`](PingPongTransfer::stop)ped.
+    #[inline]
+    pub fn transfer_error(&mut self) -> bool {
+        core::mem::replace(&mut self.error, false)
+    }
+
+    /// Immediately stop the DMA transfer and release all owned resources
+    #[inline]
+    pub fn stop(
+        self,
+    ) -> (
+        Channel<ChannelId<C>, Ready>,
+        S,
+        D,
+        &'static mut LinkedDescriptors<2>,
+    ) {
+        let (chan, source, destination, descriptors) = self.xfer.stop();
+        for descriptor in descriptors.descriptors.iter_mut() {
+            descriptor.btctrl.set_valid(true);
+        }
+        (chan, source, destination, descriptors)
+    }
+
+    #[inline]
+    fn hold(&mut self, index: usize) {
+        self.held[index] = true;
+        // Invalidate the descriptor so that the DMAC suspends instead of
+        // overwriting a half the application is still using.
+        self.xfer.descriptors.descriptors[index]
+            .btctrl
+            .set_valid(false);
+    }
+}
+
+impl<C, S, D, W> PingPongTransfer<C, S, D, W>
+where
+    S: BlockBuffers<2>,
+    D: BlockBuffers<2, Beat = S::Beat>,
+    C: AnyChannel<Status = Busy>,
+    W: FnMut(CallbackStatus) + 'static,
+{
+    /// This function should be put inside the DMAC interrupt handler. It
+    /// services the interrupt flags like
+    /// [`service`](PingPongTransfer::service), then calls the
+    /// [`PingPongTransfer`]'s waker (if it exists) if a half was completed,
+    /// the DMAC stalled or a transfer error occurred.
+    #[inline]
+    pub fn callback(&mut self) -> Option<Half> {
+        let overrun = self.overrun;
+        let error = self.error;
+        let half = self.service();
+
+        let status = if self.error && !error {
+            Some(CallbackStatus::TransferError)
+        } else if half.is_some() {
+            Some(CallbackStatus::TransferComplete)
+        } else if self.overrun && !overrun {
+            Some(CallbackStatus::TransferSuspended)
+        } else {
+            None
+        };
+
+        if let (Some(w), Some(status)) = (self.waker.as_mut(), status) {
+            w(status);
+        }
+        half
+    }
+}
+
+impl<C, P, B, W> PingPongTransfer<C, Repeat<P>, [B; 2], W>
+where
+    P: Buffer,
+    B: Buffer<Beat = P::Beat>,
+    C: AnyChannel<Status = Busy>,
+{
+    /// Borrow the [`ready`](PingPongTransfer::ready) destination half, if
+    /// any. The DMAC will not write to it until it is
+    /// [`release`](PingPongTransfer::release)d.
+    #[inline]
+    pub fn ready_destination(&mut self) -> Option<(Half, &mut B)> {
+        let half = self.ready()?;
+        // Make sure all the DMAC writes are visible before reading the buffer
+        core::sync::atomic::fence(core::sync::atomic::Ordering::Acquire);
+        Some((half, &mut self.xfer.destination[half.index()]))
+    }
+}
+
+impl<C, B, P, W> PingPongTransfer<C, [B; 2], Repeat<P>, W>
+where
+    B: Buffer,
+    P: Buffer<Beat = B::Beat>,
+    C: AnyChannel<Status = Busy>,
+{
+    /// Borrow the [`ready`](PingPongTransfer::ready) source half, if any, to
+    /// refill it. The DMAC will not read from it until it is
+    /// [`release`](PingPongTransfer::release)d.
+    #[inline]
+    pub fn ready_source(&mut self) -> Option<(Half, &mut B)> {
+        let half = self.ready()?;
+        Some((half, &mut self.xfer.source[half.index()]))
+    }
+}
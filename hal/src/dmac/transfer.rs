@@ -86,7 +86,7 @@
 use super::{
     channel::{AnyChannel, Busy, CallbackStatus, Channel, ChannelId, InterruptFlags, Ready},
     dma_controller::{ChId, TriggerAction, TriggerSource},
-    BlockTransferControl, DmacDescriptor, Error, Result, DESCRIPTOR_SECTION,
+    BlockTransferControl, DmacDescriptor, Error, Result, DEFAULT_DESCRIPTOR, DESCRIPTOR_SECTION,
 };
 use crate::typelevel::{Is, Sealed};
 use core::{ptr::null_mut, sync::atomic};
@@ -108,6 +108,22 @@ pub enum BeatSize {
     Word = 0x02,
 }
 
+/// Action taken by the DMAC when a block transfer completes
+#[derive(Clone, Copy, BitfieldSpecifier)]
+#[bits = 2]
+pub enum BlockAction {
+    /// Disable the channel if this was the last block, otherwise continue
+    /// with the next block
+    NoAct = 0x00,
+    /// Same as [`NoAct`](BlockAction::NoAct), and additionally raise the
+    /// transfer complete interrupt
+    Int = 0x01,
+    /// Suspend the channel after this block
+    Suspend = 0x02,
+    /// Suspend the channel and raise the transfer complete interrupt
+    Both = 0x03,
+}
+
 /// Convert 8, 16 and 32 bit types
 /// into [`BeatSize`](BeatSize)
 ///
@@ -356,76 +372,110 @@ where
 {
     #[inline]
     fn check_buffer_pair(source: &S, destination: &D) -> Result<()> {
-        let src_len = source.buffer_len();
-        let dst_len = destination.buffer_len();
-
-        if src_len > 1 && dst_len > 1 && src_len != dst_len {
-            Err(Error::LengthMismatch)
-        } else {
-            Ok(())
-        }
+        check_buffer_pair(source, destination)
     }
 
     #[inline]
     unsafe fn fill_descriptor(source: &mut S, destination: &mut D, circular: bool) {
-        let id = <C as AnyChannel>::Id::USIZE;
-
-        // Enable support for circular transfers. If circular_xfer is true,
-        // we set the address of the "next" block descriptor to actually
-        // be the same address as the current block descriptor.
-        // Otherwise we set it to NULL, which terminates the transaction.
-        // TODO: Enable support for linked lists (?)
-        let descaddr = if circular {
-            // SAFETY This is safe as we are only reading the descriptor's address,
-            // and not actually writing any data to it. We also assume the descriptor
-            // will never be moved.
-            &mut DESCRIPTOR_SECTION[id] as *mut _
-        } else {
-            null_mut()
-        };
+        fill_descriptor(
+            <C as AnyChannel>::Id::USIZE,
+            source,
+            destination,
+            circular,
+            BlockAction::NoAct,
+        )
+    }
+}
 
-        let src_ptr = source.dma_ptr();
-        let src_inc = source.incrementing();
-        let src_len = source.buffer_len();
-
-        let dst_ptr = destination.dma_ptr();
-        let dst_inc = destination.incrementing();
-        let dst_len = destination.buffer_len();
-
-        let length = core::cmp::max(src_len, dst_len);
-
-        // Channel::xfer_complete() tests the channel enable bit, which indicates
-        // that a transfer has completed iff the blockact field in btctrl is not
-        // set to SUSPEND.  We implicitly leave blockact set to NOACT here; if
-        // that changes Channel::xfer_complete() may need to be modified.
-        let btctrl = BlockTransferControl::new()
-            .with_srcinc(src_inc)
-            .with_dstinc(dst_inc)
-            .with_beatsize(S::Beat::BEATSIZE)
-            .with_valid(true);
-
-        let xfer_descriptor = DmacDescriptor {
-            // Next descriptor address:  0x0 terminates the transaction (no linked list),
-            // any other address points to the next block descriptor
-            descaddr,
-            // Source address: address of the last beat transfer source in block
-            srcaddr: src_ptr as *mut _,
-            // Destination address: address of the last beat transfer destination in block
-            dstaddr: dst_ptr as *mut _,
-            // Block transfer count: number of beats in block transfer
-            btcnt: length as u16,
-            // Block transfer control: Datasheet  section 19.8.2.1 p.329
-            btctrl,
-        };
+/// Check that the source and destination buffers can be used in the same
+/// block transfer
+#[inline]
+pub(super) fn check_buffer_pair<S, D>(source: &S, destination: &D) -> Result<()>
+where
+    S: Buffer,
+    D: Buffer<Beat = S::Beat>,
+{
+    let src_len = source.buffer_len();
+    let dst_len = destination.buffer_len();
 
-        // SAFETY this is safe as long as we ONLY write to the descriptor
-        // belonging to OUR channel. We assume this is the only place
-        // in the entire library that this section or the array
-        // will be written to.
-        DESCRIPTOR_SECTION[id] = xfer_descriptor;
+    if src_len > 1 && dst_len > 1 && src_len != dst_len {
+        Err(Error::LengthMismatch)
+    } else {
+        Ok(())
     }
 }
 
+/// Fill the descriptor of channel `id` with a single block transfer from
+/// `source` to `destination`, ending with `blockact`
+///
+/// # Safety
+///
+/// The channel `id` must not be running, and must be owned by the caller.
+#[inline]
+pub(super) unsafe fn fill_descriptor<S, D>(
+    id: usize,
+    source: &mut S,
+    destination: &mut D,
+    circular: bool,
+    blockact: BlockAction,
+) where
+    S: Buffer,
+    D: Buffer<Beat = S::Beat>,
+{
+    // Enable support for circular transfers. If circular_xfer is true,
+    // we set the address of the "next" block descriptor to actually
+    // be the same address as the current block descriptor.
+    // Otherwise we set it to NULL, which terminates the transaction.
+    let descaddr = if circular {
+        // SAFETY This is safe as we are only reading the descriptor's address,
+        // and not actually writing any data to it. We also assume the descriptor
+        // will never be moved.
+        &mut DESCRIPTOR_SECTION[id] as *mut _
+    } else {
+        null_mut()
+    };
+
+    let src_ptr = source.dma_ptr();
+    let src_inc = source.incrementing();
+    let src_len = source.buffer_len();
+
+    let dst_ptr = destination.dma_ptr();
+    let dst_inc = destination.incrementing();
+    let dst_len = destination.buffer_len();
+
+    let length = core::cmp::max(src_len, dst_len);
+
+    // Channel::xfer_complete() tests the channel enable bit, which indicates
+    // that a transfer has completed iff the blockact field in btctrl is not
+    // set to SUSPEND.  Callers pass either NOACT, or INT when they wait for
+    // the transfer complete interrupt; if that changes
+    // Channel::xfer_complete() may need to be modified.
+    let btctrl = BlockTransferControl::new()
+        .with_srcinc(src_inc)
+        .with_dstinc(dst_inc)
+        .with_beatsize(S::Beat::BEATSIZE)
+        .with_blockact(blockact)
+        .with_valid(true);
+
+    let xfer_descriptor = DmacDescriptor {
+        // Next descriptor address:  0x0 terminates the transaction (no linked list),
+        // any other address points to the next block descriptor
+        descaddr,
+        // Source address: address of the last beat transfer source in block
+        srcaddr: src_ptr as *mut _,
+        // Destination address: address of the last beat transfer destination in block
+        dstaddr: dst_ptr as *mut _,
+        // Block transfer count: number of beats in block transfer
+        btcnt: length as u16,
+        // Block transfer control: Datasheet  section 19.8.2.1 p.329
+        btctrl,
+    };
+
+    // SAFETY this is safe as long as we ONLY write to the descriptor
+    // belonging to OUR channel, while the channel is not running.
+    DESCRIPTOR_SECTION[id] = xfer_descriptor;
+}
+
 impl<C, S, D> Transfer<C, BufferPair<S, D>>
 where
     S: Buffer,
@@ -735,3 +785,520 @@ where
         }
     }
 }
+
+//==============================================================================
+// BlockBuffers
+//==============================================================================
+
+/// Source or destination side of a [`LinkedTransfer`], providing one
+/// [`Buffer`] for each of the `N` blocks of the transfer.
+///
+/// This trait is implemented for arrays of [`Buffer`]s, where each element is
+/// used by a single block, and for [`Repeat`], which uses the same buffer for
+/// every block.
+///
+/// # Safety
+///
+/// The same invariants as for [`Buffer`] must be upheld for every block index
+/// in `0..N`.
+pub unsafe trait BlockBuffers<const N: usize> {
+    /// DMAC beat size
+    type Beat: Beat;
+    /// Pointer to the buffer used by block `index`. See [`Buffer::dma_ptr`].
+    fn block_dma_ptr(&mut self, index: usize) -> *mut Self::Beat;
+    /// Return whether the buffer used by block `index` is incrementing
+    fn block_incrementing(&self, index: usize) -> bool;
+    /// Length, in beats, of the buffer used by block `index`
+    fn block_len(&self, index: usize) -> usize;
+}
+
+unsafe impl<B: Buffer, const N: usize> BlockBuffers<N> for [B; N] {
+    type Beat = B::Beat;
+
+    #[inline]
+    fn block_dma_ptr(&mut self, index: usize) -> *mut Self::Beat {
+        self[index].dma_ptr()
+    }
+
+    #[inline]
+    fn block_incrementing(&self, index: usize) -> bool {
+        self[index].incrementing()
+    }
+
+    #[inline]
+    fn block_len(&self, index: usize) -> usize {
+        self[index].buffer_len()
+    }
+}
+
+/// Use the same [`Buffer`] for every block of a [`LinkedTransfer`].
+///
+/// This is typically used for the peripheral side of a scatter-gather
+/// transfer, for example an [`Spi`](crate::sercom::spi::Spi) receiving
+/// several memory buffers one after the other.
+pub struct Repeat<B: Buffer>(pub B);
+
+unsafe impl<B: Buffer, const N: usize> BlockBuffers<N> for Repeat<B> {
+    type Beat = B::Beat;
+
+    #[inline]
+    fn block_dma_ptr(&mut self, _index: usize) -> *mut Self::Beat {
+        self.0.dma_ptr()
+    }
+
+    #[inline]
+    fn block_incrementing(&self, _index: usize) -> bool {
+        self.0.incrementing()
+    }
+
+    #[inline]
+    fn block_len(&self, _index: usize) -> usize {
+        self.0.buffer_len()
+    }
+}
+
+//==============================================================================
+// LinkedDescriptors
+//==============================================================================
+
+/// Storage for the `N` block descriptors of a [`LinkedTransfer`].
+///
+/// The DMAC reads the descriptors from memory while the transfer is running,
+/// so the storage must outlive the transfer. It is usually placed in a
+/// `static`:
+///
+/// ```
+/// static mut DESCRIPTORS: LinkedDescriptors<4> = LinkedDescriptors::new();
+/// ```
+pub struct LinkedDescriptors<const N: usize> {
+    pub(super) descriptors: [DmacDescriptor; N],
+}
+
+impl<const N: usize> LinkedDescriptors<N> {
+    /// Create a new, empty set of descriptors
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            descriptors: [DEFAULT_DESCRIPTOR; N],
+        }
+    }
+}
+
+impl<const N: usize> Default for LinkedDescriptors<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// SAFETY: The raw pointers held by the descriptors are only ever dereferenced
+// by the DMAC, never by software.
+unsafe impl<const N: usize> Send for LinkedDescriptors<N> {}
+
+//==============================================================================
+// LinkedTransfer
+//==============================================================================
+
+/// Linked-list DMA transfer, made of `N` consecutive blocks.
+///
+/// Block `i` transfers from the `i`th source buffer to the `i`th destination
+/// buffer, as provided by the [`BlockBuffers`] implementations of `S` and `D`.
+/// Like a [`Transfer`], a `LinkedTransfer` owns the channel, buffers and
+/// descriptors until the transfer is done and [`LinkedTransfer::wait`] or
+/// [`LinkedTransfer::stop`] is called.
+pub struct LinkedTransfer<Chan, S, D, const N: usize, W = ()>
+where
+    Chan: AnyChannel,
+    S: BlockBuffers<N>,
+    D: BlockBuffers<N, Beat = S::Beat>,
+{
+    pub(super) chan: Chan,
+    pub(super) source: S,
+    pub(super) destination: D,
+    pub(super) descriptors: &'static mut LinkedDescriptors<N>,
+    waker: Option<W>,
+    complete: bool,
+}
+
+impl<C, S, D, const N: usize> LinkedTransfer<C, S, D, N>
+where
+    S: BlockBuffers<N> + 'static,
+    D: BlockBuffers<N, Beat = S::Beat> + 'static,
+    C: AnyChannel<Status = Ready>,
+{
+    /// Safely construct a new `LinkedTransfer`. To guarantee memory safety,
+    /// both sides of the transfer are required to be `'static`.
+    ///
+    /// If `circular` is `true`, the last block links back to the first one and
+    /// the transfer runs until it is [`stop`](LinkedTransfer::stop)ped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::LengthMismatch`] if, for any block, the source and
+    /// destination buffers both have a length > 1 and are not of equal length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any block is longer than 65535 beats, the maximum block
+    /// transfer count of the DMAC.
+    #[allow(clippy::new_ret_no_self)]
+    #[inline]
+    pub fn new(
+        chan: C,
+        source: S,
+        destination: D,
+        descriptors: &'static mut LinkedDescriptors<N>,
+        circular: bool,
+    ) -> Result<Self> {
+        Self::check_blocks(&source, &destination)?;
+
+        // SAFETY: The safety checks are done by the function signature and the buffer
+        // length verification
+        Ok(unsafe { Self::new_unchecked(chan, source, destination, descriptors, circular) })
+    }
+}
+
+impl<C, S, D, const N: usize> LinkedTransfer<C, S, D, N>
+where
+    S: BlockBuffers<N>,
+    D: BlockBuffers<N, Beat = S::Beat>,
+    C: AnyChannel<Status = Ready>,
+{
+    /// Construct a new `LinkedTransfer` without checking for memory safety.
+    ///
+    /// # Safety
+    ///
+    /// To guarantee the safety of creating a `LinkedTransfer` using this
+    /// method, you must uphold some invariants:
+    ///
+    /// * A `LinkedTransfer` holding a `Channel<Id, Running>` must *never* be
+    ///   dropped. It should *always* be explicitly be `wait`ed upon or
+    ///   `stop`ped.
+    ///
+    /// * For every block, the size in bytes or the source and destination
+    ///   buffers should be exacly the same, unless one or both buffers are of
+    ///   length 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any block is longer than 65535 beats, the maximum block
+    /// transfer count of the DMAC.
+    #[inline]
+    pub unsafe fn new_unchecked(
+        chan: C,
+        mut source: S,
+        mut destination: D,
+        descriptors: &'static mut LinkedDescriptors<N>,
+        circular: bool,
+    ) -> Self {
+        Self::fill_descriptors(&mut source, &mut destination, descriptors, circular);
+
+        LinkedTransfer {
+            chan,
+            source,
+            destination,
+            descriptors,
+            waker: None,
+            complete: false,
+        }
+    }
+
+    /// Raise the transfer complete interrupt at the end of every block,
+    /// instead of only at the end of the whole transfer. The waker (if any)
+    /// will then be called once per block.
+    #[inline]
+    pub fn block_interrupts(self, enable: bool) -> Self {
+        for descriptor in self.descriptors.descriptors.iter_mut() {
+            // The last block of a one-shot transfer always signals completion
+            let blockact = if enable || descriptor.descaddr.is_null() {
+                BlockAction::Int
+            } else {
+                BlockAction::NoAct
+            };
+            descriptor.btctrl.set_blockact(blockact);
+        }
+
+        // SAFETY: The channel is not running yet, so we are free to update its
+        // descriptor.
+        unsafe {
+            DESCRIPTOR_SECTION[<C as AnyChannel>::Id::USIZE] = self.descriptors.descriptors[0];
+        }
+        self
+    }
+
+    /// Append a waker to the transfer. This will be called when the DMAC
+    /// interrupt is called.
+    #[inline]
+    pub fn with_waker<W: FnMut(CallbackStatus) + 'static>(
+        self,
+        waker: W,
+    ) -> LinkedTransfer<C, S, D, N, W> {
+        LinkedTransfer {
+            chan: self.chan,
+            source: self.source,
+            destination: self.destination,
+            descriptors: self.descriptors,
+            waker: Some(waker),
+            complete: self.complete,
+        }
+    }
+}
+
+impl<C, S, D, const N: usize, W> LinkedTransfer<C, S, D, N, W>
+where
+    S: BlockBuffers<N>,
+    D: BlockBuffers<N, Beat = S::Beat>,
+    C: AnyChannel,
+{
+    #[inline]
+    fn check_blocks(source: &S, destination: &D) -> Result<()> {
+        if N == 0 {
+            return Err(Error::LengthMismatch);
+        }
+
+        for i in 0..N {
+            let src_len = source.block_len(i);
+            let dst_len = destination.block_len(i);
+
+            if src_len > 1 && dst_len > 1 && src_len != dst_len {
+                return Err(Error::LengthMismatch);
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    unsafe fn fill_descriptors(
+        source: &mut S,
+        destination: &mut D,
+        descriptors: &mut LinkedDescriptors<N>,
+        circular: bool,
+    ) {
+        let id = <C as AnyChannel>::Id::USIZE;
+        let base = descriptors.descriptors.as_mut_ptr();
+
+        for i in 0..N {
+            // Each descriptor points to the next one in the list. The last one
+            // either points back to the first descriptor (circular), or to NULL,
+            // which terminates the transaction.
+            let descaddr = if i + 1 < N {
+                base.add(i + 1)
+            } else if circular {
+                base
+            } else {
+                null_mut()
+            };
+
+            let length = core::cmp::max(source.block_len(i), destination.block_len(i));
+            assert!(
+                length <= u16::MAX as usize,
+                "DMA blocks are limited to 65535 beats"
+            );
+
+            // The last block of a one-shot transfer raises the transfer complete
+            // interrupt, like a regular `Transfer` would.
+            let blockact = if descaddr.is_null() {
+                BlockAction::Int
+            } else {
+                BlockAction::NoAct
+            };
+
+            let btctrl = BlockTransferControl::new()
+                .with_srcinc(source.block_incrementing(i))
+                .with_dstinc(destination.block_incrementing(i))
+                .with_beatsize(S::Beat::BEATSIZE)
+                .with_blockact(blockact)
+                .with_valid(true);
+
+            *base.add(i) = DmacDescriptor {
+                descaddr,
+                srcaddr: source.block_dma_ptr(i) as *mut _,
+                dstaddr: destination.block_dma_ptr(i) as *mut _,
+                btcnt: length as u16,
+                btctrl,
+            };
+        }
+
+        // The DMAC always fetches the first descriptor from the descriptor section.
+        // The next descriptor address of this copy points into the list, so the
+        // following blocks are fetched from `descriptors`.
+        //
+        // SAFETY this is safe as long as we ONLY write to the descriptor
+        // belonging to OUR channel.
+        DESCRIPTOR_SECTION[id] = *base;
+    }
+}
+
+impl<C, S, D, const N: usize, W> LinkedTransfer<C, S, D, N, W>
+where
+    S: BlockBuffers<N>,
+    D: BlockBuffers<N, Beat = S::Beat>,
+    C: AnyChannel<Status = Ready>,
+{
+    /// Begin DMA transfer. If [TriggerSource::DISABLE](TriggerSource::DISABLE)
+    /// is used, a software trigger will be issued to the DMA channel to
+    /// launch the transfer.
+    ///
+    /// Note that with [`TriggerAction::BLOCK`], one trigger is required for
+    /// each block of the transfer.
+    #[inline]
+    pub fn begin(
+        mut self,
+        trig_src: TriggerSource,
+        trig_act: TriggerAction,
+    ) -> LinkedTransfer<Channel<ChannelId<C>, Busy>, S, D, N, W> {
+        self.complete = false;
+
+        // Memory barrier to prevent the compiler/CPU from re-ordering read/write
+        // operations beyond this fence.
+        // (see https://docs.rust-embedded.org/embedonomicon/dma.html#compiler-misoptimizations)
+        atomic::fence(atomic::Ordering::Release); //  ▲
+        let chan = self.chan.into().start(trig_src, trig_act);
+
+        LinkedTransfer {
+            chan,
+            source: self.source,
+            destination: self.destination,
+            descriptors: self.descriptors,
+            waker: self.waker,
+            complete: self.complete,
+        }
+    }
+}
+
+impl<C, S, D, const N: usize, W> LinkedTransfer<C, S, D, N, W>
+where
+    S: BlockBuffers<N>,
+    D: BlockBuffers<N, Beat = S::Beat>,
+    C: AnyChannel<Status = Busy>,
+{
+    /// Issue a software trigger request to the corresponding channel.
+    /// Note that is not guaranteed that the trigger request will register,
+    /// if a trigger request is already pending for the channel.
+    #[inline]
+    pub fn software_trigger(&mut self) {
+        self.chan.as_mut().software_trigger();
+    }
+
+    /// Check if the transfer has completed. A circular transfer never
+    /// completes.
+    #[inline]
+    pub fn complete(&mut self) -> bool {
+        if !self.complete {
+            let chan = self.chan.as_mut();
+            let complete = chan.xfer_complete();
+            self.complete = complete;
+        }
+        self.complete
+    }
+
+    /// Return the index of the block the DMAC is currently working on, or
+    /// `None` if the transfer has completed or the write-back descriptor does
+    /// not point into the descriptor list.
+    ///
+    /// The DMAC only updates its write-back descriptor when it fetches a new
+    /// block, so the returned index is the block that was most recently
+    /// started.
+    #[inline]
+    pub fn active_block(&mut self) -> Option<usize> {
+        if self.complete() {
+            return None;
+        }
+
+        let id = <C as AnyChannel>::Id::USIZE;
+        // SAFETY: The write-back section is only ever written to by the DMAC. We
+        // read the field without creating a reference to the static.
+        let next =
+            unsafe { core::ptr::read_volatile(core::ptr::addr_of!(super::WRITEBACK[id].descaddr)) };
+
+        if next.is_null() {
+            // Only the last block of a one-shot transfer has no next descriptor
+            return Some(N - 1);
+        }
+
+        // Compare addresses rather than use `offset_from`, since the write-back
+        // descriptor might not point within `descriptors`, e.g. if it has not
+        // been written by the DMAC yet
+        let size = core::mem::size_of::<DmacDescriptor>();
+        let base = self.descriptors.descriptors.as_ptr() as usize;
+        let offset = (next as usize).checked_sub(base)?;
+        if offset % size != 0 || offset / size >= N {
+            return None;
+        }
+        Some((offset / size + N - 1) % N)
+    }
+
+    /// Checks and clears the block transfer complete interrupt flag
+    #[inline]
+    pub fn block_transfer_interrupt(&mut self) -> bool {
+        self.chan
+            .as_mut()
+            .check_and_clear_interrupts(InterruptFlags::new().with_tcmpl(true))
+            .tcmpl()
+    }
+
+    /// Wait for the DMA transfer to complete and release all owned
+    /// resources
+    ///
+    /// # Blocking: This method may block
+    #[inline]
+    pub fn wait(
+        mut self,
+    ) -> (
+        Channel<ChannelId<C>, Ready>,
+        S,
+        D,
+        &'static mut LinkedDescriptors<N>,
+    ) {
+        // Wait for transfer to complete
+        while !self.complete() {}
+        self.stop()
+    }
+
+    /// Non-blocking; Immediately stop the DMA transfer and release all owned
+    /// resources
+    #[inline]
+    pub fn stop(
+        self,
+    ) -> (
+        Channel<ChannelId<C>, Ready>,
+        S,
+        D,
+        &'static mut LinkedDescriptors<N>,
+    ) {
+        let chan = self.chan.into().free();
+
+        // Memory barrier to prevent the compiler/CPU from re-ordering read/write
+        // operations beyond this fence.
+        // (see https://docs.rust-embedded.org/embedonomicon/dma.html#compiler-misoptimizations)
+        atomic::fence(atomic::Ordering::Acquire); // ▼
+
+        (chan, self.source, self.destination, self.descriptors)
+    }
+}
+
+impl<C, S, D, const N: usize, W> LinkedTransfer<C, S, D, N, W>
+where
+    S: BlockBuffers<N>,
+    D: BlockBuffers<N, Beat = S::Beat>,
+    C: AnyChannel<Status = Busy>,
+    W: FnMut(CallbackStatus) + 'static,
+{
+    /// This function should be put inside the DMAC interrupt handler.
+    /// It will take care of calling the [`LinkedTransfer`]'s waker (if it
+    /// exists). Unlike a [`Transfer`]'s waker, the waker is kept and called
+    /// again on every interrupt, ie at the end of every block if
+    /// [`block_interrupts`](LinkedTransfer::block_interrupts) is enabled.
+    #[inline]
+    pub fn callback(&mut self) {
+        let status = self.chan.as_mut().callback();
+
+        if let CallbackStatus::TransferComplete = status {
+            self.complete = self.chan.as_mut().xfer_complete();
+        }
+
+        if let Some(w) = self.waker.as_mut() {
+            w(status)
+        }
+    }
+}
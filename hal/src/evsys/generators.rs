@@ -0,0 +1,281 @@
+//! Type-level event generators
+//!
+//! Each type in this module implements [`Generator`](super::Generator) and
+//! identifies an event output that can be connected to a
+//! [`Channel`](super::Channel).
+
+use seq_macro::seq;
+
+//==============================================================================
+// SAMD11
+//==============================================================================
+
+#[cfg(feature = "samd11")]
+event_ids!(Generator:
+    (RtcCmp0 = 0x01, "RTC compare 0 or alarm 0")
+    (RtcCmp1 = 0x02, "RTC compare 1")
+    (RtcOvf = 0x03, "RTC overflow")
+);
+
+#[cfg(feature = "samd11")]
+seq!(N in 0..8 {
+    event_ids!(Generator:
+        #( (RtcPer~N = 0x04 + N, concat!("RTC period ", N)) )*
+        #( (EicExtInt~N = 0x0C + N, concat!("EIC external interrupt ", N)) )*
+    );
+});
+
+#[cfg(feature = "samd11")]
+seq!(N in 0..6 {
+    event_ids!(Generator:
+        #( (DmacCh~N = 0x14 + N, concat!("DMAC channel ", N)) )*
+    );
+});
+
+#[cfg(feature = "samd11")]
+event_ids!(Generator:
+    (Tcc0Ovf = 0x1A, "TCC0 overflow")
+    (Tcc0Trg = 0x1B, "TCC0 trigger")
+    (Tcc0Cnt = 0x1C, "TCC0 counter")
+    (Tcc0Mc0 = 0x1D, "TCC0 match/capture 0")
+    (Tcc0Mc1 = 0x1E, "TCC0 match/capture 1")
+    (Tcc0Mc2 = 0x1F, "TCC0 match/capture 2")
+    (Tcc0Mc3 = 0x20, "TCC0 match/capture 3")
+    (Tc1Ovf = 0x21, "TC1 overflow/underflow")
+    (Tc1Mc0 = 0x22, "TC1 match/capture 0")
+    (Tc1Mc1 = 0x23, "TC1 match/capture 1")
+    (Tc2Ovf = 0x24, "TC2 overflow/underflow")
+    (Tc2Mc0 = 0x25, "TC2 match/capture 0")
+    (Tc2Mc1 = 0x26, "TC2 match/capture 1")
+    (AdcResRdy = 0x27, "ADC result ready")
+    (AdcWinMon = 0x28, "ADC window monitor")
+    (AcComp0 = 0x29, "AC comparator 0")
+    (AcComp1 = 0x2A, "AC comparator 1")
+    (AcWin0 = 0x2B, "AC window 0")
+    (DacEmpty = 0x2C, "DAC data buffer empty")
+);
+
+//==============================================================================
+// SAMD21
+//==============================================================================
+
+#[cfg(feature = "samd21")]
+event_ids!(Generator:
+    (RtcCmp0 = 0x01, "RTC compare 0 or alarm 0")
+    (RtcCmp1 = 0x02, "RTC compare 1")
+    (RtcOvf = 0x03, "RTC overflow")
+);
+
+#[cfg(feature = "samd21")]
+seq!(N in 0..8 {
+    event_ids!(Generator:
+        #( (RtcPer~N = 0x04 + N, concat!("RTC period ", N)) )*
+    );
+});
+
+#[cfg(feature = "samd21")]
+seq!(N in 0..16 {
+    event_ids!(Generator:
+        #( (EicExtInt~N = 0x0C + N, concat!("EIC external interrupt ", N)) )*
+    );
+});
+
+#[cfg(feature = "samd21")]
+seq!(N in 0..4 {
+    event_ids!(Generator:
+        #( (DmacCh~N = 0x1E + N, concat!("DMAC channel ", N)) )*
+    );
+});
+
+#[cfg(feature = "samd21")]
+event_ids!(Generator:
+    (Tcc0Ovf = 0x22, "TCC0 overflow")
+    (Tcc0Trg = 0x23, "TCC0 trigger")
+    (Tcc0Cnt = 0x24, "TCC0 counter")
+    (Tcc0Mc0 = 0x25, "TCC0 match/capture 0")
+    (Tcc0Mc1 = 0x26, "TCC0 match/capture 1")
+    (Tcc0Mc2 = 0x27, "TCC0 match/capture 2")
+    (Tcc0Mc3 = 0x28, "TCC0 match/capture 3")
+    (Tcc1Ovf = 0x29, "TCC1 overflow")
+    (Tcc1Trg = 0x2A, "TCC1 trigger")
+    (Tcc1Cnt = 0x2B, "TCC1 counter")
+    (Tcc1Mc0 = 0x2C, "TCC1 match/capture 0")
+    (Tcc1Mc1 = 0x2D, "TCC1 match/capture 1")
+    (Tcc2Ovf = 0x2E, "TCC2 overflow")
+    (Tcc2Trg = 0x2F, "TCC2 trigger")
+    (Tcc2Cnt = 0x30, "TCC2 counter")
+    (Tcc2Mc0 = 0x31, "TCC2 match/capture 0")
+    (Tcc2Mc1 = 0x32, "TCC2 match/capture 1")
+);
+
+#[cfg(feature = "samd21")]
+event_ids!(Generator:
+    (Tc3Ovf = 0x33, "TC3 overflow/underflow")
+    (Tc3Mc0 = 0x34, "TC3 match/capture 0")
+    (Tc3Mc1 = 0x35, "TC3 match/capture 1")
+    (Tc4Ovf = 0x36, "TC4 overflow/underflow")
+    (Tc4Mc0 = 0x37, "TC4 match/capture 0")
+    (Tc4Mc1 = 0x38, "TC4 match/capture 1")
+    (Tc5Ovf = 0x39, "TC5 overflow/underflow")
+    (Tc5Mc0 = 0x3A, "TC5 match/capture 0")
+    (Tc5Mc1 = 0x3B, "TC5 match/capture 1")
+    (Tc6Ovf = 0x3C, "TC6 overflow/underflow")
+    (Tc6Mc0 = 0x3D, "TC6 match/capture 0")
+    (Tc6Mc1 = 0x3E, "TC6 match/capture 1")
+    (Tc7Ovf = 0x3F, "TC7 overflow/underflow")
+    (Tc7Mc0 = 0x40, "TC7 match/capture 0")
+    (Tc7Mc1 = 0x41, "TC7 match/capture 1")
+);
+
+#[cfg(feature = "samd21")]
+event_ids!(Generator:
+    (AdcResRdy = 0x42, "ADC result ready")
+    (AdcWinMon = 0x43, "ADC window monitor")
+    (AcComp0 = 0x44, "AC comparator 0")
+    (AcComp1 = 0x45, "AC comparator 1")
+    (AcWin0 = 0x46, "AC window 0")
+    (DacEmpty = 0x47, "DAC data buffer empty")
+);
+
+//==============================================================================
+// SAMD5x/E5x
+//==============================================================================
+
+#[cfg(feature = "thumbv7")]
+event_ids!(Generator:
+    (XoscFail0 = 0x01, "XOSC0 clock failure")
+    (XoscFail1 = 0x02, "XOSC1 clock failure")
+    (Xosc32kFail = 0x03, "XOSC32K clock failure")
+);
+
+#[cfg(feature = "thumbv7")]
+seq!(N in 0..8 {
+    event_ids!(Generator:
+        #( (RtcPer~N = 0x04 + N, concat!("RTC period ", N)) )*
+    );
+});
+
+#[cfg(feature = "thumbv7")]
+seq!(N in 0..4 {
+    event_ids!(Generator:
+        #( (RtcCmp~N = 0x0C + N, concat!("RTC compare ", N, " or alarm ", N)) )*
+    );
+});
+
+#[cfg(feature = "thumbv7")]
+event_ids!(Generator:
+    (RtcTamper = 0x10, "RTC tamper detection")
+    (RtcOvf = 0x11, "RTC overflow")
+);
+
+#[cfg(feature = "thumbv7")]
+seq!(N in 0..16 {
+    event_ids!(Generator:
+        #( (EicExtInt~N = 0x12 + N, concat!("EIC external interrupt ", N)) )*
+    );
+});
+
+#[cfg(feature = "thumbv7")]
+seq!(N in 0..4 {
+    event_ids!(Generator:
+        #( (DmacCh~N = 0x22 + N, concat!("DMAC channel ", N)) )*
+    );
+});
+
+#[cfg(feature = "thumbv7")]
+event_ids!(Generator:
+    (PacAccErr = 0x26, "PAC access error")
+    (Tcc0Ovf = 0x29, "TCC0 overflow")
+    (Tcc0Trg = 0x2A, "TCC0 trigger")
+    (Tcc0Cnt = 0x2B, "TCC0 counter")
+    (Tcc0Mc0 = 0x2C, "TCC0 match/capture 0")
+    (Tcc0Mc1 = 0x2D, "TCC0 match/capture 1")
+    (Tcc0Mc2 = 0x2E, "TCC0 match/capture 2")
+    (Tcc0Mc3 = 0x2F, "TCC0 match/capture 3")
+    (Tcc0Mc4 = 0x30, "TCC0 match/capture 4")
+    (Tcc0Mc5 = 0x31, "TCC0 match/capture 5")
+    (Tcc1Ovf = 0x32, "TCC1 overflow")
+    (Tcc1Trg = 0x33, "TCC1 trigger")
+    (Tcc1Cnt = 0x34, "TCC1 counter")
+    (Tcc1Mc0 = 0x35, "TCC1 match/capture 0")
+    (Tcc1Mc1 = 0x36, "TCC1 match/capture 1")
+    (Tcc1Mc2 = 0x37, "TCC1 match/capture 2")
+    (Tcc1Mc3 = 0x38, "TCC1 match/capture 3")
+    (Tcc2Ovf = 0x39, "TCC2 overflow")
+    (Tcc2Trg = 0x3A, "TCC2 trigger")
+    (Tcc2Cnt = 0x3B, "TCC2 counter")
+    (Tcc2Mc0 = 0x3C, "TCC2 match/capture 0")
+    (Tcc2Mc1 = 0x3D, "TCC2 match/capture 1")
+    (Tcc2Mc2 = 0x3E, "TCC2 match/capture 2")
+    (Tcc3Ovf = 0x3F, "TCC3 overflow")
+    (Tcc3Trg = 0x40, "TCC3 trigger")
+    (Tcc3Cnt = 0x41, "TCC3 counter")
+    (Tcc3Mc0 = 0x42, "TCC3 match/capture 0")
+    (Tcc3Mc1 = 0x43, "TCC3 match/capture 1")
+    (Tcc4Ovf = 0x44, "TCC4 overflow")
+    (Tcc4Trg = 0x45, "TCC4 trigger")
+    (Tcc4Cnt = 0x46, "TCC4 counter")
+    (Tcc4Mc0 = 0x47, "TCC4 match/capture 0")
+    (Tcc4Mc1 = 0x48, "TCC4 match/capture 1")
+);
+
+#[cfg(feature = "thumbv7")]
+event_ids!(Generator:
+    (Tc0Ovf = 0x49, "TC0 overflow/underflow")
+    (Tc0Mc0 = 0x4A, "TC0 match/capture 0")
+    (Tc0Mc1 = 0x4B, "TC0 match/capture 1")
+    (Tc1Ovf = 0x4C, "TC1 overflow/underflow")
+    (Tc1Mc0 = 0x4D, "TC1 match/capture 0")
+    (Tc1Mc1 = 0x4E, "TC1 match/capture 1")
+    (Tc2Ovf = 0x4F, "TC2 overflow/underflow")
+    (Tc2Mc0 = 0x50, "TC2 match/capture 0")
+    (Tc2Mc1 = 0x51, "TC2 match/capture 1")
+    (Tc3Ovf = 0x52, "TC3 overflow/underflow")
+    (Tc3Mc0 = 0x53, "TC3 match/capture 0")
+    (Tc3Mc1 = 0x54, "TC3 match/capture 1")
+    (Tc4Ovf = 0x55, "TC4 overflow/underflow")
+    (Tc4Mc0 = 0x56, "TC4 match/capture 0")
+    (Tc4Mc1 = 0x57, "TC4 match/capture 1")
+    (Tc5Ovf = 0x58, "TC5 overflow/underflow")
+    (Tc5Mc0 = 0x59, "TC5 match/capture 0")
+    (Tc5Mc1 = 0x5A, "TC5 match/capture 1")
+    (Tc6Ovf = 0x5B, "TC6 overflow/underflow")
+    (Tc6Mc0 = 0x5C, "TC6 match/capture 0")
+    (Tc6Mc1 = 0x5D, "TC6 match/capture 1")
+    (Tc7Ovf = 0x5E, "TC7 overflow/underflow")
+    (Tc7Mc0 = 0x5F, "TC7 match/capture 0")
+    (Tc7Mc1 = 0x60, "TC7 match/capture 1")
+);
+
+#[cfg(feature = "thumbv7")]
+event_ids!(Generator:
+    (PdecOvf = 0x61, "PDEC overflow/underflow")
+    (PdecErr = 0x62, "PDEC error")
+    (PdecDir = 0x63, "PDEC direction change")
+    (PdecVlc = 0x64, "PDEC velocity")
+    (PdecMc0 = 0x65, "PDEC match 0")
+    (PdecMc1 = 0x66, "PDEC match 1")
+);
+
+#[cfg(feature = "thumbv7")]
+event_ids!(Generator:
+    (Adc0ResRdy = 0x67, "ADC0 result ready")
+    (Adc0WinMon = 0x68, "ADC0 window monitor")
+    (Adc1ResRdy = 0x69, "ADC1 result ready")
+    (Adc1WinMon = 0x6A, "ADC1 window monitor")
+    (AcComp0 = 0x6B, "AC comparator 0")
+    (AcComp1 = 0x6C, "AC comparator 1")
+    (AcWin0 = 0x6D, "AC window 0")
+    (DacEmpty0 = 0x6E, "DAC channel 0 data buffer empty")
+    (DacEmpty1 = 0x6F, "DAC channel 1 data buffer empty")
+    (DacResRdy0 = 0x70, "DAC channel 0 result ready")
+    (DacResRdy1 = 0x71, "DAC channel 1 result ready")
+    (TrngReady = 0x73, "TRNG data ready")
+);
+
+#[cfg(feature = "thumbv7")]
+seq!(N in 0..4 {
+    event_ids!(Generator:
+        #( (CclLutOut~N = 0x74 + N, concat!("CCL LUT ", N, " output")) )*
+    );
+});
@@ -0,0 +1,506 @@
+//! # Event System
+//!
+//! The Event System (EVSYS) routes signals, called events, from one peripheral
+//! (the event *generator*) to other peripherals (the event *users*) without
+//! CPU intervention. For example, an RTC periodic event can start an ADC
+//! conversion at a fixed interval, and the ADC result ready event can in turn
+//! trigger a DMA transfer.
+//!
+//! # Initializing
+//!
+//! Create an [`EventSystem`] with [`EventSystem::new`]. It enables the EVSYS
+//! APB clock, resets the peripheral and returns a [`Channels`] struct
+//! containing a handle to each event channel.
+//!
+//! # Routing events
+//!
+//! Each [`Channel`] is typed by its [`ChId`] and by the [`Generator`] it is
+//! connected to. A new channel has no generator. Connect one with
+//! [`Channel::asynchronous`], [`Channel::synchronous`] or
+//! [`Channel::resynchronized`], which select the path the event takes through
+//! the channel. Then connect any number of [`User`]s with
+//! [`Channel::connect`].
+//!
+//! The type-level generators and users are found in the [`generators`] and
+//! [`users`] modules. Note that the event output of the generator and the event
+//! input of the user must also be enabled in their respective peripherals,
+//! e.g. with the `enable_event` method of the [`eic`](crate::eic) pins or
+//! [`Rtc::enable_periodic_event`](crate::rtc::Rtc::enable_periodic_event).
+//!
+//! # Paths
+//!
+//! * The asynchronous path does not need a clock, so it can operate in sleep
+//!   modes, but it cannot detect edges and some users require a synchronous
+//!   event.
+//! * The synchronous path requires the generator and the channel to use the
+//!   same generic clock.
+//! * The resynchronized path is used when the generator and the channel use
+//!   different generic clocks.
+//!
+//! Both clocked paths require the generic clock of the channel to be
+//! configured.
+#![cfg_attr(
+    feature = "thumbv7",
+    doc = "On SAMD5x/E5x targets, only channels 0-11 have a generic clock,
+so only they implement [`SyncChId`] and support the clocked paths."
+)]
+//!
+//! ```no_run
+//! use atsamd_hal::evsys::{generators::EicExtInt3, users::DmacCh0, EventSystem};
+//! # let mut peripherals = atsamd_hal::pac::Peripherals::take().unwrap();
+//! # #[cfg(feature = "thumbv6")]
+//! # let mut apb = peripherals.PM;
+//! # #[cfg(feature = "thumbv7")]
+//! # let mut apb = peripherals.MCLK;
+//! let (evsys, channels) = EventSystem::new(peripherals.EVSYS, &mut apb);
+//! let mut ch0 = channels.0.asynchronous::<EicExtInt3>();
+//! ch0.connect::<DmacCh0>();
+//! ```
+
+use core::marker::PhantomData;
+
+use seq_macro::seq;
+
+use crate::pac::EVSYS;
+use crate::typelevel::{NoneT, Sealed};
+
+#[cfg(feature = "thumbv6")]
+use crate::pac::evsys::channel::{EDGSEL_A, PATH_A};
+#[cfg(feature = "thumbv6")]
+use crate::pac::PM as APB_CLK_CTRL;
+
+#[cfg(feature = "thumbv7")]
+use crate::pac::evsys::channel::channel::{EDGSEL_A, PATH_A};
+#[cfg(feature = "thumbv7")]
+use crate::pac::MCLK as APB_CLK_CTRL;
+
+/// Define uninhabited types implementing [`Generator`] or [`User`]
+macro_rules! event_ids {
+    ($Trait:ident: $( ($Name:ident = $id:expr, $doc:expr) )+) => {
+        $(
+            #[doc = $doc]
+            pub enum $Name {}
+            impl crate::typelevel::Sealed for $Name {}
+            impl super::$Trait for $Name {
+                // Ids generated with `seq!` are written as `base + N`
+                #[allow(clippy::identity_op)]
+                const ID: u8 = $id;
+            }
+        )+
+    };
+}
+
+pub mod generators;
+pub mod users;
+
+#[cfg(feature = "samd11")]
+macro_rules! with_num_evsys_channels {
+    ($some_macro:ident) => {
+        $some_macro! {6}
+    };
+}
+
+#[cfg(feature = "samd21")]
+macro_rules! with_num_evsys_channels {
+    ($some_macro:ident) => {
+        $some_macro! {12}
+    };
+}
+
+#[cfg(feature = "thumbv7")]
+macro_rules! with_num_evsys_channels {
+    ($some_macro:ident) => {
+        $some_macro! {32}
+    };
+}
+
+//==============================================================================
+// Ids
+//==============================================================================
+
+/// Trait representing an event channel ID
+pub trait ChId: Sealed {
+    const NUM: u8;
+}
+
+/// Event channels supporting the synchronous and resynchronized paths
+pub trait SyncChId: ChId {}
+
+/// Event generator
+///
+/// Types implementing this trait identify a peripheral event output. See the
+/// [`generators`] module.
+pub trait Generator: Sealed {
+    /// Value of the `EVGEN` field selecting this generator
+    const ID: u8;
+}
+
+/// Event user
+///
+/// Types implementing this trait identify a peripheral event input. See the
+/// [`users`] module.
+pub trait User: Sealed {
+    /// Index of the user multiplexer of this user
+    const ID: u8;
+}
+
+macro_rules! define_channels_struct {
+    ($num_channels:literal) => {
+        seq!(N in 0..$num_channels {
+            #(
+                /// Type-level event channel number
+                pub enum Ch~N {}
+
+                impl Sealed for Ch~N {}
+
+                impl ChId for Ch~N {
+                    const NUM: u8 = N;
+                }
+            )*
+
+            /// Struct containing handles to each event channel
+            pub struct Channels(
+                #(
+                    pub Channel<Ch~N, NoneT>,
+                )*
+            );
+
+            impl Channels {
+                fn new() -> Self {
+                    Channels(
+                        #(
+                            Channel::new(),
+                        )*
+                    )
+                }
+            }
+        });
+    };
+}
+
+with_num_evsys_channels!(define_channels_struct);
+
+#[cfg(feature = "thumbv6")]
+macro_rules! impl_sync_channels {
+    ($num_channels:literal) => {
+        seq!(N in 0..$num_channels {
+            #(
+                impl SyncChId for Ch~N {}
+            )*
+        });
+    };
+}
+
+#[cfg(feature = "thumbv6")]
+with_num_evsys_channels!(impl_sync_channels);
+
+#[cfg(feature = "thumbv7")]
+seq!(N in 0..12 {
+    #(
+        impl SyncChId for Ch~N {}
+    )*
+});
+
+//==============================================================================
+// Edge
+//==============================================================================
+
+/// Edge detection for the synchronous and resynchronized paths
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edge {
+    /// Generate an event on the rising edge of the generator signal
+    Rising,
+    /// Generate an event on the falling edge of the generator signal
+    Falling,
+    /// Generate an event on both edges of the generator signal
+    Both,
+}
+
+impl From<Edge> for EDGSEL_A {
+    #[inline]
+    fn from(edge: Edge) -> Self {
+        match edge {
+            Edge::Rising => EDGSEL_A::RISING_EDGE,
+            Edge::Falling => EDGSEL_A::FALLING_EDGE,
+            Edge::Both => EDGSEL_A::BOTH_EDGES,
+        }
+    }
+}
+
+//==============================================================================
+// EventSystem
+//==============================================================================
+
+/// Initialized Event System
+pub struct EventSystem {
+    evsys: EVSYS,
+}
+
+impl EventSystem {
+    /// Enable the EVSYS APB clock, reset the peripheral and split it into
+    /// individual [`Channels`]
+    #[inline]
+    pub fn new(evsys: EVSYS, apb: &mut APB_CLK_CTRL) -> (Self, Channels) {
+        #[cfg(feature = "thumbv6")]
+        apb.apbcmask.modify(|_, w| w.evsys_().set_bit());
+        #[cfg(feature = "thumbv7")]
+        apb.apbbmask.modify(|_, w| w.evsys_().set_bit());
+
+        let mut evsys = Self { evsys };
+        evsys.swreset();
+        (evsys, Channels::new())
+    }
+
+    /// Reset the EVSYS and return the register block
+    ///
+    /// **Note**: The [`Channels`] struct is consumed by this method. Any
+    /// [`Channel`] must be [`reset`](Channel::reset) and moved back into the
+    /// [`Channels`] struct first.
+    #[inline]
+    pub fn free(mut self, _channels: Channels) -> EVSYS {
+        self.swreset();
+        self.evsys
+    }
+
+    /// Issue a software reset to the EVSYS
+    #[inline]
+    fn swreset(&mut self) {
+        #[cfg(feature = "thumbv6")]
+        self.evsys.ctrl.write(|w| w.swrst().set_bit());
+        #[cfg(feature = "thumbv7")]
+        {
+            self.evsys.ctrla.write(|w| w.swrst().set_bit());
+            while self.evsys.ctrla.read().swrst().bit_is_set() {}
+        }
+    }
+}
+
+//==============================================================================
+// Channel
+//==============================================================================
+
+/// Event channel
+///
+/// The type parameter `Id` is the [`ChId`] of the channel, and `G` is the
+/// [`Generator`] connected to it, or [`NoneT`] if the channel is not in use.
+pub struct Channel<Id: ChId, G> {
+    id: PhantomData<Id>,
+    generator: PhantomData<G>,
+}
+
+/// Access the EVSYS register block
+///
+/// Each [`Channel`] only writes to the registers, or register fields, of its
+/// own channel. On thumbv6 targets, the `CHANNEL` and `USER` registers are
+/// shared between all the channels and users, and select the one to access.
+/// Writes configure the selected one in a single atomic access, but
+/// read-modify-write sequences must first select it, so
+/// [`disconnect`](Channel::disconnect) and [`trigger`](Channel::trigger) run
+/// them in a critical section.
+#[inline]
+fn evsys() -> &'static crate::pac::evsys::RegisterBlock {
+    // Safety: See above
+    unsafe { &*EVSYS::PTR }
+}
+
+impl<Id: ChId, G> Channel<Id, G> {
+    #[inline]
+    fn new() -> Self {
+        Self {
+            id: PhantomData,
+            generator: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn change<G2>(self) -> Channel<Id, G2> {
+        Channel::new()
+    }
+
+    /// Write the `CHANNEL` register
+    #[inline]
+    fn configure(&mut self, evgen: u8, path: PATH_A, edge: EDGSEL_A) {
+        #[cfg(feature = "thumbv6")]
+        evsys().channel.write(|w| {
+            // Safety: `Id::NUM` is a valid channel number and `evgen` is the ID
+            // of a valid generator, or zero
+            unsafe {
+                w.channel().bits(Id::NUM);
+                w.evgen().bits(evgen);
+            }
+            w.path().variant(path);
+            w.edgsel().variant(edge)
+        });
+        #[cfg(feature = "thumbv7")]
+        evsys().channel[Id::NUM as usize].channel.modify(|_, w| {
+            // Safety: `evgen` is the ID of a valid generator, or zero
+            unsafe { w.evgen().bits(evgen) };
+            w.path().variant(path);
+            w.edgsel().variant(edge)
+        });
+    }
+}
+
+impl<Id: ChId> Channel<Id, NoneT> {
+    /// Connect a [`Generator`] through the asynchronous path
+    ///
+    /// The asynchronous path does not use a clock, so edge detection is not
+    /// available.
+    #[inline]
+    pub fn asynchronous<G: Generator>(mut self) -> Channel<Id, G> {
+        self.configure(G::ID, PATH_A::ASYNCHRONOUS, EDGSEL_A::NO_EVT_OUTPUT);
+        self.change()
+    }
+}
+
+impl<Id: SyncChId> Channel<Id, NoneT> {
+    /// Connect a [`Generator`] through the synchronous path
+    ///
+    /// The generator and the channel must use the same generic clock.
+    #[inline]
+    pub fn synchronous<G: Generator>(mut self, edge: Edge) -> Channel<Id, G> {
+        self.configure(G::ID, PATH_A::SYNCHRONOUS, edge.into());
+        self.change()
+    }
+
+    /// Connect a [`Generator`] through the resynchronized path
+    ///
+    /// The event is resynchronized to the generic clock of the channel.
+    #[inline]
+    pub fn resynchronized<G: Generator>(mut self, edge: Edge) -> Channel<Id, G> {
+        self.configure(G::ID, PATH_A::RESYNCHRONIZED, edge.into());
+        self.change()
+    }
+}
+
+impl<Id: ChId, G: Generator> Channel<Id, G> {
+    /// Connect a [`User`] to this channel
+    ///
+    /// Each user can only listen to a single channel. Connecting a user
+    /// disconnects it from any other channel.
+    #[inline]
+    pub fn connect<U: User>(&mut self) {
+        #[cfg(feature = "thumbv6")]
+        evsys().user.write(|w| {
+            // Safety: `U::ID` is a valid user and `Id::NUM + 1` selects this
+            // channel
+            unsafe {
+                w.user().bits(U::ID);
+                w.channel().bits(Id::NUM + 1)
+            }
+        });
+        #[cfg(feature = "thumbv7")]
+        evsys().user[U::ID as usize].write(|w| {
+            // Safety: `Id::NUM + 1` selects this channel
+            unsafe { w.channel().bits(Id::NUM + 1) }
+        });
+    }
+
+    /// Disconnect a [`User`] from this channel
+    ///
+    /// Nothing happens if the user is connected to another channel.
+    #[inline]
+    pub fn disconnect<U: User>(&mut self) {
+        #[cfg(feature = "thumbv6")]
+        cortex_m::interrupt::free(|_| {
+            let user = &evsys().user;
+            // Safety: Writing only the low byte of the `USER` register selects
+            // the user multiplexer to read, without modifying any of them
+            unsafe { core::ptr::write_volatile(user.as_ptr() as *mut u8, U::ID) };
+            if user.read().channel().bits() == Id::NUM + 1 {
+                user.write(|w| {
+                    // Safety: `U::ID` is a valid user, and zero disconnects it
+                    unsafe {
+                        w.user().bits(U::ID);
+                        w.channel().bits(0)
+                    }
+                });
+            }
+        });
+        #[cfg(feature = "thumbv7")]
+        {
+            let user = &evsys().user[U::ID as usize];
+            if user.read().channel().bits() == Id::NUM + 1 {
+                // Safety: Zero disconnects the user
+                user.write(|w| unsafe { w.channel().bits(0) });
+            }
+        }
+    }
+
+    /// Generate an event on this channel from software
+    #[inline]
+    pub fn trigger(&mut self) {
+        #[cfg(feature = "thumbv6")]
+        cortex_m::interrupt::free(|_| {
+            let channel = &evsys().channel;
+            // Safety: Writing only the low byte of the `CHANNEL` register selects
+            // the channel to read, without modifying its configuration
+            unsafe { core::ptr::write_volatile(channel.as_ptr() as *mut u8, Id::NUM) };
+            channel.modify(|_, w| w.swevt().set_bit());
+        });
+        #[cfg(feature = "thumbv7")]
+        // Safety: Writing a one to the bit of this channel triggers a software
+        // event, while writing zeros has no effect
+        evsys().swevt.write(|w| unsafe { w.bits(1 << Id::NUM) });
+    }
+
+    /// Return `true` if an event is being propagated through this channel
+    ///
+    /// Only meaningful for the synchronous and resynchronized paths.
+    #[inline]
+    pub fn is_busy(&self) -> bool {
+        #[cfg(feature = "thumbv6")]
+        {
+            let bit = if Id::NUM < 8 {
+                8 + Id::NUM
+            } else {
+                16 + Id::NUM
+            };
+            evsys().chstatus.read().bits() & (1 << bit) != 0
+        }
+        #[cfg(feature = "thumbv7")]
+        evsys().channel[Id::NUM as usize]
+            .chstatus
+            .read()
+            .busych()
+            .bit_is_set()
+    }
+
+    /// Return `true` if all the users connected to this channel are ready to
+    /// handle an event
+    ///
+    /// Only meaningful for the synchronous and resynchronized paths.
+    #[inline]
+    pub fn users_ready(&self) -> bool {
+        #[cfg(feature = "thumbv6")]
+        {
+            let bit = if Id::NUM < 8 { Id::NUM } else { 8 + Id::NUM };
+            evsys().chstatus.read().bits() & (1 << bit) != 0
+        }
+        #[cfg(feature = "thumbv7")]
+        evsys().channel[Id::NUM as usize]
+            .chstatus
+            .read()
+            .rdyusr()
+            .bit_is_set()
+    }
+
+    /// Keep the channel running in standby sleep mode
+    #[cfg(feature = "thumbv7")]
+    #[inline]
+    pub fn run_in_standby(&mut self, set: bool) {
+        evsys().channel[Id::NUM as usize]
+            .channel
+            .modify(|_, w| w.runstdby().bit(set));
+    }
+
+    /// Disconnect the [`Generator`] and return a channel that is not in use
+    ///
+    /// The [`User`]s connected to this channel should be
+    /// [`disconnect`](Channel::disconnect)ed first.
+    #[inline]
+    pub fn reset(mut self) -> Channel<Id, NoneT> {
+        self.configure(0, PATH_A::SYNCHRONOUS, EDGSEL_A::NO_EVT_OUTPUT);
+        self.change()
+    }
+}
@@ -0,0 +1,146 @@
+//! Type-level event users
+//!
+//! Each type in this module implements [`User`](super::User) and identifies an
+//! event input that can be connected to a [`Channel`](super::Channel).
+
+use seq_macro::seq;
+
+//==============================================================================
+// SAMD11
+//==============================================================================
+
+#[cfg(feature = "samd11")]
+seq!(N in 0..6 {
+    event_ids!(User:
+        #( (DmacCh~N = N, concat!("DMAC channel ", N, " trigger")) )*
+    );
+});
+
+#[cfg(feature = "samd11")]
+event_ids!(User:
+    (Tcc0Ev0 = 0x06, "TCC0 input event 0")
+    (Tcc0Ev1 = 0x07, "TCC0 input event 1")
+    (Tcc0Mc0 = 0x08, "TCC0 match/capture 0")
+    (Tcc0Mc1 = 0x09, "TCC0 match/capture 1")
+    (Tcc0Mc2 = 0x0A, "TCC0 match/capture 2")
+    (Tcc0Mc3 = 0x0B, "TCC0 match/capture 3")
+    (Tc1 = 0x0C, "TC1 input event")
+    (Tc2 = 0x0D, "TC2 input event")
+    (AdcStart = 0x0E, "ADC start conversion")
+    (AdcSync = 0x0F, "ADC flush")
+    (AcSoc0 = 0x10, "AC comparator 0 start comparison")
+    (AcSoc1 = 0x11, "AC comparator 1 start comparison")
+    (DacStart = 0x12, "DAC start conversion")
+);
+
+//==============================================================================
+// SAMD21
+//==============================================================================
+
+#[cfg(feature = "samd21")]
+seq!(N in 0..4 {
+    event_ids!(User:
+        #( (DmacCh~N = N, concat!("DMAC channel ", N, " trigger")) )*
+    );
+});
+
+#[cfg(feature = "samd21")]
+event_ids!(User:
+    (Tcc0Ev0 = 0x04, "TCC0 input event 0")
+    (Tcc0Ev1 = 0x05, "TCC0 input event 1")
+    (Tcc0Mc0 = 0x06, "TCC0 match/capture 0")
+    (Tcc0Mc1 = 0x07, "TCC0 match/capture 1")
+    (Tcc0Mc2 = 0x08, "TCC0 match/capture 2")
+    (Tcc0Mc3 = 0x09, "TCC0 match/capture 3")
+    (Tcc1Ev0 = 0x0A, "TCC1 input event 0")
+    (Tcc1Ev1 = 0x0B, "TCC1 input event 1")
+    (Tcc1Mc0 = 0x0C, "TCC1 match/capture 0")
+    (Tcc1Mc1 = 0x0D, "TCC1 match/capture 1")
+    (Tcc2Ev0 = 0x0E, "TCC2 input event 0")
+    (Tcc2Ev1 = 0x0F, "TCC2 input event 1")
+    (Tcc2Mc0 = 0x10, "TCC2 match/capture 0")
+    (Tcc2Mc1 = 0x11, "TCC2 match/capture 1")
+);
+
+#[cfg(feature = "samd21")]
+seq!(N in 3..8 {
+    event_ids!(User:
+        #( (Tc~N = 0x12 + N - 3, concat!("TC", N, " input event")) )*
+    );
+});
+
+#[cfg(feature = "samd21")]
+event_ids!(User:
+    (AdcStart = 0x17, "ADC start conversion")
+    (AdcSync = 0x18, "ADC flush")
+    (AcSoc0 = 0x19, "AC comparator 0 start comparison")
+    (AcSoc1 = 0x1A, "AC comparator 1 start comparison")
+    (DacStart = 0x1B, "DAC start conversion")
+);
+
+//==============================================================================
+// SAMD5x/E5x
+//==============================================================================
+
+#[cfg(feature = "thumbv7")]
+event_ids!(User:
+    (RtcTamper = 0, "RTC tamper detection")
+);
+
+#[cfg(feature = "thumbv7")]
+seq!(N in 0..4 {
+    event_ids!(User:
+        #( (PortEv~N = 1 + N, concat!("PORT event ", N)) )*
+        #( (CclLutIn~N = 16 + N, concat!("CCL LUT ", N, " input")) )*
+    );
+});
+
+#[cfg(feature = "thumbv7")]
+seq!(N in 0..8 {
+    event_ids!(User:
+        #( (DmacCh~N = 5 + N, concat!("DMAC channel ", N, " trigger")) )*
+        #( (Tc~N = 48 + N, concat!("TC", N, " input event")) )*
+    );
+});
+
+#[cfg(feature = "thumbv7")]
+event_ids!(User:
+    (Tcc0Ev0 = 21, "TCC0 input event 0")
+    (Tcc0Ev1 = 22, "TCC0 input event 1")
+    (Tcc0Mc0 = 23, "TCC0 match/capture 0")
+    (Tcc0Mc1 = 24, "TCC0 match/capture 1")
+    (Tcc0Mc2 = 25, "TCC0 match/capture 2")
+    (Tcc0Mc3 = 26, "TCC0 match/capture 3")
+    (Tcc0Mc4 = 27, "TCC0 match/capture 4")
+    (Tcc0Mc5 = 28, "TCC0 match/capture 5")
+    (Tcc1Ev0 = 29, "TCC1 input event 0")
+    (Tcc1Ev1 = 30, "TCC1 input event 1")
+    (Tcc1Mc0 = 31, "TCC1 match/capture 0")
+    (Tcc1Mc1 = 32, "TCC1 match/capture 1")
+    (Tcc1Mc2 = 33, "TCC1 match/capture 2")
+    (Tcc1Mc3 = 34, "TCC1 match/capture 3")
+    (Tcc2Ev0 = 35, "TCC2 input event 0")
+    (Tcc2Ev1 = 36, "TCC2 input event 1")
+    (Tcc2Mc0 = 37, "TCC2 match/capture 0")
+    (Tcc2Mc1 = 38, "TCC2 match/capture 1")
+    (Tcc2Mc2 = 39, "TCC2 match/capture 2")
+    (Tcc3Ev0 = 40, "TCC3 input event 0")
+    (Tcc3Ev1 = 41, "TCC3 input event 1")
+    (Tcc3Mc0 = 42, "TCC3 match/capture 0")
+    (Tcc3Mc1 = 43, "TCC3 match/capture 1")
+    (Tcc4Ev0 = 44, "TCC4 input event 0")
+    (Tcc4Ev1 = 45, "TCC4 input event 1")
+    (Tcc4Mc0 = 46, "TCC4 match/capture 0")
+    (Tcc4Mc1 = 47, "TCC4 match/capture 1")
+    (PdecEv0 = 56, "PDEC input event 0")
+    (PdecEv1 = 57, "PDEC input event 1")
+    (PdecEv2 = 58, "PDEC input event 2")
+    (Adc0Start = 59, "ADC0 start conversion")
+    (Adc0Sync = 60, "ADC0 flush")
+    (Adc1Start = 61, "ADC1 start conversion")
+    (Adc1Sync = 62, "ADC1 flush")
+    (AcSoc0 = 63, "AC comparator 0 start comparison")
+    (AcSoc1 = 64, "AC comparator 1 start comparison")
+    (DacStart0 = 65, "DAC channel 0 start conversion")
+    (DacStart1 = 66, "DAC channel 1 start conversion")
+);
@@ -61,9 +61,9 @@ use core::convert::TryFrom;
 
 use paste::paste;
 
-use crate::ehal::digital::v2::OutputPin;
-#[cfg(feature = "unproven")]
-use crate::ehal::digital::v2::{InputPin, StatefulOutputPin, ToggleableOutputPin};
+use crate::ehal_1::digital::{ErrorType, InputPin, OutputPin, StatefulOutputPin};
+#[cfg(feature = "ehal-02")]
+use crate::ehal_02::digital::v2 as ehal_02;
 
 use super::pin::*;
 use super::reg::RegisterInterface;
@@ -112,7 +112,6 @@ pub enum DynAlternate {
     E,
     F,
     G,
-    #[cfg(any(feature = "samd21", feature = "thumbv7"))]
     H,
     #[cfg(feature = "thumbv7")]
     I,
@@ -184,7 +183,6 @@ macro_rules! dyn_alternate {
 }
 
 dyn_alternate!(B, C, D, E, F, G);
-#[cfg(any(feature = "samd21", feature = "thumbv7"))]
 dyn_alternate!(H);
 #[cfg(feature = "thumbv7")]
 dyn_alternate!(I, J, K, L, M, N);
@@ -254,6 +252,7 @@ impl DynRegisters {
 ///
 /// [`DynPin`]s are not tracked and verified at compile-time, so run-time
 /// operations are fallible. This `enum` represents the corresponding errors.
+#[derive(Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Error {
     /// The pin did not have the correct ID or mode for the requested operation
@@ -508,8 +507,18 @@ where
 // Embedded HAL traits
 //==============================================================================
 
-impl OutputPin for DynPin {
+impl crate::ehal_1::digital::Error for Error {
+    #[inline]
+    fn kind(&self) -> crate::ehal_1::digital::ErrorKind {
+        crate::ehal_1::digital::ErrorKind::Other
+    }
+}
+
+impl ErrorType for DynPin {
     type Error = Error;
+}
+
+impl OutputPin for DynPin {
     #[inline]
     fn set_high(&mut self) -> Result<(), Self::Error> {
         self._set_high()
@@ -520,36 +529,79 @@ impl OutputPin for DynPin {
     }
 }
 
-#[cfg(feature = "unproven")]
 impl InputPin for DynPin {
+    #[inline]
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        self._is_high()
+    }
+    #[inline]
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self._is_low()
+    }
+}
+
+impl StatefulOutputPin for DynPin {
+    #[inline]
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        self._is_set_high()
+    }
+    #[inline]
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        self._is_set_low()
+    }
+    #[inline]
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        self._toggle()
+    }
+}
+
+//==============================================================================
+// Embedded HAL v0.2 traits
+//==============================================================================
+
+#[cfg(feature = "ehal-02")]
+impl ehal_02::OutputPin for DynPin {
     type Error = Error;
     #[inline]
-    fn is_high(&self) -> Result<bool, Self::Error> {
+    fn set_high(&mut self) -> Result<(), Error> {
+        self._set_high()
+    }
+    #[inline]
+    fn set_low(&mut self) -> Result<(), Error> {
+        self._set_low()
+    }
+}
+
+#[cfg(feature = "ehal-02")]
+impl ehal_02::InputPin for DynPin {
+    type Error = Error;
+    #[inline]
+    fn is_high(&self) -> Result<bool, Error> {
         self._is_high()
     }
     #[inline]
-    fn is_low(&self) -> Result<bool, Self::Error> {
+    fn is_low(&self) -> Result<bool, Error> {
         self._is_low()
     }
 }
 
-#[cfg(feature = "unproven")]
-impl ToggleableOutputPin for DynPin {
+#[cfg(feature = "ehal-02")]
+impl ehal_02::ToggleableOutputPin for DynPin {
     type Error = Error;
     #[inline]
-    fn toggle(&mut self) -> Result<(), Self::Error> {
+    fn toggle(&mut self) -> Result<(), Error> {
         self._toggle()
     }
 }
 
-#[cfg(feature = "unproven")]
-impl StatefulOutputPin for DynPin {
+#[cfg(feature = "ehal-02")]
+impl ehal_02::StatefulOutputPin for DynPin {
     #[inline]
-    fn is_set_high(&self) -> Result<bool, Self::Error> {
+    fn is_set_high(&self) -> Result<bool, Error> {
         self._is_set_high()
     }
     #[inline]
-    fn is_set_low(&self) -> Result<bool, Self::Error> {
+    fn is_set_low(&self) -> Result<bool, Error> {
         self._is_set_low()
     }
 }
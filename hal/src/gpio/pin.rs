@@ -63,15 +63,17 @@
 //! # Embedded HAL traits
 //!
 //! This module implements all of the embedded HAL GPIO traits for each [`Pin`]
-//! in the corresponding [`PinMode`]s, namely: [`InputPin`], [`OutputPin`],
-//! [`ToggleableOutputPin`] and [`StatefulOutputPin`].
+//! in the corresponding [`PinMode`]s, namely: [`InputPin`], [`OutputPin`] and
+//! [`StatefulOutputPin`]. With the `ehal-02` feature, the embedded HAL 0.2
+//! versions of these traits, along with `ToggleableOutputPin`, are also
+//! implemented.
 //!
 //! For example, you can control the logic level of an `OutputPin` like so
 //!
 //! ```
 //! use atsamd_hal::pac::Peripherals;
 //! use atsamd_hal::gpio::Pins;
-//! use embedded_hal::digital::v2::OutputPin;
+//! use atsamd_hal::ehal_1::digital::OutputPin;
 //!
 //! let mut peripherals = Peripherals::take().unwrap();
 //! let mut pins = Pins::new(peripherals.PORT);
@@ -99,9 +101,9 @@ use core::convert::Infallible;
 use core::marker::PhantomData;
 use core::mem::transmute;
 
-use crate::ehal::digital::v2::OutputPin;
-#[cfg(feature = "unproven")]
-use crate::ehal::digital::v2::{InputPin, StatefulOutputPin, ToggleableOutputPin};
+use crate::ehal_1::digital::{ErrorType, InputPin, OutputPin, StatefulOutputPin};
+#[cfg(feature = "ehal-02")]
+use crate::ehal_02::digital::v2 as ehal_02;
 use paste::paste;
 
 use crate::pac::PORT;
@@ -341,7 +343,6 @@ macro_rules! alternate {
 
 alternate!(B, C, D, E, F, G);
 
-#[cfg(any(feature = "samd21", feature = "thumbv7"))]
 alternate!(H);
 
 #[cfg(feature = "thumbv7")]
@@ -749,7 +750,6 @@ impl_core_convert_from!(
     AlternateE,
     AlternateF,
     AlternateG,
-    #[cfg(any(feature = "samd21", feature = "thumbv7"))]
     AlternateH,
     #[cfg(feature = "thumbv7")]
     AlternateI,
@@ -881,12 +881,19 @@ impl<P: AnyPin> SomePin for P {}
 //  Embedded HAL traits
 //==============================================================================
 
+impl<I, M> ErrorType for Pin<I, M>
+where
+    I: PinId,
+    M: PinMode,
+{
+    type Error = Infallible;
+}
+
 impl<I, C> OutputPin for Pin<I, Output<C>>
 where
     I: PinId,
     C: OutputConfig,
 {
-    type Error = Infallible;
     #[inline]
     fn set_high(&mut self) -> Result<(), Self::Error> {
         self._set_high();
@@ -899,82 +906,169 @@ where
     }
 }
 
-#[cfg(feature = "unproven")]
 impl<I> InputPin for Pin<I, ReadableOutput>
 where
     I: PinId,
 {
-    type Error = Infallible;
     #[inline]
-    fn is_high(&self) -> Result<bool, Self::Error> {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
         Ok(self._is_high())
     }
     #[inline]
-    fn is_low(&self) -> Result<bool, Self::Error> {
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
         Ok(self._is_low())
     }
 }
 
-#[cfg(feature = "unproven")]
 impl<I, C> InputPin for Pin<I, Input<C>>
 where
     I: PinId,
     C: InputConfig,
 {
-    type Error = Infallible;
     #[inline]
-    fn is_high(&self) -> Result<bool, Self::Error> {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
         Ok(self._is_high())
     }
     #[inline]
-    fn is_low(&self) -> Result<bool, Self::Error> {
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
         Ok(self._is_low())
     }
 }
 
-#[cfg(feature = "unproven")]
 impl<I, C> InputPin for Pin<I, Interrupt<C>>
 where
     I: PinId,
     C: InterruptConfig,
+{
+    #[inline]
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self._is_high())
+    }
+    #[inline]
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(self._is_low())
+    }
+}
+
+impl<I, C> StatefulOutputPin for Pin<I, Output<C>>
+where
+    I: PinId,
+    C: OutputConfig,
+{
+    #[inline]
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self._is_set_high())
+    }
+    #[inline]
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(self._is_set_low())
+    }
+    #[inline]
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        self._toggle();
+        Ok(())
+    }
+}
+
+//==============================================================================
+//  Embedded HAL v0.2 traits
+//==============================================================================
+
+#[cfg(feature = "ehal-02")]
+impl<I, C> ehal_02::OutputPin for Pin<I, Output<C>>
+where
+    I: PinId,
+    C: OutputConfig,
 {
     type Error = Infallible;
     #[inline]
-    fn is_high(&self) -> Result<bool, Self::Error> {
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        self._set_high();
+        Ok(())
+    }
+    #[inline]
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        self._set_low();
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ehal-02")]
+impl<I> ehal_02::InputPin for Pin<I, ReadableOutput>
+where
+    I: PinId,
+{
+    type Error = Infallible;
+    #[inline]
+    fn is_high(&self) -> Result<bool, Infallible> {
+        Ok(self._is_high())
+    }
+    #[inline]
+    fn is_low(&self) -> Result<bool, Infallible> {
+        Ok(self._is_low())
+    }
+}
+
+#[cfg(feature = "ehal-02")]
+impl<I, C> ehal_02::InputPin for Pin<I, Input<C>>
+where
+    I: PinId,
+    C: InputConfig,
+{
+    type Error = Infallible;
+    #[inline]
+    fn is_high(&self) -> Result<bool, Infallible> {
         Ok(self._is_high())
     }
     #[inline]
-    fn is_low(&self) -> Result<bool, Self::Error> {
+    fn is_low(&self) -> Result<bool, Infallible> {
         Ok(self._is_low())
     }
 }
 
-#[cfg(feature = "unproven")]
-impl<I, C> ToggleableOutputPin for Pin<I, Output<C>>
+#[cfg(feature = "ehal-02")]
+impl<I, C> ehal_02::InputPin for Pin<I, Interrupt<C>>
+where
+    I: PinId,
+    C: InterruptConfig,
+{
+    type Error = Infallible;
+    #[inline]
+    fn is_high(&self) -> Result<bool, Infallible> {
+        Ok(self._is_high())
+    }
+    #[inline]
+    fn is_low(&self) -> Result<bool, Infallible> {
+        Ok(self._is_low())
+    }
+}
+
+#[cfg(feature = "ehal-02")]
+impl<I, C> ehal_02::ToggleableOutputPin for Pin<I, Output<C>>
 where
     I: PinId,
     C: OutputConfig,
 {
     type Error = Infallible;
     #[inline]
-    fn toggle(&mut self) -> Result<(), Self::Error> {
+    fn toggle(&mut self) -> Result<(), Infallible> {
         self._toggle();
         Ok(())
     }
 }
 
-#[cfg(feature = "unproven")]
-impl<I, C> StatefulOutputPin for Pin<I, Output<C>>
+#[cfg(feature = "ehal-02")]
+impl<I, C> ehal_02::StatefulOutputPin for Pin<I, Output<C>>
 where
     I: PinId,
     C: OutputConfig,
 {
     #[inline]
-    fn is_set_high(&self) -> Result<bool, Self::Error> {
+    fn is_set_high(&self) -> Result<bool, Infallible> {
         Ok(self._is_set_high())
     }
     #[inline]
-    fn is_set_low(&self) -> Result<bool, Self::Error> {
+    fn is_set_low(&self) -> Result<bool, Infallible> {
         Ok(self._is_set_low())
     }
 }
@@ -0,0 +1,1122 @@
+//! # Inter-IC Sound
+//!
+//! The I2S peripheral transmits and receives audio samples over a serial bus.
+//! It is made of two clock units, which generate or receive the serial clock
+//! (`SCK`) and frame sync (`FS`) signals, and two serializers, which shift the
+//! data in or out on their `SD` pin according to one of the clock units.
+#![cfg_attr(
+    feature = "thumbv6",
+    doc = "On SAMD21 targets, each serializer ([`Ser0`] and [`Ser1`]) can be
+used as a transmitter, a receiver or a PDM receiver."
+)]
+#![cfg_attr(
+    feature = "thumbv7",
+    doc = "On SAMD5x/E5x targets, the serializers have a fixed direction: [`Tx`]
+transmits on the `SDO` pin and [`Rx`] receives on the `SDI` pin, either in
+I2S or PDM mode."
+)]
+//!
+//! # Initializing
+//!
+//! Create an [`I2s`] with [`I2s::new`], which enables the I2S APB clock and
+//! resets the peripheral. The generic clock of each clock unit used must be
+//! configured separately.
+//!
+//! Each clock unit is configured from a [`ClockConfig`], either as the bus
+//! master, generating `SCK` and `FS` from its generic clock, or as a slave,
+//! receiving them from their pins. The pads are checked at compile time by
+//! [`ClockConfig::sck_pin`], [`ClockConfig::fs_pin`] and
+//! [`ClockConfig::mck_output`]. Each serializer is configured from a
+//! [`SerializerConfig`] with [`I2s::configure_tx`], [`I2s::configure_rx`] or
+//! [`I2s::configure_pdm`].
+//!
+//! ```no_run
+//! use atsamd_hal::gpio::Pins;
+//! use atsamd_hal::i2s::{
+//!     Clk0, ClockConfig, DataSize, Format, I2s, PadMode, SerializerConfig, SlotSize,
+//! };
+//! # #[cfg(feature = "thumbv6")]
+//! # use atsamd_hal::i2s::Ser0 as Rx;
+//! # #[cfg(feature = "thumbv7")]
+//! # use atsamd_hal::i2s::Rx;
+//! # let mut peripherals = atsamd_hal::pac::Peripherals::take().unwrap();
+//! # #[cfg(feature = "thumbv6")]
+//! # let mut apb = peripherals.PM;
+//! # #[cfg(feature = "thumbv7")]
+//! # let mut apb = peripherals.MCLK;
+//! let pins = Pins::new(peripherals.PORT);
+//! # #[cfg(feature = "thumbv6")]
+//! # let (sck, fs) = (pins.pa10, pins.pa11);
+//! # #[cfg(feature = "thumbv7")]
+//! # let (sck, fs) = (pins.pb16, pins.pa20);
+//! let sck = sck.into_mode::<PadMode>();
+//! let fs = fs.into_mode::<PadMode>();
+//!
+//! // Assume the GCLK_I2S_0 generic clock runs at 12.288 MHz
+//! let mut i2s = I2s::new(peripherals.I2S, &mut apb);
+//!
+//! // 48 kHz stereo frames made of two 32-bit slots
+//! let clock = ClockConfig::<Clk0>::master(4)
+//!     .format(Format::I2s)
+//!     .slot_size(SlotSize::Bits32)
+//!     .slots(2)
+//!     .sck_pin(&sck)
+//!     .fs_pin(&fs);
+//! i2s.configure_clock(clock);
+//!
+//! let config = SerializerConfig {
+//!     data_size: DataSize::Bits24,
+//!     ..Default::default()
+//! };
+//! # #[cfg(feature = "thumbv6")]
+//! i2s.configure_rx::<Rx, Clk0>(config);
+//! # #[cfg(feature = "thumbv7")]
+//! i2s.configure_rx::<Clk0>(config);
+//!
+//! i2s.enable();
+//! i2s.enable_clock::<Clk0>();
+//! i2s.enable_serializer::<Rx>();
+//! ```
+//!
+//! # Streaming
+//!
+//! Samples can be read and written one word at a time with [`I2s::read`] and
+//! [`I2s::write`]. For continuous streaming, [`I2s::receive_with_dma`] and
+//! [`I2s::send_with_dma`] start a [`PingPongTransfer`] between a serializer and
+//! a pair of buffers, so that the application can process one buffer while the
+//! DMAC fills or empties the other one.
+//!
+//! # PDM microphones
+//!
+//! In PDM mode, the clock unit drives the microphone clock on its `SCK` pin,
+//! and the serializer samples the data line on both clock edges, so that two
+//! microphones can share a single data line. The received words contain the
+//! raw 1-bit PDM streams of both microphones, interleaved bit by bit, which
+//! must be decimated in software to obtain PCM samples.
+
+use core::convert::Infallible;
+use core::marker::PhantomData;
+
+use bitflags::bitflags;
+
+use crate::gpio::*;
+use crate::pac::I2S;
+use crate::typelevel::Sealed;
+
+#[cfg(feature = "thumbv6")]
+use crate::pac::PM as APB_CLK_CTRL;
+
+#[cfg(feature = "thumbv7")]
+use crate::pac::MCLK as APB_CLK_CTRL;
+
+#[cfg(feature = "dma")]
+use crate::dmac::{
+    channel::{AnyChannel, Busy, CallbackStatus, Channel, Ready},
+    Buffer, LinkedDescriptors, PingPongTransfer, Repeat, TriggerAction, TriggerSource,
+};
+
+/// Largest division factor of the clock unit dividers
+#[cfg(feature = "thumbv6")]
+const MAX_DIV: u8 = 32;
+#[cfg(feature = "thumbv7")]
+const MAX_DIV: u8 = 64;
+
+//==============================================================================
+// Ids
+//==============================================================================
+
+/// Type-level clock unit number
+pub trait ClkId: Sealed {
+    const NUM: usize;
+}
+
+/// Type-level clock unit 0
+pub enum Clk0 {}
+impl Sealed for Clk0 {}
+impl ClkId for Clk0 {
+    const NUM: usize = 0;
+}
+
+/// Type-level clock unit 1
+pub enum Clk1 {}
+impl Sealed for Clk1 {}
+impl ClkId for Clk1 {
+    const NUM: usize = 1;
+}
+
+/// Type-level serializer number
+pub trait SerId: Sealed {
+    const NUM: usize;
+}
+
+/// [`SerId`]s that can transmit
+pub trait TxSerializer: SerId {
+    /// DMA trigger of the transmit ready condition
+    #[cfg(feature = "dma")]
+    const DMA_TX_TRIGGER: TriggerSource;
+}
+
+/// [`SerId`]s that can receive, in I2S or PDM mode
+pub trait RxSerializer: SerId {
+    /// DMA trigger of the receive ready condition
+    #[cfg(feature = "dma")]
+    const DMA_RX_TRIGGER: TriggerSource;
+}
+
+/// Type-level serializer 0
+#[cfg(feature = "thumbv6")]
+pub enum Ser0 {}
+#[cfg(feature = "thumbv6")]
+impl Sealed for Ser0 {}
+#[cfg(feature = "thumbv6")]
+impl SerId for Ser0 {
+    const NUM: usize = 0;
+}
+#[cfg(feature = "thumbv6")]
+impl TxSerializer for Ser0 {
+    #[cfg(feature = "dma")]
+    const DMA_TX_TRIGGER: TriggerSource = TriggerSource::I2S_TX_0;
+}
+#[cfg(feature = "thumbv6")]
+impl RxSerializer for Ser0 {
+    #[cfg(feature = "dma")]
+    const DMA_RX_TRIGGER: TriggerSource = TriggerSource::I2S_RX_0;
+}
+
+/// Type-level serializer 1
+#[cfg(feature = "thumbv6")]
+pub enum Ser1 {}
+#[cfg(feature = "thumbv6")]
+impl Sealed for Ser1 {}
+#[cfg(feature = "thumbv6")]
+impl SerId for Ser1 {
+    const NUM: usize = 1;
+}
+#[cfg(feature = "thumbv6")]
+impl TxSerializer for Ser1 {
+    #[cfg(feature = "dma")]
+    const DMA_TX_TRIGGER: TriggerSource = TriggerSource::I2S_TX_1;
+}
+#[cfg(feature = "thumbv6")]
+impl RxSerializer for Ser1 {
+    #[cfg(feature = "dma")]
+    const DMA_RX_TRIGGER: TriggerSource = TriggerSource::I2S_RX_1;
+}
+
+/// Type-level transmit serializer
+#[cfg(feature = "thumbv7")]
+pub enum Tx {}
+#[cfg(feature = "thumbv7")]
+impl Sealed for Tx {}
+#[cfg(feature = "thumbv7")]
+impl SerId for Tx {
+    const NUM: usize = 0;
+}
+#[cfg(feature = "thumbv7")]
+impl TxSerializer for Tx {
+    #[cfg(feature = "dma")]
+    const DMA_TX_TRIGGER: TriggerSource = TriggerSource::I2S_TX_0;
+}
+
+/// Type-level receive serializer
+#[cfg(feature = "thumbv7")]
+pub enum Rx {}
+#[cfg(feature = "thumbv7")]
+impl Sealed for Rx {}
+#[cfg(feature = "thumbv7")]
+impl SerId for Rx {
+    const NUM: usize = 1;
+}
+#[cfg(feature = "thumbv7")]
+impl RxSerializer for Rx {
+    #[cfg(feature = "dma")]
+    const DMA_RX_TRIGGER: TriggerSource = TriggerSource::I2S_RX_0;
+}
+
+//==============================================================================
+// Pads
+//==============================================================================
+
+/// Alternate function of the I2S pads
+#[cfg(feature = "thumbv6")]
+pub type PadMode = AlternateG;
+
+/// Alternate function of the I2S pads
+#[cfg(feature = "thumbv7")]
+pub type PadMode = AlternateJ;
+
+/// [`PinId`]s that can be used as the serial clock pad of a clock unit
+pub trait SckPin: PinId {
+    type Clk: ClkId;
+}
+
+/// [`PinId`]s that can be used as the frame sync pad of a clock unit
+pub trait FsPin: PinId {
+    type Clk: ClkId;
+}
+
+/// [`PinId`]s that can be used as the master clock pad of a clock unit
+pub trait MckPin: PinId {
+    type Clk: ClkId;
+}
+
+/// [`PinId`]s that can be used as the data pad of a serializer
+pub trait SdPin: PinId {
+    type Ser: SerId;
+}
+
+macro_rules! pads {
+    (
+        $Trait:ident::$Assoc:ident:
+        $( $( #[$cfg:meta] )? $PinId:ident = $Id:ident ),+ $(,)?
+    ) => {
+        $(
+            $( #[$cfg] )?
+            impl $Trait for $PinId {
+                type $Assoc = $Id;
+            }
+        )+
+    };
+}
+
+#[cfg(feature = "thumbv6")]
+pads!(SckPin::Clk:
+    PA10 = Clk0,
+    #[cfg(feature = "pins-48")]
+    PA20 = Clk0,
+    #[cfg(feature = "pins-48")]
+    PB11 = Clk1,
+);
+
+#[cfg(feature = "thumbv6")]
+pads!(FsPin::Clk:
+    PA11 = Clk0,
+    #[cfg(feature = "pins-48")]
+    PA21 = Clk0,
+    #[cfg(feature = "pins-64")]
+    PB12 = Clk1,
+);
+
+#[cfg(feature = "thumbv6")]
+pads!(MckPin::Clk:
+    PA09 = Clk0,
+    #[cfg(feature = "pins-64")]
+    PB17 = Clk0,
+    #[cfg(feature = "pins-48")]
+    PB10 = Clk1,
+);
+
+#[cfg(feature = "thumbv6")]
+pads!(SdPin::Ser:
+    PA07 = Ser0,
+    #[cfg(feature = "pins-32")]
+    PA19 = Ser0,
+    PA08 = Ser1,
+    #[cfg(feature = "pins-64")]
+    PB16 = Ser1,
+);
+
+#[cfg(feature = "thumbv7")]
+pads!(SckPin::Clk: PB16 = Clk0);
+
+#[cfg(feature = "thumbv7")]
+pads!(FsPin::Clk: PA20 = Clk0, PA23 = Clk1);
+
+#[cfg(feature = "thumbv7")]
+pads!(MckPin::Clk: PB17 = Clk0);
+
+#[cfg(feature = "thumbv7")]
+pads!(SdPin::Ser: PA21 = Tx, PA22 = Rx);
+
+//==============================================================================
+// Clock unit configuration
+//==============================================================================
+
+/// Size of a slot, ie the number of serial clock periods per slot
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SlotSize {
+    Bits8,
+    Bits16,
+    Bits24,
+    Bits32,
+}
+
+/// Width of the frame sync pulse
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FsWidth {
+    /// One slot wide
+    Slot,
+    /// Half a frame wide
+    Half,
+    /// One bit wide
+    Bit,
+    /// As wide as the number of active slots, for burst transfers
+    Burst,
+}
+
+/// Frame format
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Format {
+    /// Standard I2S format: the frame sync toggles every half frame, and the
+    /// data starts one bit after each frame sync edge
+    I2s,
+    /// Left-justified format: the frame sync toggles every half frame, and the
+    /// data starts on each frame sync edge
+    LeftJustified,
+    /// Time-division multiplexed format: a one-bit frame sync pulse marks the
+    /// start of the frame, and the data starts one bit after it
+    Tdm,
+}
+
+/// Configuration of a clock unit
+///
+/// Apply it with [`I2s::configure_clock`].
+#[derive(Clone, Copy, Debug)]
+pub struct ClockConfig<Id: ClkId> {
+    id: PhantomData<Id>,
+    master: bool,
+    sck_div: u8,
+    mck_div: Option<u8>,
+    slot_size: SlotSize,
+    slots: u8,
+    fs_width: FsWidth,
+    bit_delay: bool,
+    fs_invert: bool,
+}
+
+impl<Id: ClkId> ClockConfig<Id> {
+    #[inline]
+    fn new(master: bool, sck_div: u8) -> Self {
+        Self {
+            id: PhantomData,
+            master,
+            sck_div,
+            mck_div: None,
+            slot_size: SlotSize::Bits32,
+            slots: 2,
+            fs_width: FsWidth::Half,
+            bit_delay: true,
+            fs_invert: false,
+        }
+    }
+
+    /// Create a configuration for a bus master, which generates `SCK` by
+    /// dividing the generic clock of the clock unit by `sck_div`, and `FS` by
+    /// dividing `SCK`
+    ///
+    /// The default configuration is a standard I2S frame made of two 32-bit
+    /// slots.
+    #[inline]
+    pub fn master(sck_div: u8) -> Self {
+        Self::new(true, sck_div)
+    }
+
+    /// Create a configuration for a bus slave, which receives `SCK` and `FS`
+    /// from their pins
+    ///
+    /// The default configuration is a standard I2S frame made of two 32-bit
+    /// slots.
+    #[inline]
+    pub fn slave() -> Self {
+        Self::new(false, 1)
+    }
+
+    /// Select the frame format. This sets the frame sync width, which can then
+    /// be overridden with [`fs_width`](Self::fs_width).
+    #[inline]
+    pub fn format(mut self, format: Format) -> Self {
+        let (fs_width, bit_delay) = match format {
+            Format::I2s => (FsWidth::Half, true),
+            Format::LeftJustified => (FsWidth::Half, false),
+            Format::Tdm => (FsWidth::Bit, true),
+        };
+        self.fs_width = fs_width;
+        self.bit_delay = bit_delay;
+        self
+    }
+
+    /// Select the slot size
+    #[inline]
+    pub fn slot_size(mut self, slot_size: SlotSize) -> Self {
+        self.slot_size = slot_size;
+        self
+    }
+
+    /// Select the number of slots per frame, in the range `1..=8`
+    #[inline]
+    pub fn slots(mut self, slots: u8) -> Self {
+        self.slots = slots;
+        self
+    }
+
+    /// Select the width of the frame sync pulse
+    #[inline]
+    pub fn fs_width(mut self, fs_width: FsWidth) -> Self {
+        self.fs_width = fs_width;
+        self
+    }
+
+    /// Invert the frame sync signal
+    #[inline]
+    pub fn fs_invert(mut self, invert: bool) -> Self {
+        self.fs_invert = invert;
+        self
+    }
+
+    /// Check at compile time that `pin` is the `SCK` pad of this clock unit
+    #[inline]
+    pub fn sck_pin<P: SckPin<Clk = Id>>(self, _pin: &Pin<P, PadMode>) -> Self {
+        self
+    }
+
+    /// Check at compile time that `pin` is the `FS` pad of this clock unit
+    #[inline]
+    pub fn fs_pin<P: FsPin<Clk = Id>>(self, _pin: &Pin<P, PadMode>) -> Self {
+        self
+    }
+
+    /// Output a master clock on the `MCK` pad of this clock unit, by dividing
+    /// its generic clock by `div`
+    ///
+    /// This is typically used to clock an audio codec at a multiple of the
+    /// sample rate.
+    #[inline]
+    pub fn mck_output<P: MckPin<Clk = Id>>(mut self, _pin: &Pin<P, PadMode>, div: u8) -> Self {
+        self.mck_div = Some(div);
+        self
+    }
+}
+
+//==============================================================================
+// Serializer configuration
+//==============================================================================
+
+/// Number of significant bits per data word
+///
+/// The compact sizes pack two 16-bit or four 8-bit words in each 32-bit data
+/// register access, which halves or quarters the DMA bandwidth.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DataSize {
+    Bits32,
+    Bits24,
+    Bits20,
+    Bits18,
+    Bits16,
+    Bits16Compact,
+    Bits8,
+    Bits8Compact,
+}
+
+/// Value of the data line during disabled slots, or when the transmitter
+/// underruns
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TxDefault {
+    Zero,
+    One,
+    HighImpedance,
+}
+
+/// Configuration of a serializer
+///
+/// Apply it with [`I2s::configure_tx`], [`I2s::configure_rx`] or
+/// [`I2s::configure_pdm`].
+#[derive(Clone, Copy, Debug)]
+pub struct SerializerConfig {
+    /// Number of significant bits per data word
+    pub data_size: DataSize,
+    /// Left-adjust the data in each slot, as required by the I2S and
+    /// left-justified formats when the data is smaller than the slot
+    pub left_adjust: bool,
+    /// Shift the least significant bit first
+    pub lsb_first: bool,
+    /// Only transfer the even slots, and duplicate their data to the odd
+    /// slots
+    pub mono: bool,
+    /// Bit mask of the slots to skip
+    pub disabled_slots: u8,
+    /// Value of the data line during disabled slots, for transmitters
+    pub tx_default: TxDefault,
+    /// On underrun, send the last word again instead of zero, for
+    /// transmitters
+    pub tx_repeat: bool,
+}
+
+impl Default for SerializerConfig {
+    fn default() -> Self {
+        Self {
+            data_size: DataSize::Bits16,
+            left_adjust: true,
+            lsb_first: false,
+            mono: false,
+            disabled_slots: 0,
+            tx_default: TxDefault::Zero,
+            tx_repeat: false,
+        }
+    }
+}
+
+/// Write the fields shared by all serializer control registers
+macro_rules! serializer_fields {
+    ($w:ident, $config:ident, $Clk:ident) => {{
+        $w.clksel().bit($Clk::NUM == 1);
+        $w.slotadj().bit($config.left_adjust);
+        match $config.data_size {
+            DataSize::Bits32 => $w.datasize()._32(),
+            DataSize::Bits24 => $w.datasize()._24(),
+            DataSize::Bits20 => $w.datasize()._20(),
+            DataSize::Bits18 => $w.datasize()._18(),
+            DataSize::Bits16 => $w.datasize()._16(),
+            DataSize::Bits16Compact => $w.datasize()._16c(),
+            DataSize::Bits8 => $w.datasize()._8(),
+            DataSize::Bits8Compact => $w.datasize()._8c(),
+        };
+        $w.bitrev().bit($config.lsb_first);
+        $w.mono().bit($config.mono);
+        $w.slotdis0().bit($config.disabled_slots & 0x01 != 0);
+        $w.slotdis1().bit($config.disabled_slots & 0x02 != 0);
+        $w.slotdis2().bit($config.disabled_slots & 0x04 != 0);
+        $w.slotdis3().bit($config.disabled_slots & 0x08 != 0);
+        $w.slotdis4().bit($config.disabled_slots & 0x10 != 0);
+        $w.slotdis5().bit($config.disabled_slots & 0x20 != 0);
+        $w.slotdis6().bit($config.disabled_slots & 0x40 != 0);
+        $w.slotdis7().bit($config.disabled_slots & 0x80 != 0);
+        $w.dma().single()
+    }};
+}
+
+/// Write the fields specific to transmitters
+macro_rules! tx_fields {
+    ($w:ident, $config:ident) => {{
+        match $config.tx_default {
+            TxDefault::Zero => $w.txdefault().zero(),
+            TxDefault::One => $w.txdefault().one(),
+            TxDefault::HighImpedance => $w.txdefault().hiz(),
+        };
+        $w.txsame().bit($config.tx_repeat)
+    }};
+}
+
+//==============================================================================
+// Flags
+//==============================================================================
+
+bitflags! {
+    /// Interrupt bit flags
+    ///
+    /// The binary format of the underlying bits exactly matches the INTFLAG
+    /// bits.
+    ///
+    #[cfg_attr(
+        feature = "thumbv7",
+        doc = "On SAMD5x/E5x targets, the `0` flags belong to the [`Rx`] and
+[`Tx`] serializers, and the `1` flags are only used when the DMA requests are
+split between even and odd slots."
+    )]
+    pub struct Flags: u16 {
+        const RXRDY0 = 0x0001;
+        const RXRDY1 = 0x0002;
+        const RXOR0 = 0x0010;
+        const RXOR1 = 0x0020;
+        const TXRDY0 = 0x0100;
+        const TXRDY1 = 0x0200;
+        const TXUR0 = 0x1000;
+        const TXUR1 = 0x2000;
+    }
+}
+
+impl Flags {
+    /// Index of the flags of a serializer
+    #[inline]
+    fn index<S: SerId>() -> usize {
+        // On SAMD5x/E5x, `Tx` and `Rx` each use their own `0` flags
+        if cfg!(feature = "thumbv6") {
+            S::NUM
+        } else {
+            0
+        }
+    }
+
+    /// Receive ready flag of serializer `S`
+    #[inline]
+    pub fn rx_ready<S: RxSerializer>() -> Self {
+        Self::from_bits_truncate(Self::RXRDY0.bits() << Self::index::<S>())
+    }
+
+    /// Receive overrun flag of serializer `S`
+    #[inline]
+    pub fn rx_overrun<S: RxSerializer>() -> Self {
+        Self::from_bits_truncate(Self::RXOR0.bits() << Self::index::<S>())
+    }
+
+    /// Transmit ready flag of serializer `S`
+    #[inline]
+    pub fn tx_ready<S: TxSerializer>() -> Self {
+        Self::from_bits_truncate(Self::TXRDY0.bits() << Self::index::<S>())
+    }
+
+    /// Transmit underrun flag of serializer `S`
+    #[inline]
+    pub fn tx_underrun<S: TxSerializer>() -> Self {
+        Self::from_bits_truncate(Self::TXUR0.bits() << Self::index::<S>())
+    }
+}
+
+//==============================================================================
+// I2s
+//==============================================================================
+
+/// Inter-IC Sound peripheral
+pub struct I2s {
+    i2s: I2S,
+}
+
+impl I2s {
+    /// Enable the I2S APB clock and reset the peripheral
+    #[inline]
+    pub fn new(i2s: I2S, apb: &mut APB_CLK_CTRL) -> Self {
+        #[cfg(feature = "thumbv6")]
+        apb.apbcmask.modify(|_, w| w.i2s_().set_bit());
+        #[cfg(feature = "thumbv7")]
+        apb.apbdmask.modify(|_, w| w.i2s_().set_bit());
+
+        let i2s = Self { i2s };
+        i2s.i2s.ctrla.write(|w| w.swrst().set_bit());
+        i2s.sync();
+        i2s
+    }
+
+    #[inline]
+    fn sync(&self) {
+        while self.i2s.syncbusy.read().bits() != 0 {}
+    }
+
+    /// Enable the I2S peripheral
+    #[inline]
+    pub fn enable(&mut self) {
+        self.i2s.ctrla.modify(|_, w| w.enable().set_bit());
+        self.sync();
+    }
+
+    /// Disable the I2S peripheral
+    #[inline]
+    pub fn disable(&mut self) {
+        self.i2s.ctrla.modify(|_, w| w.enable().clear_bit());
+        self.sync();
+    }
+
+    /// Apply a [`ClockConfig`] to clock unit `Id`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of slots or a clock division factor is out of
+    /// range.
+    #[inline]
+    pub fn configure_clock<Id: ClkId>(&mut self, config: ClockConfig<Id>) {
+        assert!(
+            (1..=8).contains(&config.slots),
+            "Invalid number of I2S slots"
+        );
+        assert!(
+            (1..=MAX_DIV).contains(&config.sck_div),
+            "Invalid I2S SCK division factor"
+        );
+        let mck_div = config.mck_div.unwrap_or(1);
+        assert!(
+            (1..=MAX_DIV).contains(&mck_div),
+            "Invalid I2S MCK division factor"
+        );
+
+        self.while_disabled(|i2s| {
+            i2s.clkctrl[Id::NUM].write(|w| {
+                match config.slot_size {
+                    SlotSize::Bits8 => w.slotsize()._8(),
+                    SlotSize::Bits16 => w.slotsize()._16(),
+                    SlotSize::Bits24 => w.slotsize()._24(),
+                    SlotSize::Bits32 => w.slotsize()._32(),
+                };
+                // Safety: The number of slots was checked above
+                unsafe { w.nbslots().bits(config.slots - 1) };
+                match config.fs_width {
+                    FsWidth::Slot => w.fswidth().slot(),
+                    FsWidth::Half => w.fswidth().half(),
+                    FsWidth::Bit => w.fswidth().bit_(),
+                    FsWidth::Burst => w.fswidth().burst(),
+                };
+                w.bitdelay().bit(config.bit_delay);
+                w.fsinv().bit(config.fs_invert);
+                if config.master {
+                    w.mcksel().gclk();
+                    w.scksel().mckdiv();
+                    w.fssel().sckdiv();
+                } else {
+                    w.scksel().sckpin();
+                    w.fssel().fspin();
+                }
+                w.mcken().bit(config.mck_div.is_some());
+                // Safety: The division factors were checked above
+                unsafe {
+                    w.mckdiv().bits(config.sck_div - 1);
+                    w.mckoutdiv().bits(mck_div - 1)
+                }
+            });
+        });
+    }
+
+    /// Enable clock unit `Id`
+    #[inline]
+    pub fn enable_clock<Id: ClkId>(&mut self) {
+        self.set_ctrla_bit(2 + Id::NUM, true);
+    }
+
+    /// Disable clock unit `Id`
+    #[inline]
+    pub fn disable_clock<Id: ClkId>(&mut self) {
+        self.set_ctrla_bit(2 + Id::NUM, false);
+    }
+
+    /// Configure serializer `S` as a transmitter clocked by clock unit `Clk`
+    #[cfg(feature = "thumbv6")]
+    #[inline]
+    pub fn configure_tx<S: TxSerializer, Clk: ClkId>(&mut self, config: SerializerConfig) {
+        self.while_disabled(|i2s| {
+            i2s.serctrl[S::NUM].write(|w| {
+                w.sermode().tx();
+                tx_fields!(w, config);
+                serializer_fields!(w, config, Clk)
+            });
+        });
+    }
+
+    /// Configure [`Tx`] as a transmitter clocked by clock unit `Clk`
+    #[cfg(feature = "thumbv7")]
+    #[inline]
+    pub fn configure_tx<Clk: ClkId>(&mut self, config: SerializerConfig) {
+        self.while_disabled(|i2s| {
+            i2s.txctrl.write(|w| {
+                w.sermode().tx();
+                tx_fields!(w, config);
+                serializer_fields!(w, config, Clk)
+            });
+        });
+    }
+
+    /// Configure serializer `S` as a receiver clocked by clock unit `Clk`
+    #[cfg(feature = "thumbv6")]
+    #[inline]
+    pub fn configure_rx<S: RxSerializer, Clk: ClkId>(&mut self, config: SerializerConfig) {
+        self.while_disabled(|i2s| {
+            i2s.serctrl[S::NUM].write(|w| {
+                w.sermode().rx();
+                serializer_fields!(w, config, Clk)
+            });
+        });
+    }
+
+    /// Configure [`Rx`] as a receiver clocked by clock unit `Clk`
+    #[cfg(feature = "thumbv7")]
+    #[inline]
+    pub fn configure_rx<Clk: ClkId>(&mut self, config: SerializerConfig) {
+        self.while_disabled(|i2s| {
+            i2s.rxctrl.write(|w| {
+                w.sermode().rx();
+                serializer_fields!(w, config, Clk)
+            });
+        });
+    }
+
+    /// Configure serializer `S` as a PDM receiver clocked by clock unit `Clk`
+    ///
+    /// The data line is sampled on both edges of `SCK`. The clock unit should
+    /// be configured as a master, with two slots per frame, and the data size
+    /// should match the slot size.
+    #[cfg(feature = "thumbv6")]
+    #[inline]
+    pub fn configure_pdm<S: RxSerializer, Clk: ClkId>(&mut self, config: SerializerConfig) {
+        self.while_disabled(|i2s| {
+            i2s.serctrl[S::NUM].write(|w| {
+                w.sermode().pdm2();
+                serializer_fields!(w, config, Clk)
+            });
+        });
+    }
+
+    /// Configure [`Rx`] as a PDM receiver clocked by clock unit `Clk`
+    ///
+    /// The data line is sampled on both edges of `SCK`. The clock unit should
+    /// be configured as a master, with two slots per frame, and the data size
+    /// should match the slot size.
+    #[cfg(feature = "thumbv7")]
+    #[inline]
+    pub fn configure_pdm<Clk: ClkId>(&mut self, config: SerializerConfig) {
+        self.while_disabled(|i2s| {
+            i2s.rxctrl.write(|w| {
+                w.sermode().pdm2();
+                serializer_fields!(w, config, Clk)
+            });
+        });
+    }
+
+    /// Enable serializer `S`
+    #[inline]
+    pub fn enable_serializer<S: SerId>(&mut self) {
+        self.set_ctrla_bit(4 + S::NUM, true);
+    }
+
+    /// Disable serializer `S`
+    #[inline]
+    pub fn disable_serializer<S: SerId>(&mut self) {
+        self.set_ctrla_bit(4 + S::NUM, false);
+    }
+
+    /// Write a data word to transmitter `S`
+    ///
+    /// Returns [`WouldBlock`](nb::Error::WouldBlock) until the previous word
+    /// has been moved to the shift register.
+    #[inline]
+    pub fn write<S: TxSerializer>(&mut self, word: u32) -> nb::Result<(), Infallible> {
+        if !self.read_flags().contains(Flags::tx_ready::<S>()) {
+            return Err(nb::Error::WouldBlock);
+        }
+        // Safety: Any data word is valid
+        #[cfg(feature = "thumbv6")]
+        self.i2s.data[S::NUM].write(|w| unsafe { w.data().bits(word) });
+        #[cfg(feature = "thumbv7")]
+        self.i2s.txdata.write(|w| unsafe { w.data().bits(word) });
+        self.sync();
+        Ok(())
+    }
+
+    /// Read a data word from receiver `S`
+    ///
+    /// Returns [`WouldBlock`](nb::Error::WouldBlock) until a word has been
+    /// received.
+    #[inline]
+    pub fn read<S: RxSerializer>(&mut self) -> nb::Result<u32, Infallible> {
+        if !self.read_flags().contains(Flags::rx_ready::<S>()) {
+            return Err(nb::Error::WouldBlock);
+        }
+        #[cfg(feature = "thumbv6")]
+        let word = self.i2s.data[S::NUM].read().data().bits();
+        #[cfg(feature = "thumbv7")]
+        let word = self.i2s.rxdata.read().data().bits();
+        Ok(word)
+    }
+
+    /// Enable interrupts for the specified flags
+    #[inline]
+    pub fn enable_interrupts(&mut self, flags: Flags) {
+        // Safety: `Flags` bits match the INTENSET bits
+        self.i2s.intenset.write(|w| unsafe { w.bits(flags.bits()) });
+    }
+
+    /// Disable interrupts for the specified flags
+    #[inline]
+    pub fn disable_interrupts(&mut self, flags: Flags) {
+        // Safety: `Flags` bits match the INTENCLR bits
+        self.i2s.intenclr.write(|w| unsafe { w.bits(flags.bits()) });
+    }
+
+    /// Read the interrupt flags
+    #[inline]
+    pub fn read_flags(&self) -> Flags {
+        Flags::from_bits_truncate(self.i2s.intflag.read().bits())
+    }
+
+    /// Clear the specified interrupt flags
+    #[inline]
+    pub fn clear_flags(&mut self, flags: Flags) {
+        // Safety: `Flags` bits match the INTFLAG bits
+        self.i2s.intflag.write(|w| unsafe { w.bits(flags.bits()) });
+    }
+
+    /// Reset the I2S peripheral and return the register block
+    #[inline]
+    pub fn free(self) -> I2S {
+        self.i2s.ctrla.write(|w| w.swrst().set_bit());
+        self.sync();
+        self.i2s
+    }
+
+    #[inline]
+    fn set_ctrla_bit(&mut self, bit: usize, set: bool) {
+        // Safety: Only the selected CKENn or SERENn bit is modified
+        self.i2s.ctrla.modify(|r, w| unsafe {
+            w.bits(if set {
+                r.bits() | (1 << bit)
+            } else {
+                r.bits() & !(1 << bit)
+            })
+        });
+        self.sync();
+    }
+
+    /// The clock unit and serializer configuration is enable-protected, so
+    /// disable the peripheral while it is written, then restore its state
+    #[inline]
+    fn while_disabled(&mut self, f: impl FnOnce(&I2S)) {
+        let enabled = self.i2s.ctrla.read().enable().bit_is_set();
+        self.disable();
+        f(&self.i2s);
+        if enabled {
+            self.enable();
+        }
+    }
+
+    #[cfg(feature = "dma")]
+    #[inline]
+    fn data_ptr<S: SerId>(&self) -> *mut u32 {
+        #[cfg(feature = "thumbv6")]
+        return self.i2s.data[S::NUM].as_ptr();
+        #[cfg(feature = "thumbv7")]
+        return if S::NUM == 0 {
+            self.i2s.txdata.as_ptr()
+        } else {
+            self.i2s.rxdata.as_ptr()
+        };
+    }
+}
+
+//==============================================================================
+// DMA
+//==============================================================================
+
+/// [`I2s`] used as the source or destination of a DMA transfer
+///
+/// Created by [`I2s::receive_with_dma`] and [`I2s::send_with_dma`]. It points
+/// to the data register of serializer `S`. Call [`free`](DmaI2s::free) to get
+/// the [`I2s`] back once the transfer is stopped.
+#[cfg(feature = "dma")]
+pub struct DmaI2s<S: SerId> {
+    i2s: I2s,
+    ser: PhantomData<S>,
+}
+
+#[cfg(feature = "dma")]
+impl<S: SerId> DmaI2s<S> {
+    /// Return the [`I2s`]
+    #[inline]
+    pub fn free(self) -> I2s {
+        self.i2s
+    }
+}
+
+#[cfg(feature = "dma")]
+unsafe impl<S: SerId> Buffer for DmaI2s<S> {
+    type Beat = u32;
+
+    #[inline]
+    fn dma_ptr(&mut self) -> *mut Self::Beat {
+        self.i2s.data_ptr::<S>()
+    }
+
+    #[inline]
+    fn incrementing(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn buffer_len(&self) -> usize {
+        1
+    }
+}
+
+/// [`PingPongTransfer`] streaming words received by serializer `S` into
+/// two buffers
+///
+/// Returned by [`I2s::receive_with_dma`]. `C` is the id of the DMA channel.
+#[cfg(feature = "dma")]
+pub type I2sRxTransfer<C, S, B, W> =
+    PingPongTransfer<Channel<C, Busy>, Repeat<DmaI2s<S>>, [B; 2], W>;
+
+/// [`PingPongTransfer`] streaming two buffers to serializer `S`
+///
+/// Returned by [`I2s::send_with_dma`]. `C` is the id of the DMA channel.
+#[cfg(feature = "dma")]
+pub type I2sTxTransfer<C, S, B, W> =
+    PingPongTransfer<Channel<C, Busy>, [B; 2], Repeat<DmaI2s<S>>, W>;
+
+#[cfg(feature = "dma")]
+impl I2s {
+    /// Transform an [`I2s`] into a [`PingPongTransfer`] and start streaming
+    /// the words received by serializer `S` into `buffers`
+    ///
+    /// The clock unit and serializer must be configured and enabled. The
+    /// transfer runs until it is stopped, and each buffer is handed to the
+    /// application when it is full, see [`PingPongTransfer`].
+    #[inline]
+    pub fn receive_with_dma<S, Ch, B, W>(
+        self,
+        buffers: [B; 2],
+        channel: Ch,
+        descriptors: &'static mut LinkedDescriptors<2>,
+        waker: W,
+    ) -> I2sRxTransfer<Ch::Id, S, B, W>
+    where
+        S: RxSerializer,
+        Ch: AnyChannel<Status = Ready>,
+        B: Buffer<Beat = u32> + 'static,
+        W: FnMut(CallbackStatus) + 'static,
+    {
+        let source = Repeat(DmaI2s {
+            i2s: self,
+            ser: PhantomData,
+        });
+
+        #[cfg(feature = "thumbv7")]
+        let trigger_action = TriggerAction::BURST;
+
+        #[cfg(feature = "thumbv6")]
+        let trigger_action = TriggerAction::BEAT;
+
+        // SAFETY: This is safe because the of the `'static` bound check
+        // for `B`, and the fact that the buffer length of a `DmaI2s` is always 1.
+        let xfer =
+            unsafe { PingPongTransfer::new_unchecked(channel, source, buffers, descriptors) };
+        xfer.with_waker(waker)
+            .begin(S::DMA_RX_TRIGGER, trigger_action)
+    }
+
+    /// Transform an [`I2s`] into a [`PingPongTransfer`] and start streaming
+    /// `buffers` to serializer `S`
+    ///
+    /// The clock unit and serializer must be configured and enabled. The
+    /// transfer runs until it is stopped, and each buffer is handed to the
+    /// application when it has been sent, so that it can be refilled, see
+    /// [`PingPongTransfer`].
+    #[inline]
+    pub fn send_with_dma<S, Ch, B, W>(
+        self,
+        buffers: [B; 2],
+        channel: Ch,
+        descriptors: &'static mut LinkedDescriptors<2>,
+        waker: W,
+    ) -> I2sTxTransfer<Ch::Id, S, B, W>
+    where
+        S: TxSerializer,
+        Ch: AnyChannel<Status = Ready>,
+        B: Buffer<Beat = u32> + 'static,
+        W: FnMut(CallbackStatus) + 'static,
+    {
+        let dest = Repeat(DmaI2s {
+            i2s: self,
+            ser: PhantomData,
+        });
+
+        #[cfg(feature = "thumbv7")]
+        let trigger_action = TriggerAction::BURST;
+
+        #[cfg(feature = "thumbv6")]
+        let trigger_action = TriggerAction::BEAT;
+
+        // SAFETY: This is safe because the of the `'static` bound check
+        // for `B`, and the fact that the buffer length of a `DmaI2s` is always 1.
+        let xfer = unsafe { PingPongTransfer::new_unchecked(channel, buffers, dest, descriptors) };
+        xfer.with_waker(waker)
+            .begin(S::DMA_TX_TRIGGER, trigger_action)
+    }
+}
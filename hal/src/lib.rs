@@ -1,6 +1,8 @@
 #![no_std]
 
-pub use embedded_hal as ehal;
+pub use embedded_hal_02 as ehal_02;
+pub use embedded_hal_02 as ehal;
+pub use embedded_hal_1 as ehal_1;
 pub use fugit;
 pub use paste;
 pub mod typelevel;
@@ -64,10 +66,20 @@ macro_rules! dbgprint {
     ($($arg:tt)*) => {{}};
 }
 
+#[cfg(feature = "device")]
+pub mod ac;
+#[cfg(feature = "device")]
+pub mod capture;
+#[cfg(feature = "device")]
+pub mod dac;
 #[cfg(feature = "device")]
 pub mod delay;
 #[cfg(feature = "device")]
+pub mod evsys;
+#[cfg(feature = "device")]
 pub mod gpio;
+#[cfg(all(feature = "device", feature = "has-i2s"))]
+pub mod i2s;
 #[cfg(feature = "device")]
 pub mod prelude;
 #[cfg(feature = "device")]
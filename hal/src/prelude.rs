@@ -6,12 +6,13 @@ pub use fugit::RateExtU32 as _;
 
 // embedded-hal doesn’t yet have v2 in its prelude, so we need to
 // export it ourselves
-#[cfg(feature = "unproven")]
-pub use crate::ehal::digital::v2::InputPin as _atsamd_hal_embedded_hal_digital_v2_InputPin;
-pub use crate::ehal::digital::v2::OutputPin as _atsamd_hal_embedded_hal_digital_v2_OutputPin;
-#[cfg(feature = "unproven")]
-pub use crate::ehal::digital::v2::ToggleableOutputPin as _atsamd_hal_embedded_hal_digital_v2_ToggleableOutputPin;
+#[cfg(feature = "ehal-02")]
+pub use crate::ehal_02::digital::v2::InputPin as _atsamd_hal_embedded_hal_digital_v2_InputPin;
+#[cfg(feature = "ehal-02")]
+pub use crate::ehal_02::digital::v2::OutputPin as _atsamd_hal_embedded_hal_digital_v2_OutputPin;
+#[cfg(feature = "ehal-02")]
+pub use crate::ehal_02::digital::v2::ToggleableOutputPin as _atsamd_hal_embedded_hal_digital_v2_ToggleableOutputPin;
 
-pub use crate::ehal::prelude::*;
+pub use crate::ehal_02::prelude::*;
 
 pub use nb;
@@ -1,5 +1,5 @@
 //! Real-time clock/counter
-use crate::ehal::timer::{CountDown, Periodic};
+use crate::ehal_02::timer::{CountDown, Periodic};
 use crate::pac::rtc::{MODE0, MODE2};
 use crate::pac::RTC;
 use crate::time::{Hertz, Nanoseconds};
@@ -222,6 +222,41 @@ impl<Mode: RtcMode> Rtc<Mode> {
         self.into_mode()
     }
 
+    /// Enables the periodic event output `n`, in the range `0..8`, for use with
+    /// the [event system](crate::evsys).
+    ///
+    /// The event is generated at a frequency of `rtc_clock_freq / 2^(n + 3)`.
+    pub fn enable_periodic_event(&mut self, n: u8) {
+        assert!(n < 8, "Invalid RTC periodic event");
+        self.set_periodic_event(n, true);
+    }
+
+    /// Disables the periodic event output `n`, in the range `0..8`.
+    pub fn disable_periodic_event(&mut self, n: u8) {
+        assert!(n < 8, "Invalid RTC periodic event");
+        self.set_periodic_event(n, false);
+    }
+
+    /// The EVCTRL register is enable-protected, so the RTC is disabled while it
+    /// is written.
+    fn set_periodic_event(&mut self, n: u8, set: bool) {
+        let enabled = self.mode0_ctrla().read().enable().bit_is_set();
+        self.enable(false);
+        self.mode0().evctrl.modify(|r, w| {
+            let bits = r.bits();
+            let bits = if set {
+                bits | (1 << n)
+            } else {
+                bits & !(1 << n)
+            };
+            // Safety: Only the PEREOn bit is modified
+            unsafe { w.bits(bits) }
+        });
+        if enabled {
+            self.enable(true);
+        }
+    }
+
     /// Releases the RTC resource
     pub fn free(self) -> RTC {
         self.rtc
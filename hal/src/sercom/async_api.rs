@@ -0,0 +1,63 @@
+//! # `async` support for SERCOM peripherals
+//!
+//! With the `async` feature enabled, the SERCOM drivers provide futures which
+//! are woken from the SERCOM interrupt(s). [`on_interrupt`] must be called
+//! from the interrupt handler(s) of every SERCOM used in `async` mode, and the
+//! interrupt(s) must be unmasked in the NVIC. On SAMD11/SAMD21, each SERCOM has
+//! a single interrupt. On SAMD51/SAME5x, each SERCOM has four interrupts
+//! (`SERCOMn_0` to `SERCOMn_3`). [`on_interrupt`] services all of them, so it
+//! can be called from any of them.
+//!
+//! ```
+//! #[interrupt]
+//! fn SERCOM0() {
+//!     atsamd_hal::sercom::on_interrupt::<Sercom0>();
+//! }
+//! ```
+
+use super::Sercom;
+use embassy_sync::waitqueue::AtomicWaker;
+
+/// Number of SERCOM instances, including the ones missing on smaller chips
+#[cfg(feature = "thumbv6")]
+const NUM_SERCOM: usize = 6;
+#[cfg(feature = "thumbv7")]
+const NUM_SERCOM: usize = 8;
+
+#[allow(clippy::declare_interior_mutable_const)]
+const NEW_WAKER: AtomicWaker = AtomicWaker::new();
+
+/// Wakers for the receiving futures of every SERCOM
+pub(super) static RX_WAKERS: [AtomicWaker; NUM_SERCOM] = [NEW_WAKER; NUM_SERCOM];
+
+/// Wakers for the transmitting futures of every SERCOM
+///
+/// Keeping them separate from the [`RX_WAKERS`] lets the two halves of a
+/// split peripheral be awaited from different tasks.
+pub(super) static TX_WAKERS: [AtomicWaker; NUM_SERCOM] = [NEW_WAKER; NUM_SERCOM];
+
+/// SERCOM interrupt handler for `async` futures.
+///
+/// This function disables every enabled interrupt with a pending flag, then
+/// wakes both the receiving and transmitting futures of the SERCOM. The
+/// interrupt flags are left untouched, so that the futures can check them
+/// when they are polled.
+#[inline]
+pub fn on_interrupt<S: Sercom>() {
+    // SAFETY: The INTENCLR, INTENSET and INTFLAG registers are at the same
+    // offsets in every SERCOM mode, so they can be accessed through the USART
+    // view regardless of the current mode. We only disable interrupts, which
+    // the futures re-enable when they are polled.
+    let sercom = unsafe { &*S::reg_block() };
+
+    #[cfg(feature = "thumbv6")]
+    let usart = sercom.usart();
+    #[cfg(feature = "thumbv7")]
+    let usart = sercom.usart_int();
+
+    let pending = usart.intflag.read().bits() & usart.intenset.read().bits();
+    usart.intenclr.write(|w| unsafe { w.bits(pending) });
+
+    RX_WAKERS[S::NUM].wake();
+    TX_WAKERS[S::NUM].wake();
+}
@@ -18,6 +18,33 @@ use crate::{
     },
 };
 
+//=============================================================================
+// DATA register buffer
+//=============================================================================
+
+/// Non-incrementing DMA buffer, pointing to the SERCOM `DATA` register or to
+/// a dummy word
+pub(super) struct SercomPtr<T: Beat>(pub(super) *mut T);
+
+unsafe impl<T: Beat> Buffer for SercomPtr<T> {
+    type Beat = T;
+
+    #[inline]
+    fn dma_ptr(&mut self) -> *mut Self::Beat {
+        self.0
+    }
+
+    #[inline]
+    fn incrementing(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn buffer_len(&self) -> usize {
+        1
+    }
+}
+
 //=============================================================================
 // I2C DMA transfers
 //=============================================================================
@@ -378,3 +405,137 @@ where
             .begin(C::Sercom::DMA_RX_TRIGGER, trigger_action)
     }
 }
+
+//=============================================================================
+// SPI slave DMA frames
+//=============================================================================
+
+/// DMA transfer from the SPI `DATA` register to the buffer of an
+/// [`SpiSlaveFrame`]
+type SpiSlaveTransfer<Id, T, B> = Transfer<Channel<Id, Busy>, BufferPair<SercomPtr<T>, B>>;
+
+/// SPI slave frame received with DMA
+///
+/// Create this struct with
+/// [`receive_frame_with_dma`](Spi::receive_frame_with_dma). The frame ends when
+/// `SS` rises, even if the buffer is not full.
+pub struct SpiSlaveFrame<Ch, B, C, A>
+where
+    Ch: AnyChannel<Status = Ready>,
+    B: Buffer<Beat = C::Word> + 'static,
+    C: spi::ValidConfig<OpMode = spi::Slave>,
+    C::Word: Beat,
+    A: spi::Receive,
+{
+    spi: Spi<C, A>,
+    transfer: SpiSlaveTransfer<Ch::Id, C::Word, B>,
+    len: usize,
+}
+
+impl<C, A> Spi<C, A>
+where
+    C: spi::ValidConfig<OpMode = spi::Slave>,
+    C::Size: spi::AtomicSize<Word = C::Word>,
+    C::Word: Beat,
+    A: spi::Receive,
+{
+    /// Receive the next frame into `buf` with a DMA transfer
+    ///
+    /// The [`Flags::TXC`](spi::Flags::TXC) flag is cleared, so the frame ends
+    /// on the next rising edge of `SS`. Words that don't fit in `buf` are left
+    /// in the receive buffer, and discarded when the frame is
+    /// [`stop`](SpiSlaveFrame::stop)ped.
+    #[inline]
+    pub fn receive_frame_with_dma<Ch, B>(
+        mut self,
+        buf: B,
+        mut channel: Ch,
+    ) -> SpiSlaveFrame<Ch, B, C, A>
+    where
+        Ch: AnyChannel<Status = Ready>,
+        B: Buffer<Beat = C::Word> + 'static,
+    {
+        self.clear_flags(spi::Flags::TXC);
+        let len = buf.buffer_len();
+        channel.as_mut().set_remaining_beats(len);
+
+        #[cfg(feature = "thumbv7")]
+        let trigger_action = TriggerAction::BURST;
+
+        #[cfg(feature = "thumbv6")]
+        let trigger_action = TriggerAction::BEAT;
+
+        let source = SercomPtr(self.data_ptr());
+        // SAFETY: This is safe because of the `'static` bound check for `B`,
+        // and because the `Spi` owning the `DATA` register is kept alive until
+        // the transfer is stopped.
+        let transfer = unsafe { Transfer::new_unchecked(channel, source, buf, false) }
+            .begin(C::Sercom::DMA_RX_TRIGGER, trigger_action);
+
+        SpiSlaveFrame {
+            spi: self,
+            transfer,
+            len,
+        }
+    }
+}
+
+impl<Ch, B, C, A> SpiSlaveFrame<Ch, B, C, A>
+where
+    Ch: AnyChannel<Status = Ready>,
+    B: Buffer<Beat = C::Word> + 'static,
+    C: spi::ValidConfig<OpMode = spi::Slave>,
+    C::Word: Beat,
+    A: spi::Receive,
+{
+    /// Check whether `SS` rose, ending the frame
+    #[inline]
+    pub fn frame_ended(&self) -> bool {
+        self.spi.read_flags().contains(spi::Flags::TXC)
+    }
+
+    /// Wait for the end of the frame, stop the DMA transfer and release all
+    /// owned resources
+    ///
+    /// The last element is the number of words written to the buffer.
+    ///
+    /// # Blocking: This method may block
+    #[inline]
+    pub fn wait(self) -> (Channel<Ch::Id, Ready>, B, Spi<C, A>, usize) {
+        while !self.frame_ended() {}
+        self.stop()
+    }
+
+    /// Non-blocking; Immediately stop the DMA transfer and release all owned
+    /// resources
+    ///
+    /// The last element is the number of words written to the buffer. The
+    /// receive buffer is only drained once `buf` is full, so that the DMAC
+    /// does not lose the last words of a shorter frame. If the frame has not
+    /// ended, the rest of it is left to the peripheral.
+    #[inline]
+    pub fn stop(self) -> (Channel<Ch::Id, Ready>, B, Spi<C, A>, usize) {
+        let Self {
+            mut spi,
+            mut transfer,
+            len,
+        } = self;
+
+        // Let the DMAC move the words still in the receive buffer, unless the
+        // buffer is already full
+        while spi.read_flags().contains(spi::Flags::RXC) && !transfer.complete() {}
+
+        let (channel, _, buf) = transfer.stop();
+        let remaining = channel.remaining_beats().min(len);
+
+        // Discard the words that did not fit in the buffer
+        if remaining == 0 {
+            while spi.read_flags().contains(spi::Flags::RXC) {
+                unsafe { spi.read_data() };
+            }
+        }
+        spi.clear_flags(spi::Flags::TXC);
+        let received = len - remaining;
+        (channel, buf, spi, received)
+    }
+}
@@ -4,8 +4,8 @@
 //! a set of [`Pads`] for use by the peripheral. Next, you assemble pieces into
 //! a [`Config`] struct. After configuring the peripheral, you then [`enable`]
 //! it, yielding a functional [`I2c`] struct.
-//! Transactions are performed using the [`i2c`](embedded_hal::blocking::i2c)
-//! traits from embedded HAL.
+//! Transactions are performed using the [`I2c`](crate::ehal_1::i2c::I2c) trait
+//! from embedded HAL.
 //!
 //! # [`Pads`]
 //!
@@ -136,15 +136,20 @@
 //! parameter, representing the underlying [`Config`].
 //!
 //! Only the [`I2c`] struct can actually perform
-//! transactions. To do so, use the embedded HAL traits, like
-//! [`i2c::WriteRead`], [`i2c::Read`] and [`i2c::Write`].
+//! transactions. To do so, use the embedded HAL
+//! [`I2c`](crate::ehal_1::i2c::I2c) trait. Adjacent operations of the same type
+//! are merged, and a repeated start is sent whenever the type of operation
+//! changes.
 //!
 //! ```
-//! use embedded_hal::blocking::i2c::Write;
+//! use atsamd_hal::ehal_1::i2c::I2c;
 //!
-//! i2c.write(0x54, 0x0fe)
+//! i2c.write(0x54, &[0x0f, 0xe0])?;
 //! ```
 //!
+//! With the `ehal-02` feature, the embedded HAL 0.2 traits, like
+//! [`i2c::WriteRead`], [`i2c::Read`] and [`i2c::Write`], are also implemented.
+//!
 //! # Reading the current configuration
 //!
 //! The `AsRef<Config<P>>` trait is implemented for `I2c<Config<P>>`.
@@ -177,9 +182,14 @@
 //! let config = i2c.disable();
 //! ```
 //!
+//! # Target mode
+//!
+//! The peripheral can also respond to the transactions of another controller,
+//! as an I2C target (slave). See the [`TargetConfig`] and [`I2cTarget`]
+//! documentation for more details.
+//!
 //! # Non-supported features
 //!
-//! * Slave mode is not supported at this time.
 //! * High-speed mode is not supported.
 //! * 4-wire mode is not supported.
 //! * 32-bit extension mode is not supported (SAMx5x). If you need to transfer
@@ -199,9 +209,9 @@
 //! [`Pin`]: crate::gpio::pin::Pin
 //! [`PinId`]: crate::gpio::pin::PinId
 //! [`PinMode`]: crate::gpio::pin::PinMode
-//! [`i2c::Write`]: embedded_hal::blocking::i2c::Write
-//! [`i2c::Read`]: embedded_hal::blocking::i2c::Read
-//! [`i2c::WriteRead`]: embedded_hal::blocking::i2c::WriteRead
+//! [`i2c::Write`]: embedded_hal_02::blocking::i2c::Write
+//! [`i2c::Read`]: embedded_hal_02::blocking::i2c::Read
+//! [`i2c::WriteRead`]: embedded_hal_02::blocking::i2c::WriteRead
 #![cfg_attr(
     feature = "dma",
     doc = "
@@ -252,6 +262,32 @@ fn i2c_send_with_dma<A: AnyConfig, C: AnyChannel<Status = Ready>>(i2c: I2c<A>, c
 [`Channel`]: crate::dmac::channel::Channel
 [`dmac`]: crate::dmac
 
+"
+)]
+#![cfg_attr(
+    feature = "async",
+    doc = "
+# `async` operation
+
+With the `async` feature enabled, an [`I2c`] can be turned into an
+[`I2cFuture`] with [`into_future`](I2c::into_future). [`I2cFuture`]
+implements [`embedded_hal_async::i2c::I2c`], running complete write, read and
+write-read transactions driven by the `MB`, `SB` and `ERROR` interrupts. The
+futures are woken from the SERCOM interrupt(s) through
+[`sercom::on_interrupt`](crate::sercom::on_interrupt).
+
+```
+#[interrupt]
+fn SERCOM0() {
+    atsamd_hal::sercom::on_interrupt::<Sercom0>();
+}
+
+async fn read_register(i2c: &mut I2cFuture<Config<Pads>>) -> Result<u8, AsyncError> {
+    let mut value = [0];
+    i2c.write_read(0x54, &[0x0f], &mut value).await?;
+    Ok(value[0])
+}
+```
 "
 )]
 
@@ -274,7 +310,17 @@ pub use flags::*;
 mod config;
 pub use config::*;
 
+mod target;
+pub use target::*;
+
 mod impl_ehal;
+#[cfg(feature = "ehal-02")]
+mod impl_ehal_02;
+
+#[cfg(feature = "async")]
+mod async_api;
+#[cfg(feature = "async")]
+pub use async_api::*;
 
 /// Word size for an I2C message
 pub type Word = u8;
@@ -365,16 +411,19 @@ impl<C: AnyConfig> I2c<C> {
         self.config.as_ref().registers.check_bus_status()
     }
 
+    #[cfg(feature = "ehal-02")]
     #[inline]
     fn do_write(&mut self, addr: u8, bytes: &[u8]) -> Result<(), Error> {
         self.config.as_mut().registers.do_write(addr, bytes)
     }
 
+    #[cfg(feature = "ehal-02")]
     #[inline]
     fn do_read(&mut self, addr: u8, bytes: &mut [u8]) -> Result<(), Error> {
         self.config.as_mut().registers.do_read(addr, bytes)
     }
 
+    #[cfg(feature = "ehal-02")]
     #[inline]
     fn do_write_read(&mut self, addr: u8, bytes: &[u8], buffer: &mut [u8]) -> Result<(), Error> {
         self.config
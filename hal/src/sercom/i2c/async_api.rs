@@ -0,0 +1,244 @@
+//! `async` support for [`I2c`]s
+
+use super::{AnyConfig, Error, Flags, I2c};
+use crate::sercom::{async_api::RX_WAKERS, Sercom};
+use core::task::Poll;
+use embedded_hal_async::i2c::{ErrorKind, ErrorType, NoAcknowledgeSource, Operation};
+
+impl<C: AnyConfig> I2c<C> {
+    /// Turn an [`I2c`] into an [`I2cFuture`]
+    ///
+    /// The SERCOM interrupt(s) must be unmasked in the NVIC, and
+    /// [`sercom::on_interrupt`](crate::sercom::on_interrupt) must be called
+    /// from the interrupt handler(s).
+    #[inline]
+    pub fn into_future(self) -> I2cFuture<C> {
+        I2cFuture { i2c: self }
+    }
+}
+
+/// `async` version of an [`I2c`]
+///
+/// Create this struct by calling [`I2c::into_future`].
+pub struct I2cFuture<C: AnyConfig> {
+    i2c: I2c<C>,
+}
+
+impl<C: AnyConfig> I2cFuture<C> {
+    /// Return the underlying [`I2c`]
+    #[inline]
+    pub fn free(self) -> I2c<C> {
+        self.i2c
+    }
+
+    /// Wait until one of the specified interrupt flags is set
+    ///
+    /// The interrupts are enabled before returning `Pending`, since the
+    /// interrupt handler disables them before waking the task.
+    async fn wait_flags(&mut self, flags: Flags) -> Flags {
+        core::future::poll_fn(|cx| {
+            RX_WAKERS[<C::Sercom as Sercom>::NUM].register(cx.waker());
+            let pending = self.i2c.read_flags() & flags;
+            if !pending.is_empty() {
+                return Poll::Ready(pending);
+            }
+            self.i2c.enable_interrupts(flags);
+            Poll::Pending
+        })
+        .await
+    }
+
+    /// Wait for the address or a byte to be transmitted, then check the bus
+    /// status
+    ///
+    /// A NACK is reported as `nack`.
+    async fn wait_transmitted(&mut self, nack: AsyncError) -> Result<(), AsyncError> {
+        self.wait_flags(Flags::MB | Flags::ERROR).await;
+        self.i2c
+            .read_status()
+            .check_bus_error()
+            .map_err(|err| AsyncError::from_error(err, nack))
+    }
+
+    /// Wait for a byte to be received, then check the bus status
+    ///
+    /// While reading, `MB` is only set if the address was not acknowledged or
+    /// the arbitration was lost. The master acknowledges every byte but the
+    /// last one, so a NACK can only come from the address.
+    async fn wait_received(&mut self) -> Result<(), AsyncError> {
+        let flags = self.wait_flags(Flags::SB | Flags::MB | Flags::ERROR).await;
+        self.i2c
+            .read_status()
+            .check_bus_error()
+            .map_err(|err| AsyncError::from_error(err, AsyncError::AddressNack))?;
+        if flags.contains(Flags::MB) {
+            return Err(AsyncError::I2c(Error::ArbitrationLost));
+        }
+        Ok(())
+    }
+
+    /// Run every operation of a transaction, without sending the final STOP
+    ///
+    /// Adjacent operations of the same type are merged, as required by
+    /// [`embedded_hal_async::i2c::I2c::transaction`]. A repeated start is
+    /// sent whenever the type of operation changes.
+    async fn run_operations(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), AsyncError> {
+        // Whether the previous operation was a read, if any
+        let mut previous_read = None;
+
+        for i in 0..operations.len() {
+            let next_is_read = matches!(operations.get(i + 1), Some(Operation::Read(_)));
+
+            match &mut operations[i] {
+                Operation::Write(bytes) => {
+                    if previous_read != Some(false) {
+                        self.registers()
+                            .start_write(address)
+                            .map_err(AsyncError::I2c)?;
+                        self.wait_transmitted(AsyncError::AddressNack).await?;
+                    }
+                    for &byte in bytes.iter() {
+                        self.registers().write_data(byte);
+                        self.wait_transmitted(AsyncError::DataNack).await?;
+                    }
+                    previous_read = Some(false);
+                }
+                // A read with no bytes can't be performed, since the
+                // peripheral receives the first byte as soon as the address
+                // has been acknowledged.
+                Operation::Read([]) => {}
+                Operation::Read(buffer) => {
+                    let continued = previous_read == Some(true);
+                    if !continued {
+                        self.registers()
+                            .start_read(address)
+                            .map_err(AsyncError::I2c)?;
+                        self.wait_received().await?;
+                    }
+
+                    let len = buffer.len();
+                    for (j, byte) in buffer.iter_mut().enumerate() {
+                        if continued || j > 0 {
+                            // Acknowledge the previous byte to receive another one
+                            self.registers().cmd_read();
+                            self.wait_received().await?;
+                        }
+                        *byte = self.registers().read_data();
+
+                        // NACK the last byte, on the following repeated start
+                        // or STOP, unless the next operation keeps reading.
+                        if j == len - 1 && !next_is_read {
+                            self.registers().set_ack_action(true);
+                        }
+                    }
+                    previous_read = Some(true);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn registers(&mut self) -> &mut super::Registers<C::Sercom> {
+        &mut self.i2c.config.as_mut().registers
+    }
+}
+
+/// Sends a STOP when dropped, so that a cancelled transaction never leaves the
+/// bus owned by the peripheral.
+struct StopGuard<'a, C: AnyConfig> {
+    future: &'a mut I2cFuture<C>,
+}
+
+impl<'a, C: AnyConfig> StopGuard<'a, C> {
+    #[inline]
+    fn new(future: &'a mut I2cFuture<C>) -> Self {
+        Self { future }
+    }
+
+    #[inline]
+    fn future(&mut self) -> &mut I2cFuture<C> {
+        self.future
+    }
+}
+
+impl<C: AnyConfig> Drop for StopGuard<'_, C> {
+    #[inline]
+    fn drop(&mut self) {
+        let registers = self.future.registers();
+        // NACK a byte received by a cancelled read
+        registers.set_ack_action(true);
+        registers.cmd_stop();
+    }
+}
+
+/// Error `enum` for [`I2cFuture`] transactions
+///
+/// Unlike [`Error::Nack`], a NACK tells whether the address or a data byte was
+/// not acknowledged.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AsyncError {
+    /// Error of the I2C peripheral, other than a NACK
+    I2c(Error),
+    /// The address was not acknowledged
+    AddressNack,
+    /// A data byte was not acknowledged
+    DataNack,
+}
+
+impl AsyncError {
+    /// Convert an [`Error`], reporting a NACK as `nack`
+    #[inline]
+    fn from_error(err: Error, nack: AsyncError) -> Self {
+        match err {
+            Error::Nack => nack,
+            err => AsyncError::I2c(err),
+        }
+    }
+}
+
+impl embedded_hal_async::i2c::Error for AsyncError {
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        match self {
+            AsyncError::I2c(err) => embedded_hal_async::i2c::Error::kind(err),
+            AsyncError::AddressNack => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+            AsyncError::DataNack => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
+        }
+    }
+}
+
+impl<C: AnyConfig> ErrorType for I2cFuture<C> {
+    type Error = AsyncError;
+}
+
+impl<C: AnyConfig> embedded_hal_async::i2c::I2c for I2cFuture<C> {
+    /// Run a transaction, driven by the `MB`, `SB` and `ERROR` interrupts
+    ///
+    /// A STOP is sent once every operation completes, when a NACK is
+    /// received, or when the future is dropped before completion.
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), AsyncError> {
+        if operations.is_empty() {
+            return Ok(());
+        }
+        self.i2c.clear_flags(Flags::ERROR);
+
+        let mut guard = StopGuard::new(self);
+        let result = guard.future().run_operations(address, operations).await;
+        if let Err(AsyncError::I2c(_)) = result {
+            // The peripheral lost the bus, so no STOP must be sent
+            core::mem::forget(guard);
+            self.i2c.clear_flags(Flags::ERROR);
+        }
+        result
+    }
+}
@@ -38,7 +38,7 @@ where
 impl<P: PadSet> Config<P> {
     /// Create a new [`Config`] in the default configuration.
     #[inline]
-    fn default(sercom: P::Sercom, pads: P, freq: impl Into<Hertz>) -> Self {
+    pub(super) fn default(sercom: P::Sercom, pads: P, freq: impl Into<Hertz>) -> Self {
         let mut registers = Registers::new(sercom);
         registers.swrst();
         registers.set_op_mode(MODE_A::I2C_MASTER);
@@ -53,7 +53,8 @@ impl<P: PadSet> Config<P> {
     ///
     /// This function will enable the corresponding APB clock, reset the
     /// [`Sercom`] peripheral, and return a [`Config`] in the default
-    /// configuration. The only available operating mode is currently Master.
+    /// configuration in Master mode. Use [`TargetConfig`](super::TargetConfig)
+    /// for target (slave) mode.
     ///
     /// Note that [`Config`] takes ownership of both the
     /// PAC [`Sercom`] struct as well as the [`Pads`](super::Pads).
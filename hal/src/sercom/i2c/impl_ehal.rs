@@ -1,34 +1,83 @@
 //! `embedded-hal` trait implementations for [`I2c`]s
 
 use super::{config::AnyConfig, flags::Error, I2c};
-use embedded_hal::blocking::i2c::{Read, Write, WriteRead};
+use crate::ehal_1::i2c::{self, ErrorKind, ErrorType, NoAcknowledgeSource, Operation};
 
-impl<C: AnyConfig> Write for I2c<C> {
-    type Error = Error;
-
-    fn write(&mut self, addr: u8, bytes: &[u8]) -> Result<(), Self::Error> {
-        self.do_write(addr, bytes)?;
-        self.cmd_stop();
-        Ok(())
+impl i2c::Error for Error {
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Error::BusError => ErrorKind::Bus,
+            Error::ArbitrationLost => ErrorKind::ArbitrationLoss,
+            Error::Nack => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown),
+            Error::LengthError | Error::Timeout => ErrorKind::Other,
+        }
     }
 }
 
-impl<C: AnyConfig> Read for I2c<C> {
+impl<C: AnyConfig> ErrorType for I2c<C> {
     type Error = Error;
+}
 
-    fn read(&mut self, addr: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
-        self.do_read(addr, buffer)?;
-        self.cmd_stop();
+impl<C: AnyConfig> I2c<C> {
+    /// Run every operation of a transaction, without sending the final STOP
+    ///
+    /// Adjacent operations of the same type are merged, and a repeated start
+    /// is sent whenever the type of operation changes.
+    fn run_operations(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Error> {
+        let registers = &mut self.config.as_mut().registers;
+
+        // Whether the previous operation was a read, if any
+        let mut previous_read = None;
+
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => {
+                    if previous_read != Some(false) {
+                        registers.start_write_blocking(address)?;
+                    }
+                    registers.send_bytes(bytes)?;
+                    previous_read = Some(false);
+                }
+                // A read with no bytes can't be performed, since the
+                // peripheral receives the first byte as soon as the address
+                // has been acknowledged.
+                Operation::Read([]) => {}
+                Operation::Read(buffer) => {
+                    if previous_read == Some(true) {
+                        // Acknowledge the last byte of the previous read to
+                        // receive another one
+                        registers.cmd_read();
+                    } else {
+                        registers.start_read_blocking(address)?;
+                    }
+                    registers.fill_buffer(buffer)?;
+                    previous_read = Some(true);
+                }
+            }
+        }
         Ok(())
     }
 }
 
-impl<C: AnyConfig> WriteRead for I2c<C> {
-    type Error = Error;
+impl<C: AnyConfig> i2c::I2c for I2c<C> {
+    /// Run a blocking transaction
+    ///
+    /// A STOP is sent once every operation completes, or when a NACK is
+    /// received.
+    fn transaction(&mut self, address: u8, operations: &mut [Operation<'_>]) -> Result<(), Error> {
+        if operations.is_empty() {
+            return Ok(());
+        }
 
-    fn write_read(&mut self, addr: u8, bytes: &[u8], buffer: &mut [u8]) -> Result<(), Self::Error> {
-        self.do_write_read(addr, bytes, buffer)?;
-        self.cmd_stop();
-        Ok(())
+        let result = self.run_operations(address, operations);
+        if let Ok(()) | Err(Error::Nack) = result {
+            self.cmd_stop();
+        }
+        result
     }
 }
@@ -209,9 +209,10 @@ impl<S: Sercom> Registers<S> {
         }
     }
 
-    /// Start a blocking write transaction
+    /// Start a write transaction, without waiting for the address to be
+    /// transmitted
     #[inline]
-    pub(super) fn start_write_blocking(&mut self, addr: u8) -> Result<(), Error> {
+    pub(super) fn start_write(&mut self, addr: u8) -> Result<(), Error> {
         if self.get_smart_mode() {
             self.disable();
             self.set_smart_mode(false);
@@ -228,14 +229,23 @@ impl<S: Sercom> Registers<S> {
                 .write(|w| w.addr().bits(encode_write_address(addr)));
         }
 
+        Ok(())
+    }
+
+    /// Start a blocking write transaction
+    #[inline]
+    pub(super) fn start_write_blocking(&mut self, addr: u8) -> Result<(), Error> {
+        self.start_write(addr)?;
+
         // wait for transmission to complete
         while !self.i2c_master().intflag.read().mb().bit_is_set() {}
         self.read_status().check_bus_error()
     }
 
-    /// Start a blocking read transaction
+    /// Start a read transaction, without waiting for the address to be
+    /// transmitted
     #[inline]
-    pub(super) fn start_read_blocking(&mut self, addr: u8) -> Result<(), Error> {
+    pub(super) fn start_read(&mut self, addr: u8) -> Result<(), Error> {
         if self.get_smart_mode() {
             self.disable();
             self.set_smart_mode(false);
@@ -256,6 +266,14 @@ impl<S: Sercom> Registers<S> {
                 .write(|w| w.addr().bits(encode_read_address(addr)));
         }
 
+        Ok(())
+    }
+
+    /// Start a blocking read transaction
+    #[inline]
+    pub(super) fn start_read_blocking(&mut self, addr: u8) -> Result<(), Error> {
+        self.start_read(addr)?;
+
         // wait for transmission to complete
         loop {
             let intflag = self.i2c_master().intflag.read();
@@ -347,6 +365,32 @@ impl<S: Sercom> Registers<S> {
         self.sync_sysop();
     }
 
+    /// Set the acknowledge action sent after a received byte, by the next
+    /// command or (repeated) start
+    ///
+    /// `true` sends a NACK, `false` sends an ACK.
+    #[cfg(feature = "async")]
+    #[inline]
+    pub(super) fn set_ack_action(&mut self, nack: bool) {
+        self.i2c_master().ctrlb.modify(|_, w| w.ackact().bit(nack));
+    }
+
+    /// Write a byte to the `DATA` register
+    #[cfg(feature = "async")]
+    #[inline]
+    pub(super) fn write_data(&mut self, byte: u8) {
+        unsafe {
+            self.i2c_master().data.write(|w| w.bits(byte));
+        }
+    }
+
+    /// Read a byte from the `DATA` register
+    #[cfg(feature = "async")]
+    #[inline]
+    pub(super) fn read_data(&mut self) -> u8 {
+        self.i2c_master().data.read().bits()
+    }
+
     #[inline]
     pub(super) fn send_bytes(&mut self, bytes: &[u8]) -> Result<(), Error> {
         for b in bytes {
@@ -395,18 +439,21 @@ impl<S: Sercom> Registers<S> {
         Ok(())
     }
 
+    #[cfg(feature = "ehal-02")]
     #[inline]
     pub(super) fn do_write(&mut self, addr: u8, bytes: &[u8]) -> Result<(), Error> {
         self.start_write_blocking(addr)?;
         self.send_bytes(bytes)
     }
 
+    #[cfg(feature = "ehal-02")]
     #[inline]
     pub(super) fn do_read(&mut self, addr: u8, buffer: &mut [u8]) -> Result<(), Error> {
         self.start_read_blocking(addr)?;
         self.fill_buffer(buffer)
     }
 
+    #[cfg(feature = "ehal-02")]
     #[inline]
     pub(super) fn do_write_read(
         &mut self,
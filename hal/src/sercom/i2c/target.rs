@@ -0,0 +1,477 @@
+//! I2C target (slave) mode
+//!
+//! A [`TargetConfig`] is built from the same [`Pads`] as a controller
+//! [`Config`], either from scratch with [`TargetConfig::new`], or by
+//! converting an existing [`Config`] with [`Config::into_target`]. After
+//! configuring the address(es) to respond to, [`enable`] it to obtain an
+//! [`I2cTarget`].
+//!
+//! ```no_run
+//! use atsamd_hal::sercom::i2c::{TargetAddress, TargetConfig};
+//!
+//! let target = TargetConfig::new(&mclk, sercom, pads)
+//!     .address(TargetAddress::Mask {
+//!         address: 0x54,
+//!         mask: 0,
+//!     })
+//!     .enable();
+//! ```
+//!
+//! # Events
+//!
+//! [`I2cTarget`] exposes the same [`read_flags`]/[`clear_flags`] and
+//! [`enable_interrupts`] methods as [`I2c`](super::I2c), using
+//! [`TargetFlags`] instead. On top of them, [`read_event`] decodes the flags
+//! and status into a [`TargetEvent`]:
+//!
+//! * [`AddressMatch`](TargetEvent::AddressMatch): a controller addressed the
+//!   target. Accept or refuse the transaction with [`ack_address`] or
+//!   [`nack_address`].
+//! * [`ByteReceived`](TargetEvent::ByteReceived): read the byte written by the
+//!   controller with [`read_byte`].
+//! * [`ByteRequested`](TargetEvent::ByteRequested): send the next byte to the
+//!   controller with [`write_byte`].
+//! * [`Stop`](TargetEvent::Stop): the controller ended the transaction.
+//!
+//! The `AMATCH` and `DRDY` flags are only cleared by the methods above, so
+//! [`read_event`] keeps returning the same event until it is handled. While
+//! they are set, the target stretches the clock by holding SCL low, so the
+//! controller waits for the target. A read transaction ends when the
+//! controller does not acknowledge a byte. The target then releases the bus
+//! automatically, and [`read_event`] returns
+//! [`ReadDone`](TargetEvent::ReadDone).
+//!
+//! ```no_run
+//! use atsamd_hal::sercom::i2c::TargetEvent;
+//!
+//! loop {
+//!     match target.read_event()? {
+//!         Some(TargetEvent::AddressMatch { .. }) => target.ack_address(),
+//!         Some(TargetEvent::ByteReceived) => register = target.read_byte(true),
+//!         Some(TargetEvent::ByteRequested) => target.write_byte(value),
+//!         Some(TargetEvent::ReadDone) | Some(TargetEvent::Stop) | None => (),
+//!     }
+//! }
+//! ```
+//!
+//! [`Pads`]: super::Pads
+//! [`enable`]: TargetConfig::enable
+//! [`read_flags`]: I2cTarget::read_flags
+//! [`clear_flags`]: I2cTarget::clear_flags
+//! [`enable_interrupts`]: I2cTarget::enable_interrupts
+//! [`read_event`]: I2cTarget::read_event
+//! [`ack_address`]: I2cTarget::ack_address
+//! [`nack_address`]: I2cTarget::nack_address
+//! [`read_byte`]: I2cTarget::read_byte
+//! [`write_byte`]: I2cTarget::write_byte
+
+use super::{Config, Error, PadSet, Registers};
+use crate::{pac::sercom0::i2cm::ctrla::MODE_A, sercom::*, time::Hertz};
+
+#[path = "target_flags.rs"]
+mod flags;
+pub use flags::*;
+
+#[path = "target_reg.rs"]
+mod reg;
+
+/// Address matching mode of an [`I2cTarget`]
+///
+/// The addresses are 7 bits long, or 10 bits long if
+/// [`ten_bit_address`](TargetConfig::ten_bit_address) is set.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TargetAddress {
+    /// Match `address`, ignoring the bits set in `mask`
+    Mask { address: u16, mask: u16 },
+    /// Match either of the two addresses
+    Two(u16, u16),
+    /// Match every address from `low` to `high`, inclusive
+    Range { low: u16, high: u16 },
+}
+
+/// Event decoded from the flags of an [`I2cTarget`]
+///
+/// See [`I2cTarget::read_event`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TargetEvent {
+    /// A controller addressed the target, following a START or a repeated
+    /// START. `read` is `true` if the controller reads from the target.
+    AddressMatch { address: u16, read: bool },
+    /// The controller wrote a byte
+    ByteReceived,
+    /// The controller requests a byte
+    ByteRequested,
+    /// The controller did not acknowledge the last byte sent, ending the read
+    ReadDone,
+    /// The controller sent a STOP
+    Stop,
+}
+
+//=============================================================================
+// TargetConfig
+//=============================================================================
+
+/// A configurable, disabled I2C peripheral in target mode
+///
+/// This `struct` represents an I2C peripheral configured in target (slave)
+/// mode, in its disabled state. It is generic over the set of
+/// [`Pads`](super::Pads), like [`Config`].
+///
+/// [`TargetConfig`] uses a builder-pattern API to configure the peripheral,
+/// culminating in a call to [`enable`], which consumes the [`TargetConfig`]
+/// and returns an enabled [`I2cTarget`].
+///
+/// [`enable`]: TargetConfig::enable
+pub struct TargetConfig<P>
+where
+    P: PadSet,
+{
+    registers: Registers<P::Sercom>,
+    pads: P,
+}
+
+impl<P: PadSet> TargetConfig<P> {
+    /// Create a new [`TargetConfig`] in the default configuration.
+    #[inline]
+    pub(super) fn default(sercom: P::Sercom, pads: P) -> Self {
+        let mut registers = Registers::new(sercom);
+        registers.swrst();
+        registers.set_op_mode(MODE_A::I2C_SLAVE);
+        Self { registers, pads }
+    }
+
+    /// Create a new [`TargetConfig`] in the default configuration
+    ///
+    /// This function will enable the corresponding APB clock, reset the
+    /// [`Sercom`] peripheral, and return a [`TargetConfig`] in the default
+    /// configuration. The default address is `0x00`, with no mask.
+    ///
+    /// Users must configure GCLK manually. In target mode, the SCL clock is
+    /// generated by the controller, so no frequency is needed.
+    #[inline]
+    pub fn new(apb_clk_ctrl: &APB_CLK_CTRL, mut sercom: P::Sercom, pads: P) -> Self {
+        sercom.enable_apb_clock(apb_clk_ctrl);
+        Self::default(sercom, pads)
+    }
+
+    /// Obtain a reference to the PAC `SERCOM` struct
+    ///
+    /// # Safety
+    ///
+    /// Directly accessing the `SERCOM` could break the invariants of the
+    /// type-level tracking in this module, so it is unsafe.
+    #[inline]
+    pub unsafe fn sercom(&self) -> &P::Sercom {
+        &self.registers.sercom
+    }
+
+    /// Trigger the [`Sercom`]'s SWRST and return a [`TargetConfig`] in the
+    /// default configuration.
+    #[inline]
+    pub fn reset(self) -> TargetConfig<P> {
+        TargetConfig::default(self.registers.sercom, self.pads)
+    }
+
+    /// Reset the peripheral and convert it into a controller [`Config`]
+    ///
+    /// The `freq` parameter represents the GCLK frequency for this [`Sercom`]
+    /// instance.
+    #[inline]
+    pub fn into_controller(self, freq: impl Into<Hertz>) -> Config<P> {
+        Config::default(self.registers.sercom, self.pads, freq)
+    }
+
+    /// Consume the [`TargetConfig`], reset the peripheral, and return the
+    /// [`Sercom`] and [`Pads`](super::Pads)
+    #[inline]
+    pub fn free(mut self) -> (P::Sercom, P) {
+        self.registers.swrst();
+        (self.registers.free(), self.pads)
+    }
+
+    /// Run in standby mode (builder pattern version)
+    ///
+    /// When set, the I2C peripheral will run in standby mode. See the
+    /// datasheet for more details.
+    #[inline]
+    pub fn run_in_standby(mut self, set: bool) -> Self {
+        self.set_run_in_standby(set);
+        self
+    }
+
+    /// Run in standby mode (setter version)
+    ///
+    /// When set, the I2C peripheral will run in standby mode. See the
+    /// datasheet for more details.
+    #[inline]
+    pub fn set_run_in_standby(&mut self, set: bool) {
+        self.registers.set_run_in_standby(set);
+    }
+
+    /// Get the current run in standby mode
+    #[inline]
+    pub fn get_run_in_standby(&self) -> bool {
+        self.registers.get_run_in_standby()
+    }
+
+    /// Set the address(es) to respond to (builder pattern version)
+    #[inline]
+    pub fn address(mut self, address: TargetAddress) -> Self {
+        self.set_address(address);
+        self
+    }
+
+    /// Set the address(es) to respond to (setter version)
+    #[inline]
+    pub fn set_address(&mut self, address: TargetAddress) {
+        self.registers.set_target_address(address);
+    }
+
+    /// Get the address(es) to respond to
+    #[inline]
+    pub fn get_address(&self) -> TargetAddress {
+        self.registers.get_target_address()
+    }
+
+    /// Use 10-bit addresses (builder pattern version)
+    #[inline]
+    pub fn ten_bit_address(mut self, set: bool) -> Self {
+        self.set_ten_bit_address(set);
+        self
+    }
+
+    /// Use 10-bit addresses (setter version)
+    #[inline]
+    pub fn set_ten_bit_address(&mut self, set: bool) {
+        self.registers.set_ten_bit_address(set);
+    }
+
+    /// Get the 10-bit address setting
+    #[inline]
+    pub fn get_ten_bit_address(&self) -> bool {
+        self.registers.get_ten_bit_address()
+    }
+
+    /// Respond to the general call address (builder pattern version)
+    ///
+    /// When set, the target also matches the general call address, `0x00`.
+    #[inline]
+    pub fn general_call(mut self, set: bool) -> Self {
+        self.set_general_call(set);
+        self
+    }
+
+    /// Respond to the general call address (setter version)
+    ///
+    /// When set, the target also matches the general call address, `0x00`.
+    #[inline]
+    pub fn set_general_call(&mut self, set: bool) {
+        self.registers.set_general_call(set);
+    }
+
+    /// Get the general call setting
+    #[inline]
+    pub fn get_general_call(&self) -> bool {
+        self.registers.get_general_call()
+    }
+
+    /// Enable the I2C peripheral in target mode
+    ///
+    /// The peripheral is enabled in Smart Mode, so the acknowledge action is
+    /// sent as soon as a received byte is read.
+    #[inline]
+    pub fn enable(mut self) -> I2cTarget<P> {
+        self.registers.set_target_smart_mode();
+        self.registers.enable_peripheral(true);
+
+        I2cTarget {
+            config: self,
+            sent: false,
+        }
+    }
+}
+
+impl<P: PadSet> Config<P> {
+    /// Reset the peripheral and convert it into a [`TargetConfig`]
+    #[inline]
+    pub fn into_target(self) -> TargetConfig<P> {
+        let (sercom, pads) = self.free();
+        TargetConfig::default(sercom, pads)
+    }
+}
+
+//=============================================================================
+// I2cTarget
+//=============================================================================
+
+/// Abstraction over an I2C peripheral in target mode, responding to the
+/// transactions of a controller.
+pub struct I2cTarget<P: PadSet> {
+    config: TargetConfig<P>,
+    /// Whether a byte was sent since the last address match. Until then,
+    /// `STATUS.RXNACK` does not apply to the current read.
+    sent: bool,
+}
+
+impl<P: PadSet> I2cTarget<P> {
+    /// Read the interrupt flags
+    #[inline]
+    pub fn read_flags(&self) -> TargetFlags {
+        self.config.registers.read_target_flags()
+    }
+
+    /// Clear interrupt status flags
+    ///
+    /// Clearing `AMATCH` or `DRDY` directly releases the clock hold without
+    /// executing any acknowledge action. Prefer the dedicated methods of
+    /// [`I2cTarget`].
+    #[inline]
+    pub fn clear_flags(&mut self, flags: TargetFlags) {
+        self.config.registers.clear_target_flags(flags);
+    }
+
+    /// Enable interrupts for the specified flags.
+    #[inline]
+    pub fn enable_interrupts(&mut self, flags: TargetFlags) {
+        self.config.registers.enable_target_interrupts(flags);
+    }
+
+    /// Disable interrupts for the specified flags.
+    #[inline]
+    pub fn disable_interrupts(&mut self, flags: TargetFlags) {
+        self.config.registers.disable_target_interrupts(flags);
+    }
+
+    /// Read the status flags
+    #[inline]
+    pub fn read_status(&self) -> TargetStatus {
+        self.config.registers.read_target_status()
+    }
+
+    /// Clear the status flags
+    #[inline]
+    pub fn clear_status(&mut self, status: TargetStatus) {
+        self.config.registers.clear_target_status(status);
+    }
+
+    /// Decode the pending flags into a [`TargetEvent`]
+    ///
+    /// Errors are checked first. The error status and `ERROR` flag are
+    /// cleared before returning the error. `PREC` is cleared when the
+    /// [`Stop`](TargetEvent::Stop) event is returned. `AMATCH` and `DRDY` are
+    /// left set, until the event is handled with the corresponding method.
+    ///
+    /// For an [`AddressMatch`](TargetEvent::AddressMatch), the received
+    /// address is read from the `DATA` register, which is useful in two
+    /// address or range modes.
+    pub fn read_event(&mut self) -> Result<Option<TargetEvent>, Error> {
+        let flags = self.read_flags();
+
+        if flags.contains(TargetFlags::ERROR) {
+            let status = self.read_status();
+            let result = status.check_bus_error();
+            self.clear_status(status);
+            self.clear_flags(TargetFlags::ERROR);
+            result?;
+        }
+
+        if flags.contains(TargetFlags::PREC) {
+            self.clear_flags(TargetFlags::PREC);
+            return Ok(Some(TargetEvent::Stop));
+        }
+
+        if flags.contains(TargetFlags::AMATCH) {
+            let read = self.read_status().dir();
+            let address = (self.config.registers.read_target_data() >> 1).into();
+            return Ok(Some(TargetEvent::AddressMatch { address, read }));
+        }
+
+        if flags.contains(TargetFlags::DRDY) {
+            let status = self.read_status();
+            if !status.dir() {
+                return Ok(Some(TargetEvent::ByteReceived));
+            }
+            if self.sent && status.rxnack() {
+                self.sent = false;
+                self.config.registers.cmd_target_wait_start();
+                return Ok(Some(TargetEvent::ReadDone));
+            }
+            return Ok(Some(TargetEvent::ByteRequested));
+        }
+
+        Ok(None)
+    }
+
+    /// Acknowledge the address, accepting the transaction
+    ///
+    /// This clears the `AMATCH` flag and releases the clock hold.
+    #[inline]
+    pub fn ack_address(&mut self) {
+        self.sent = false;
+        self.config.registers.set_target_ack_action(false);
+        self.config.registers.cmd_target_next();
+    }
+
+    /// Refuse the transaction by not acknowledging the address
+    ///
+    /// This clears the `AMATCH` flag and releases the clock hold.
+    #[inline]
+    pub fn nack_address(&mut self) {
+        self.sent = false;
+        self.config.registers.set_target_ack_action(true);
+        self.config.registers.cmd_target_next();
+        self.config.registers.set_target_ack_action(false);
+    }
+
+    /// Read a byte written by the controller
+    ///
+    /// The byte is acknowledged if `ack` is `true`. Otherwise, the controller
+    /// is told to stop writing. This clears the `DRDY` flag and releases the
+    /// clock hold.
+    #[inline]
+    pub fn read_byte(&mut self, ack: bool) -> u8 {
+        self.config.registers.set_target_ack_action(!ack);
+        self.config.registers.read_target_data()
+    }
+
+    /// Send a byte to the controller
+    ///
+    /// This clears the `DRDY` flag and releases the clock hold.
+    #[inline]
+    pub fn write_byte(&mut self, byte: u8) {
+        self.sent = true;
+        self.config.registers.write_target_data(byte);
+    }
+
+    /// Reconfigure the I2C peripheral.
+    ///
+    /// Calling this method will temporarily disable the SERCOM peripheral, as
+    /// some registers are enable-protected. This may interrupt any ongoing
+    /// transactions.
+    #[inline]
+    pub fn reconfigure<F>(&mut self, update: F)
+    where
+        F: FnOnce(&mut TargetConfig<P>),
+    {
+        self.config.registers.enable_peripheral(false);
+        update(&mut self.config);
+        self.config.registers.enable_peripheral(true);
+    }
+
+    /// Disable the I2C peripheral and return the underlying [`TargetConfig`]
+    #[inline]
+    pub fn disable(self) -> TargetConfig<P> {
+        let mut config = self.config;
+        config.registers.enable_peripheral(false);
+        config
+    }
+}
+
+impl<P: PadSet> AsRef<TargetConfig<P>> for I2cTarget<P> {
+    #[inline]
+    fn as_ref(&self) -> &TargetConfig<P> {
+        &self.config
+    }
+}
@@ -0,0 +1,80 @@
+//! Flag definitions for I2C target mode
+#![allow(clippy::identity_op)]
+#![allow(clippy::new_without_default)]
+#![allow(unused_braces)]
+
+use bitflags::bitflags;
+use modular_bitfield::specifiers::{B1, B4};
+use modular_bitfield::*;
+
+use crate::sercom::i2c::Error;
+
+bitflags! {
+    /// Interrupt bitflags for I2C target mode
+    ///
+    /// The available interrupt flags are `PREC`, `AMATCH`, `DRDY` and `ERROR`.
+    /// The binary format of the underlying bits exactly matches the INTFLAG
+    /// bits.
+    pub struct TargetFlags: u8 {
+        /// Stop received interrupt
+        const PREC = 0x01;
+        /// Address match interrupt
+        const AMATCH = 0x02;
+        /// Data ready interrupt
+        const DRDY = 0x04;
+        /// Error interrupt
+        const ERROR = 0x80;
+    }
+}
+
+/// Status flags for I2C target mode
+///
+/// The available status flags are `BUSERR`, `COLL`, `RXNACK`, `DIR`, `SR`,
+/// `LOWTOUT`, `CLKHOLD`, `SEXTTOUT`, `HS` and `LENERR`. The binary format of
+/// the underlying bits exactly matches the STATUS bits.
+#[bitfield]
+#[repr(u16)]
+#[derive(Clone, Copy)]
+pub struct TargetStatus {
+    pub buserr: bool,
+    pub coll: bool,
+    #[skip(setters)]
+    pub rxnack: bool,
+    #[skip(setters)]
+    pub dir: bool,
+    #[skip(setters)]
+    pub sr: bool,
+    #[skip]
+    _reserved: B1,
+    pub lowtout: bool,
+    #[skip(setters)]
+    pub clkhold: bool,
+    #[skip]
+    _reserved: B1,
+    pub sexttout: bool,
+    pub hs: bool,
+    pub lenerr: bool,
+    #[skip]
+    _reserved: B4,
+}
+
+impl TargetStatus {
+    /// Check the status for a bus error, a collision, a timeout or a length
+    /// error
+    ///
+    /// A collision means the target lost the bus while sending data, so it is
+    /// reported as [`Error::ArbitrationLost`].
+    pub fn check_bus_error(self) -> Result<(), Error> {
+        if self.buserr() {
+            Err(Error::BusError)
+        } else if self.coll() {
+            Err(Error::ArbitrationLost)
+        } else if self.lowtout() || self.sexttout() {
+            Err(Error::Timeout)
+        } else if self.lenerr() {
+            Err(Error::LengthError)
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,178 @@
+//! Register-level access to I2C target configuration
+
+use super::{TargetAddress, TargetFlags, TargetStatus};
+use crate::pac;
+use crate::sercom::i2c::Registers;
+use crate::sercom::*;
+
+/// Execute the acknowledge action, then receive or send the next byte
+const TARGET_CMD_NEXT: u8 = 3;
+/// Execute the acknowledge action, then wait for any START condition
+const TARGET_CMD_WAIT_START: u8 = 2;
+
+impl<S: Sercom> Registers<S> {
+    /// Helper function to access the underlying `I2CS` from the given `SERCOM`
+    #[inline]
+    fn i2c_target(&self) -> &pac::sercom0::I2CS {
+        self.sercom.i2cs()
+    }
+
+    /// Set Smart Mode and disable automatic address acknowledge
+    ///
+    /// In Smart Mode, the acknowledge action is sent as soon as the `DATA`
+    /// register is read.
+    #[inline]
+    pub(super) fn set_target_smart_mode(&mut self) {
+        self.i2c_target()
+            .ctrlb
+            .modify(|_, w| w.smen().set_bit().aacken().clear_bit());
+    }
+
+    /// Set the address matching mode and the address(es)
+    #[inline]
+    pub(super) fn set_target_address(&mut self, address: TargetAddress) {
+        let (amode, addr, addrmask) = match address {
+            TargetAddress::Mask { address, mask } => (0, address, mask),
+            TargetAddress::Two(first, second) => (1, first, second),
+            TargetAddress::Range { low, high } => (2, high, low),
+        };
+        self.i2c_target()
+            .ctrlb
+            .modify(|_, w| unsafe { w.amode().bits(amode) });
+        self.i2c_target().addr.modify(|_, w| unsafe {
+            w.addr().bits(addr);
+            w.addrmask().bits(addrmask)
+        });
+    }
+
+    /// Get the address matching mode and the address(es)
+    #[inline]
+    pub(super) fn get_target_address(&self) -> TargetAddress {
+        let addr = self.i2c_target().addr.read();
+        let (addr, addrmask) = (addr.addr().bits(), addr.addrmask().bits());
+        match self.i2c_target().ctrlb.read().amode().bits() {
+            1 => TargetAddress::Two(addr, addrmask),
+            2 => TargetAddress::Range {
+                low: addrmask,
+                high: addr,
+            },
+            _ => TargetAddress::Mask {
+                address: addr,
+                mask: addrmask,
+            },
+        }
+    }
+
+    /// Enable 10-bit addressing
+    #[inline]
+    pub(super) fn set_ten_bit_address(&mut self, set: bool) {
+        self.i2c_target().addr.modify(|_, w| w.tenbiten().bit(set));
+    }
+
+    /// Get the 10-bit addressing setting
+    #[inline]
+    pub(super) fn get_ten_bit_address(&self) -> bool {
+        self.i2c_target().addr.read().tenbiten().bit()
+    }
+
+    /// Respond to the general call address (`0x00`)
+    #[inline]
+    pub(super) fn set_general_call(&mut self, set: bool) {
+        self.i2c_target().addr.modify(|_, w| w.gencen().bit(set));
+    }
+
+    /// Get the general call setting
+    #[inline]
+    pub(super) fn get_general_call(&self) -> bool {
+        self.i2c_target().addr.read().gencen().bit()
+    }
+
+    /// Clear specified target interrupt flags
+    #[inline]
+    pub(super) fn clear_target_flags(&mut self, flags: TargetFlags) {
+        self.i2c_target()
+            .intflag
+            .modify(|_, w| unsafe { w.bits(flags.bits()) });
+    }
+
+    /// Read target interrupt flags
+    #[inline]
+    pub(super) fn read_target_flags(&self) -> TargetFlags {
+        TargetFlags::from_bits_truncate(self.i2c_target().intflag.read().bits())
+    }
+
+    /// Enable specified target interrupts
+    #[inline]
+    pub(super) fn enable_target_interrupts(&mut self, flags: TargetFlags) {
+        self.i2c_target()
+            .intenset
+            .write(|w| unsafe { w.bits(flags.bits()) });
+    }
+
+    /// Disable specified target interrupts
+    #[inline]
+    pub(super) fn disable_target_interrupts(&mut self, flags: TargetFlags) {
+        self.i2c_target()
+            .intenclr
+            .write(|w| unsafe { w.bits(flags.bits()) });
+    }
+
+    /// Clear specified target status flags
+    #[inline]
+    pub(super) fn clear_target_status(&mut self, status: TargetStatus) {
+        self.i2c_target()
+            .status
+            .modify(|_, w| unsafe { w.bits(status.into()) });
+    }
+
+    /// Read target status flags
+    #[inline]
+    pub(super) fn read_target_status(&self) -> TargetStatus {
+        self.i2c_target().status.read().bits().into()
+    }
+
+    /// Set the acknowledge action, sent on the next command or, in Smart
+    /// Mode, when `DATA` is read
+    #[inline]
+    pub(super) fn set_target_ack_action(&mut self, nack: bool) {
+        self.i2c_target().ctrlb.modify(|_, w| w.ackact().bit(nack));
+    }
+
+    /// Execute the acknowledge action, then receive or send the next byte
+    ///
+    /// This clears the `AMATCH` or `DRDY` flag and releases the clock hold.
+    #[inline]
+    pub(super) fn cmd_target_next(&mut self) {
+        self.i2c_target()
+            .ctrlb
+            .modify(|_, w| unsafe { w.cmd().bits(TARGET_CMD_NEXT) });
+    }
+
+    /// Execute the acknowledge action, then wait for any START condition
+    ///
+    /// This clears the `DRDY` flag and releases the clock hold.
+    #[inline]
+    pub(super) fn cmd_target_wait_start(&mut self) {
+        self.i2c_target()
+            .ctrlb
+            .modify(|_, w| unsafe { w.cmd().bits(TARGET_CMD_WAIT_START) });
+    }
+
+    /// Read the `DATA` register
+    // The `DATA` register is 8 bits wide on thumbv6m targets, but 32 bits wide
+    // on thumbv7em targets.
+    #[allow(clippy::unnecessary_cast)]
+    #[inline]
+    pub(super) fn read_target_data(&mut self) -> u8 {
+        self.i2c_target().data.read().bits() as u8
+    }
+
+    /// Write the `DATA` register
+    #[allow(clippy::useless_conversion)]
+    #[inline]
+    pub(super) fn write_target_data(&mut self, byte: u8) {
+        self.i2c_target()
+            .data
+            .write(|w| unsafe { w.bits(byte.into()) });
+    }
+}
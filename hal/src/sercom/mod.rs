@@ -60,6 +60,11 @@ pub mod uart;
 #[cfg(feature = "dma")]
 pub mod dma;
 
+#[cfg(feature = "async")]
+pub mod async_api;
+#[cfg(feature = "async")]
+pub use async_api::*;
+
 //==============================================================================
 //  Sercom
 //==============================================================================
@@ -76,6 +81,9 @@ pub trait Sercom: Sealed + Deref<Target = sercom0::RegisterBlock> {
     const DMA_TX_TRIGGER: TriggerSource;
     /// Enable the corresponding APB clock
     fn enable_apb_clock(&mut self, ctrl: &APB_CLK_CTRL);
+    /// Get a pointer to the SERCOM's register block
+    #[cfg(feature = "async")]
+    fn reg_block() -> *const sercom0::RegisterBlock;
 }
 
 macro_rules! sercom {
@@ -100,6 +108,11 @@ macro_rules! sercom {
                     fn enable_apb_clock(&mut self, ctrl: &APB_CLK_CTRL) {
                         ctrl.$apbmask.modify(|_, w| w.[<sercom~N _>]().set_bit());
                     }
+                    #[cfg(feature = "async")]
+                    #[inline]
+                    fn reg_block() -> *const sercom0::RegisterBlock {
+                        SERCOM~N::ptr()
+                    }
                 }
             }
         });
@@ -4,10 +4,12 @@
 //! [`gpio`] [`Pin`]s to create a set of [`Pads`]. Next, you combine the
 //! `Pads` with other pieces to form a [`Config`] struct. Finally, after
 //! configuring the peripheral, you [`enable`] it to yield a functional
-//! [`Spi`] struct. Transactions are performed using traits from the
-//! [`embedded_hal`] crate, specifically those from the
-//! [`spi`](embedded_hal::spi), [`serial`](embedded_hal::serial), and
-//! [`blocking`](embedded_hal::blocking) modules.
+//! [`Spi`] struct. Transactions are performed using the
+//! [`SpiBus`](crate::ehal_1::spi::SpiBus) trait from [`embedded_hal`] 1.0. With
+//! the `ehal-02` feature, the traits from the [`spi`](embedded_hal_02::spi),
+//! [`serial`](embedded_hal_02::serial), and
+//! [`blocking`](embedded_hal_02::blocking) modules of embedded HAL 0.2 are also
+//! implemented.
 //!
 //! # Crating a set of [`Pads`]
 //!
@@ -45,6 +47,32 @@
 //! type Pads = spi::Pads<Sercom0, IoSet1, Miso, NoneT, Sclk>;
 //! ```
 //!
+//! # Slave mode
+//!
+//! In [`Slave`] mode, every transaction is framed by the `SS` line. A
+//! [`Config`] in [`Slave`] mode can enable the slave select low detection,
+//! which sets the [`Flags::SSL`] flag when a frame starts, and can wake the
+//! device from standby. The [`Flags::TXC`] flag is set when `SS` rises at the
+//! end of the frame. [`Spi::frame_started`] and [`Spi::frame_ended`] check
+//! and clear these flags. With an [`address`](Config::address), only the
+//! frames starting with a matching address are received.
+//!
+//! With [`preload`](Config::preload) enabled, a word written with
+//! [`Spi::preload_word`] while `SS` is high is sent as soon as `SS` falls.
+//! [`Spi::read_frame`] receives words until `SS` rises.
+//!
+//! ```
+//! let mut spi = spi::Config::new(&mclk, sercom, pads, freq)
+//!     .op_mode::<Slave>()
+//!     .ss_low_detect(true)
+//!     .preload(true)
+//!     .enable();
+//!
+//! spi.preload_word(0xa5);
+//! let mut buffer = [0; 16];
+//! let len = spi.read_frame(&mut buffer)?;
+//! ```
+//!
 //! [`enable`]: Config::enable
 //! [`gpio`]: crate::gpio
 //! [`Pin`]: crate::gpio::pin::Pin
@@ -197,7 +225,7 @@ type Pads = spi::PadsFromIds<Sercom0, IoSet1, PA08, NoneT, PA09>;
 //! usually the more ergonomic option.
 //!
 //! ```
-//! use embedded_hal::spi::MODE_1;
+//! use embedded_hal_02::spi::MODE_1;
 //!
 //! // SAMD11/SAMD21 version
 //! let spi = spi::Config::new(&pm, sercom, pads, freq)
@@ -254,17 +282,27 @@ type Pads = spi::PadsFromIds<Sercom0, IoSet1, PA08, NoneT, PA09>;
 //! ```
 //!
 //! Only `Spi` structs can actually perform transactions. To do so, use the
-//! various embedded HAL traits, like
-//! [`spi::FullDuplex`](embedded_hal::spi::FullDuplex),
-//! [`serial::Read`](embedded_hal::serial::Read) or
-//! [`serial::Write`](embedded_hal::serial::Write).
-//! See the [`impl_ehal`] module documentation for more details about the
+//! [`SpiBus`](crate::ehal_1::spi::SpiBus) trait. See the [`impl_ehal`] module
+//! documentation for more details about its implementation.
+//!
+//! ```
+//! use atsamd_hal::ehal_1::spi::SpiBus;
+//!
+//! let mut buffer = [0xAA, 0x55];
+//! spi.transfer_in_place(&mut buffer)?;
+//! ```
+//!
+//! With the `ehal-02` feature, the various embedded HAL 0.2 traits, like
+//! [`spi::FullDuplex`](embedded_hal_02::spi::FullDuplex),
+//! [`serial::Read`](embedded_hal_02::serial::Read) or
+//! [`serial::Write`](embedded_hal_02::serial::Write), are also implemented.
+//! See the `impl_ehal_02` module documentation for more details about the
 //! specific trait implementations, which vary based on [`Size`] and
 //! [`Capability`].
 //!
 //! ```
 //! use nb::block;
-//! use embedded_hal::spi::FullDuplex;
+//! use embedded_hal_02::spi::FullDuplex;
 //!
 //! block!(spi.send(0xAA55));
 //! let rcvd: u16 = block!(spi.read());
@@ -303,7 +341,17 @@ let dma_transfer = spi.send_with_dma(&mut buffer, channel, ());
 let (chan0, _, spi, _) = dma_transfer.wait();
 ```
 
+In [`Slave`] mode, [`receive_frame_with_dma`](Spi::receive_frame_with_dma)
+receives a single frame into a buffer. The returned [`SpiSlaveFrame`] ends
+when `SS` rises, and reports how many words were received.
+
+```
+let frame = spi.receive_frame_with_dma(buffer, channel);
+let (channel, buffer, spi, len) = frame.wait();
+```
+
 [`Buffer`]: crate::dmac::transfer::Buffer
+[`SpiSlaveFrame`]: crate::sercom::dma::SpiSlaveFrame
 [`send_with_dma`]: Spi::send_with_dma
 [`receive_with_dma`]: Spi::receive_with_dma
 [`dmac::Transfer`]: crate::dmac::Transfer
@@ -311,18 +359,54 @@ let (chan0, _, spi, _) = dma_transfer.wait();
 [`dmac`]: crate::dmac
 "
 )]
+#![cfg_attr(
+    feature = "async",
+    doc = "
+# `async` operation
+
+With the `async` feature enabled, an [`Spi`] can be turned into an
+[`SpiFuture`] with [`into_future`](Spi::into_future). [`SpiFuture`]s in a
+[`MasterMode`] that can [`Receive`] implement
+[`embedded_hal_async::spi::SpiBus`], while [`Tx`] [`SpiFuture`]s provide an
+`async` [`write`](SpiFuture::write) method.
+
+By default, every word is transferred from the SERCOM interrupt(s), through
+[`sercom::on_interrupt`](crate::sercom::on_interrupt). Adding DMA channels with
+[`with_dma_channels`](SpiFuture::with_dma_channels) or
+[`with_tx_dma_channel`](SpiFuture::with_tx_dma_channel) runs each operation as
+DMA transfers instead, woken through
+[`dmac::on_interrupt`](crate::dmac::on_interrupt). In both cases, the buffers
+are only borrowed for the duration of the operation.
 
+```
+async fn exchange<S: SpiBus>(spi: &mut S) -> Result<[u8; 4], S::Error> {
+    let mut buffer = [0x9f, 0, 0, 0];
+    spi.transfer_in_place(&mut buffer).await?;
+    Ok(buffer)
+}
+
+let mut spi = spi.into_future().with_dma_channels(chan0, chan1);
+let id = exchange(&mut spi).await?;
+```
+"
+)]
 use core::convert::TryFrom;
 use core::marker::PhantomData;
 
 use bitflags::bitflags;
-use embedded_hal::spi;
-pub use embedded_hal::spi::{Phase, Polarity, MODE_0, MODE_1, MODE_2, MODE_3};
+use embedded_hal_02::spi;
+pub use embedded_hal_02::spi::{Phase, Polarity, MODE_0, MODE_1, MODE_2, MODE_3};
 
 use crate::sercom::*;
 use crate::time::Hertz;
 use crate::typelevel::{Is, NoneT, Sealed};
 
+#[cfg(feature = "thumbv7")]
+use crate::clock::v2::{
+    dynamic::ClockConsumer,
+    pclk::{DynPclkId, PclkId},
+};
+
 mod reg;
 use reg::Registers;
 
@@ -363,13 +447,23 @@ pub mod lengths {
     });
 }
 
-#[cfg(feature = "thumbv6")]
-#[path = "spi/impl_ehal_thumbv6m.rs"]
 pub mod impl_ehal;
 
-#[cfg(feature = "thumbv7")]
-#[path = "spi/impl_ehal_thumbv7em.rs"]
-pub mod impl_ehal;
+mod slave;
+pub use slave::*;
+
+#[cfg(all(feature = "ehal-02", feature = "thumbv6"))]
+#[path = "spi/impl_ehal_02_thumbv6m.rs"]
+pub mod impl_ehal_02;
+
+#[cfg(all(feature = "ehal-02", feature = "thumbv7"))]
+#[path = "spi/impl_ehal_02_thumbv7em.rs"]
+pub mod impl_ehal_02;
+
+#[cfg(feature = "async")]
+mod async_api;
+#[cfg(feature = "async")]
+pub use async_api::*;
 
 //=============================================================================
 // BitOrder
@@ -595,6 +689,8 @@ pub trait Transmit: Capability {}
 /// progress. This struct contains a `bool` to track that progress.
 #[derive(Default)]
 pub struct Rx {
+    // Only the embedded HAL 0.2 `serial::Read` implementation tracks progress
+    #[cfg_attr(not(feature = "ehal-02"), allow(dead_code))]
     pub(super) in_progress: bool,
 }
 
@@ -646,6 +742,8 @@ where
     mode: PhantomData<M>,
     size: PhantomData<Z>,
     freq: Hertz,
+    /// Last requested baud rate, used to recompute `BAUD` when `freq` changes
+    baud: Option<Hertz>,
 }
 
 impl<P: ValidPads> Config<P> {
@@ -666,6 +764,7 @@ impl<P: ValidPads> Config<P> {
             mode: PhantomData,
             size: PhantomData,
             freq: freq.into(),
+            baud: None,
         }
     }
 
@@ -716,6 +815,7 @@ where
             mode: PhantomData,
             size: PhantomData,
             freq: self.freq,
+            baud: self.baud,
         }
     }
 
@@ -881,9 +981,23 @@ where
     /// 512. Values outside this range will saturate at the extremes.
     #[inline]
     pub fn set_baud(&mut self, baud: Hertz) {
+        self.baud = Some(baud);
         self.regs.set_baud(self.freq, baud);
     }
 
+    /// Update the stored GCLK frequency
+    ///
+    /// Use this when the GCLK feeding this [`Sercom`] changes frequency. If a
+    /// baud rate was previously set, the `BAUD` register is recalculated to
+    /// keep the same baud rate.
+    #[inline]
+    pub fn set_freq(&mut self, freq: impl Into<Hertz>) {
+        self.freq = freq.into();
+        if let Some(baud) = self.baud {
+            self.regs.set_baud(self.freq, baud);
+        }
+    }
+
     /// Set the baud rate using the builder API
     ///
     /// This function will calculate the best BAUD register setting based on the
@@ -1120,13 +1234,15 @@ where
 /// An enabled SPI peripheral that can perform transactions
 ///
 /// See the [`impl_ehal`] documentation for details on the implementations of
-/// the embedded HAL traits, which vary based on [`Size`] and [`Capability`].
+/// the embedded HAL traits. The embedded HAL 0.2 implementations vary based on
+/// [`Size`] and [`Capability`].
 pub struct Spi<C, A>
 where
     C: ValidConfig,
     A: Capability,
 {
     config: C,
+    #[cfg_attr(not(feature = "ehal-02"), allow(dead_code))]
     capability: A,
 }
 
@@ -1215,7 +1331,7 @@ where
     /// clears the `TXC` flag. Manually clearing this flag could cause it to
     /// hang indefinitely.
     ///
-    /// [`serial::Write::flush`]: embedded_hal::serial::Write::flush
+    /// [`serial::Write::flush`]: embedded_hal_02::serial::Write::flush
     #[inline]
     pub fn clear_flags(&mut self, flags: Flags) {
         self.config.as_mut().regs.clear_flags(flags);
@@ -1268,6 +1384,28 @@ where
     }
 }
 
+#[cfg(feature = "thumbv7")]
+impl<C, A> ClockConsumer for Spi<C, A>
+where
+    C: ValidConfig,
+    C::Sercom: PclkId,
+    A: Capability,
+{
+    #[inline]
+    fn pclk_id(&self) -> DynPclkId {
+        <C::Sercom as PclkId>::DYN
+    }
+
+    /// Recompute the baud rate for the new clock frequency
+    ///
+    /// This temporarily disables the SERCOM peripheral. See
+    /// [`Spi::reconfigure`].
+    #[inline]
+    fn set_clock_freq(&mut self, freq: Hertz) {
+        self.reconfigure(|c| c.set_freq(freq));
+    }
+}
+
 #[cfg(feature = "thumbv7")]
 impl<P, M, A> Spi<Config<P, M, DynLength>, A>
 where
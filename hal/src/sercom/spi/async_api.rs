@@ -0,0 +1,579 @@
+//! `async` support for [`Spi`]s
+
+use super::{
+    AtomicSize, Capability, DataWidth, Error, Flags, MasterMode, Receive, Spi, Status, Tx,
+    ValidConfig,
+};
+use crate::{
+    dmac::{
+        channel::{AnyChannel, Ready},
+        Beat, Buffer, TriggerAction,
+    },
+    sercom::{
+        async_api::{RX_WAKERS, TX_WAKERS},
+        dma::SercomPtr,
+        Sercom,
+    },
+    typelevel::NoneT,
+};
+use core::{future::Future, task::Poll};
+use embassy_sync::waitqueue::AtomicWaker;
+use embedded_hal_async::spi::{ErrorType, SpiBus};
+use num_traits::{AsPrimitive, PrimInt, Zero};
+
+/// DMA trigger action used for SERCOM transfers
+#[cfg(feature = "thumbv6")]
+const TRIGGER_ACTION: TriggerAction = TriggerAction::BEAT;
+#[cfg(feature = "thumbv7")]
+const TRIGGER_ACTION: TriggerAction = TriggerAction::BURST;
+
+/// Maximum number of beats in a single DMA block transfer
+const MAX_BEATS: usize = u16::MAX as usize;
+
+impl<C, A> Spi<C, A>
+where
+    C: ValidConfig,
+    A: Capability,
+{
+    /// Turn an [`Spi`] into an [`SpiFuture`]
+    ///
+    /// Without DMA channels, every word is transferred from the SERCOM
+    /// interrupt(s). The interrupt(s) must be unmasked in the NVIC, and
+    /// [`sercom::on_interrupt`](crate::sercom::on_interrupt) must be called
+    /// from the interrupt handler(s).
+    #[inline]
+    pub fn into_future(self) -> SpiFuture<C, A> {
+        SpiFuture {
+            spi: self,
+            rx_channel: NoneT,
+            tx_channel: NoneT,
+        }
+    }
+}
+
+/// `async` version of an [`Spi`]
+///
+/// Create this struct by calling [`Spi::into_future`], then optionally add DMA
+/// channels with [`with_dma_channels`](SpiFuture::with_dma_channels) or
+/// [`with_tx_dma_channel`](SpiFuture::with_tx_dma_channel). The `R` and `T`
+/// type parameters are the RX and TX DMA channels, or [`NoneT`] when transfers
+/// are driven by interrupts.
+///
+/// Unlike [`spi_future::SpiFuture`](crate::sercom::spi_future::SpiFuture),
+/// this struct borrows its buffers for the duration of each transfer, and
+/// works with any `async` executor.
+pub struct SpiFuture<C, A, R = NoneT, T = NoneT>
+where
+    C: ValidConfig,
+    A: Capability,
+{
+    spi: Spi<C, A>,
+    rx_channel: R,
+    tx_channel: T,
+}
+
+impl<C, A> SpiFuture<C, A>
+where
+    C: ValidConfig,
+    A: Receive,
+{
+    /// Use a pair of DMA channels for all transfers
+    ///
+    /// Both channels are needed, even for [`Rx`](super::Rx) [`Spi`]s, because
+    /// an SPI master must write to the `DATA` register to clock in each word.
+    /// The DMAC interrupt(s) must be set up as described in the
+    /// [`dmac::async_api`](crate::dmac::async_api) module. The SERCOM
+    /// interrupt(s) are not used.
+    #[inline]
+    pub fn with_dma_channels<R, T>(self, rx_channel: R, tx_channel: T) -> SpiFuture<C, A, R, T>
+    where
+        R: AnyChannel<Status = Ready>,
+        T: AnyChannel<Status = Ready>,
+    {
+        SpiFuture {
+            spi: self.spi,
+            rx_channel,
+            tx_channel,
+        }
+    }
+}
+
+impl<C> SpiFuture<C, Tx>
+where
+    C: ValidConfig,
+{
+    /// Use a DMA channel for all transfers
+    ///
+    /// The DMAC interrupt(s) must be set up as described in the
+    /// [`dmac::async_api`](crate::dmac::async_api) module. The SERCOM
+    /// interrupt(s) are still used to wait for the end of each transfer.
+    #[inline]
+    pub fn with_tx_dma_channel<T>(self, tx_channel: T) -> SpiFuture<C, Tx, NoneT, T>
+    where
+        T: AnyChannel<Status = Ready>,
+    {
+        SpiFuture {
+            spi: self.spi,
+            rx_channel: NoneT,
+            tx_channel,
+        }
+    }
+}
+
+impl<C, A, R, T> SpiFuture<C, A, R, T>
+where
+    C: ValidConfig,
+    A: Capability,
+{
+    /// Return the underlying [`Spi`] and DMA channels
+    #[inline]
+    pub fn free(self) -> (Spi<C, A>, R, T) {
+        (self.spi, self.rx_channel, self.tx_channel)
+    }
+
+    /// Wait until one of the specified interrupt flags is set
+    ///
+    /// The interrupts are enabled before returning `Pending`, since the
+    /// interrupt handler disables them before waking the task.
+    async fn wait_flags(&mut self, waker: &AtomicWaker, flags: Flags) {
+        core::future::poll_fn(|cx| {
+            waker.register(cx.waker());
+            if self.spi.read_flags().intersects(flags) {
+                return Poll::Ready(());
+            }
+            self.spi.enable_interrupts(flags);
+            Poll::Pending
+        })
+        .await;
+    }
+
+    /// Check for a buffer overflow, and clear the error if there is one
+    #[inline]
+    fn check_errors(&mut self) -> Result<(), AsyncError> {
+        self.spi.read_flags_errors().map(|_| ()).map_err(|err| {
+            self.spi.clear_status(Status::BUFOVF | Status::LENERR);
+            self.spi.clear_flags(Flags::ERROR);
+            AsyncError::Spi(err)
+        })
+    }
+
+    /// Wait until the last word has been shifted out
+    ///
+    /// Writing to the `DATA` register clears the `TXC` flag, so this must only
+    /// be called after writing at least one word.
+    async fn wait_tx_complete(&mut self) {
+        let waker = &TX_WAKERS[<C::Sercom as Sercom>::NUM];
+        self.wait_flags(waker, Flags::TXC).await;
+    }
+}
+
+//=============================================================================
+// Interrupt-driven transfers
+//=============================================================================
+
+impl<C, A> SpiFuture<C, A>
+where
+    C: ValidConfig,
+    C::OpMode: MasterMode,
+    C::Size: AtomicSize<Word = C::Word>,
+    C::Word: PrimInt + AsPrimitive<DataWidth>,
+    DataWidth: AsPrimitive<C::Word>,
+    A: Capability,
+{
+    /// Wait for a `DRE` flag, then write a word
+    async fn send_word(&mut self, word: C::Word) {
+        let waker = &TX_WAKERS[<C::Sercom as Sercom>::NUM];
+        self.wait_flags(waker, Flags::DRE).await;
+        unsafe { self.spi.write_data(word.as_()) };
+    }
+
+    /// Send a word, then wait for an `RXC` flag and read the received word
+    async fn transfer_word(&mut self, word: C::Word) -> Result<C::Word, AsyncError> {
+        self.send_word(word).await;
+        let waker = &RX_WAKERS[<C::Sercom as Sercom>::NUM];
+        self.wait_flags(waker, Flags::RXC | Flags::ERROR).await;
+        self.check_errors()?;
+        Ok(unsafe { self.spi.read_data() }.as_())
+    }
+}
+
+/// Implement [`SpiBus`] for [`Spi`]s that can [`Receive`], using interrupts
+///
+/// Each word is sent once the `DRE` flag is set, and read back once the `RXC`
+/// flag is set. Words sent while reading are `0`. [`Rx`](super::Rx) [`Spi`]s
+/// have no Data Out pad, so the words they write only generate the clock.
+impl<C, A> SpiBus<C::Word> for SpiFuture<C, A>
+where
+    C: ValidConfig,
+    C::OpMode: MasterMode,
+    C::Size: AtomicSize<Word = C::Word>,
+    C::Word: PrimInt + AsPrimitive<DataWidth>,
+    DataWidth: AsPrimitive<C::Word>,
+    A: Receive,
+{
+    async fn read(&mut self, words: &mut [C::Word]) -> Result<(), AsyncError> {
+        for word in words.iter_mut() {
+            *word = self.transfer_word(C::Word::zero()).await?;
+        }
+        Ok(())
+    }
+
+    async fn write(&mut self, words: &[C::Word]) -> Result<(), AsyncError> {
+        for &word in words {
+            self.transfer_word(word).await?;
+        }
+        Ok(())
+    }
+
+    async fn transfer(
+        &mut self,
+        read: &mut [C::Word],
+        write: &[C::Word],
+    ) -> Result<(), AsyncError> {
+        for i in 0..read.len().max(write.len()) {
+            let word = write.get(i).copied().unwrap_or_else(C::Word::zero);
+            let received = self.transfer_word(word).await?;
+            if let Some(dest) = read.get_mut(i) {
+                *dest = received;
+            }
+        }
+        Ok(())
+    }
+
+    async fn transfer_in_place(&mut self, words: &mut [C::Word]) -> Result<(), AsyncError> {
+        for word in words.iter_mut() {
+            *word = self.transfer_word(*word).await?;
+        }
+        Ok(())
+    }
+
+    /// Every other operation waits for the last word to be received, so there
+    /// is nothing to flush
+    async fn flush(&mut self) -> Result<(), AsyncError> {
+        Ok(())
+    }
+}
+
+impl<C> SpiFuture<C, Tx>
+where
+    C: ValidConfig,
+    C::OpMode: MasterMode,
+    C::Size: AtomicSize<Word = C::Word>,
+    C::Word: PrimInt + AsPrimitive<DataWidth>,
+    DataWidth: AsPrimitive<C::Word>,
+{
+    /// Write words, using interrupts
+    ///
+    /// Each word is sent once the `DRE` flag is set. The future completes once
+    /// the last word has been shifted out. [`Tx`] [`Spi`]s never receive data,
+    /// so they don't implement [`SpiBus`].
+    pub async fn write(&mut self, words: &[C::Word]) -> Result<(), AsyncError> {
+        if words.is_empty() {
+            return Ok(());
+        }
+        for &word in words {
+            self.send_word(word).await;
+        }
+        self.wait_tx_complete().await;
+        Ok(())
+    }
+}
+
+//=============================================================================
+// DMA transfers
+//=============================================================================
+
+/// Incrementing DMA buffer built from a raw slice
+///
+/// This allows using a shared slice as a DMA source, or the same slice as both
+/// source and destination for in-place transfers.
+struct RawSlice<T: Beat> {
+    ptr: *mut T,
+    len: usize,
+}
+
+impl<T: Beat> RawSlice<T> {
+    /// # Safety
+    ///
+    /// The slice must only be used as a DMA source.
+    #[inline]
+    unsafe fn from_ref(slice: &[T]) -> Self {
+        Self {
+            ptr: slice.as_ptr() as *mut T,
+            len: slice.len(),
+        }
+    }
+
+    #[inline]
+    fn from_mut(slice: &mut [T]) -> Self {
+        Self {
+            ptr: slice.as_mut_ptr(),
+            len: slice.len(),
+        }
+    }
+}
+
+unsafe impl<T: Beat> Buffer for RawSlice<T> {
+    type Beat = T;
+
+    #[inline]
+    fn dma_ptr(&mut self) -> *mut Self::Beat {
+        if self.incrementing() {
+            // SAFETY: The pointer stays within, or one past the end of, the
+            // original slice
+            unsafe { self.ptr.add(self.len) }
+        } else {
+            self.ptr
+        }
+    }
+
+    #[inline]
+    fn incrementing(&self) -> bool {
+        self.len > 1
+    }
+
+    #[inline]
+    fn buffer_len(&self) -> usize {
+        self.len
+    }
+}
+
+/// Poll two futures concurrently, polling `a` first on every wake
+async fn join<Fa, Fb>(a: Fa, b: Fb) -> (Fa::Output, Fb::Output)
+where
+    Fa: Future,
+    Fb: Future,
+{
+    let mut a = core::pin::pin!(a);
+    let mut b = core::pin::pin!(b);
+    let mut a_output = None;
+    let mut b_output = None;
+
+    core::future::poll_fn(|cx| {
+        if a_output.is_none() {
+            if let Poll::Ready(output) = a.as_mut().poll(cx) {
+                a_output = Some(output);
+            }
+        }
+        if b_output.is_none() {
+            if let Poll::Ready(output) = b.as_mut().poll(cx) {
+                b_output = Some(output);
+            }
+        }
+        match (a_output.take(), b_output.take()) {
+            (Some(a), Some(b)) => Poll::Ready((a, b)),
+            (a, b) => {
+                a_output = a;
+                b_output = b;
+                Poll::Pending
+            }
+        }
+    })
+    .await
+}
+
+impl<C, A, R, T> SpiFuture<C, A, R, T>
+where
+    C: ValidConfig,
+    C::OpMode: MasterMode,
+    C::Size: AtomicSize<Word = C::Word>,
+    C::Word: PrimInt + Beat,
+    A: Capability,
+    T: AnyChannel<Status = Ready>,
+{
+    /// Send a buffer with the TX channel
+    async fn send_dma<S>(&mut self, source: S) -> Result<(), AsyncError>
+    where
+        S: Buffer<Beat = C::Word>,
+    {
+        let data = SercomPtr(self.spi.data_ptr());
+        self.tx_channel
+            .as_mut()
+            .transfer_future(source, data, C::Sercom::DMA_TX_TRIGGER, TRIGGER_ACTION)
+            .await
+            .map_err(AsyncError::Dma)
+    }
+}
+
+impl<C, A, R, T> SpiFuture<C, A, R, T>
+where
+    C: ValidConfig,
+    C::OpMode: MasterMode,
+    C::Size: AtomicSize<Word = C::Word>,
+    C::Word: PrimInt + Beat,
+    A: Receive,
+    R: AnyChannel<Status = Ready>,
+    T: AnyChannel<Status = Ready>,
+{
+    /// Run a full-duplex transfer of at most [`MAX_BEATS`] words, with the RX
+    /// and TX channels running concurrently
+    async fn transfer_dma<S, D>(&mut self, source: S, destination: D) -> Result<(), AsyncError>
+    where
+        S: Buffer<Beat = C::Word>,
+        D: Buffer<Beat = C::Word>,
+    {
+        let data = self.spi.data_ptr();
+
+        // The RX channel is polled, and thus started, before the TX channel, so
+        // no received word can be missed.
+        let rx = self.rx_channel.as_mut().transfer_future(
+            SercomPtr(data),
+            destination,
+            C::Sercom::DMA_RX_TRIGGER,
+            TRIGGER_ACTION,
+        );
+        let tx = self.tx_channel.as_mut().transfer_future(
+            source,
+            SercomPtr(data),
+            C::Sercom::DMA_TX_TRIGGER,
+            TRIGGER_ACTION,
+        );
+        let (rx_result, tx_result) = join(rx, tx).await;
+        rx_result.and(tx_result).map_err(AsyncError::Dma)?;
+        self.check_errors()
+    }
+}
+
+/// Implement [`SpiBus`] for [`Spi`]s that can [`Receive`], using a pair of DMA
+/// channels
+///
+/// Each operation is split into DMA transfers of at most 65535 words. Words
+/// sent while reading are `0`. [`Rx`](super::Rx) [`Spi`]s have no Data Out
+/// pad, so the words they write only generate the clock.
+impl<C, A, R, T> SpiBus<C::Word> for SpiFuture<C, A, R, T>
+where
+    C: ValidConfig,
+    C::OpMode: MasterMode,
+    C::Size: AtomicSize<Word = C::Word>,
+    C::Word: PrimInt + Beat,
+    A: Receive,
+    R: AnyChannel<Status = Ready>,
+    T: AnyChannel<Status = Ready>,
+{
+    async fn read(&mut self, words: &mut [C::Word]) -> Result<(), AsyncError> {
+        let mut source = C::Word::zero();
+        for chunk in words.chunks_mut(MAX_BEATS) {
+            self.transfer_dma(SercomPtr(&mut source), RawSlice::from_mut(chunk))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn write(&mut self, words: &[C::Word]) -> Result<(), AsyncError> {
+        let mut sink = C::Word::zero();
+        for chunk in words.chunks(MAX_BEATS) {
+            // SAFETY: The slice is only used as the DMA source
+            let source = unsafe { RawSlice::from_ref(chunk) };
+            self.transfer_dma(source, SercomPtr(&mut sink)).await?;
+        }
+        Ok(())
+    }
+
+    async fn transfer(
+        &mut self,
+        read: &mut [C::Word],
+        write: &[C::Word],
+    ) -> Result<(), AsyncError> {
+        let common = read.len().min(write.len());
+        let (read, read_rest) = read.split_at_mut(common);
+        let (write, write_rest) = write.split_at(common);
+
+        for (dest, src) in read.chunks_mut(MAX_BEATS).zip(write.chunks(MAX_BEATS)) {
+            // SAFETY: The slice is only used as the DMA source
+            let source = unsafe { RawSlice::from_ref(src) };
+            self.transfer_dma(source, RawSlice::from_mut(dest)).await?;
+        }
+
+        // At most one of these is not empty
+        self.read(read_rest).await?;
+        self.write(write_rest).await
+    }
+
+    async fn transfer_in_place(&mut self, words: &mut [C::Word]) -> Result<(), AsyncError> {
+        for chunk in words.chunks_mut(MAX_BEATS) {
+            // Each word is sent before the word received in its place is
+            // written back, so the same buffer can be used for both channels.
+            let buffer = RawSlice::from_mut(chunk);
+            let source = RawSlice {
+                ptr: buffer.ptr,
+                len: buffer.len,
+            };
+            self.transfer_dma(source, buffer).await?;
+        }
+        Ok(())
+    }
+
+    /// Every other operation waits for the last word to be received, so there
+    /// is nothing to flush
+    async fn flush(&mut self) -> Result<(), AsyncError> {
+        Ok(())
+    }
+}
+
+impl<C, T> SpiFuture<C, Tx, NoneT, T>
+where
+    C: ValidConfig,
+    C::OpMode: MasterMode,
+    C::Size: AtomicSize<Word = C::Word>,
+    C::Word: PrimInt + Beat,
+    T: AnyChannel<Status = Ready>,
+{
+    /// Write words, using the TX DMA channel
+    ///
+    /// The future completes once the last word has been shifted out. [`Tx`]
+    /// [`Spi`]s never receive data, so they don't implement [`SpiBus`].
+    pub async fn write(&mut self, words: &[C::Word]) -> Result<(), AsyncError> {
+        if words.is_empty() {
+            return Ok(());
+        }
+        for chunk in words.chunks(MAX_BEATS) {
+            // SAFETY: The slice is only used as the DMA source
+            let source = unsafe { RawSlice::from_ref(chunk) };
+            self.send_dma(source).await?;
+        }
+        self.wait_tx_complete().await;
+        Ok(())
+    }
+}
+
+//=============================================================================
+// Errors
+//=============================================================================
+
+/// Error `enum` for [`SpiFuture`] transactions
+///
+/// In addition to the [`Error`]s of the SPI peripheral, DMA transfers can fail
+/// with a [`dmac::Error`](crate::dmac::Error).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AsyncError {
+    /// Error of the SPI peripheral
+    Spi(Error),
+    /// Error during a DMA transfer
+    Dma(crate::dmac::Error),
+}
+
+impl From<Error> for AsyncError {
+    #[inline]
+    fn from(err: Error) -> Self {
+        AsyncError::Spi(err)
+    }
+}
+
+impl embedded_hal_async::spi::Error for AsyncError {
+    #[inline]
+    fn kind(&self) -> embedded_hal_async::spi::ErrorKind {
+        match self {
+            AsyncError::Spi(err) => err.kind(),
+            AsyncError::Dma(_) => embedded_hal_async::spi::ErrorKind::Other,
+        }
+    }
+}
+
+impl<C, A, R, T> ErrorType for SpiFuture<C, A, R, T>
+where
+    C: ValidConfig,
+    A: Capability,
+{
+    type Error = AsyncError;
+}
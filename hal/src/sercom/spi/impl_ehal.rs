@@ -0,0 +1,107 @@
+//! Implement [`embedded_hal`] traits for [`Spi`] structs
+//!
+//! [`SpiBus`] is implemented for [`Spi`] structs in a [`MasterMode`] that can
+//! [`Receive`], with an [`AtomicSize`]. The [`Word`](super::Word) is then a
+//! primitive integer, with a size that depends on the transaction [`Size`]
+//! (`u8` or `u16` on SAMD11/SAMD21, `u8`, `u16` or `u32` on SAMx5x).
+//!
+//! Every word sent is read back, so [`Duplex`](super::Duplex) structs never
+//! introduce an [`Error::Overflow`]. Words sent while reading are `0`.
+//! [`Rx`](super::Rx) structs have no Data Out pad, so the words they send only
+//! generate the clock.
+//!
+//! Each word is transferred as soon as the previous one has been written to
+//! the `DATA` register, so [`SpiBus::flush`] has nothing to wait for.
+//!
+//! [`Size`]: super::Size
+
+use num_traits::{AsPrimitive, PrimInt, Zero};
+
+use crate::ehal_1::spi::{self, ErrorKind, ErrorType, SpiBus};
+
+use super::{
+    AtomicSize, Capability, DataWidth, Error, Flags, MasterMode, Receive, Spi, ValidConfig,
+};
+
+impl spi::Error for Error {
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Error::Overflow => ErrorKind::Overrun,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+impl<C, A> ErrorType for Spi<C, A>
+where
+    C: ValidConfig,
+    A: Capability,
+{
+    type Error = Error;
+}
+
+impl<C, A> SpiBus<C::Word> for Spi<C, A>
+where
+    C: ValidConfig,
+    C::OpMode: MasterMode,
+    C::Size: AtomicSize<Word = C::Word>,
+    C::Word: PrimInt + AsPrimitive<DataWidth>,
+    DataWidth: AsPrimitive<C::Word>,
+    A: Receive,
+{
+    #[inline]
+    fn read(&mut self, words: &mut [C::Word]) -> Result<(), Error> {
+        self.transfer(words, &[])
+    }
+
+    #[inline]
+    fn write(&mut self, words: &[C::Word]) -> Result<(), Error> {
+        self.transfer(&mut [], words)
+    }
+
+    fn transfer(&mut self, read: &mut [C::Word], write: &[C::Word]) -> Result<(), Error> {
+        let len = read.len().max(write.len());
+        let mut sent = 0;
+        let mut received = 0;
+        while received < len {
+            let flags = self.read_flags_errors()?;
+            if sent < len && flags.contains(Flags::DRE) {
+                let word = write.get(sent).copied().unwrap_or_else(C::Word::zero);
+                unsafe { self.write_data(word.as_()) };
+                sent += 1;
+            }
+            if received < sent && flags.contains(Flags::RXC) {
+                let word = unsafe { self.read_data() }.as_();
+                if let Some(dest) = read.get_mut(received) {
+                    *dest = word;
+                }
+                received += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn transfer_in_place(&mut self, words: &mut [C::Word]) -> Result<(), Error> {
+        let mut sent = 0;
+        let mut received = 0;
+        while received < words.len() {
+            let flags = self.read_flags_errors()?;
+            if sent < words.len() && flags.contains(Flags::DRE) {
+                unsafe { self.write_data(words[sent].as_()) };
+                sent += 1;
+            }
+            // Each word is sent before it is overwritten with the received one
+            if received < sent && flags.contains(Flags::RXC) {
+                words[received] = unsafe { self.read_data() }.as_();
+                received += 1;
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+}
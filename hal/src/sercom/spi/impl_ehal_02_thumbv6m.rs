@@ -1,4 +1,4 @@
-//! Implement [`embedded_hal`] traits for [`Spi`] structs
+//! Implement [`embedded_hal_02`] traits for [`Spi`] structs
 //!
 //! As noted in the [spi module](super) documentation, the embedded-hal trait
 //! implementations vary by both [`Size`] and [`Capability`]. Each
@@ -68,7 +68,7 @@
 //! These traits are implemented following all of the rules outlined above for
 //! the different [`Size`] and [`Capability`] options.
 
-use embedded_hal::{blocking, serial, spi};
+use embedded_hal_02::{blocking, serial, spi};
 use nb::Error::WouldBlock;
 use num_traits::{AsPrimitive, PrimInt};
 
@@ -383,7 +383,6 @@ impl_blocking_spi_write!(EightBit, NineBit);
 // blocking::spi::WriteIter
 //=============================================================================
 
-#[cfg(feature = "unproven")]
 macro_rules! impl_blocking_spi_write_iter {
     ( $($CharSize:ident),+ ) => {
         $(
@@ -473,5 +472,4 @@ macro_rules! impl_blocking_spi_write_iter {
     };
 }
 
-#[cfg(feature = "unproven")]
 impl_blocking_spi_write_iter!(EightBit, NineBit);
@@ -1,4 +1,4 @@
-//! Implement [`embedded_hal`] traits for [`Spi`] structs
+//! Implement [`embedded_hal_02`] traits for [`Spi`] structs
 //!
 //! As noted in the [spi module](super) documentation, the embedded-hal trait
 //! implementations vary by both [`Size`] and [`Capability`]. Each
@@ -90,7 +90,7 @@
 //! These traits are implemented following all of the rules outlined above for
 //! the different [`Size`] and [`Capability`] options.
 
-use embedded_hal::{blocking, serial, spi};
+use embedded_hal_02::{blocking, serial, spi};
 use nb::Error::WouldBlock;
 use num_traits::{AsPrimitive, PrimInt};
 use typenum::{U1, U2, U3, U4};
@@ -585,7 +585,6 @@ macro_rules! impl_blocking_spi_write_iter {
             /// (`u8`, `u16` or `u32`).
             ///
             /// [`WriteIter`]: blocking::spi::WriteIter
-            #[cfg(feature = "unproven")]
             impl<P, M> blocking::spi::WriteIter<Word<$Length>> for Spi<Config<P, M, $Length>, Duplex>
             where
                 Config<P, M, $Length>: ValidConfig,
@@ -633,7 +632,6 @@ macro_rules! impl_blocking_spi_write_iter {
             /// reads the DATA register and ignores all buffer overflow errors.
             ///
             /// [`WriteIter`]: blocking::spi::WriteIter
-            #[cfg(feature = "unproven")]
             impl<P, M> blocking::spi::WriteIter<Word<$Length>> for Spi<Config<P, M, $Length>, Tx>
             where
                 Config<P, M, $Length>: ValidConfig,
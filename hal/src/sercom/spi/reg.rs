@@ -1,6 +1,6 @@
 use core::convert::TryInto;
 
-use embedded_hal::spi;
+use embedded_hal_02::spi;
 
 #[cfg(feature = "thumbv6")]
 use crate::pac::sercom0::SPI;
@@ -15,7 +15,12 @@ use crate::pac::sercom0::spim::ctrla::MODE_A;
 use crate::sercom::Sercom;
 use crate::time::Hertz;
 
-use super::{BitOrder, DataWidth, Error, Flags, Phase, Polarity, Status};
+use super::{BitOrder, DataWidth, Error, Flags, Phase, Polarity, SlaveAddress, Status};
+
+/// `CTRLA.FORM` value for SPI frames without address
+const FORM_SPI_FRAME: u8 = 0;
+/// `CTRLA.FORM` value for SPI frames with address
+const FORM_SPI_FRAME_WITH_ADDR: u8 = 2;
 
 //==============================================================================
 // Registers
@@ -253,6 +258,81 @@ impl<S: Sercom> Registers<S> {
         self.spi().ctrla.modify(|_, w| w.runstdby().bit(set));
     }
 
+    /// Get the enable state of the slave select low detection
+    #[inline]
+    pub fn get_ssde(&self) -> bool {
+        self.spi().ctrlb.read().ssde().bit()
+    }
+
+    /// Set the enable state of the slave select low detection
+    #[inline]
+    pub fn set_ssde(&mut self, enabled: bool) {
+        self.spi().ctrlb.modify(|_, w| w.ssde().bit(enabled));
+        while self.spi().syncbusy.read().ctrlb().bit_is_set() {}
+    }
+
+    /// Get the enable state of the slave data preload
+    #[inline]
+    pub fn get_ploaden(&self) -> bool {
+        self.spi().ctrlb.read().ploaden().bit()
+    }
+
+    /// Set the enable state of the slave data preload
+    #[inline]
+    pub fn set_ploaden(&mut self, enabled: bool) {
+        self.spi().ctrlb.modify(|_, w| w.ploaden().bit(enabled));
+        while self.spi().syncbusy.read().ctrlb().bit_is_set() {}
+    }
+
+    /// Get the slave address matching mode and address(es), if the frame
+    /// format includes an address
+    #[inline]
+    pub fn get_slave_address(&self) -> Option<SlaveAddress> {
+        if self.spi().ctrla.read().form().bits() != FORM_SPI_FRAME_WITH_ADDR {
+            return None;
+        }
+        let addr = self.spi().addr.read();
+        let (addr, addrmask) = (addr.addr().bits(), addr.addrmask().bits());
+        let address = match self.spi().ctrlb.read().amode().bits() {
+            1 => SlaveAddress::Two(addr, addrmask),
+            2 => SlaveAddress::Range {
+                low: addrmask,
+                high: addr,
+            },
+            _ => SlaveAddress::Mask {
+                address: addr,
+                mask: addrmask,
+            },
+        };
+        Some(address)
+    }
+
+    /// Set the slave address matching mode and address(es)
+    ///
+    /// `None` selects the frame format without address.
+    #[inline]
+    pub fn set_slave_address(&mut self, address: Option<SlaveAddress>) {
+        let (form, amode, addr, addrmask) = match address {
+            None => (FORM_SPI_FRAME, 0, 0, 0),
+            Some(SlaveAddress::Mask { address, mask }) => {
+                (FORM_SPI_FRAME_WITH_ADDR, 0, address, mask)
+            }
+            Some(SlaveAddress::Two(first, second)) => (FORM_SPI_FRAME_WITH_ADDR, 1, first, second),
+            Some(SlaveAddress::Range { low, high }) => (FORM_SPI_FRAME_WITH_ADDR, 2, high, low),
+        };
+        self.spi()
+            .ctrla
+            .modify(|_, w| unsafe { w.form().bits(form) });
+        self.spi()
+            .ctrlb
+            .modify(|_, w| unsafe { w.amode().bits(amode) });
+        while self.spi().syncbusy.read().ctrlb().bit_is_set() {}
+        self.spi().addr.write(|w| unsafe {
+            w.addr().bits(addr);
+            w.addrmask().bits(addrmask)
+        });
+    }
+
     /// Enable interrupts for the specified flags
     #[inline]
     pub fn enable_interrupts(&mut self, flags: Flags) {
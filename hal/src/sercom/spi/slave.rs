@@ -0,0 +1,181 @@
+//! Slave mode transactions for [`Spi`]s
+
+use num_traits::{AsPrimitive, PrimInt};
+
+use super::{
+    AtomicSize, Capability, Config, DataWidth, Error, Flags, Receive, Size, Slave, Spi, Transmit,
+    ValidConfig, ValidPads,
+};
+
+/// Address matching mode of an [`Spi`] in [`Slave`] mode
+///
+/// When an address is set, the first word of each frame is an address. If it
+/// does not match, the rest of the frame is ignored.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SlaveAddress {
+    /// Match `address`, ignoring the bits set in `mask`
+    Mask { address: u8, mask: u8 },
+    /// Match either of the two addresses
+    Two(u8, u8),
+    /// Match every address from `low` to `high`, inclusive
+    Range { low: u8, high: u8 },
+}
+
+impl<P, Z> Config<P, Slave, Z>
+where
+    P: ValidPads,
+    Z: Size,
+{
+    /// Get the enable state of the slave select low detection
+    #[inline]
+    pub fn get_ss_low_detect(&self) -> bool {
+        self.regs.get_ssde()
+    }
+
+    /// Set the enable state of the slave select low detection
+    ///
+    /// When enabled, the [`Flags::SSL`] flag is set when `SS` falls.
+    #[inline]
+    pub fn set_ss_low_detect(&mut self, enabled: bool) {
+        self.regs.set_ssde(enabled);
+    }
+
+    /// Enable or disable the slave select low detection
+    ///
+    /// When enabled, the [`Flags::SSL`] flag is set when `SS` falls.
+    #[inline]
+    pub fn ss_low_detect(mut self, enabled: bool) -> Self {
+        self.set_ss_low_detect(enabled);
+        self
+    }
+
+    /// Get the enable state of the data preload
+    #[inline]
+    pub fn get_preload(&self) -> bool {
+        self.regs.get_ploaden()
+    }
+
+    /// Set the enable state of the data preload
+    ///
+    /// When enabled, a word written before `SS` falls is sent first.
+    #[inline]
+    pub fn set_preload(&mut self, enabled: bool) {
+        self.regs.set_ploaden(enabled);
+    }
+
+    /// Enable or disable the data preload
+    ///
+    /// When enabled, a word written before `SS` falls is sent first.
+    #[inline]
+    pub fn preload(mut self, enabled: bool) -> Self {
+        self.set_preload(enabled);
+        self
+    }
+
+    /// Get the address matching mode, if any
+    #[inline]
+    pub fn get_address(&self) -> Option<SlaveAddress> {
+        self.regs.get_slave_address()
+    }
+
+    /// Set the address matching mode
+    ///
+    /// `None` disables address matching, so every frame is received.
+    #[inline]
+    pub fn set_address(&mut self, address: Option<SlaveAddress>) {
+        self.regs.set_slave_address(address);
+    }
+
+    /// Only receive the frames starting with a matching address
+    #[inline]
+    pub fn address(mut self, address: SlaveAddress) -> Self {
+        self.set_address(Some(address));
+        self
+    }
+}
+
+impl<P, Z, A> Spi<Config<P, Slave, Z>, A>
+where
+    Config<P, Slave, Z>: ValidConfig,
+    P: ValidPads,
+    Z: Size,
+    A: Capability,
+{
+    /// Check whether a frame started, then clear the [`Flags::SSL`] flag
+    ///
+    /// The slave select low detection must be enabled.
+    #[inline]
+    pub fn frame_started(&mut self) -> bool {
+        let started = self.read_flags().contains(Flags::SSL);
+        if started {
+            self.clear_flags(Flags::SSL);
+        }
+        started
+    }
+
+    /// Check whether a frame ended, then clear the [`Flags::TXC`] flag
+    ///
+    /// In [`Slave`] mode, `TXC` is set when `SS` rises.
+    #[inline]
+    pub fn frame_ended(&mut self) -> bool {
+        let ended = self.read_flags().contains(Flags::TXC);
+        if ended {
+            self.clear_flags(Flags::TXC);
+        }
+        ended
+    }
+}
+
+impl<P, Z, A> Spi<Config<P, Slave, Z>, A>
+where
+    Config<P, Slave, Z>: ValidConfig,
+    P: ValidPads,
+    Z: AtomicSize,
+    Z::Word: PrimInt + AsPrimitive<DataWidth>,
+    A: Transmit,
+{
+    /// Write the first word to send in the next frame
+    ///
+    /// With [`preload`](Config::preload) enabled, the word is sent as soon as
+    /// `SS` falls. It must be written while `SS` is high.
+    #[inline]
+    pub fn preload_word(&mut self, word: Z::Word) {
+        unsafe { self.write_data(word.as_()) };
+    }
+}
+
+impl<P, Z, A> Spi<Config<P, Slave, Z>, A>
+where
+    Config<P, Slave, Z>: ValidConfig,
+    P: ValidPads,
+    Z: AtomicSize,
+    Z::Word: PrimInt,
+    DataWidth: AsPrimitive<Z::Word>,
+    A: Receive,
+{
+    /// Receive words until `SS` rises, and return the number of words
+    /// received
+    ///
+    /// Words already in the receive buffer are read first. Words that don't
+    /// fit in `buffer` are discarded, but still counted, so a length greater
+    /// than `buffer.len()` means the frame was truncated.
+    ///
+    /// # Blocking: This method blocks until the end of the frame
+    pub fn read_frame(&mut self, buffer: &mut [Z::Word]) -> Result<usize, Error> {
+        let mut count = 0;
+        loop {
+            let flags = self.read_flags_errors()?;
+            if flags.contains(Flags::RXC) {
+                let word = unsafe { self.read_data() }.as_();
+                if let Some(dest) = buffer.get_mut(count) {
+                    *dest = word;
+                }
+                count += 1;
+            } else if flags.contains(Flags::TXC) {
+                self.clear_flags(Flags::TXC);
+                return Ok(count);
+            }
+        }
+    }
+}
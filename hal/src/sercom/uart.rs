@@ -4,7 +4,7 @@
 //! a set of [`Pads`] for use by the peripheral. Next, you assemble pieces into
 //! a [`Config`] struct. After configuring the peripheral, you then [`enable`]
 //! it, yielding a functional [`Uart`] struct.
-//! Transactions are performed using the [`serial`](embedded_hal::serial) traits
+//! Transactions are performed using the [`serial`](embedded_hal_02::serial) traits
 //! from embedded HAL.
 //!
 //! # [`Pads`]
@@ -209,7 +209,7 @@ type Pads = uart::PadsFromIds<Sercom0, PA08, PA09>;
 //!
 //! ```
 //! use nb::block;
-//! use embedded_hal::serial::Write;
+//! use embedded_hal_02::serial::Write;
 //!
 //! block!(uart_tx.write(0x0fe));
 //! ```
@@ -234,6 +234,34 @@ type Pads = uart::PadsFromIds<Sercom0, PA08, PA09>;
 //! conditions. [This application note](https://www.silabs.com/documents/public/application-notes/an0059.0-uart-flow-control.pdf)
 //! provides more information about UART hardware flow control.
 //!
+//! # RS-485
+//!
+//! On thumbv7em targets, a set of [`Pads`] with `TX` and `TE` pads, but no
+//! `CTS` pad, implements `Rs485Pads`. The `TE` pad, which is the `RTS` pad,
+//! is then driven high by the SERCOM while transmitting. It is held for a
+//! guard time of up to 7 bit periods after the last stop bit, set with
+//! `Config::guard_time`.
+//!
+//! On a half-duplex bus, the receiver sees its own transmissions. With
+//! [`echo_suppression`](Config::echo_suppression) enabled, these words are
+//! discarded by `transmission_finished`, which returns `true` once the last
+//! stop bit is sent. It should be polled after a DMA transmission, since the
+//! DMA transfer completes as soon as the last word is written.
+//!
+//! ```
+//! let pads = uart::Pads::default().rx(rx).tx(tx).te(te);
+//! let mut uart = uart::Config::new(&mclk, sercom, pads, freq)
+//!     .baud(19200.Hz(), BaudMode::Fractional(Oversampling::Bits16))
+//!     .guard_time(2)
+//!     .echo_suppression(true)
+//!     .enable();
+//! block!(uart.write(0x01));
+//! while !uart.transmission_finished() {}
+//! ```
+//!
+//! On thumbv6m targets, which have no hardware `TE` output, an `Rs485Uart`
+//! drives `TE` from a GPIO pin instead.
+//!
 //! # Splitting
 //!
 //! A `Uart<C, Duplex>` can be split into its [`RxDuplex`] and [`TxDuplex`]
@@ -325,7 +353,6 @@ type Pads = uart::PadsFromIds<Sercom0, PA08, PA09>;
 //! # Non-supported advanced features
 //!
 //! * Synchronous mode (USART) is not supported
-//! * LIN mode is not supported (SAMx5x)
 //! * 32-bit extension mode is not supported (SAMx5x). If you need to transfer
 //!   slices, consider using the DMA methods instead. The `dma` Cargo feature
 //!   must be enabled.
@@ -341,8 +368,8 @@ type Pads = uart::PadsFromIds<Sercom0, PA08, PA09>;
 //! [`split`]: Uart::split
 //! [`join`]: Uart::join
 //! [`NoneT`]: crate::typelevel::NoneT
-//! [`serial::Write`]: embedded_hal::serial::Write
-//! [`serial::Read`]: embedded_hal::serial::Read
+//! [`serial::Write`]: embedded_hal_02::serial::Write
+//! [`serial::Read`]: embedded_hal_02::serial::Read
 #![cfg_attr(
     feature = "dma",
     doc = "
@@ -384,6 +411,95 @@ let (chan1, rx, rx_buffer) = rx_dma.wait();
 [`Channel`]: crate::dmac::channel::Channel
 [`dmac`]: crate::dmac
 
+"
+)]
+#![cfg_attr(
+    feature = "async",
+    doc = "
+# `async` operation
+
+With the `async` feature enabled, a [`Uart`] can be turned into a
+[`UartFuture`] with [`into_future`](Uart::into_future). [`UartFuture`]s
+implement the [`embedded_io_async::Read`] and [`embedded_io_async::Write`]
+traits, and are woken from the SERCOM interrupt(s) through
+[`sercom::on_interrupt`](crate::sercom::on_interrupt).
+
+A [`Duplex`] [`UartFuture`] can be [`split`](UartFuture::split) into
+[`RxDuplex`] and [`TxDuplex`] halves. Each half has its own waker, so the two
+halves can be awaited from different tasks.
+
+```
+#[interrupt]
+fn SERCOM0() {
+    atsamd_hal::sercom::on_interrupt::<Sercom0>();
+}
+
+async fn echo(uart: Uart<Config<Pads>, Duplex>) {
+    let (mut rx, mut tx) = uart.into_future().split();
+    let mut buf = [0; 16];
+    loop {
+        let n = rx.read(&mut buf).await.unwrap();
+        tx.write_all(&buf[..n]).await.unwrap();
+    }
+}
+```
+"
+)]
+#![cfg_attr(
+    feature = "thumbv7",
+    doc = "
+# LIN
+
+On thumbv7em targets, a [`Duplex`] [`Config`] with an [`EightBit`]
+character size can be enabled as a LIN master or slave, with
+[`enable_lin_master`](Config::enable_lin_master) or
+[`enable_lin_slave`](Config::enable_lin_slave). The master generates the break
+and sync fields in hardware, while the slave detects breaks and adjusts its
+baud rate on the sync fields. Both compute and check the
+[classic or enhanced](LinChecksum) checksum of the responses, and report
+collisions, inconsistent sync fields and unexpected breaks as [`LinError`]s.
+
+```
+let mut master = uart::Config::new(&mclk, sercom, pads, freq)
+    .baud(19200.Hz(), BaudMode::Fractional(Oversampling::Bits16))
+    .enable_lin_master(LinBreakLength::Bits13, LinHeaderDelay::Bits1);
+
+master.send_frame(0x10, &[0x01, 0x02], LinChecksum::Enhanced)?;
+let mut status = [0; 4];
+master.request_frame(0x11, &mut status, LinChecksum::Enhanced)?;
+```
+"
+)]
+#![cfg_attr(
+    feature = "thumbv7",
+    doc = "
+# ISO7816 smart cards
+
+On thumbv7em targets, a [`Config`] created with [`Iso7816Pads`], which hold
+the single `I/O` line of a smart card, can be enabled as a T=0
+[`SmartCard`] interface with [`enable_iso7816`](Config::enable_iso7816). The
+card clock is typically provided by a GCLK output pin, and the elementary time
+unit is derived from its frequency with [`etu`](Config::etu).
+
+Characters NACKed by the card are repeated by the SERCOM, up to the
+[maximum number of iterations](Config::max_iterations). Once it is reached,
+[`Error::MaxIterations`] is reported.
+
+```
+let pads = uart::Iso7816Pads::new(pins.pa04);
+let mut card = uart::Config::new(&mclk, sercom, pads, freq)
+    .etu(4.MHz().convert(), 372, 1)
+    .max_iterations(3)
+    .enable_iso7816();
+
+// Release the card reset, then
+let mut atr = [0; 33];
+let len = card.read_atr(&mut atr)?;
+
+let select = [0x00, 0xA4, 0x04, 0x00, 0x02, 0x3F, 0x00];
+let mut response = [0; 258];
+let len = card.transmit(&select, &mut response)?;
+```
 "
 )]
 
@@ -409,10 +525,37 @@ pub use flags::*;
 mod config;
 pub use config::*;
 
+mod rs485;
+pub use rs485::*;
+
+#[cfg(feature = "thumbv7")]
+mod lin;
+#[cfg(feature = "thumbv7")]
+pub use lin::*;
+
+#[cfg(feature = "thumbv7")]
+mod iso7816;
+#[cfg(feature = "thumbv7")]
+pub use iso7816::*;
+
 pub mod impl_ehal;
 
+#[cfg(feature = "async")]
+mod async_api;
+#[cfg(feature = "async")]
+pub use async_api::*;
+
 use crate::{sercom::*, typelevel::Sealed};
 use core::{convert::TryInto, marker::PhantomData};
+
+#[cfg(feature = "thumbv7")]
+use crate::{
+    clock::v2::{
+        dynamic::ClockConsumer,
+        pclk::{DynPclkId, PclkId},
+    },
+    time::Hertz,
+};
 use num_traits::AsPrimitive;
 
 /// Size of the SERCOM's `DATA` register
@@ -659,7 +802,7 @@ where
     ///   all flags available.
     ///
     /// **Warning:** The implementation of of
-    /// [`Write::flush`](embedded_hal::serial::Write::flush) waits on and
+    /// [`Write::flush`](embedded_hal_02::serial::Write::flush) waits on and
     /// clears the `TXC` flag. Manually clearing this flag could cause it to
     /// hang indefinitely.
     #[inline]
@@ -743,6 +886,28 @@ where
     }
 }
 
+#[cfg(feature = "thumbv7")]
+impl<C, D> ClockConsumer for Uart<C, D>
+where
+    C: ValidConfig,
+    C::Sercom: PclkId,
+    D: Capability,
+{
+    #[inline]
+    fn pclk_id(&self) -> DynPclkId {
+        <C::Sercom as PclkId>::DYN
+    }
+
+    /// Recompute the baud rate for the new clock frequency
+    ///
+    /// This temporarily disables the SERCOM peripheral. See
+    /// [`Uart::reconfigure`].
+    #[inline]
+    fn set_clock_freq(&mut self, freq: Hertz) {
+        self._reconfigure(|c| c.set_freq(freq));
+    }
+}
+
 impl<C, D> Uart<C, D>
 where
     C: ValidConfig,
@@ -0,0 +1,234 @@
+//! `async` support for [`Uart`]s
+
+use super::{
+    Capability, Duplex, Error, Flags, Receive, RxDuplex, Transmit, TxDuplex, Uart, ValidConfig,
+};
+use crate::sercom::{
+    async_api::{RX_WAKERS, TX_WAKERS},
+    Sercom,
+};
+use core::task::Poll;
+use embassy_sync::waitqueue::AtomicWaker;
+use embedded_io_async::{ErrorKind, ErrorType, Read, Write};
+
+impl<C, D> Uart<C, D>
+where
+    C: ValidConfig,
+    D: Capability,
+{
+    /// Turn a [`Uart`] into a [`UartFuture`]
+    ///
+    /// The SERCOM interrupt(s) must be unmasked in the NVIC, and
+    /// [`sercom::on_interrupt`](crate::sercom::on_interrupt) must be called
+    /// from the interrupt handler(s).
+    #[inline]
+    pub fn into_future(self) -> UartFuture<C, D> {
+        UartFuture {
+            uart: self,
+            tx_pending: false,
+        }
+    }
+}
+
+/// `async` version of a [`Uart`]
+///
+/// Create this struct by calling [`Uart::into_future`].
+pub struct UartFuture<C, D>
+where
+    C: ValidConfig,
+    D: Capability,
+{
+    uart: Uart<C, D>,
+    /// Whether words were written since the last flush. `TXC` is only set
+    /// once a transmission completes, so flushing an idle [`Uart`] must not
+    /// wait on it.
+    tx_pending: bool,
+}
+
+impl<C> UartFuture<C, Duplex>
+where
+    C: ValidConfig,
+{
+    /// Split the [`UartFuture`] into [`RxDuplex`] and [`TxDuplex`] halves
+    #[inline]
+    pub fn split(self) -> (UartFuture<C, RxDuplex>, UartFuture<C, TxDuplex>) {
+        let (rx, tx) = self.uart.split();
+        (
+            UartFuture {
+                uart: rx,
+                tx_pending: false,
+            },
+            UartFuture {
+                uart: tx,
+                tx_pending: self.tx_pending,
+            },
+        )
+    }
+
+    /// Join [`RxDuplex`] and [`TxDuplex`] halves back into a full
+    /// `UartFuture<C, Duplex>`
+    #[inline]
+    pub fn join(rx: UartFuture<C, RxDuplex>, tx: UartFuture<C, TxDuplex>) -> Self {
+        Self {
+            uart: Uart::join(rx.uart, tx.uart),
+            tx_pending: tx.tx_pending,
+        }
+    }
+}
+
+impl<C, D> UartFuture<C, D>
+where
+    C: ValidConfig,
+    D: Capability,
+{
+    /// Return the underlying [`Uart`]
+    #[inline]
+    pub fn free(self) -> Uart<C, D> {
+        self.uart
+    }
+
+    /// Wait until one of the specified interrupt flags is set
+    ///
+    /// The interrupts are enabled before returning `Pending`, since the
+    /// interrupt handler disables them before waking the task.
+    async fn wait_flags(&mut self, waker: &AtomicWaker, flags: Flags) {
+        core::future::poll_fn(|cx| {
+            waker.register(cx.waker());
+            if self.uart.read_flags().intersects(flags) {
+                return Poll::Ready(());
+            }
+            self.uart.enable_interrupts(flags);
+            Poll::Pending
+        })
+        .await;
+    }
+}
+
+impl<C, D> UartFuture<C, D>
+where
+    C: ValidConfig,
+    D: Receive,
+{
+    /// Clear the status flags of an error, along with the `ERROR` flag
+    #[inline]
+    fn clear_error(&mut self, err: Error) {
+        self.uart.clear_status(err.into());
+        self.uart.clear_flags(Flags::ERROR);
+    }
+}
+
+impl<C, D> ErrorType for UartFuture<C, D>
+where
+    C: ValidConfig,
+    D: Capability,
+{
+    type Error = Error;
+}
+
+impl embedded_io_async::Error for Error {
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Error::ParityError | Error::FrameError => ErrorKind::InvalidData,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+impl<C, D> Read for UartFuture<C, D>
+where
+    C: ValidConfig<Word = u8>,
+    D: Receive,
+{
+    /// Wait for an `RXC` or `ERROR` flag, then read every word already
+    /// received, up to the length of `buf`
+    ///
+    /// An error is only returned if it is detected before the first word is
+    /// read. Otherwise, the words read so far are returned, and the error is
+    /// reported by the next call.
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let waker = &RX_WAKERS[<C::Sercom as Sercom>::NUM];
+        self.wait_flags(waker, Flags::RXC | Flags::ERROR).await;
+
+        let mut count = 0;
+        for word in buf.iter_mut() {
+            match self.uart.read_flags_errors() {
+                Err(err) if count == 0 => {
+                    self.clear_error(err);
+                    return Err(err);
+                }
+                Ok(flags) if flags.contains(Flags::RXC) => {
+                    *word = unsafe { self.uart.read_data() } as u8;
+                    count += 1;
+                }
+                _ => break,
+            }
+        }
+        Ok(count)
+    }
+}
+
+impl<C, D> Write for UartFuture<C, D>
+where
+    C: ValidConfig<Word = u8>,
+    D: Transmit,
+{
+    /// Wait for a `DRE` flag, then write words as long as the `DATA` register
+    /// is empty, up to the length of `buf`
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let waker = &TX_WAKERS[<C::Sercom as Sercom>::NUM];
+        self.wait_flags(waker, Flags::DRE).await;
+
+        let mut count = 0;
+        for &word in buf {
+            if !self.uart.read_flags().contains(Flags::DRE) {
+                break;
+            }
+            unsafe { self.uart.write_data(word.into()) };
+            count += 1;
+        }
+        self.tx_pending = true;
+        Ok(count)
+    }
+
+    /// Wait for a `TXC` flag, if any word was written since the last flush
+    ///
+    /// Writing to the `DATA` register clears the `TXC` flag, so it is not
+    /// cleared here.
+    async fn flush(&mut self) -> Result<(), Error> {
+        if self.tx_pending {
+            let waker = &TX_WAKERS[<C::Sercom as Sercom>::NUM];
+            self.wait_flags(waker, Flags::TXC).await;
+            self.tx_pending = false;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "thumbv7")]
+impl<C, D> UartFuture<C, D>
+where
+    C: ValidConfig,
+    C::Pads: super::Rs485Pads,
+    D: Transmit,
+{
+    /// Wait until the last transmission is finished
+    ///
+    /// See [`Uart::transmission_finished`]. Use this after an RS-485
+    /// transmission, including a DMA one, before releasing the bus.
+    pub async fn transmission_finished(&mut self) {
+        let waker = &TX_WAKERS[<C::Sercom as Sercom>::NUM];
+        while !self.uart.transmission_finished() {
+            self.wait_flags(waker, Flags::TXC).await;
+        }
+        self.tx_pending = false;
+    }
+}
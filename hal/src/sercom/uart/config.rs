@@ -46,6 +46,11 @@ where
     pads: P,
     chsize: PhantomData<C>,
     freq: Hertz,
+    /// Last requested baud rate, used to recompute `BAUD` when `freq` changes
+    baud: Option<(Hertz, BaudMode)>,
+    /// Discard the words received while transmitting, once the transmission
+    /// finishes
+    pub(super) echo_suppression: bool,
 }
 
 /// Clock type needed to create a new [`Config`]. [`PM`](pac::PM) for thumbv6m
@@ -96,6 +101,8 @@ impl<P: ValidPads> Config<P> {
             pads,
             chsize: PhantomData,
             freq: freq.into(),
+            baud: None,
+            echo_suppression: false,
         }
     }
 }
@@ -116,6 +123,8 @@ where
             pads: self.pads,
             chsize: PhantomData,
             freq: self.freq,
+            baud: self.baud,
+            echo_suppression: self.echo_suppression,
         }
     }
 
@@ -283,9 +292,23 @@ where
     /// Note that 3x oversampling is not supported.
     #[inline]
     pub fn set_baud(&mut self, baud: Hertz, mode: BaudMode) {
+        self.baud = Some((baud, mode));
         self.registers.set_baud(self.freq, baud, mode);
     }
 
+    /// Update the stored GCLK frequency
+    ///
+    /// Use this when the GCLK feeding this [`Sercom`] changes frequency. If a
+    /// baud rate was previously set, the `BAUD` register is recalculated to
+    /// keep the same baud rate.
+    #[inline]
+    pub fn set_freq(&mut self, freq: impl Into<Hertz>) {
+        self.freq = freq.into();
+        if let Some((baud, mode)) = self.baud {
+            self.registers.set_baud(self.freq, baud, mode);
+        }
+    }
+
     /// Get the contents of the `BAUD` register and the current baud mode. Note
     /// that only the CONTENTS of `BAUD` are returned, and not the actual baud
     /// rate. Refer to the datasheet to convert the `BAUD` register contents
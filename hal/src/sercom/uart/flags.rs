@@ -48,9 +48,15 @@ const BUFOVF: u16 = 0x04;
 const CTS: u16 = 0x08;
 const ISF: u16 = 0x10;
 const COLL: u16 = 0x20;
+#[cfg(feature = "thumbv7")]
+const ITER: u16 = 0x80;
 
 /// Status flags available for RX transactions
+#[cfg(feature = "thumbv6")]
 pub const RX_STATUS_MASK: u16 = PERR | FERR | BUFOVF | ISF | COLL;
+/// Status flags available for RX transactions
+#[cfg(feature = "thumbv7")]
+pub const RX_STATUS_MASK: u16 = PERR | FERR | BUFOVF | ISF | COLL | ITER;
 /// Status flags available for Duplex transactions
 pub const DUPLEX_STATUS_MASK: u16 = RX_STATUS_MASK;
 
@@ -58,7 +64,7 @@ bitflags! {
     /// Status flags for UART Rx transactions
     ///
     /// The available status flags are `PERR`, `FERR`, `BUFOVF`,
-    /// `CTS`, `ISF` and `COLL`.
+    /// `CTS`, `ISF`, `COLL` and, on thumbv7em targets, `ITER`.
     /// The binary format of the underlying bits exactly matches
     /// the STATUS bits.
     pub struct Status: u16 {
@@ -68,6 +74,8 @@ bitflags! {
         const CTS = CTS;
         const ISF = ISF;
         const COLL = COLL;
+        #[cfg(feature = "thumbv7")]
+        const ITER = ITER;
     }
 }
 
@@ -89,6 +97,9 @@ pub enum Error {
     InconsistentSyncField,
     /// Detected a collision
     CollisionDetected,
+    /// Reached the maximum number of ISO7816 repetitions
+    #[cfg(feature = "thumbv7")]
+    MaxIterations,
 }
 
 impl TryFrom<Status> for () {
@@ -108,6 +119,10 @@ impl TryFrom<Status> for () {
         } else if errors.contains(Status::COLL) {
             Err(CollisionDetected)
         } else {
+            #[cfg(feature = "thumbv7")]
+            if errors.contains(Status::ITER) {
+                return Err(MaxIterations);
+            }
             Ok(())
         }
     }
@@ -123,6 +138,8 @@ impl From<Error> for Status {
             Overflow => Status::BUFOVF,
             InconsistentSyncField => Status::ISF,
             CollisionDetected => Status::COLL,
+            #[cfg(feature = "thumbv7")]
+            MaxIterations => Status::ITER,
         }
     }
 }
@@ -0,0 +1,523 @@
+//! ISO7816 smart card support for [`Uart`]s
+
+use super::{
+    BaudMode, Config, Duplex, EightBit, Error, Flags, Oversampling, PadSet, Parity, RxpoTxpo,
+    SpecificConfig, Status, StopBits, Uart, ValidConfig, ValidPads,
+};
+use crate::{
+    gpio::AnyPin,
+    sercom::*,
+    time::Hertz,
+    typelevel::{NoneT, Sealed},
+};
+use core::marker::PhantomData;
+
+//=============================================================================
+// Iso7816Pads
+//=============================================================================
+
+/// Pad of an ISO7816 smart card interface
+///
+/// The card exchanges data on a single, bidirectional `I/O` line, which is
+/// always [`Pad0`]. The card clock is not generated by the SERCOM. It is
+/// typically provided by a GCLK output pin, while the card reset is driven by
+/// a GPIO pin.
+pub struct Iso7816Pads<S, I, IO>
+where
+    S: Sercom,
+    I: IoSet,
+    IO: IsPad<PadNum = Pad0>,
+{
+    sercom: PhantomData<S>,
+    ioset: PhantomData<I>,
+    io: IO,
+}
+
+impl<S, I, Id> Iso7816Pads<S, I, Pad<S, Id>>
+where
+    S: Sercom,
+    I: IoSet,
+    Id: GetPad<S, PadNum = Pad0>,
+    Pad<S, Id>: InIoSet<I> + IsPad<PadNum = Pad0>,
+{
+    /// Create a new [`Iso7816Pads`] from the `I/O` pin
+    #[inline]
+    pub fn new(pin: impl AnyPin<Id = Id>) -> Self {
+        Self {
+            sercom: PhantomData,
+            ioset: PhantomData,
+            io: pin.into().into_mode(),
+        }
+    }
+}
+
+impl<S, I, IO> Iso7816Pads<S, I, IO>
+where
+    S: Sercom,
+    I: IoSet,
+    IO: IsPad<PadNum = Pad0>,
+{
+    /// Consume the [`Iso7816Pads`] and return the `I/O` [`Pad`]
+    #[inline]
+    pub fn free(self) -> IO {
+        self.io
+    }
+}
+
+impl<S, I, IO> Sealed for Iso7816Pads<S, I, IO>
+where
+    S: Sercom,
+    I: IoSet,
+    IO: IsPad<PadNum = Pad0>,
+{
+}
+
+impl<S, I, IO> PadSet for Iso7816Pads<S, I, IO>
+where
+    S: Sercom,
+    I: IoSet,
+    IO: IsPad<PadNum = Pad0> + OptionalPad,
+{
+    type Sercom = S;
+    type IoSet = I;
+    type Rx = IO;
+    type Tx = NoneT;
+    type Rts = NoneT;
+    type Cts = NoneT;
+}
+
+/// Both `RX` and `TX` are on [`Pad0`]
+impl<S, I, IO> RxpoTxpo for Iso7816Pads<S, I, IO>
+where
+    S: Sercom,
+    I: IoSet,
+    IO: IsPad<PadNum = Pad0>,
+{
+    const RXPO: u8 = 0;
+    const TXPO: u8 = 0;
+}
+
+impl<S, I, IO> ValidPads for Iso7816Pads<S, I, IO>
+where
+    S: Sercom,
+    I: IoSet,
+    IO: IsPad<PadNum = Pad0> + OptionalPad,
+{
+    type Capability = Duplex;
+}
+
+//=============================================================================
+// Config
+//=============================================================================
+
+impl<S, I, IO> Config<Iso7816Pads<S, I, IO>, EightBit>
+where
+    S: Sercom,
+    I: IoSet,
+    IO: IsPad<PadNum = Pad0> + OptionalPad,
+{
+    /// Set the elementary time unit (builder pattern version)
+    ///
+    /// The baud rate is set to `card_clock * d / f`, where `f` and `d` are
+    /// the clock rate conversion and baud rate adjustment factors. Before a
+    /// PPS exchange, they are `372` and `1`.
+    #[inline]
+    pub fn etu(mut self, card_clock: Hertz, f: u16, d: u16) -> Self {
+        self.set_etu(card_clock, f, d);
+        self
+    }
+
+    /// Set the elementary time unit (setter version)
+    ///
+    /// The baud rate is set to `card_clock * d / f`, where `f` and `d` are
+    /// the clock rate conversion and baud rate adjustment factors. Before a
+    /// PPS exchange, they are `372` and `1`.
+    #[inline]
+    pub fn set_etu(&mut self, card_clock: Hertz, f: u16, d: u16) {
+        let baud = card_clock.to_Hz() * d as u32 / f as u32;
+        self.set_baud(
+            Hertz::from_raw(baud),
+            BaudMode::Fractional(Oversampling::Bits16),
+        );
+    }
+
+    /// Set the maximum number of repetitions (builder pattern version)
+    ///
+    /// A character NACKed by the receiver is repeated up to `iterations`
+    /// times, after which [`Error::MaxIterations`] is reported. Values above 7
+    /// saturate.
+    #[inline]
+    pub fn max_iterations(mut self, iterations: u8) -> Self {
+        self.set_max_iterations(iterations);
+        self
+    }
+
+    /// Set the maximum number of repetitions (setter version)
+    ///
+    /// A character NACKed by the receiver is repeated up to `iterations`
+    /// times, after which [`Error::MaxIterations`] is reported. Values above 7
+    /// saturate.
+    #[inline]
+    pub fn set_max_iterations(&mut self, iterations: u8) {
+        self.registers.set_max_iterations(iterations.min(7));
+    }
+
+    /// Get the maximum number of repetitions
+    #[inline]
+    pub fn get_max_iterations(&self) -> u8 {
+        self.registers.get_max_iterations()
+    }
+
+    /// Inhibit the NACK sent on parity errors (builder pattern version)
+    ///
+    /// When set, characters received with a parity error are not NACKed, so
+    /// they are not repeated by the card.
+    #[inline]
+    pub fn inhibit_nack(mut self, set: bool) -> Self {
+        self.set_inhibit_nack(set);
+        self
+    }
+
+    /// Inhibit the NACK sent on parity errors (setter version)
+    ///
+    /// When set, characters received with a parity error are not NACKed, so
+    /// they are not repeated by the card.
+    #[inline]
+    pub fn set_inhibit_nack(&mut self, set: bool) {
+        self.registers.set_inhibit_nack(set);
+    }
+
+    /// Get the current NACK inhibit setting
+    #[inline]
+    pub fn get_inhibit_nack(&self) -> bool {
+        self.registers.get_inhibit_nack()
+    }
+
+    /// Stop NACKing a character repeated the maximum number of times (builder
+    /// pattern version)
+    ///
+    /// When set, the last repetition is accepted and [`Error::MaxIterations`]
+    /// is reported.
+    #[inline]
+    pub fn disable_successive_nack(mut self, set: bool) -> Self {
+        self.set_disable_successive_nack(set);
+        self
+    }
+
+    /// Stop NACKing a character repeated the maximum number of times (setter
+    /// version)
+    ///
+    /// When set, the last repetition is accepted and [`Error::MaxIterations`]
+    /// is reported.
+    #[inline]
+    pub fn set_disable_successive_nack(&mut self, set: bool) {
+        self.registers.set_disable_successive_nack(set);
+    }
+
+    /// Get the current successive NACK setting
+    #[inline]
+    pub fn get_disable_successive_nack(&self) -> bool {
+        self.registers.get_disable_successive_nack()
+    }
+
+    /// Enable the UART peripheral as a T=0 [`SmartCard`] interface
+    ///
+    /// The character frame is set to 8 data bits, even parity and two stop
+    /// bits. The elementary time unit must be set beforehand.
+    #[inline]
+    pub fn enable_iso7816(mut self) -> SmartCard<Self> {
+        let settings = UsartSettings::override_iso7816(&mut self);
+        self.registers.configure_iso7816(true);
+        SmartCard {
+            uart: self.enable(),
+            settings,
+        }
+    }
+}
+
+/// USART settings overridden by the ISO7816 mode, restored when it is disabled
+struct UsartSettings {
+    parity: Parity,
+    stop_bits: StopBits,
+    echo_suppression: bool,
+}
+
+impl UsartSettings {
+    /// Save the settings of `config`, then apply the ISO7816 ones
+    #[inline]
+    fn override_iso7816<P: ValidPads>(config: &mut Config<P, EightBit>) -> Self {
+        let settings = Self {
+            parity: config.get_parity(),
+            stop_bits: config.get_stop_bits(),
+            echo_suppression: config.get_echo_suppression(),
+        };
+        config.set_parity(Parity::Even);
+        config.set_stop_bits(StopBits::TwoBits);
+        // The receiver sees every character sent on the single `I/O` line
+        config.set_echo_suppression(true);
+        settings
+    }
+
+    /// Restore the saved settings
+    #[inline]
+    fn restore<C: ValidConfig>(self, mut config: C) -> C {
+        let config_mut = config.as_mut();
+        config_mut.set_parity(self.parity);
+        config_mut.set_stop_bits(self.stop_bits);
+        config_mut.set_echo_suppression(self.echo_suppression);
+        config
+    }
+}
+
+//=============================================================================
+// SmartCard
+//=============================================================================
+
+/// Errors available for ISO7816 transactions
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Iso7816Error {
+    /// An error was detected by the USART, including
+    /// [`Error::MaxIterations`]
+    Uart(Error),
+    /// The answer to reset was malformed, or used the inverse convention
+    Atr,
+    /// The card did not accept the PPS request
+    Pps,
+    /// The card sent an unexpected procedure byte
+    Procedure(u8),
+    /// The command is shorter than its 5 byte header
+    Command,
+    /// The buffer is too small for the data received
+    BufferTooSmall,
+}
+
+impl From<Error> for Iso7816Error {
+    #[inline]
+    fn from(err: Error) -> Self {
+        Iso7816Error::Uart(err)
+    }
+}
+
+/// A [`Uart`] acting as an ISO7816 T=0 smart card interface
+///
+/// After a reset of the card, [`read_atr`](Self::read_atr) receives its
+/// answer to reset. The transmission parameters can then be negotiated with
+/// [`send_pps`](Self::send_pps), and commands are exchanged with
+/// [`transmit`](Self::transmit).
+///
+/// The blocking methods of this struct don't implement the ISO7816 waiting
+/// times. They never return if the card stops responding.
+///
+/// Create this struct by calling [`Config::enable_iso7816`].
+pub struct SmartCard<C: ValidConfig> {
+    uart: Uart<C, Duplex>,
+    settings: UsartSettings,
+}
+
+impl<C: ValidConfig<Word = u8>> SmartCard<C> {
+    /// Receive a character
+    fn read_byte(&mut self) -> Result<u8, Iso7816Error> {
+        loop {
+            match self.uart.read_flags_errors() {
+                Ok(flags) if flags.contains(Flags::RXC) => {
+                    return Ok(unsafe { self.uart.read_data() } as u8);
+                }
+                Ok(_) => (),
+                Err(err) => {
+                    self.uart.clear_status(err.into());
+                    self.uart.clear_flags(Flags::ERROR);
+                    return Err(err.into());
+                }
+            }
+        }
+    }
+
+    /// Send characters, and wait until they are accepted by the card
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Iso7816Error> {
+        for &byte in bytes {
+            while !self.uart.read_flags().contains(Flags::DRE) {}
+            unsafe { self.uart.write_data(byte.into()) };
+        }
+        while !self.uart.finish_transmission() {}
+        if self.uart.read_status().contains(Status::ITER) {
+            self.uart.clear_status(Status::ITER);
+            self.uart.clear_flags(Flags::ERROR);
+            return Err(Error::MaxIterations.into());
+        }
+        Ok(())
+    }
+
+    /// Receive the answer to reset, and return its length
+    ///
+    /// Call this method right after releasing the card reset. The length of
+    /// the answer is parsed from its interface bytes, and the check byte is
+    /// verified when present. Only the direct convention is supported.
+    ///
+    /// # Blocking: This method blocks until the answer is received
+    pub fn read_atr(&mut self, buffer: &mut [u8]) -> Result<usize, Iso7816Error> {
+        let mut len = 0;
+        let mut next = |card: &mut Self| -> Result<u8, Iso7816Error> {
+            let byte = card.read_byte()?;
+            *buffer.get_mut(len).ok_or(Iso7816Error::BufferTooSmall)? = byte;
+            len += 1;
+            Ok(byte)
+        };
+
+        if next(self)? != 0x3B {
+            return Err(Iso7816Error::Atr);
+        }
+        let t0 = next(self)?;
+        let mut check = t0;
+        let mut indicators = t0 >> 4;
+        let mut has_tck = false;
+        loop {
+            // TAi, TBi and TCi
+            for _ in 0..(indicators & 0x7).count_ones() {
+                check ^= next(self)?;
+            }
+            if indicators & 0x8 == 0 {
+                break;
+            }
+            let td = next(self)?;
+            check ^= td;
+            // Only T=0 cards may omit TCK
+            has_tck |= td & 0xF != 0;
+            indicators = td >> 4;
+        }
+        for _ in 0..(t0 & 0xF) {
+            check ^= next(self)?;
+        }
+        if has_tck && next(self)? != check {
+            return Err(Iso7816Error::Atr);
+        }
+        Ok(len)
+    }
+
+    /// Request T=0 with the `FI`/`DI` parameters in `ta1`, through a PPS
+    /// exchange
+    ///
+    /// `ta1` holds `FI` in its upper nibble and `DI` in its lower nibble, as
+    /// in the `TA1` byte of the answer to reset. Once the card accepts the
+    /// request, set the new elementary time unit with
+    /// [`reconfigure`](Self::reconfigure).
+    ///
+    /// # Blocking: This method blocks until the card answers
+    pub fn send_pps(&mut self, ta1: u8) -> Result<(), Iso7816Error> {
+        let mut request = [0xFF, 0x10, ta1, 0];
+        request[3] = request[..3].iter().fold(0, |check, byte| check ^ byte);
+        self.write_bytes(&request)?;
+        for &byte in request.iter() {
+            if self.read_byte()? != byte {
+                return Err(Iso7816Error::Pps);
+            }
+        }
+        Ok(())
+    }
+
+    /// Exchange a T=0 command with the card, and return the length of the
+    /// response
+    ///
+    /// `command` holds the `CLA`, `INS`, `P1`, `P2` and `P3` header, followed
+    /// by the data sent to the card, if any. If no data is sent, the card may
+    /// send `P3` bytes of data, or 256 if `P3` is 0. The response holds the
+    /// data received, followed by the `SW1` and `SW2` status bytes.
+    ///
+    /// The `0x61` and `0x6C` status bytes are returned as is, so the caller
+    /// can issue the corresponding `GET RESPONSE` or repeat the command.
+    ///
+    /// # Blocking: This method blocks until the status bytes are received
+    pub fn transmit(&mut self, command: &[u8], response: &mut [u8]) -> Result<usize, Iso7816Error> {
+        if command.len() < 5 {
+            return Err(Iso7816Error::Command);
+        }
+        let (header, data) = command.split_at(5);
+        let ins = header[1];
+        let expected = match (data.is_empty(), header[4]) {
+            (true, 0) => 256,
+            (true, p3) => p3 as usize,
+            (false, _) => 0,
+        };
+
+        self.write_bytes(header)?;
+        let mut sent = 0;
+        let mut received = 0;
+        loop {
+            let procedure = self.read_byte()?;
+            match procedure {
+                // NULL byte, requesting more time
+                0x60 => (),
+                // SW1, followed by SW2
+                0x61..=0x6F | 0x90..=0x9F => {
+                    let sw = response
+                        .get_mut(received..received + 2)
+                        .ok_or(Iso7816Error::BufferTooSmall)?;
+                    sw[0] = procedure;
+                    sw[1] = self.read_byte()?;
+                    return Ok(received + 2);
+                }
+                // Transfer all remaining data bytes
+                _ if procedure == ins => {
+                    if sent < data.len() {
+                        self.write_bytes(&data[sent..])?;
+                        sent = data.len();
+                    } else {
+                        while received < expected {
+                            self.receive_into(response, received)?;
+                            received += 1;
+                        }
+                    }
+                }
+                // Transfer the next data byte only
+                _ if procedure == !ins => {
+                    if sent < data.len() {
+                        self.write_bytes(&data[sent..=sent])?;
+                        sent += 1;
+                    } else if received < expected {
+                        self.receive_into(response, received)?;
+                        received += 1;
+                    }
+                }
+                _ => return Err(Iso7816Error::Procedure(procedure)),
+            }
+        }
+    }
+
+    /// Receive a data byte into `response[index]`
+    fn receive_into(&mut self, response: &mut [u8], index: usize) -> Result<(), Iso7816Error> {
+        let byte = self.read_byte()?;
+        *response
+            .get_mut(index)
+            .ok_or(Iso7816Error::BufferTooSmall)? = byte;
+        Ok(())
+    }
+
+    /// Update the configuration, e.g. with [`Config::set_etu`] after a PPS
+    /// exchange
+    #[inline]
+    pub fn reconfigure<F>(&mut self, update: F)
+    where
+        F: FnOnce(&mut SpecificConfig<C>),
+    {
+        self.uart.reconfigure(update);
+    }
+
+    /// Disable the UART peripheral and return the underlying [`Config`]
+    ///
+    /// The USART frame format is restored, along with the parity, stop bits
+    /// and echo suppression settings overridden by
+    /// [`Config::enable_iso7816`].
+    #[inline]
+    pub fn disable(self) -> C {
+        let mut config = self.uart.disable();
+        config.as_mut().registers.configure_iso7816(false);
+        self.settings.restore(config)
+    }
+}
+
+impl<C: ValidConfig> AsRef<Uart<C, Duplex>> for SmartCard<C> {
+    #[inline]
+    fn as_ref(&self) -> &Uart<C, Duplex> {
+        &self.uart
+    }
+}
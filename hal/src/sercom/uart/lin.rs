@@ -0,0 +1,447 @@
+//! LIN master and slave support for [`Uart`]s
+
+use super::{Config, Duplex, EightBit, Error, Flags, Parity, Status, Uart, ValidConfig, ValidPads};
+
+//=============================================================================
+// LIN frame helpers
+//=============================================================================
+
+/// Break field length sent by a [`LinMaster`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LinBreakLength {
+    /// 13 bit periods, the LIN minimum
+    Bits13,
+    /// 17 bit periods
+    Bits17,
+    /// 21 bit periods
+    Bits21,
+    /// 26 bit periods
+    Bits26,
+}
+
+/// Delay inserted by a [`LinMaster`] between the break and sync fields
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LinHeaderDelay {
+    /// 1 bit period
+    Bits1,
+    /// 4 bit periods
+    Bits4,
+    /// 8 bit periods
+    Bits8,
+    /// 14 bit periods
+    Bits14,
+}
+
+/// Checksum model of a LIN frame
+///
+/// LIN 1.x nodes, and the diagnostic frames `0x3C` and `0x3D`, use the
+/// [`Classic`](LinChecksum::Classic) checksum. LIN 2.x nodes use the
+/// [`Enhanced`](LinChecksum::Enhanced) checksum for every other frame.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LinChecksum {
+    /// Checksum over the data bytes
+    Classic,
+    /// Checksum over the protected identifier and the data bytes
+    Enhanced,
+}
+
+impl LinChecksum {
+    /// Compute the checksum of a frame, given its protected identifier
+    ///
+    /// The checksum is the inverted 8-bit sum with carry of the bytes.
+    pub fn compute(self, pid: u8, data: &[u8]) -> u8 {
+        let init = match self {
+            LinChecksum::Classic => 0,
+            LinChecksum::Enhanced => pid as u16,
+        };
+        let sum = data.iter().fold(init, |sum, &byte| {
+            let sum = sum + byte as u16;
+            if sum > 0xFF {
+                sum - 0xFF
+            } else {
+                sum
+            }
+        });
+        !(sum as u8)
+    }
+}
+
+/// Compute the protected identifier of a 6-bit frame identifier
+///
+/// The two parity bits are appended as bits 6 and 7. The upper two bits of
+/// `id` are ignored.
+pub fn lin_protected_id(id: u8) -> u8 {
+    let id = id & 0x3F;
+    let bit = |n: u8| (id >> n) & 1;
+    let p0 = bit(0) ^ bit(1) ^ bit(2) ^ bit(4);
+    let p1 = !(bit(1) ^ bit(3) ^ bit(4) ^ bit(5)) & 1;
+    id | (p0 << 6) | (p1 << 7)
+}
+
+/// Errors available for LIN transactions
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LinError {
+    /// A parity, frame or overflow error was detected by the USART
+    Uart(Error),
+    /// The bus level differed from the level transmitted. The transmission
+    /// was aborted.
+    Collision,
+    /// The sync field of a received header was inconsistent
+    SyncField,
+    /// A break field was received in the middle of a frame
+    Break,
+    /// The parity bits of a received protected identifier were wrong
+    IdParity,
+    /// The checksum of a received response was wrong
+    Checksum,
+}
+
+impl From<Error> for LinError {
+    #[inline]
+    fn from(err: Error) -> Self {
+        match err {
+            Error::InconsistentSyncField => LinError::SyncField,
+            Error::CollisionDetected => LinError::Collision,
+            err => LinError::Uart(err),
+        }
+    }
+}
+
+/// [`Config`] settings overridden by the LIN modes, restored by `disable`
+#[derive(Clone, Copy)]
+struct UsartSettings {
+    parity: Parity,
+    collision_detection: bool,
+    echo_suppression: bool,
+}
+
+impl UsartSettings {
+    /// Save the settings of `config`, then apply the LIN ones
+    #[inline]
+    fn override_lin<P: ValidPads>(config: &mut Config<P, EightBit>) -> Self {
+        let settings = Self {
+            parity: config.get_parity(),
+            collision_detection: config.get_collision_detection(),
+            echo_suppression: config.get_echo_suppression(),
+        };
+        config.set_parity(Parity::None);
+        config.set_collision_detection(true);
+        // The receiver sees every byte sent on the single-wire bus
+        config.set_echo_suppression(true);
+        settings
+    }
+
+    /// Restore the saved settings, and the USART frame format
+    #[inline]
+    fn restore<C: ValidConfig>(self, mut config: C) -> C {
+        let config_mut = config.as_mut();
+        config_mut.set_parity(self.parity);
+        config_mut.set_collision_detection(self.collision_detection);
+        config_mut.set_echo_suppression(self.echo_suppression);
+        config
+    }
+}
+
+impl<C> Uart<C, Duplex>
+where
+    C: ValidConfig<Word = u8>,
+{
+    /// Clear the status flags of an error, along with the `ERROR` flag
+    #[inline]
+    fn clear_lin_error(&mut self, err: Error) {
+        self.clear_status(err.into());
+        self.clear_flags(Flags::ERROR);
+    }
+
+    /// Wait until the words written are sent, discard their echo, and check
+    /// for a collision
+    fn finish_lin_transmission(&mut self) -> Result<(), LinError> {
+        while !self.finish_transmission() {}
+        if self.read_status().contains(Status::COLL) {
+            self.clear_lin_error(Error::CollisionDetected);
+            // A collision disables the transmitter
+            self.config.as_mut().registers.enable(false, true);
+            return Err(LinError::Collision);
+        }
+        Ok(())
+    }
+
+    /// Send a response, followed by its checksum
+    fn write_lin_response(
+        &mut self,
+        pid: u8,
+        data: &[u8],
+        checksum: LinChecksum,
+    ) -> Result<(), LinError> {
+        let checksum = checksum.compute(pid, data);
+        for &byte in data.iter().chain(core::iter::once(&checksum)) {
+            while !self.read_flags().contains(Flags::DRE) {}
+            unsafe { self.write_data(byte.into()) };
+        }
+        self.finish_lin_transmission()
+    }
+
+    /// Receive a response, followed by its checksum
+    fn read_lin_response(
+        &mut self,
+        pid: u8,
+        buffer: &mut [u8],
+        checksum: LinChecksum,
+    ) -> Result<(), LinError> {
+        let len = buffer.len();
+        let mut received = 0;
+        while received <= len {
+            let flags = match self.read_flags_errors() {
+                Ok(flags) => flags,
+                Err(err) => {
+                    self.clear_lin_error(err);
+                    return Err(err.into());
+                }
+            };
+            if flags.contains(Flags::RXBRK) {
+                return Err(LinError::Break);
+            }
+            if flags.contains(Flags::RXC) {
+                let byte = unsafe { self.read_data() } as u8;
+                if received < len {
+                    buffer[received] = byte;
+                } else if byte != checksum.compute(pid, buffer) {
+                    return Err(LinError::Checksum);
+                }
+                received += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+//=============================================================================
+// LinMaster
+//=============================================================================
+
+/// A [`Uart`] acting as a LIN master
+///
+/// The master sends the header of each frame. The response is then either
+/// sent by the master, with [`send_frame`](Self::send_frame), or by a slave,
+/// with [`request_frame`](Self::request_frame).
+///
+/// Create this struct by calling [`Config::enable_lin_master`].
+pub struct LinMaster<C: ValidConfig> {
+    uart: Uart<C, Duplex>,
+    settings: UsartSettings,
+}
+
+impl<P> Config<P, EightBit>
+where
+    P: ValidPads<Capability = Duplex>,
+{
+    /// Enable the UART peripheral as a [`LinMaster`]
+    ///
+    /// The baud rate must be set beforehand. Parity is disabled, and
+    /// collision detection is enabled to detect bit errors.
+    #[inline]
+    pub fn enable_lin_master(
+        mut self,
+        break_length: LinBreakLength,
+        header_delay: LinHeaderDelay,
+    ) -> LinMaster<Self> {
+        let settings = UsartSettings::override_lin(&mut self);
+        self.registers
+            .configure_lin_master(break_length as u8, header_delay as u8);
+        LinMaster {
+            uart: self.enable(),
+            settings,
+        }
+    }
+
+    /// Enable the UART peripheral as a [`LinSlave`]
+    ///
+    /// The baud rate must be set to the nominal bus rate beforehand. It is
+    /// then adjusted on the sync field of every header. Parity is disabled,
+    /// and collision detection is enabled to detect bit errors.
+    #[inline]
+    pub fn enable_lin_slave(mut self) -> LinSlave<Self> {
+        let settings = UsartSettings::override_lin(&mut self);
+        self.registers.configure_lin_slave();
+        LinSlave {
+            uart: self.enable(),
+            settings,
+            header_pending: false,
+        }
+    }
+}
+
+impl<C: ValidConfig<Word = u8>> LinMaster<C> {
+    /// Send the header of a frame: break, sync and protected identifier
+    ///
+    /// # Blocking: This method blocks until the header is sent
+    pub fn send_header(&mut self, id: u8) -> Result<(), LinError> {
+        while !self.uart.read_flags().contains(Flags::DRE) {}
+        self.uart.config.as_mut().registers.send_lin_header();
+        unsafe { self.uart.write_data(lin_protected_id(id).into()) };
+        self.uart.finish_lin_transmission()
+    }
+
+    /// Send the header of a frame, then its response and checksum
+    ///
+    /// # Blocking: This method blocks until the frame is sent
+    pub fn send_frame(
+        &mut self,
+        id: u8,
+        data: &[u8],
+        checksum: LinChecksum,
+    ) -> Result<(), LinError> {
+        self.send_header(id)?;
+        self.uart
+            .write_lin_response(lin_protected_id(id), data, checksum)
+    }
+
+    /// Send the header of a frame, then receive the response of a slave and
+    /// check its checksum
+    ///
+    /// The length of the response is the length of `buffer`.
+    ///
+    /// # Blocking: This method blocks until the response is received. If no
+    /// slave responds, it never returns.
+    pub fn request_frame(
+        &mut self,
+        id: u8,
+        buffer: &mut [u8],
+        checksum: LinChecksum,
+    ) -> Result<(), LinError> {
+        self.send_header(id)?;
+        self.uart
+            .read_lin_response(lin_protected_id(id), buffer, checksum)
+    }
+
+    /// Disable the UART peripheral and return the underlying [`Config`]
+    ///
+    /// The USART frame format, the break length and header delay, and the
+    /// parity, collision detection and echo suppression settings are
+    /// restored.
+    #[inline]
+    pub fn disable(self) -> C {
+        let mut config = self.uart.disable();
+        config.as_mut().registers.reset_lin_master();
+        self.settings.restore(config)
+    }
+}
+
+impl<C: ValidConfig> AsRef<Uart<C, Duplex>> for LinMaster<C> {
+    #[inline]
+    fn as_ref(&self) -> &Uart<C, Duplex> {
+        &self.uart
+    }
+}
+
+//=============================================================================
+// LinSlave
+//=============================================================================
+
+/// A [`Uart`] acting as a LIN slave
+///
+/// The slave waits for the header of a frame with
+/// [`read_header`](Self::read_header). Depending on the identifier, it then
+/// sends the response with [`write_response`](Self::write_response), receives
+/// it with [`read_response`](Self::read_response), or ignores the frame.
+///
+/// The break field is detected in hardware, and the baud rate is adjusted on
+/// the sync field, which is not stored in the receive buffer.
+///
+/// Create this struct by calling [`Config::enable_lin_slave`].
+pub struct LinSlave<C: ValidConfig> {
+    uart: Uart<C, Duplex>,
+    settings: UsartSettings,
+    /// Whether a break was detected, and the protected identifier is expected
+    header_pending: bool,
+}
+
+impl<C: ValidConfig<Word = u8>> LinSlave<C> {
+    /// Wait for the header of a frame, and return its 6-bit identifier
+    ///
+    /// Bytes received outside of a header are discarded.
+    pub fn read_header(&mut self) -> nb::Result<u8, LinError> {
+        let flags = match self.uart.read_flags_errors() {
+            Ok(flags) => flags,
+            Err(err) => {
+                self.uart.clear_lin_error(err);
+                self.header_pending = false;
+                return Err(nb::Error::Other(err.into()));
+            }
+        };
+
+        if flags.contains(Flags::RXBRK) {
+            self.uart.clear_flags(Flags::RXBRK);
+            self.header_pending = true;
+        } else if flags.contains(Flags::RXC) {
+            let pid = unsafe { self.uart.read_data() } as u8;
+            if core::mem::take(&mut self.header_pending) {
+                let id = pid & 0x3F;
+                return if lin_protected_id(id) == pid {
+                    Ok(id)
+                } else {
+                    Err(nb::Error::Other(LinError::IdParity))
+                };
+            }
+        }
+        Err(nb::Error::WouldBlock)
+    }
+
+    /// Send the response to the last header, followed by its checksum
+    ///
+    /// # Blocking: This method blocks until the response is sent
+    pub fn write_response(
+        &mut self,
+        id: u8,
+        data: &[u8],
+        checksum: LinChecksum,
+    ) -> Result<(), LinError> {
+        self.uart
+            .write_lin_response(lin_protected_id(id), data, checksum)
+    }
+
+    /// Receive the response to the last header, and check its checksum
+    ///
+    /// The length of the response is the length of `buffer`. If a break is
+    /// received first, [`LinError::Break`] is returned, and the next call to
+    /// [`read_header`](Self::read_header) returns the identifier of the new
+    /// frame.
+    ///
+    /// # Blocking: This method blocks until the response is received
+    pub fn read_response(
+        &mut self,
+        id: u8,
+        buffer: &mut [u8],
+        checksum: LinChecksum,
+    ) -> Result<(), LinError> {
+        let result = self
+            .uart
+            .read_lin_response(lin_protected_id(id), buffer, checksum);
+        if let Err(LinError::Break) = result {
+            self.uart.clear_flags(Flags::RXBRK);
+            self.header_pending = true;
+        }
+        result
+    }
+
+    /// Disable the UART peripheral and return the underlying [`Config`]
+    ///
+    /// The USART frame format, and the parity, collision detection and echo
+    /// suppression settings are restored. The baud rate may differ from the
+    /// one configured, since it follows the sync fields received.
+    #[inline]
+    pub fn disable(self) -> C {
+        self.settings.restore(self.uart.disable())
+    }
+}
+
+impl<C: ValidConfig> AsRef<Uart<C, Duplex>> for LinSlave<C> {
+    #[inline]
+    fn as_ref(&self) -> &Uart<C, Duplex> {
+        &self.uart
+    }
+}
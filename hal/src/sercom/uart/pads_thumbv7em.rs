@@ -251,6 +251,20 @@ where
         }
     }
 
+    /// Set the RS-485 transmit enable (`TE`) [`Pad`], which is always
+    /// [`Pad2`]
+    ///
+    /// `TE` shares its pad with `RTS`, so this is equivalent to
+    /// [`rts`](Self::rts). See [`Rs485Pads`](super::Rs485Pads).
+    #[inline]
+    pub fn te<Id>(self, pin: impl AnyPin<Id = Id>) -> Pads<S, I, RX, TX, Pad<S, Id>, CTS>
+    where
+        Id: GetPad<S>,
+        Pad<S, Id>: InIoSet<I>,
+    {
+        self.rts(pin)
+    }
+
     /// Set the `CTS` [`Pad`], which is always [`Pad3`]
     #[inline]
     pub fn cts<Id>(self, pin: impl AnyPin<Id = Id>) -> Pads<S, I, RX, TX, RTS, Pad<S, Id>>
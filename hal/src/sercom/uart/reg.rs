@@ -9,7 +9,7 @@ use crate::sercom::*;
 use pac::sercom0::usart::ctrla::MODE_A;
 
 #[cfg(feature = "thumbv7")]
-use pac::sercom0::usart_int::ctrla::MODE_A;
+use pac::sercom0::usart_int::ctrla::{FORM_A, MODE_A};
 
 use crate::time::Hertz;
 
@@ -343,6 +343,126 @@ impl<S: Sercom> Registers<S> {
         }
     }
 
+    /// Set the RS-485 guard time, in bit periods
+    #[cfg(feature = "thumbv7")]
+    #[inline]
+    pub(super) fn set_guard_time(&mut self, bits: u8) {
+        self.usart()
+            .ctrlc
+            .modify(|_, w| unsafe { w.gtime().bits(bits) });
+    }
+
+    /// Get the RS-485 guard time, in bit periods
+    #[cfg(feature = "thumbv7")]
+    #[inline]
+    pub(super) fn get_guard_time(&self) -> u8 {
+        self.usart().ctrlc.read().gtime().bits()
+    }
+
+    /// Use the LIN master frame format, with the given break length and
+    /// header delay (`CTRLC.BRKLEN` and `CTRLC.HDRDLY` values)
+    #[cfg(feature = "thumbv7")]
+    #[inline]
+    pub(super) fn configure_lin_master(&mut self, brklen: u8, hdrdly: u8) {
+        self.usart()
+            .ctrla
+            .modify(|_, w| w.form().variant(FORM_A::USART_FRAME_LIN_MASTER_MODE));
+        self.usart().ctrlc.modify(|_, w| unsafe {
+            w.brklen().bits(brklen);
+            w.hdrdly().bits(hdrdly)
+        });
+    }
+
+    /// Restore the reset break length and header delay of the LIN master
+    /// frame format
+    #[cfg(feature = "thumbv7")]
+    #[inline]
+    pub(super) fn reset_lin_master(&mut self) {
+        self.usart().ctrlc.modify(|_, w| unsafe {
+            w.brklen().bits(0);
+            w.hdrdly().bits(0)
+        });
+    }
+
+    /// Use the auto-baud frame format, which detects LIN breaks and measures
+    /// the sync field
+    #[cfg(feature = "thumbv7")]
+    #[inline]
+    pub(super) fn configure_lin_slave(&mut self) {
+        self.usart()
+            .ctrla
+            .modify(|_, w| w.form().variant(FORM_A::USART_FRAME_AUTO_BAUD_NO_PARITY));
+    }
+
+    /// Send the break and sync fields of a LIN header, followed by the next
+    /// word written to `DATA`
+    #[cfg(feature = "thumbv7")]
+    #[inline]
+    pub(super) fn send_lin_header(&mut self) {
+        let usart = self.usart();
+        usart.ctrlb.modify(|_, w| unsafe { w.lincmd().bits(0x2) });
+        while usart.syncbusy.read().ctrlb().bit_is_set() {}
+    }
+
+    /// Enable or disable the ISO7816 T=0 frame format
+    #[cfg(feature = "thumbv7")]
+    #[inline]
+    pub(super) fn configure_iso7816(&mut self, enabled: bool) {
+        let form = match enabled {
+            true => FORM_A::USART_FRAME_ISO_7816,
+            false => FORM_A::USART_FRAME_NO_PARITY,
+        };
+        self.usart().ctrla.modify(|_, w| {
+            w.form().variant(form);
+            w.cmode().bit(enabled)
+        });
+    }
+
+    /// Set the maximum number of ISO7816 repetitions
+    #[cfg(feature = "thumbv7")]
+    #[inline]
+    pub(super) fn set_max_iterations(&mut self, iterations: u8) {
+        self.usart()
+            .ctrlc
+            .modify(|_, w| unsafe { w.maxiter().bits(iterations) });
+    }
+
+    /// Get the maximum number of ISO7816 repetitions
+    #[cfg(feature = "thumbv7")]
+    #[inline]
+    pub(super) fn get_max_iterations(&self) -> u8 {
+        self.usart().ctrlc.read().maxiter().bits()
+    }
+
+    /// Inhibit the ISO7816 NACK on parity errors
+    #[cfg(feature = "thumbv7")]
+    #[inline]
+    pub(super) fn set_inhibit_nack(&mut self, set: bool) {
+        self.usart().ctrlc.modify(|_, w| w.inack().bit(set));
+    }
+
+    /// Get the current ISO7816 NACK inhibit setting
+    #[cfg(feature = "thumbv7")]
+    #[inline]
+    pub(super) fn get_inhibit_nack(&self) -> bool {
+        self.usart().ctrlc.read().inack().bit()
+    }
+
+    /// Disable the ISO7816 NACK once the maximum number of repetitions is
+    /// reached
+    #[cfg(feature = "thumbv7")]
+    #[inline]
+    pub(super) fn set_disable_successive_nack(&mut self, set: bool) {
+        self.usart().ctrlc.modify(|_, w| w.dsnack().bit(set));
+    }
+
+    /// Get the current ISO7816 successive NACK setting
+    #[cfg(feature = "thumbv7")]
+    #[inline]
+    pub(super) fn get_disable_successive_nack(&self) -> bool {
+        self.usart().ctrlc.read().dsnack().bit()
+    }
+
     /// Clear specified interrupt flags
     #[inline]
     pub(super) fn clear_flags(&mut self, flags: Flags) {
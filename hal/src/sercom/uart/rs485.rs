@@ -0,0 +1,257 @@
+//! RS-485 support for [`Uart`]s
+
+use super::{Capability, CharSize, Config, Flags, Status, Uart, ValidConfig, ValidPads};
+
+#[cfg(feature = "thumbv7")]
+use super::{Pads, Transmit};
+#[cfg(feature = "thumbv7")]
+use crate::{sercom::*, typelevel::NoneT};
+
+#[cfg(feature = "thumbv6")]
+use super::{DataReg, Transmit};
+#[cfg(feature = "thumbv6")]
+use crate::ehal_1::digital::OutputPin;
+#[cfg(feature = "thumbv6")]
+use core::convert::Infallible;
+#[cfg(feature = "thumbv6")]
+use num_traits::AsPrimitive;
+
+//=============================================================================
+// Rs485Pads
+//=============================================================================
+
+/// Marker trait for sets of [`Pads`] in RS-485 mode
+///
+/// A set of [`Pads`] with `TX` and `TE` (i.e. `RTS`) pads, but no `CTS` pad,
+/// is configured with `TXPO = 3`. In this mode, the SERCOM drives `TE` high
+/// while transmitting, and holds it for the configured
+/// [guard time](Config::guard_time) after the last stop bit.
+#[cfg(feature = "thumbv7")]
+pub trait Rs485Pads: ValidPads {}
+
+#[cfg(feature = "thumbv7")]
+impl<S, I, RX, TX, TE> Rs485Pads for Pads<S, I, RX, TX, TE, NoneT>
+where
+    S: Sercom,
+    I: IoSet,
+    RX: OptionalPad,
+    TX: SomePad,
+    TE: SomePad,
+    Self: ValidPads,
+{
+}
+
+impl<P, C> Config<P, C>
+where
+    P: ValidPads,
+    C: CharSize,
+{
+    /// Discard the echo of transmitted words (builder pattern version)
+    ///
+    /// On a half-duplex bus, the receiver sees every word sent by the
+    /// transmitter. When set, the words received during a transmission are
+    /// discarded once the transmission is finished.
+    #[inline]
+    pub fn echo_suppression(mut self, set: bool) -> Self {
+        self.set_echo_suppression(set);
+        self
+    }
+
+    /// Discard the echo of transmitted words (setter version)
+    ///
+    /// On a half-duplex bus, the receiver sees every word sent by the
+    /// transmitter. When set, the words received during a transmission are
+    /// discarded once the transmission is finished.
+    #[inline]
+    pub fn set_echo_suppression(&mut self, set: bool) {
+        self.echo_suppression = set;
+    }
+
+    /// Get the current echo suppression setting
+    #[inline]
+    pub fn get_echo_suppression(&self) -> bool {
+        self.echo_suppression
+    }
+}
+
+#[cfg(feature = "thumbv7")]
+impl<P, C> Config<P, C>
+where
+    P: Rs485Pads,
+    C: CharSize,
+{
+    /// Set the RS-485 guard time (builder pattern version)
+    ///
+    /// `TE` is held high for `bits` bit periods after the last stop bit.
+    /// Values above 7 saturate.
+    #[inline]
+    pub fn guard_time(mut self, bits: u8) -> Self {
+        self.set_guard_time(bits);
+        self
+    }
+
+    /// Set the RS-485 guard time (setter version)
+    ///
+    /// `TE` is held high for `bits` bit periods after the last stop bit.
+    /// Values above 7 saturate.
+    #[inline]
+    pub fn set_guard_time(&mut self, bits: u8) {
+        self.registers.set_guard_time(bits.min(7));
+    }
+
+    /// Get the current RS-485 guard time, in bit periods
+    #[inline]
+    pub fn get_guard_time(&self) -> u8 {
+        self.registers.get_guard_time()
+    }
+}
+
+impl<C, D> Uart<C, D>
+where
+    C: ValidConfig,
+    D: Capability,
+{
+    /// Check for the `TXC` flag, then clear it and discard the echo, if
+    /// enabled
+    #[inline]
+    pub(super) fn finish_transmission(&mut self) -> bool {
+        let config = self.config.as_mut();
+        if !config.registers.read_flags().contains(Flags::TXC) {
+            return false;
+        }
+        config.registers.clear_flags(Flags::TXC);
+
+        if config.echo_suppression {
+            while config.registers.read_flags().contains(Flags::RXC) {
+                let _echo = unsafe { config.registers.read_data() };
+            }
+            config
+                .registers
+                .clear_status(Status::BUFOVF | Status::FERR | Status::PERR);
+        }
+        true
+    }
+}
+
+#[cfg(feature = "thumbv7")]
+impl<C, D> Uart<C, D>
+where
+    C: ValidConfig,
+    C::Pads: Rs485Pads,
+    D: Transmit,
+{
+    /// Check whether the last transmission is finished
+    ///
+    /// Returns `true` once the last word written, including its stop bits,
+    /// has been sent. The `TXC` flag is then cleared, so `true` is only
+    /// returned once per transmission. If
+    /// [echo suppression](Config::echo_suppression) is enabled, the words
+    /// received in the meantime are discarded.
+    ///
+    /// A DMA transfer completes as soon as its last word is written to the
+    /// `DATA` register, so poll this method before releasing the bus.
+    #[inline]
+    pub fn transmission_finished(&mut self) -> bool {
+        self.finish_transmission()
+    }
+}
+
+/// A [`Uart`] driving the transmit enable of an RS-485 transceiver from a GPIO
+/// pin
+///
+/// The thumbv6m SERCOMs have no hardware transmit enable output. Instead, `TE`
+/// is driven high by [`begin_transmission`](Self::begin_transmission), and
+/// driven low by [`transmission_finished`](Self::transmission_finished) once
+/// the last word is sent. No guard time is inserted.
+#[cfg(feature = "thumbv6")]
+pub struct Rs485Uart<C, D, TE>
+where
+    C: ValidConfig,
+    D: Transmit,
+    TE: OutputPin<Error = Infallible>,
+{
+    uart: Uart<C, D>,
+    te: TE,
+}
+
+#[cfg(feature = "thumbv6")]
+impl<C, D, TE> Rs485Uart<C, D, TE>
+where
+    C: ValidConfig,
+    D: Transmit,
+    TE: OutputPin<Error = Infallible>,
+{
+    /// Create a new [`Rs485Uart`], driving `te` low
+    #[inline]
+    pub fn new(uart: Uart<C, D>, mut te: TE) -> Self {
+        te.set_low().unwrap();
+        Self { uart, te }
+    }
+
+    /// Return the underlying [`Uart`] and `TE` pin
+    #[inline]
+    pub fn free(self) -> (Uart<C, D>, TE) {
+        (self.uart, self.te)
+    }
+
+    /// Drive `TE` high, before writing the words to send
+    #[inline]
+    pub fn begin_transmission(&mut self) {
+        self.te.set_high().unwrap();
+    }
+
+    /// Check whether the last transmission is finished, and drive `TE` low if
+    /// it is
+    ///
+    /// Returns `true` once the last word written, including its stop bits,
+    /// has been sent. The `TXC` flag is then cleared, so `true` is only
+    /// returned once per transmission. If
+    /// [echo suppression](Config::echo_suppression) is enabled, the words
+    /// received in the meantime are discarded.
+    #[inline]
+    pub fn transmission_finished(&mut self) -> bool {
+        let finished = self.uart.finish_transmission();
+        if finished {
+            self.te.set_low().unwrap();
+        }
+        finished
+    }
+
+    /// Send `words`, then wait until the transmission is finished
+    ///
+    /// # Blocking: This method blocks until `TE` is driven low again
+    pub fn write_all(&mut self, words: &[C::Word]) {
+        self.begin_transmission();
+        for word in words {
+            while !self.uart.read_flags().contains(Flags::DRE) {}
+            unsafe { self.uart.write_data(AsPrimitive::<DataReg>::as_(*word)) };
+        }
+        while !self.transmission_finished() {}
+    }
+}
+
+#[cfg(feature = "thumbv6")]
+impl<C, D, TE> AsRef<Uart<C, D>> for Rs485Uart<C, D, TE>
+where
+    C: ValidConfig,
+    D: Transmit,
+    TE: OutputPin<Error = Infallible>,
+{
+    #[inline]
+    fn as_ref(&self) -> &Uart<C, D> {
+        &self.uart
+    }
+}
+
+#[cfg(feature = "thumbv6")]
+impl<C, D, TE> AsMut<Uart<C, D>> for Rs485Uart<C, D, TE>
+where
+    C: ValidConfig,
+    D: Transmit,
+    TE: OutputPin<Error = Infallible>,
+{
+    #[inline]
+    fn as_mut(&mut self) -> &mut Uart<C, D> {
+        &mut self.uart
+    }
+}
@@ -1,6 +1,6 @@
 //! Analogue-to-Digital Conversion
 use crate::clock::GenericClockController;
-use crate::ehal::adc::{Channel, OneShot};
+use crate::ehal_02::adc::{Channel, OneShot};
 use crate::gpio::*;
 use crate::pac::{adc, ADC, PM};
 
@@ -18,6 +18,8 @@ pub use adc::inputctrl::GAIN_A as Gain;
 /// Reference voltage (or its source)
 pub use adc::refctrl::REFSEL_A as Reference;
 
+pub mod temperature;
+
 /// `Adc` encapsulates the device ADC
 pub struct Adc<ADC> {
     adc: ADC,
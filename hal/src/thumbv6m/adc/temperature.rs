@@ -0,0 +1,210 @@
+//! # Die temperature measurement
+//!
+//! The SAMD11/21 have a temperature sensor which is converted by the ADC
+//! through its `TEMP` input. Each device is calibrated in the factory at two
+//! temperatures, and the calibration values are stored in the NVM Temperature
+//! Log Row, see [`Calibration::from_nvm`].
+//!
+//! A [`TemperatureSensor`] enables the sensor, converts it against the
+//! internal 1.0 V reference and applies the calibration to return the
+//! temperature in °C.
+//!
+//! ```no_run
+//! use atsamd_hal::adc::temperature::{Calibration, TemperatureSensor};
+//! # use atsamd_hal::adc::Adc;
+//! # fn example(adc: &mut Adc<atsamd_hal::pac::ADC>) {
+//! # let mut pac = unsafe { atsamd_hal::pac::Peripherals::steal() };
+//! let mut sensor = TemperatureSensor::new(&mut pac.SYSCTRL, Calibration::from_nvm());
+//! let celsius = sensor.read(adc);
+//! # }
+//! ```
+
+use crate::calibration;
+use crate::pac::{adc, ADC, SYSCTRL};
+
+use super::Adc;
+
+//==============================================================================
+// Calibration
+//==============================================================================
+
+/// Factory calibration of the temperature sensor
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Calibration {
+    /// Room calibration temperature, in °C
+    pub room_temp: f32,
+    /// Hot calibration temperature, in °C
+    pub hot_temp: f32,
+    /// Actual voltage of the internal 1.0 V reference at the room temperature,
+    /// in V
+    pub room_int1v: f32,
+    /// Actual voltage of the internal 1.0 V reference at the hot temperature,
+    /// in V
+    pub hot_int1v: f32,
+    /// 12-bit ADC result at the room temperature
+    pub room_adc: u16,
+    /// 12-bit ADC result at the hot temperature
+    pub hot_adc: u16,
+}
+
+impl Calibration {
+    /// Read the calibration from the NVM Temperature Log Row
+    #[inline]
+    pub fn from_nvm() -> Self {
+        Self {
+            room_temp: f32::from(calibration::room_temp_val_int())
+                + decimal(calibration::room_temp_val_dec()),
+            hot_temp: f32::from(calibration::hot_temp_val_int())
+                + decimal(calibration::hot_temp_val_dec()),
+            room_int1v: 1.0 - f32::from(calibration::room_int1v_val()) / 1000.0,
+            hot_int1v: 1.0 - f32::from(calibration::hot_int1v_val()) / 1000.0,
+            room_adc: calibration::room_adc_val(),
+            hot_adc: calibration::hot_adc_val(),
+        }
+    }
+
+    /// Compute the temperature, in °C, from a 12-bit ADC result of the `TEMP`
+    /// input against the internal 1.0 V reference
+    ///
+    /// This is the two-step computation of the "Temperature Sensor
+    /// Characteristics" section of the datasheet. A coarse temperature is
+    /// first computed assuming the reference is exactly 1.0 V, then used to
+    /// interpolate the actual reference voltage, which gives the fine
+    /// temperature.
+    #[inline]
+    pub fn celsius(&self, result: u16) -> f32 {
+        let room_vadc = f32::from(self.room_adc) * self.room_int1v / 4095.0;
+        let hot_vadc = f32::from(self.hot_adc) * self.hot_int1v / 4095.0;
+        let slope = (self.hot_temp - self.room_temp) / (hot_vadc - room_vadc);
+
+        let coarse = self.room_temp + slope * (f32::from(result) / 4095.0 - room_vadc);
+
+        let int1v = self.room_int1v
+            + (self.hot_int1v - self.room_int1v) * (coarse - self.room_temp)
+                / (self.hot_temp - self.room_temp);
+        self.room_temp + slope * (f32::from(result) * int1v / 4095.0 - room_vadc)
+    }
+}
+
+/// Convert the decimal part of a calibration temperature to a fraction
+#[inline]
+fn decimal(val: u8) -> f32 {
+    if val < 10 {
+        f32::from(val) / 10.0
+    } else {
+        f32::from(val) / 100.0
+    }
+}
+
+//==============================================================================
+// TemperatureSensor
+//==============================================================================
+
+/// Calibrated die temperature sensor
+pub struct TemperatureSensor {
+    calibration: Calibration,
+}
+
+impl TemperatureSensor {
+    /// Enable the temperature sensor
+    ///
+    /// This sets `SYSCTRL.VREF.TSEN`. The calibration is usually obtained from
+    /// [`Calibration::from_nvm`].
+    #[inline]
+    pub fn new(sysctrl: &mut SYSCTRL, calibration: Calibration) -> Self {
+        sysctrl.vref.modify(|_, w| w.tsen().set_bit());
+        Self { calibration }
+    }
+
+    /// Factory calibration used by this sensor
+    #[inline]
+    pub fn calibration(&self) -> &Calibration {
+        &self.calibration
+    }
+
+    /// Measure the die temperature, in °C
+    ///
+    /// The sensor is converted against the internal 1.0 V reference with unity
+    /// gain, and the previous reference and gain are restored afterwards. The
+    /// ADC must be configured for 12-bit results.
+    #[inline]
+    pub fn read(&mut self, adc: &mut Adc<ADC>) -> f32 {
+        let regs = &adc.adc;
+        let reference = regs.refctrl.read().refsel().variant();
+        let inputctrl = regs.inputctrl.read().bits();
+
+        regs.refctrl
+            .modify(|_, w| w.refsel().variant(adc::refctrl::REFSEL_A::INT1V));
+        regs.inputctrl.modify(|_, w| {
+            w.muxpos().temp();
+            w.gain()._1x()
+        });
+        while regs.status.read().syncbusy().bit_is_set() {}
+
+        adc.power_up();
+        let result = adc.convert();
+        adc.power_down();
+
+        let regs = &adc.adc;
+        if let Some(reference) = reference {
+            regs.refctrl.modify(|_, w| w.refsel().variant(reference));
+        }
+        regs.inputctrl.write(|w| unsafe { w.bits(inputctrl) });
+        while regs.status.read().syncbusy().bit_is_set() {}
+
+        self.calibration.celsius(result)
+    }
+
+    /// Disable the temperature sensor
+    #[inline]
+    pub fn free(self, sysctrl: &mut SYSCTRL) {
+        sysctrl.vref.modify(|_, w| w.tsen().clear_bit());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32, tolerance: f32) {
+        let error = actual - expected;
+        assert!(
+            error < tolerance && error > -tolerance,
+            "{} is not close to {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn ideal_reference() {
+        let calibration = Calibration {
+            room_temp: 25.2,
+            hot_temp: 83.7,
+            room_int1v: 1.0,
+            hot_int1v: 1.0,
+            room_adc: 2700,
+            hot_adc: 3150,
+        };
+        assert_close(calibration.celsius(2700), 25.2, 0.01);
+        assert_close(calibration.celsius(3150), 83.7, 0.01);
+        assert_close(calibration.celsius(2925), (25.2 + 83.7) / 2.0, 0.01);
+    }
+
+    #[test]
+    fn drifting_reference() {
+        let calibration = Calibration {
+            room_temp: 25.2,
+            hot_temp: 83.7,
+            room_int1v: 1.002,
+            hot_int1v: 0.997,
+            room_adc: 2700,
+            hot_adc: 3150,
+        };
+        // The fine step corrects the reference drift, so that the calibration
+        // points are recovered up to the error of the coarse interpolation
+        assert_close(calibration.celsius(2700), 25.2, 0.1);
+        assert_close(calibration.celsius(3150), 83.7, 0.1);
+    }
+}
@@ -59,3 +59,51 @@ pub fn usb_trim_cal() -> u8 {
     #[cfg(feature = "samd21")]
     return cal_with_errata(4, 23, 7, 7, 3) as u8;
 }
+
+// The Temperature Log Row follows the Software Calibration Area, at 0x806030
+
+/// Integer part of the room temperature at which the temperature sensor was
+/// calibrated, in °C
+pub fn room_temp_val_int() -> u8 {
+    cal(0x10, 0, 0xff) as u8
+}
+
+/// Decimal part of the room temperature calibration point, in 1/10 °C
+pub fn room_temp_val_dec() -> u8 {
+    cal(0x10, 8, 0xf) as u8
+}
+
+/// Integer part of the hot temperature at which the temperature sensor was
+/// calibrated, in °C
+pub fn hot_temp_val_int() -> u8 {
+    cal(0x10, 12, 0xff) as u8
+}
+
+/// Decimal part of the hot temperature calibration point, in 1/10 °C
+pub fn hot_temp_val_dec() -> u8 {
+    cal(0x10, 20, 0xf) as u8
+}
+
+/// Deviation of the internal 1V reference from 1.0 V at the room temperature
+/// calibration point, in mV
+pub fn room_int1v_val() -> i8 {
+    cal(0x10, 24, 0xff) as u8 as i8
+}
+
+/// Deviation of the internal 1V reference from 1.0 V at the hot temperature
+/// calibration point, in mV
+pub fn hot_int1v_val() -> i8 {
+    cal(0x14, 0, 0xff) as u8 as i8
+}
+
+/// 12-bit ADC result of the temperature sensor at the room temperature
+/// calibration point
+pub fn room_adc_val() -> u16 {
+    cal(0x14, 8, 0xfff) as u16
+}
+
+/// 12-bit ADC result of the temperature sensor at the hot temperature
+/// calibration point
+pub fn hot_adc_val() -> u16 {
+    cal(0x14, 20, 0xfff) as u16
+}
@@ -0,0 +1,699 @@
+//! # Version 2 of the `clock` module
+//!
+//! ## Overview
+//!
+//! This module provides a simple, ergonomic, and most of all **safe** API to
+//! create and manage the clock tree in ATSAMD21 and ATSAMD11 devices. It uses
+//! [type-level programming techniques](crate::typelevel) to prevent users from
+//! creating invalid or unsound clocking configurations.
+//!
+//! <p style="background:rgba(255,181,77,0.16);padding:0.75em;">
+//! <strong> Note: </strong> Using a type-level API does place some limitations
+//! on how the clocks can be configured. The types must be checked at
+//! compile-time, which means the state of a given clock must also be known at
+//! compile-time. This is exceedingly reasonable for most clocking
+//! configurations, because most users set up their clocks once and never change
+//! them again. However, if you need to dynamically change the clocking
+//! configuration at run-time, you may find this API less ergonomic.
+//! </p>
+//!
+//! The sections that follow provide an explanation of key concepts in the
+//! module. We highly recommend users read through them to better understand the
+//! `clock` module API. A [complete example](self#getting-started) is also
+//! provided.
+//!
+//! ## Clock safety
+//!
+//! A clock tree represents dependencies among clocks, where producer clocks
+//! feed consumer clocks. Root clocks are the original producers, as they are
+//! derived from oscillators or external clocks. Branch clocks are both
+//! producers and consumers, since they modify and distribute clocks. And leaf
+//! clocks are consumers only; they drive peripherals or external clock outputs
+//! but do not feed other clocks.
+//!
+//! To safely create and use a clock tree, it is critical that producer clocks
+//! not be modified or disabled while their consumer clocks are still in active
+//! use. Stated differently, if clock `B` consumes clock `A`, then clock `A`
+//! **must not** be modified or disabled while clock `B` is still in use.
+//!
+//! Notice that this requirement mimics the principle of "aliased XOR mutable"
+//! underlying the Rust borrow checker. A producer clock can only be modified if
+//! it is not "borrowed" (consumed) by any other clocks.
+//!
+//! The following sections will review the various type-level programming
+//! techniques used to enforce this principle in the `clock` module.
+//!
+//! ## Clock state machines
+//!
+//! Each available clock is represented in Rust as a unique, singleton object.
+//! Users cannot create two instances of the same clock without using `unsafe`.
+//!
+//! However, a given clock is not always represented with the same **type**.
+//! Specifically, each clock has at least two representations, one for the
+//! configured and enabled clock, and another for the unconfigured and disabled
+//! clock.
+//!
+//! These states are represented in Rust using distinct types, forming a
+//! type-level state machine. Moreover, the disabled state is always represented
+//! by a `Token` type. As the name implies, `Token`s have no functionality on
+//! their own; they can only be exchanged for a different type representing
+//! another state.
+//!
+//! ## Clock relationships
+//!
+//! In general, there are two classes of clock in ATSAMD chips. Some clocks map
+//! one-to-one (1:1) to a specific bus or peripheral. This is true for the AHB
+//! clocks ([`AhbClk`]s), APB clocks ([`ApbClk`]s), GCLK outputs ([`GclkOut`]s)
+//! and peripheral channel clocks ([`Pclk`]s). Other clocks form one-to-many
+//! (1:N) relationships, like the external crystal oscillator ([`Xosc`]), the
+//! 48 MHz DFLL ([`Dfll`]) or the 96 MHz DPLL ([`Dpll`]).
+//!
+//! The `clock` module uses a distinct approach for each class.
+//!
+//! ### 1:1 clocks
+//!
+//! One-to-one relationships are easily modelled in Rust using move semantics.
+//! For example, an enabled peripheral channel clock is represented as a
+//! [`Pclk`] object. The respective peripheral API can move the `Pclk` and take
+//! ownership of it. In that case, the `Pclk` acts as proof that the peripheral
+//! clock is enabled, and the transfer of ownership prevents users from
+//! modifying or disabling the `Pclk` while it is in use by the peripheral.
+//!
+//! One-to-one clocks generally have little to no configuration. They are
+//! typically converted directly from disabled `Token` types to fully enabled
+//! clock types. For example, the `Pclk` type has only two methods,
+//! [`Pclk::enable`] and [`Pclk::disable`], which convert [`PclkToken`]s to
+//! `Pclk`s and vice versa.
+//!
+//! ### 1:N clocks
+//!
+//! One-to-many relationships are more difficult to model in Rust.
+//!
+//! As discussed above, we are trying to create something akin to "aliased XOR
+//! mutable", where producer clocks cannot be modified while used by consumer
+//! clocks. A natural approach would be to use the Rust borrow checker directly.
+//! In that case, consumer clocks would hold `&Producer` references to the
+//! `Producer` clock object. The existence of outstanding shared borrows would
+//! naturally prevent users from calling `Producer` methods taking `&mut self`.
+//!
+//! Unfortunately, while this approach could work, there is a critical problem
+//! with disastrous consequences for ergonomics. To satisfy the Rust borrow
+//! checker, `Producer` clock objects *could not be moved* while `&Producer`
+//! references were still held by consumer clocks.
+//!
+//! However, this restriction is unnecessary. A `Producer` clock object is
+//! merely a semantic object representing the "idea" of a producer clock. And
+//! "borrowing" the producer is not meant to protect memory from corruption.
+//! Rather, our goal is only to restrict the `Producer` API, to prevent it from
+//! being modified or disabled once it has been connected to a consumer. We
+//! don't need to permanently hold the `Producer` object in place to do that.
+//!
+//! It is possible to build a `clock` API based on the borrow checker, but it
+//! would be extremely frustrating to use in practice, because of restrictions
+//! on the movement of `Producer` objects.
+//!
+//! Instead, the `clock` module takes a different approach. It uses type-level
+//! programming to track, at compile-time, the number of consumer clocks, N,
+//! fed by a particular producer clock. With this approach, we can move
+//! `Producer` objects while still making them impossible to modify if N > 0.
+//!
+//! The following sections will describe the implementation of this strategy.
+//!
+//! ## Tracking N at compile-time for 1:N clocks
+//!
+//! We have two specific goals. We need to both track the number of consumer
+//! clocks, N, that are actively using a given producer clock. And we need to
+//! restrict the producer clock API when N > 0.
+//!
+//! ### A compile-time counter
+//!
+//! First, we need to develop some way to track the number of consumer clocks,
+//! N, within the type system. To accomplish this, we need both a way to
+//! represent N in the type system and a way to increase or decrease N when
+//! making or breaking connections in the clock tree.
+//!
+//! To represent N, we can use type-level, [`Unsigned`] integers from the
+//! [`typenum`] crate (i.e. [`U0`], [`U1`], etc). And we can use a type
+//! *parameter*, `N`, to represent some unknown, type-level number.
+//!
+//! Next, we need a way to increase or decrease the type parameter `N`. The
+//! [`typenum`] crate provides type aliases [`Add1`] and [`Sub1`] that map from
+//! each [`Unsigned`] integer to its successor and predecessor types,
+//! respectively. We can leverage these to create our own type with a counter
+//! that we [`Increment`] or [`Decrement`] at compile-time. These two traits
+//! form the foundation for our strategy for handling 1:N clocks in this module.
+//!
+//! ### The `Enabled` wrapper
+//!
+//! Our representation of a 1:N producer clock is [`Enabled<T, N>`], which is a
+//! wrapper struct that pairs some *enabled* clock type `T` with a type `N`
+//! representing a consumer count. The wrapper restricts access to the
+//! underlying clock type, `T`, allowing us to selectively define methods when
+//! `N = U0`, that is, when there are no consumers of a given producer clock.
+//!
+//! The [`Enabled`] type itself implements [`Increment`] and [`Decrement`] as
+//! well, which allows type-level transformations to increment or decrement the
+//! counter, e.g. `Enabled<T, U0>` to `Enabled<T, U1>`. Such transformations can
+//! only be performed within the HAL; so users cannot change the consumer count
+//! arbitrarily.
+//!
+//! ### Acting as a clock `Source`
+//!
+//! Finally, we need to define some generic interface for interacting with 1:N
+//! producer clocks. However, when designing this interface, we need to be
+//! careful not to lose information during type-level transformations.
+//!
+//! In particular, the `Enabled` counter type alone is not enough for proper
+//! clock safety. If we used consumer `A` to `Increment` producer `P` from
+//! `Enabled<P, U0>` to `Enabled<P, U1>`, but then used consumer `B` to
+//! `Decrement` the producer back to `Enabled<P, U0>`, we would leave consumer
+//! `A` dangling.
+//!
+//! To solve this problem, we need some way to guarantee that a given consumer
+//! can only `Decrement` the same producer it `Increment`ed. Stated differently,
+//! we need a way to track the identity of each consumer's clock source.
+//!
+//! The [`Source`] trait is designed for this purpose. It marks
+//! [`Enabled<T, N>`] producer clocks, and it's associated type, [`Id`], is the
+//! identity type that should be stored by consumers.
+//!
+//! Given that all implementers of `Source` are instances of `Enabled<T, N>`,
+//! the naïve choice for [`Source::Id`] would be `T`. However, in a moment, we
+//! will see why this choice is not ideal.
+//!
+//! ### `Id` types
+//!
+//! Many of the clock types in this module have additional type parameters that
+//! track the clock's configuration. For instance, [`Xosc<M>`] represents the
+//! external crystal oscillator. Here, the type parameter `M` represents the
+//! XOSC's [`Mode`](xosc::Mode), which can either be [`CrystalMode`] or
+//! [`ClockMode`]. Accordingly, methods to adjust the amplitude loop control,
+//! etc. are only available on `Xosc<CrystalMode>`.
+//!
+//! While these type parameters are important and necessary for configuration of
+//! a given producer clock, they are not relevant to consumer clocks. A consumer
+//! clock does not need to know or care which `Mode` the XOSC is using, but
+//! it *does* need to track that its clock [`Source`] is the XOSC.
+//!
+//! From this, we can see that `Enabled<Xosc<M>, N>` should not implement
+//! `Source` with `Source::Id = Xosc<M>`, because that would require consumers
+//! to needlessly track the XOSC `Mode`.
+//!
+//! Instead, this module defines a series of `Id` types representing the
+//! *identity* of a given clock, rather than the clock itself. This is like the
+//! distinction between a passport and a person. A passport identifies a person,
+//! regardless of changes to their clothes or hair. The `Id` types serve to
+//! erase configuration information, representing only the clock's identity.
+//!
+//! For `Xosc<M>`, the corresponding `Id` type is [`XoscId`]. Thus,
+//! `Enabled<Xosc<M>, N>` implements `Source` with `Source::Id = XoscId`.
+//!
+//! ## Notes on memory safety
+//!
+//! ### Register interfaces
+//!
+//! Although HAL users see `Token` types as merely opaque objects, internally
+//! they serve a dual purpose as the primary register interface to control the
+//! corresponding clock. Moreover, they also fundamentally restructure the way
+//! registers are accessed relative to the [PAC].
+//!
+//! Each of the three PAC clocking structs ([`SYSCTRL`], [`GCLK`] and [`PM`])
+//! is a singleton object that controls a set of MMIO registers. It is
+//! impossible to create two instances of any PAC object without `unsafe`.
+//! However, each object controls a large set of registers that can be further
+//! sub-divided into smaller sets for individual clocks. For example, the
+//! [`GCLK`] object controls registers for up to 9 different clock generators
+//! and up to 37 peripheral channel clocks.
+//!
+//! `Token` types serve to break up the large PAC objects into smaller,
+//! more-targetted pieces. And in the process, they also remove the PAC objects'
+//! [interior mutability]. But this is only possible because each `Token` is
+//! *also* a singleton, and because individual clocks are configured through
+//! *mutually exclusive* sets of registers.
+//!
+//! ### Indirect GCLK registers
+//!
+//! Unlike the SAMD5x and E5x chips, the `GENCTRL`, `GENDIV` and `CLKCTRL`
+//! registers of the SAMD21 and SAMD11 are shared by all generators and
+//! peripheral channels. Each register holds an `ID` field that selects the
+//! generator or channel being accessed, and reading a configuration requires a
+//! write to `ID` first.
+//!
+//! A read/modify/write sequence on these registers could therefore be preempted
+//! by another `Token` selecting a different `ID`. To keep the `Token`s mutually
+//! exclusive, this module never reads them. Instead, each [`GclkToken`] and
+//! [`PclkToken`] keeps a copy of its settings and writes the complete register,
+//! including the `ID` field, in a single 32-bit or 16-bit access.
+//!
+//! ### Bus clocks
+//!
+//! Bus clocks are fundamentally different from the other clock types in this
+//! module, because they do not use mutually exclusive registers for
+//! configuration. For instance, the registers that control the [`Xosc`] are
+//! mutually exclusive to those that control the [`Dpll`], but
+//! `ApbClk<Sercom0>` and `ApbClk<Sercom1>` share a single register.
+//!
+//! This presents a challenge for memory safety, because we need some way to
+//! guarantee that there are no data races. For example, if both
+//! `ApbClk<Sercom0>` and `ApbClk<Sercom1>` tried to modify the `APBCMASK`
+//! register from two different execution contexts, a read/modify/write
+//! operation could be preempted, leading to memory corruption.
+//!
+//! To prevent data races when controlling bus clocks, we introduce two new
+//! types to mediate access to the shared registers. For [`AhbClk`]s, this is
+//! the [`Ahb`] type; and for [`ApbClk`]s, this is the [`Apb`] type. In a sense,
+//! the `Ahb` and `Apb` types represent the actual corresponding buses. Thus,
+//! enabling an APB clock by converting an [`ApbToken`] into an `ApbClk`
+//! requires exclusive access to the `Apb` in the form of `&mut Apb`.
+//!
+//! ## Getting started
+//!
+//! To set up a clock tree, start by trading the [PAC](crate::pac)-level
+//! clocking structs for their HAL equivalents. Right now, the only way to do so
+//! safely is using the [`clock_system_at_reset`] function, which assumes all
+//! clocks are in their default state at power-on reset. If this is not the
+//! case, because, for example, a bootloader has modified the clocks, then you
+//! may need to manually create the matching configuration using `unsafe` code.
+//!
+//! ```no_run
+//! use atsamd_hal::clock::v2::clock_system_at_reset;
+//! use atsamd_hal::pac::Peripherals;
+//! let mut pac = Peripherals::take().unwrap();
+//! let (buses, clocks, tokens) = clock_system_at_reset(
+//!     pac.SYSCTRL,
+//!     pac.GCLK,
+//!     pac.PM,
+//!     &mut pac.NVMCTRL,
+//! );
+//! ```
+//!
+//! At this point, you may notice that the function returned three different
+//! objects, the [`Buses`], [`Clocks`] and [`Tokens`].
+//!
+//! The [`Buses`] struct contains the [`Ahb`] and [`Apb`] objects, which
+//! represent the corresponding AHB and APB buses. See the [notes on memory
+//! safety](self#notes-on-memory-safety) for more details on these types.
+//!
+//! The [`Clocks`] struct contains all of the clocks that are enabled and
+//! running at power-on reset, specifically:
+//! - All of the [`AhbClks`]
+//! - Some of the [`ApbClks`]
+//! - The 8 MHz [`Osc8m`], with its prescaler set to divide by 8, represented as
+//!   `Enabled<Osc8m, U1>`. `N = U1` here because [`Gclk0`] consumes it. See
+//!   [above](self#tracking-n-at-compile-time-for-1n-clocks) for details on
+//!   [`Enabled<T, N>`].
+//! - [`Gclk0`], sourced by the `Osc8m` and represented as
+//!   `Enabled<Gclk0<Osc8mId>, U1>`. Note the use of [`Osc8mId`] as an [`Id`
+//!   type](self#id-types) here. Although `Gclk0` is not consumed by any clock
+//!   represented in this module, it *is* consumed by the processor's main
+//!   clock. We represent this by setting `N = U1`, which we use to restrict the
+//!   available API. Specifically, [`EnabledGclk0`] has special methods not
+//!   available to other [`Gclk`]s.
+//! - The [`OscUlp32k`] clock, which can act as a [`Source`] for the [`Gclk`]s.
+//!   It has no consumers in this module at power-on reset, so it is represented
+//!   as `Enabled<OscUlp32k, U0>`. However, it can never be disabled, so we
+//!   provide no `.disable()` method.
+//!
+//! The [`Tokens`] struct contains all of the available `Token`s, which
+//! [represent clocks that are disabled](self#clock-state-machines) at power-on
+//! reset. Each `Token` can be exchanged for a corresponding clock object.
+//!
+//! ## Example clock tree
+//!
+//! Finally, we will walk through the creation of a simple clock tree to
+//! illustrate some of the remaining concepts inherent to this module.
+//!
+//! Starting from the previous snippet, we have the [`Buses`], [`Clocks`] and
+//! [`Tokens`] to work with, and our clock tree at power-on reset looks like
+//! this.
+//!
+//! ```text
+//! OSC8M (1 MHz)
+//! └── GCLK0 (1 MHz)
+//!     └── Main clock (1 MHz)
+//! ```
+//!
+//! Our goal will be a clock tree that looks like this:
+//!
+//! ```text
+//! XOSC32K (32.768 kHz)
+//! └── GCLK1 (32.768 kHz)
+//!     └── DFLL (48 MHz)
+//!         └── GCLK0 (48 MHz)
+//!             ├── Main clock (48 MHz)
+//!             ├── SERCOM0 peripheral clock
+//!             └── Output to GPIO pin
+//! ```
+//!
+//! We will use an external 32.768 kHz crystal, distributed by GCLK1, as the
+//! reference of the DFLL in closed-loop mode. Then, we will reconfigure GCLK0
+//! to use the 48 MHz DFLL clock instead of the OSC8M.
+//!
+//! First, we create the [`Xosc32k`] from its [`Xosc32kToken`] and the two GPIO
+//! [`Pin`]s of the crystal, and we enable it. The result is an instance of
+//! `Enabled<Xosc32k<CrystalMode>, U0>`. Then, we use it as the [`Source`] of
+//! GCLK1. Because GCLK1 now consumes the XOSC32K, we must [`Increment`] the
+//! [`Enabled`] counter of the XOSC32K, so [`Gclk::from_source`] takes
+//! ownership of it and returns it as `Enabled<Xosc32k<CrystalMode>, U1>`.
+//!
+//! This is the essence of clock safety in this module. Once the counter type
+//! has been incremented to `U1`, the XOSC32K can no longer be modified or
+//! disabled. To modify it, we would first have to disable GCLK1 and
+//! [`Decrement`] the counter back to `U0`.
+//!
+//! ```no_run
+//! use atsamd_hal::{
+//!     clock::v2::{clock_system_at_reset, gclk::Gclk, xosc32k::Xosc32k},
+//!     gpio::Pins,
+//!     pac::Peripherals,
+//! };
+//! let mut pac = Peripherals::take().unwrap();
+//! let (mut buses, clocks, tokens) = clock_system_at_reset(
+//!     pac.SYSCTRL,
+//!     pac.GCLK,
+//!     pac.PM,
+//!     &mut pac.NVMCTRL,
+//! );
+//! let pins = Pins::new(pac.PORT);
+//! let xosc32k = Xosc32k::from_crystal(tokens.xosc32k, pins.pa00, pins.pa01).enable();
+//! while !xosc32k.is_ready() {}
+//! let (gclk1, xosc32k) = Gclk::from_source(tokens.gclks.gclk1, xosc32k);
+//! let gclk1 = gclk1.enable();
+//! ```
+//!
+//! Next, we feed GCLK1 to the peripheral channel clock of the DFLL, and we use
+//! that [`Pclk`] as the reference of the [`Dfll`]. The multiplication factor is
+//! derived from the frequency of the `Pclk`.
+//!
+//! ```no_run
+//! # use atsamd_hal::{
+//! #     clock::v2::{clock_system_at_reset, dfll::Dfll, gclk::Gclk, pclk::Pclk, xosc32k::Xosc32k},
+//! #     gpio::Pins,
+//! #     pac::Peripherals,
+//! # };
+//! # let mut pac = Peripherals::take().unwrap();
+//! # let (mut buses, clocks, tokens) = clock_system_at_reset(
+//! #     pac.SYSCTRL,
+//! #     pac.GCLK,
+//! #     pac.PM,
+//! #     &mut pac.NVMCTRL,
+//! # );
+//! # let pins = Pins::new(pac.PORT);
+//! # let xosc32k = Xosc32k::from_crystal(tokens.xosc32k, pins.pa00, pins.pa01).enable();
+//! # let (gclk1, xosc32k) = Gclk::from_source(tokens.gclks.gclk1, xosc32k);
+//! # let gclk1 = gclk1.enable();
+//! let (pclk_dfll, gclk1) = Pclk::enable(tokens.pclks.dfll, gclk1);
+//! let dfll = Dfll::from_pclk(tokens.dfll, pclk_dfll).enable();
+//! while !dfll.is_locked() {}
+//! ```
+//!
+//! Our next task will be to swap GCLK0 from the OSC8M to the 48 MHz DFLL. To
+//! do that, we will use the special [`swap_sources`] method on
+//! [`EnabledGclk0`] to change the base clock without disabling GCLK0 or the
+//! main clock. This time we will be modifying two [`Enabled`] counters
+//! simultaneously. We will [`Decrement`] the [`Osc8m`] count from `U1` to
+//! `U0`, and we will [`Increment`] the [`EnabledDfll`] count from `U0` to
+//! `U1`. At this point, the OSC8M is completely unused, so it can be disabled.
+//!
+//! Note that [`clock_system_at_reset`] already configured the flash wait states
+//! for a 48 MHz main clock.
+//!
+//! ```no_run
+//! # use atsamd_hal::{
+//! #     clock::v2::{clock_system_at_reset, dfll::Dfll, gclk::Gclk, pclk::Pclk, xosc32k::Xosc32k},
+//! #     gpio::Pins,
+//! #     pac::Peripherals,
+//! # };
+//! # let mut pac = Peripherals::take().unwrap();
+//! # let (mut buses, clocks, tokens) = clock_system_at_reset(
+//! #     pac.SYSCTRL,
+//! #     pac.GCLK,
+//! #     pac.PM,
+//! #     &mut pac.NVMCTRL,
+//! # );
+//! # let pins = Pins::new(pac.PORT);
+//! # let xosc32k = Xosc32k::from_crystal(tokens.xosc32k, pins.pa00, pins.pa01).enable();
+//! # let (gclk1, xosc32k) = Gclk::from_source(tokens.gclks.gclk1, xosc32k);
+//! # let gclk1 = gclk1.enable();
+//! # let (pclk_dfll, gclk1) = Pclk::enable(tokens.pclks.dfll, gclk1);
+//! # let dfll = Dfll::from_pclk(tokens.dfll, pclk_dfll).enable();
+//! let (gclk0, osc8m, dfll) = clocks.gclk0.swap_sources(clocks.osc8m, dfll);
+//! let osc8m = osc8m.disable();
+//! ```
+//!
+//! Finally, we enable the APB clock and the peripheral channel clock of
+//! SERCOM0, and we output GCLK0 to a GPIO pin. APB clocks are [1:1
+//! clocks](self#clock-relationships), so the [`ApbClk`] is not wrapped with
+//! [`Enabled`]. On the other hand, both the [`Pclk`] and the [`GclkOut`]
+//! [`Increment`] the consumer count of [`EnabledGclk0`].
+//!
+//! Putting the whole example together, we get
+//!
+//! ```no_run
+//! use atsamd_hal::{
+//!     clock::v2::{clock_system_at_reset, dfll::Dfll, gclk::Gclk, pclk::Pclk, xosc32k::Xosc32k},
+//!     gpio::Pins,
+//!     pac::Peripherals,
+//! };
+//!
+//! let mut pac = Peripherals::take().unwrap();
+//! let (mut buses, clocks, tokens) = clock_system_at_reset(
+//!     pac.SYSCTRL,
+//!     pac.GCLK,
+//!     pac.PM,
+//!     &mut pac.NVMCTRL,
+//! );
+//! let pins = Pins::new(pac.PORT);
+//! let xosc32k = Xosc32k::from_crystal(tokens.xosc32k, pins.pa00, pins.pa01).enable();
+//! while !xosc32k.is_ready() {}
+//! let (gclk1, xosc32k) = Gclk::from_source(tokens.gclks.gclk1, xosc32k);
+//! let gclk1 = gclk1.enable();
+//! let (pclk_dfll, gclk1) = Pclk::enable(tokens.pclks.dfll, gclk1);
+//! let dfll = Dfll::from_pclk(tokens.dfll, pclk_dfll).enable();
+//! while !dfll.is_locked() {}
+//! let (gclk0, osc8m, dfll) = clocks.gclk0.swap_sources(clocks.osc8m, dfll);
+//! let osc8m = osc8m.disable();
+//! let apb_sercom0 = buses.apb.enable(tokens.apbs.sercom0);
+//! let (pclk_sercom0, gclk0) = Pclk::enable(tokens.pclks.sercom0, gclk0);
+//! let (gclk0, gclk0_out) = gclk0.enable_gclk_out(pins.pa14);
+//! ```
+//!
+//! [PAC]: crate::pac
+//! [`SYSCTRL`]: crate::pac::SYSCTRL
+//! [`GCLK`]: crate::pac::GCLK
+//! [`PM`]: crate::pac::PM
+//!
+//! [`Ahb`]: ahb::Ahb
+//! [`AhbClk`]: ahb::AhbClk
+//! [`AhbClks`]: ahb::AhbClks
+//!
+//! [`Apb`]: apb::Apb
+//! [`ApbClk`]: apb::ApbClk
+//! [`ApbClks`]: apb::ApbClks
+//! [`ApbToken`]: apb::ApbToken
+//!
+//! [`Dfll`]: dfll::Dfll
+//! [`EnabledDfll`]: dfll::EnabledDfll
+//!
+//! [`Dpll`]: dpll::Dpll
+//!
+//! [`Gclk`]: gclk::Gclk
+//! [`Gclk::from_source`]: gclk::Gclk::from_source
+//! [`Gclk0`]: gclk::Gclk0
+//! [`GclkToken`]: gclk::GclkToken
+//! [`EnabledGclk0`]: gclk::EnabledGclk0
+//! [`swap_sources`]: gclk::EnabledGclk0::swap_sources
+//! [`GclkOut`]: gclk::GclkOut
+//!
+//! [`Osc8m`]: osc8m::Osc8m
+//! [`Osc8mId`]: osc8m::Osc8mId
+//!
+//! [`OscUlp32k`]: osculp32k::OscUlp32k
+//!
+//! [`Pclk`]: pclk::Pclk
+//! [`PclkToken`]: pclk::PclkToken
+//!
+//! [`Xosc`]: xosc::Xosc
+//! [`Xosc<M>`]: xosc::Xosc
+//! [`XoscId`]: xosc::XoscId
+//! [`CrystalMode`]: xosc::CrystalMode
+//! [`ClockMode`]: xosc::ClockMode
+//!
+//! [`Xosc32k`]: xosc32k::Xosc32k
+//! [`Xosc32kToken`]: xosc32k::Xosc32kToken
+//!
+//! [`Increment`]: crate::typelevel::Increment
+//! [`Decrement`]: crate::typelevel::Decrement
+//!
+//! [`Id`]: Source::Id
+//!
+//! [`Pin`]: crate::gpio::Pin
+//!
+//! [`U1`]: typenum::U1
+//! [`Add1`]: typenum::Add1
+//! [`Sub1`]: typenum::Sub1
+//! [`Unsigned`]: typenum::Unsigned
+//!
+//! [interior mutability]: https://doc.rust-lang.org/reference/interior-mutability.html
+
+use typenum::U0;
+
+use crate::time::Hertz;
+use crate::typelevel::{PrivateDecrement, PrivateIncrement, Sealed};
+
+pub mod ahb;
+pub mod apb;
+pub mod dfll;
+pub mod dpll;
+pub mod gclk;
+pub mod osc32k;
+pub mod osc8m;
+pub mod osculp32k;
+pub mod pclk;
+pub mod types;
+pub mod xosc;
+#[cfg(all(feature = "has-pa00", feature = "has-pa01"))]
+pub mod xosc32k;
+
+mod reset;
+pub use reset::*;
+
+// `Token` types and memory safety
+//
+// Each of the PAC [`Peripherals`] is a zero-sized, singleton struct that
+// mediates access to the MMIO hardware registers. It is not possible to create
+// two instances of any peripheral without causing a run-time panic. These
+// structs implement [`Deref`] by conjuring a pointer to the corresponding
+// register block, and each register within the block is represented by a
+// `vcell::VolatileCell`. Because each register is wrapped in a `VolatileCell`,
+// it is safe to both read and write them through shared references. However,
+// because a read/modify/write operation is not atomic, the [`Peripherals`]
+// structs do not implement [`Sync`].
+//
+// This is a reasonable approach for the PAC, since it is generated from an
+// SVD file. However, it is not the ideal structure for our HAL API. In
+// particular, each [`Peripherals`] struct represents an entire peripheral,
+// rather than a particular functional unit. In the HAL, we want our API to
+// focus on functional units, so we need to define our own abstraction for
+// registers, which will involve `unsafe` code.
+//
+// In the `clock` module, we represent each functional unit with a
+// corresponding `Token` type. Just like the [`Peripherals`], each `Token` type
+// is meant to be a singleton. However, unlike the PAC, we do not have to
+// allow users to create `Token`s directly. Instead, we can have users exchange
+// [`Peripherals`] for the `Token`s. Because each PAC struct is a singleton, we
+// can guarantee each `Token` will be a singleton as well. With this approach,
+// we don't need to implement our own run-time panicking; we simply extend the
+// existing guarantees of the PAC.
+//
+// To implement a memory safe API, we must ensure that all `Token` types access
+// mutually exclusive sets of registers. In that way, we guarantee no two
+// `Token` types can access the same register. Moreover, in contrast to the PAC
+// [`Peripherals`], we can make our `Token`s [`Sync`] if we remove all interior
+// mutability and guarantee that writing or modifying a register requires
+// ownership or an `&mut` reference.
+//
+// Thus, our `Token`-based API should be memory safe if we always obey the
+// following requirements:
+//   - It should be `unsafe` to create a `Token` type unless it is created in
+//     exchange for the corresponding PAC peripheral struct.
+//   - Each `Token` type should have access to a mutually exclusive set of
+//     registers relative to the other `Token`s.
+//   - Writing or modifying a register should always require ownership of, or an
+//     `&mut` reference to, the corresponding `Token`.
+//   - When conjuring references to PAC registers or register blocks, we should
+//     *only* use shared, `&` references. There is no need to use exclusive,
+//     `&mut` references, because each register is wrapped in a `VolatileCell`.
+//     Moreover, using `&mut` references could cause UB, if we accidentally
+//     create two simultaneous references to the same register block from
+//     different `Tokens`.
+//
+// [`Peripherals`]: crate::pac::Peripherals
+// [`Deref`]: core::ops::Deref
+
+/// Marks [`Enabled`] 1:N producer clocks that can act as a clock source
+///
+/// Implementers of this type act as producer clocks and feed consumer clocks in
+/// the clock tree. All implementors are [`Enabled`], 1:N clocks. The `Id`
+/// associated type maps to the corresponding [`Id` type](self#id-types) of the
+/// implementer.
+///
+/// See the documentation on [`Source` clocks](self#acting-as-a-clock-source)
+/// for more details.
+pub trait Source: Sealed {
+    /// Corresponding `Id` type for the implementer
+    ///
+    /// A given implementer of [`Source`] might have type parameters
+    /// representing its configuration. For instance, [`EnabledXosc<M>`] has a
+    /// type parameter to track its [`Mode`]. However, a consumer clock
+    /// typically does not care about such configuration. It only needs to
+    /// know *which* upstream clock is its [`Source`].
+    ///
+    /// `Id` types exist to fill this role. They represent the *identity* of a
+    /// given clock, regardless of any configuration. This is like the
+    /// distinction between a passport and a person. A passport identifies a
+    /// person, regardless of changes to their clothes or hair.
+    ///
+    /// Thus, [`EnabledXosc<M>`] implements [`Source`] with `Id = `[`XoscId`],
+    /// regardless of `M`.
+    ///
+    /// See the documentation on [`Id` types](self#id-types) for more details.
+    ///
+    /// [`EnabledXosc<M>`]: xosc::EnabledXosc
+    /// [`Mode`]: xosc::Mode
+    /// [`XoscId`]: xosc::XoscId
+    type Id;
+
+    /// Return the frequency of the clock source
+    fn freq(&self) -> Hertz;
+}
+
+/// An enabled, 1:N clock with a compile-time counter for N
+///
+/// This struct is a wrapper around other clock types from this module. It
+/// represents a clock, `T`, that has been enabled, and it maintains a
+/// compile-time counter, `N`, of its consumer clocks in the clock tree.
+///
+/// Compile-time counting allows the API to restrict when clocks may be modified
+/// or disabled. For example, `Enabled` clocks can only be disabled when their
+/// counter is [`U0`].
+///
+/// The type-level counter is implemented using [`Unsigned`] integers from
+/// the [`typenum`] crate, and it is modified using the [`Increment`] and
+/// [`Decrement`] traits.
+///
+/// See the [`Enabled` wrapper documentation](self#the-enabled-wrapper) for more
+/// details.
+///
+/// [`EnabledGclk0`]: gclk::EnabledGclk0
+/// [`Increment`]: crate::typelevel::Increment
+/// [`Decrement`]: crate::typelevel::Decrement
+/// [`Unsigned`]: typenum::Unsigned
+pub struct Enabled<T, N = U0>(pub(crate) T, N);
+
+impl<T, N> Sealed for Enabled<T, N> {}
+
+impl<T, N: Default> Enabled<T, N> {
+    #[inline]
+    pub(crate) fn new(t: T) -> Self {
+        Enabled(t, N::default())
+    }
+}
+
+impl<T, N: PrivateIncrement> PrivateIncrement for Enabled<T, N> {
+    type Inc = Enabled<T, N::Inc>;
+
+    #[inline]
+    fn inc(self) -> Self::Inc {
+        Enabled(self.0, self.1.inc())
+    }
+}
+
+impl<T, N: PrivateDecrement> PrivateDecrement for Enabled<T, N> {
+    type Dec = Enabled<T, N::Dec>;
+
+    #[inline]
+    fn dec(self) -> Self::Dec {
+        Enabled(self.0, self.1.dec())
+    }
+}
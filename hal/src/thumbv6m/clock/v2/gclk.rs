@@ -0,0 +1,1395 @@
+//! # Generic Clock Controllers
+//!
+//! ## Overview
+//!
+//! The generic clock controller is central to the clocking system in ATSAMD
+//! chips. On the ATSAMD21, it provides nine generic clock generators to modify
+//! and distribute clocks to other peripherals. The ATSAMD11 provides six.
+//! Within the clock tree, these clock generators act as the branch clocks,
+//! connecting internal or external root or branch clocks to other branch or
+//! leaf clocks.
+//!
+//! Each clock generator takes an input clock, optionally divides it, and
+//! produces an output clock. The input clock can be:
+//!
+//! - A GPIO input ([`Pin`])
+//! - The internal 8 MHz oscillator ([`Osc8m`])
+//! - The internal 32 kHz oscillator ([`Osc32k`])
+//! - The ultra-low power 32 kHz oscillator ([`OscUlp32k`])
+//! - An external crystal oscillator ([`Xosc`])
+//! - An external 32 kHz oscillator ([`Xosc32k`])
+//! - The 48 MHz DFLL ([`Dfll`])
+//! - The 96 MHz DPLL ([`Dpll`])
+//! - Generic clock generator #1 ([`Gclk1`])
+//!
+//! The output clock can be:
+//! - A peripheral channel clock ([`Pclk`])
+//! - A GPIO pin ([`GclkOut`])
+//!
+//! ## Example
+//!
+//! The configuration of a [`Gclk`] is best shown with an example. However, the
+//! example assumes you are already familiar with the basics of the `clock`
+//! module. See the [`clock` module documentation](super) for an overview.
+//!
+//! Suppose we start with the default clock tree after power-on reset.
+//!
+//! ```text
+//! OSC8M (1 MHz)
+//! └── GCLK0 (1 MHz)
+//!     └── Main clock (1 MHz)
+//! ```
+//!
+//! We would like to add the following branch to it:
+//!
+//! ```text
+//! GCLK_IN1 (PA15, 16 MHz)
+//! └── GCLK1 (8 MHz)
+//!     ├── SERCOM0
+//!     └── GCLK2 (2 MHz)
+//!         ├── SERCOM1
+//!         └── GCLK_OUT2 (PA16, 2 MHz)
+//! ```
+//!
+//! We start by using [`clock_system_at_reset`] to access the HAL clocking
+//! structs, and we take the [`gpio`] [`Pins`]. Next, we use
+//! [`Gclk::from_pin`] to create an instance of [`Gclk1`] from a [`GclkToken`],
+//! a GPIO [`Pin`] and its frequency, in [`Hertz`].
+//!
+//! [`Gclk<G, I>`] takes two type parameters. `G` is a [`GclkId`] identifying
+//! which of the generators this `Gclk` represents. `I` is an
+//! [`Id` type](super#id-types) identifying the input clock, which must be a
+//! valid [`GclkSourceId`]. In this case, `I` is [`PA15`](gpio::PA15), which
+//! implements [`GclkIo`] with [`GclkIo::GclkId`]` = Gclk1Id`.
+//!
+//! Before enabling [`Gclk1`], we set its divider to reduce the 16 MHz input to
+//! an 8 MHz output. [`Gclk1`] takes a [`GclkDiv16`], while [`Gclk2`] takes a
+//! [`GclkDiv5`]. All other [`Gclk`]s take a [`GclkDiv8`]. The three enums
+//! are connected by the [`GclkDivider`] trait.
+//!
+//! [`Gclk1`] is the only [`Gclk`] that can act as a [`Source`] for another
+//! `Gclk`. Below, we use it to drive both the [`Pclk`] for [`Sercom0`] and
+//! [`Gclk2`]. Finally, we output [`Gclk2`] directly to a GPIO pin, which
+//! yields a [`GclkOut`].
+//!
+//! ```no_run
+//! use atsamd_hal::{
+//!     clock::v2::{
+//!         clock_system_at_reset,
+//!         gclk::{Gclk, GclkDiv16, GclkDiv5},
+//!         pclk::Pclk,
+//!     },
+//!     gpio::Pins,
+//!     pac::Peripherals,
+//!     time::U32Ext,
+//! };
+//! let mut pac = Peripherals::take().unwrap();
+//! let (mut buses, clocks, tokens) = clock_system_at_reset(
+//!     pac.SYSCTRL,
+//!     pac.GCLK,
+//!     pac.PM,
+//!     &mut pac.NVMCTRL,
+//! );
+//! let pins = Pins::new(pac.PORT);
+//! let gclk1 = Gclk::from_pin(tokens.gclks.gclk1, pins.pa15, 16.mhz());
+//! let gclk1 = gclk1.div(GclkDiv16::Div(2)).enable();
+//! let (pclk_sercom0, gclk1) = Pclk::enable(tokens.pclks.sercom0, gclk1);
+//! let (gclk2, gclk1) = Gclk::from_source(tokens.gclks.gclk2, gclk1);
+//! let gclk2 = gclk2.div(GclkDiv5::Div(4)).enable();
+//! let (pclk_sercom1, gclk2) = Pclk::enable(tokens.pclks.sercom1, gclk2);
+//! let (gclk2, gclk2_out) = gclk2.enable_gclk_out(pins.pa16);
+//! ```
+//!
+//! ## Indirect registers
+//!
+//! Unlike the ATSAMD51, the `GENCTRL` and `GENDIV` registers of the ATSAMD21
+//! are shared by all generators. Each write selects the target generator with
+//! its `ID` field, while a read must be preceded by a separate write of the
+//! `ID`. To keep each [`GclkToken`] independent of the others, this module
+//! never reads these registers. Instead, the complete configuration is stored
+//! in the [`Gclk`] and written in full each time it changes.
+//!
+//! ## `Gclk0`
+//!
+//! [`Gclk0`] is significant and special relative to the other [`Gclk`]s. It is
+//! the clock generator for the processor's main clock, so it can never be
+//! disabled. Consequently, it has a special API not available to the other
+//! `Gclk`s. While normal `Gclk`s can only change their clock [`Source`] or
+//! divider while disabled, `Gclk0` can never be disabled, so we provide this
+//! functionality on [`EnabledGclk0`] instead.
+//!
+//! We model the main clock's consumption of `Gclk0` by setting its [`Enabled`]
+//! counter to [`U1`] in [`clock_system_at_reset`]. This prevents users from
+//! ever disabling `EnabledGclk0`, because there is no way to [`Decrement`] its
+//! `Counter` to [`U0`].
+//!
+//! Additionally, we provide functions to change the clock `Source`, divider,
+//! etc. on `EnabledGclk0`, but we restrict them to the case where `N = U1`.
+//! This prevents users from changing its `Source` or divider if any *other,
+//! additional* clock consumes it (besides the main clock).
+//!
+//! ## ATSAMD11
+//!
+//! The ATSAMD11 supports the same API, with generators [`Gclk0`] through
+//! [`Gclk5`]. However, no [`GclkIo`] pins are currently mapped for it, so
+//! [`Gclk::from_pin`] and [`GclkOut`] are only usable on the ATSAMD21.
+//!
+//! [`clock_system_at_reset`]: super::clock_system_at_reset
+//! [`Osc8m`]: super::osc8m::Osc8m
+//! [`Osc32k`]: super::osc32k::Osc32k
+//! [`OscUlp32k`]: super::osculp32k::OscUlp32k
+//! [`Xosc`]: super::xosc::Xosc
+//! [`Xosc32k`]: super::xosc32k::Xosc32k
+//! [`Dfll`]: super::dfll::Dfll
+//! [`Dpll`]: super::dpll::Dpll
+//! [`Pclk`]: super::pclk::Pclk
+//! [`Pins`]: crate::gpio::Pins
+//! [`Sercom0`]: crate::sercom::Sercom0
+
+use core::cmp::{max, min};
+use core::marker::PhantomData;
+
+use paste::paste;
+use typenum::{U0, U1};
+
+use crate::pac;
+use crate::pac::gclk::genctrl::SRC_A;
+use crate::pac::NVMCTRL;
+
+use crate::gpio::{self, AlternateH, AnyPin, Pin, PinId};
+use crate::time::Hertz;
+use crate::typelevel::{Decrement, Increment, PrivateDecrement, PrivateIncrement, Sealed};
+
+use super::dfll::DfllId;
+use super::dpll::DpllId;
+use super::osc32k::Osc32kId;
+use super::osc8m::Osc8mId;
+use super::osculp32k::OscUlp32kId;
+use super::xosc::XoscId;
+#[cfg(all(feature = "has-pa00", feature = "has-pa01"))]
+use super::xosc32k::Xosc32kId;
+use super::{Enabled, Source};
+
+//==============================================================================
+// GclkToken
+//==============================================================================
+
+/// Singleton token that can be exchanged for a [`Gclk`]
+///
+/// As explained in the [`clock` module documentation](super), instances of
+/// various `Token` types can be exchanged for actual clock types. They
+/// typically represent clocks that are disabled at power-on reset.
+///
+/// [`GclkToken`]s are no different. All [`Gclk`]s other than [`Gclk0`] are
+/// disabled at power-on reset. To use a [`Gclk`], you must first exchange the
+/// token for an actual clock with [`Gclk::from_source`] or [`Gclk::from_pin`].
+///
+/// [`GclkToken`] is generic over the [`GclkId`], where each corresponding token
+/// represents one of the respective [`Gclk`]s.
+pub struct GclkToken<G: GclkId> {
+    gen: PhantomData<G>,
+}
+
+impl<G: GclkId> GclkToken<G> {
+    /// Create a new instance of [`GclkToken`]
+    ///
+    /// # Safety
+    ///
+    /// Each `GclkToken`s is a singleton. There must never be two simulatenous
+    /// instances with the same [`GclkId`]. See the notes on `Token` types and
+    /// memory safety in the root of the `clock` module for more details.
+    #[inline]
+    pub(super) unsafe fn new() -> Self {
+        GclkToken { gen: PhantomData }
+    }
+
+    /// Provide a reference to the `GCLK` register block
+    #[inline]
+    fn gclk(&self) -> &pac::gclk::RegisterBlock {
+        // Safety: The `GENCTRL` and `GENDIV` registers are indirect. Each write
+        // selects the target generator with its `ID` field, so a single write
+        // only ever touches the registers of this particular `GclkId`. We never
+        // read these registers, because that would require a separate write of
+        // the `ID` field, which could race with other `GclkToken`s. See the
+        // notes on `Token` types and memory safety in the root of the `clock`
+        // module for more details.
+        unsafe { &*pac::GCLK::ptr() }
+    }
+
+    /// Block until synchronization has completed
+    ///
+    /// Writes to the `GENCTRL` and `GENDIV` registers must be synchronized to
+    /// the generator clock domain. See the datasheet for more details.
+    #[inline]
+    fn wait_syncbusy(&self) {
+        while self.gclk().status.read().syncbusy().bit_is_set() {}
+    }
+
+    /// Write the complete configuration of this [`Gclk`]
+    ///
+    /// Because the registers are indirect, every change rewrites both the
+    /// `GENDIV` and `GENCTRL` registers for this generator.
+    #[inline]
+    fn configure(&mut self, id: DynGclkSourceId, settings: Settings<G>, enable: bool) {
+        let (divsel, div) = settings.div.divsel_div();
+        // Safety: The `ID` is a valid generator index, and the `DIVSEL` and
+        // `DIV` values are derived from the `GclkDivider` type, so they are
+        // guaranteed to be valid.
+        self.gclk().gendiv.write(|w| unsafe {
+            w.id().bits(G::NUM as u8);
+            w.div().bits(div)
+        });
+        self.wait_syncbusy();
+        self.gclk().genctrl.write(|w| {
+            // Safety: The `ID` is a valid generator index
+            unsafe { w.id().bits(G::NUM as u8) };
+            w.src().variant(id.into());
+            w.genen().bit(enable);
+            w.idc().bit(settings.improve_duty_cycle);
+            w.oov().bit(settings.output_off_value);
+            w.oe().bit(settings.output_enable);
+            w.divsel().bit(divsel);
+            w.runstdby().bit(settings.run_standby)
+        });
+        self.wait_syncbusy();
+    }
+}
+
+//==============================================================================
+// DynGclkId
+//==============================================================================
+
+/// Value-level enum identifying one of the possible [`Gclk`]s
+///
+/// The variants of this enum identify one of the generic clock generators.
+/// The ATSAMD21 has nine generators, while the ATSAMD11 has six.
+///
+/// `DynGclkId` is the value-level equivalent of [`GclkId`].
+pub enum DynGclkId {
+    Gclk0,
+    Gclk1,
+    Gclk2,
+    Gclk3,
+    Gclk4,
+    Gclk5,
+    #[cfg(feature = "samd21")]
+    Gclk6,
+    #[cfg(feature = "samd21")]
+    Gclk7,
+    #[cfg(feature = "samd21")]
+    Gclk8,
+}
+
+//==============================================================================
+// GclkId
+//==============================================================================
+
+/// Type-level enum identifying one of the possible [`Gclk`]s
+///
+/// The types implementing this trait, i.e. [`Gclk0Id`] - `Gclk8Id` on the
+/// ATSAMD21 and [`Gclk0Id`] - [`Gclk5Id`] on the ATSAMD11, are type-level
+/// variants of `GclkId`, and they identify one of the generic clock
+/// generators.
+///
+/// `GclkId` is the type-level equivalent of [`DynGclkId`]. See the
+/// documentation on [type-level programming] and specifically
+/// [type-level enums] for more details.
+///
+/// [type-level programming]: crate::typelevel
+/// [type-level enums]: crate::typelevel#type-level-enums
+pub trait GclkId: Sealed {
+    /// Corresponding variant of [`DynGclkId`]
+    const DYN: DynGclkId;
+    /// Corresponding numeric index
+    const NUM: usize;
+    /// Corresponding [`GclkDivider`] type
+    ///
+    /// [`Gclk1`] uses [`GclkDiv16`] and [`Gclk2`] uses [`GclkDiv5`], while all
+    /// other [`Gclk`]s use [`GclkDiv8`].
+    type Divider: GclkDivider;
+}
+
+/// Type-level variant of [`GclkId`] representing the identity of GCLK0
+///
+/// See the documentation on [type-level programming] and specifically
+/// [type-level enums] for more details.
+///
+/// [type-level programming]: crate::typelevel
+/// [type-level enums]: crate::typelevel#type-level-enums
+pub enum Gclk0Id {}
+impl Sealed for Gclk0Id {}
+impl GclkId for Gclk0Id {
+    const DYN: DynGclkId = DynGclkId::Gclk0;
+    const NUM: usize = 0;
+    type Divider = GclkDiv8;
+}
+
+/// Type-level variant of [`GclkId`] representing the identity of GCLK1
+///
+/// See the documentation on [type-level programming] and specifically
+/// [type-level enums] for more details.
+///
+/// [type-level programming]: crate::typelevel
+/// [type-level enums]: crate::typelevel#type-level-enums
+pub enum Gclk1Id {}
+impl Sealed for Gclk1Id {}
+impl GclkId for Gclk1Id {
+    const DYN: DynGclkId = DynGclkId::Gclk1;
+    const NUM: usize = 1;
+    type Divider = GclkDiv16;
+}
+
+/// Type-level variant of [`GclkId`] representing the identity of GCLK2
+///
+/// See the documentation on [type-level programming] and specifically
+/// [type-level enums] for more details.
+///
+/// [type-level programming]: crate::typelevel
+/// [type-level enums]: crate::typelevel#type-level-enums
+pub enum Gclk2Id {}
+impl Sealed for Gclk2Id {}
+impl GclkId for Gclk2Id {
+    const DYN: DynGclkId = DynGclkId::Gclk2;
+    const NUM: usize = 2;
+    type Divider = GclkDiv5;
+}
+
+macro_rules! gclk_ids {
+    ($($N:literal),+) => {
+        paste! {
+            $(
+                /// Type-level variant of [`GclkId`] representing the identity of
+                #[doc = "GCLK" $N]
+                ///
+                /// See the documentation on [type-level programming] and
+                /// specifically [type-level enums] for more details.
+                ///
+                /// [type-level programming]: crate::typelevel
+                /// [type-level enums]: crate::typelevel#type-level-enums
+                pub enum [<Gclk $N Id>] {}
+                impl Sealed for [<Gclk $N Id>] {}
+                impl GclkId for [<Gclk $N Id>] {
+                    const DYN: DynGclkId = DynGclkId::[<Gclk $N>];
+                    const NUM: usize = $N;
+                    type Divider = GclkDiv8;
+                }
+            )+
+        }
+    };
+}
+
+gclk_ids!(3, 4, 5);
+#[cfg(feature = "samd21")]
+gclk_ids!(6, 7, 8);
+
+//==============================================================================
+// GclkDivider
+//==============================================================================
+
+/// Trait unifying the three [`Gclk`] divider types, [`GclkDiv8`],
+/// [`GclkDiv16`] and [`GclkDiv5`]
+///
+/// Choosing a [`Gclk`] division factor can be complicated. [`Gclk1`] can accept
+/// a 16-bit divider value and [`Gclk2`] only a 5-bit value, while all other
+/// [`Gclk`]s take an 8-bit value. Moreover, the set of valid clock dividers
+/// does not form a contiguous range. For example, the valid set of dividers for
+/// most [`Gclk`]s is 1-256 and 512.
+///
+/// The divider enums provide simple and intuitive user-facing interfaces to
+/// choose the *actual* clock divider value. This trait, on the other hand,
+/// provides an internal-facing interface used by HAL authors to extract the
+/// clock divider and convert it to the corresponding `DIVSEL` and `DIV`
+/// register fields. Users should have no reason to interact with this trait
+/// directly.
+pub trait GclkDivider: Sealed + Default + Copy {
+    /// Returns the actual clock divider value as a `u32`
+    fn divider(&self) -> u32;
+    /// Return the corresponding `DIVSEL` and and `DIV` register fields
+    fn divsel_div(&self) -> (bool, u16);
+}
+
+//==============================================================================
+// GclkDiv8
+//==============================================================================
+
+/// Enum for the clock division factor of all [`Gclk`]s other than [`Gclk1`]
+/// and [`Gclk2`]
+///
+/// Choosing a [`Gclk`] division factor can be complicated, because the set of
+/// valid values is not contiguous. For these clocks, the division factor can be
+/// 1-256 or 512. `GclkDiv8` provides an enum interface to enforce validity of
+/// the division factor. See the datasheet for more details.
+#[derive(Clone, Copy)]
+pub enum GclkDiv8 {
+    /// Use a literal division factor
+    ///
+    /// All values in the range `[1-255]` are valid. Zero is also valid, but it
+    /// is interpreted as `1`.
+    Div(u8),
+    /// Use a division factor of `2^8 = 256`
+    Div2Pow8,
+    /// Use a division factor of `2^9 = 512`
+    Div2Pow9,
+}
+
+impl Sealed for GclkDiv8 {}
+
+impl Default for GclkDiv8 {
+    #[inline]
+    fn default() -> Self {
+        Self::Div(0)
+    }
+}
+
+impl GclkDivider for GclkDiv8 {
+    #[inline]
+    fn divider(&self) -> u32 {
+        match self {
+            GclkDiv8::Div(div) => (*div).into(),
+            GclkDiv8::Div2Pow8 => 256,
+            GclkDiv8::Div2Pow9 => 512,
+        }
+    }
+
+    #[inline]
+    fn divsel_div(&self) -> (bool, u16) {
+        match self {
+            GclkDiv8::Div(div) => (false, (*div).into()),
+            GclkDiv8::Div2Pow8 => (true, 7),
+            GclkDiv8::Div2Pow9 => (true, 8),
+        }
+    }
+}
+
+//==============================================================================
+// GclkDiv16
+//==============================================================================
+
+/// Enum for the clock division factor of [`Gclk1`] only
+///
+/// Choosing the [`Gclk1`] division factor can be complicated, because the set
+/// of valid values is not contiguous. For [`Gclk1`], the division factor can be
+/// 1-65536 or 131072. `GclkDiv16` provides an enum interface to enforce
+/// validity of the division factor. See the datasheet for more details.
+#[derive(Clone, Copy)]
+pub enum GclkDiv16 {
+    /// Use a literal division factor
+    ///
+    /// All values in the range `[1-65535]` are valid. Zero is also valid, but
+    /// it is interpreted as `1`.
+    Div(u16),
+    /// Use a division factor of `2^16 = 65536`
+    Div2Pow16,
+    /// Use a division factor of `2^17 = 131072`
+    Div2Pow17,
+}
+
+impl Sealed for GclkDiv16 {}
+
+impl Default for GclkDiv16 {
+    #[inline]
+    fn default() -> Self {
+        Self::Div(0)
+    }
+}
+
+impl GclkDivider for GclkDiv16 {
+    #[inline]
+    fn divider(&self) -> u32 {
+        match self {
+            GclkDiv16::Div(div) => (*div).into(),
+            GclkDiv16::Div2Pow16 => 65536,
+            GclkDiv16::Div2Pow17 => 131072,
+        }
+    }
+
+    #[inline]
+    fn divsel_div(&self) -> (bool, u16) {
+        match self {
+            GclkDiv16::Div(div) => (false, *div),
+            GclkDiv16::Div2Pow16 => (true, 15),
+            GclkDiv16::Div2Pow17 => (true, 16),
+        }
+    }
+}
+
+//==============================================================================
+// GclkDiv5
+//==============================================================================
+
+/// Enum for the clock division factor of [`Gclk2`] only
+///
+/// The `DIV` field of [`Gclk2`] is only five bits wide. Literal division
+/// factors are limited to 1-31, while power-of-two division factors can range
+/// from `2^1` to `2^31`. `GclkDiv5` provides an enum interface to enforce
+/// validity of the division factor. See the datasheet for more details.
+#[derive(Clone, Copy)]
+pub enum GclkDiv5 {
+    /// Use a literal division factor
+    ///
+    /// All values in the range `[1-31]` are valid. Zero is also valid, but it
+    /// is interpreted as `1`. Larger values are saturated to `31`.
+    Div(u8),
+    /// Use a division factor of `2^n`
+    ///
+    /// All values of `n` in the range `[1-31]` are valid. Values outside of
+    /// this range are saturated to the nearest valid value.
+    Div2Pow(u8),
+}
+
+impl Sealed for GclkDiv5 {}
+
+impl Default for GclkDiv5 {
+    #[inline]
+    fn default() -> Self {
+        Self::Div(0)
+    }
+}
+
+impl GclkDivider for GclkDiv5 {
+    #[inline]
+    fn divider(&self) -> u32 {
+        match self {
+            GclkDiv5::Div(div) => min(*div, 31).into(),
+            GclkDiv5::Div2Pow(n) => 1 << (*n).clamp(1, 31),
+        }
+    }
+
+    #[inline]
+    fn divsel_div(&self) -> (bool, u16) {
+        match self {
+            GclkDiv5::Div(div) => (false, min(*div, 31).into()),
+            GclkDiv5::Div2Pow(n) => (true, ((*n).clamp(1, 31) - 1).into()),
+        }
+    }
+}
+
+//==============================================================================
+// GclkIo
+//==============================================================================
+
+/// Trait mapping each [`PinId`] to its corresponding [`GclkId`] when used as a
+/// [`Gclk`] input or output
+///
+/// If a given [`PinId`] can be used as a [`Gclk`] input or output, it can only
+/// be used with one specific [`GclkId`]. This trait provides a mapping from
+/// such a `PinId` to its corresponding `GclkId`.
+pub trait GclkIo: PinId {
+    /// Corresponding [`GclkId`] for this [`PinId`]
+    type GclkId: GclkId;
+}
+
+// These implementations are much easier to read with `#[rustfmt::skip]`
+#[cfg(feature = "samd21")]
+#[rustfmt::skip]
+mod gclkio_impl {
+
+    use super::*;
+
+    impl GclkIo for gpio::PA10 { type GclkId = Gclk4Id; }
+    impl GclkIo for gpio::PA11 { type GclkId = Gclk5Id; }
+    impl GclkIo for gpio::PA14 { type GclkId = Gclk0Id; }
+    impl GclkIo for gpio::PA15 { type GclkId = Gclk1Id; }
+    impl GclkIo for gpio::PA16 { type GclkId = Gclk2Id; }
+    impl GclkIo for gpio::PA17 { type GclkId = Gclk3Id; }
+    #[cfg(feature = "pins-48")]
+    impl GclkIo for gpio::PA20 { type GclkId = Gclk4Id; }
+    #[cfg(feature = "pins-48")]
+    impl GclkIo for gpio::PA21 { type GclkId = Gclk5Id; }
+    impl GclkIo for gpio::PA22 { type GclkId = Gclk6Id; }
+    impl GclkIo for gpio::PA23 { type GclkId = Gclk7Id; }
+    #[cfg(feature = "has-pa27")]
+    impl GclkIo for gpio::PA27 { type GclkId = Gclk0Id; }
+    #[cfg(feature = "has-pa28")]
+    impl GclkIo for gpio::PA28 { type GclkId = Gclk0Id; }
+    impl GclkIo for gpio::PA30 { type GclkId = Gclk0Id; }
+    #[cfg(feature = "pins-48")]
+    impl GclkIo for gpio::PB10 { type GclkId = Gclk4Id; }
+    #[cfg(feature = "pins-48")]
+    impl GclkIo for gpio::PB11 { type GclkId = Gclk5Id; }
+    #[cfg(feature = "pins-64")]
+    impl GclkIo for gpio::PB12 { type GclkId = Gclk6Id; }
+    #[cfg(feature = "pins-64")]
+    impl GclkIo for gpio::PB13 { type GclkId = Gclk7Id; }
+    #[cfg(feature = "pins-64")]
+    impl GclkIo for gpio::PB14 { type GclkId = Gclk0Id; }
+    #[cfg(feature = "pins-64")]
+    impl GclkIo for gpio::PB15 { type GclkId = Gclk1Id; }
+    #[cfg(feature = "pins-64")]
+    impl GclkIo for gpio::PB16 { type GclkId = Gclk2Id; }
+    #[cfg(feature = "pins-64")]
+    impl GclkIo for gpio::PB17 { type GclkId = Gclk3Id; }
+    #[cfg(feature = "has-pb22")]
+    impl GclkIo for gpio::PB22 { type GclkId = Gclk0Id; }
+    #[cfg(feature = "has-pb23")]
+    impl GclkIo for gpio::PB23 { type GclkId = Gclk1Id; }
+}
+
+// These implementations are much easier to read with `#[rustfmt::skip]`
+#[cfg(feature = "samd11")]
+#[rustfmt::skip]
+mod gclkio_impl {
+
+    use super::*;
+
+    impl GclkIo for gpio::PA08 { type GclkId = Gclk0Id; }
+    impl GclkIo for gpio::PA09 { type GclkId = Gclk1Id; }
+    #[cfg(feature = "pins-24")]
+    impl GclkIo for gpio::PA10 { type GclkId = Gclk4Id; }
+    #[cfg(feature = "pins-24")]
+    impl GclkIo for gpio::PA11 { type GclkId = Gclk5Id; }
+    impl GclkIo for gpio::PA14 { type GclkId = Gclk4Id; }
+    impl GclkIo for gpio::PA15 { type GclkId = Gclk5Id; }
+    #[cfg(feature = "pins-24")]
+    impl GclkIo for gpio::PA16 { type GclkId = Gclk2Id; }
+    #[cfg(feature = "pins-24")]
+    impl GclkIo for gpio::PA17 { type GclkId = Gclk3Id; }
+    #[cfg(feature = "pins-24")]
+    impl GclkIo for gpio::PA22 { type GclkId = Gclk4Id; }
+    #[cfg(feature = "pins-24")]
+    impl GclkIo for gpio::PA23 { type GclkId = Gclk5Id; }
+    impl GclkIo for gpio::PA24 { type GclkId = Gclk4Id; }
+    impl GclkIo for gpio::PA25 { type GclkId = Gclk5Id; }
+    #[cfg(feature = "has-pa27")]
+    impl GclkIo for gpio::PA27 { type GclkId = Gclk0Id; }
+    impl GclkIo for gpio::PA30 { type GclkId = Gclk0Id; }
+}
+
+//==============================================================================
+// Gclk0Io
+//==============================================================================
+
+/// Set of [`PinId`]s whose implementations of [`GclkIo`] map to [`Gclk0Id`]
+///
+/// This is effectively a trait alias for [`PinId`]s that implement [`GclkIo`]
+/// with a `GclkId` associated type of [`Gclk0Id`], i.e.
+/// `GclkIo<GclkId = Gclk0Id>`. The trait is useful to simply some function
+/// signatures and to help type inference in a few cases.
+pub trait Gclk0Io
+where
+    Self: Sized,
+    Self: GclkIo<GclkId = Gclk0Id>,
+    Self: GclkSourceId<Resource = Pin<Self, AlternateH>>,
+{
+}
+
+impl<I: GclkIo<GclkId = Gclk0Id>> Gclk0Io for I {}
+
+//==============================================================================
+// DynGclkSourceId
+//==============================================================================
+
+/// Value-level enum of possible clock sources for a [`Gclk`]
+///
+/// The variants of this enum identify one of the possible clock sources for a
+/// given [`Gclk`].
+///
+/// `DynGclkSourceId` is the value-level equivalent of [`GclkSourceId`].
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum DynGclkSourceId {
+    Dfll,
+    Dpll,
+    Gclk1,
+    GclkIn,
+    OscUlp32k,
+    Osc32k,
+    Osc8m,
+    Xosc,
+    #[cfg(all(feature = "has-pa00", feature = "has-pa01"))]
+    Xosc32k,
+}
+
+impl From<DynGclkSourceId> for SRC_A {
+    fn from(source: DynGclkSourceId) -> Self {
+        use DynGclkSourceId::*;
+        use SRC_A::*;
+        match source {
+            Dfll => DFLL48M,
+            Dpll => DPLL96M,
+            Gclk1 => GCLKGEN1,
+            GclkIn => GCLKIN,
+            OscUlp32k => OSCULP32K,
+            Osc32k => OSC32K,
+            Osc8m => OSC8M,
+            Xosc => XOSC,
+            #[cfg(all(feature = "has-pa00", feature = "has-pa01"))]
+            Xosc32k => XOSC32K,
+        }
+    }
+}
+
+//==============================================================================
+// GclkSourceId
+//==============================================================================
+
+/// Type-level enum of possible clock [`Source`]s for a [`Gclk`]
+///
+/// The types implementing this trait are type-level variants of `GclkSourceId`,
+/// and they identify one of the possible clock [`Source`]s for a given
+/// [`Gclk`]. All implementers of this trait are `Id` types, which are described
+/// in more detail in the [`clock` module documentation](super).
+///
+/// `GclkSourceId` is the type-level equivalent of [`DynGclkSourceId`]. See the
+/// documentation on [type-level programming] and specifically
+/// [type-level enums] for more details.
+///
+/// [type-level programming]: crate::typelevel
+/// [type-level enums]: crate::typelevel#type-level-enums
+pub trait GclkSourceId {
+    /// Corresponding variant of [`DynGclkSourceId`]
+    const DYN: DynGclkSourceId;
+
+    /// Resource to store in the [`Gclk`]
+    ///
+    /// Maps to the corresponding [`Pin`] for [`GclkIo`] types. In all other
+    /// cases, there is nothing to store, so it is `()`.
+    #[doc(hidden)]
+    type Resource;
+}
+
+impl GclkSourceId for DfllId {
+    const DYN: DynGclkSourceId = DynGclkSourceId::Dfll;
+    type Resource = ();
+}
+impl GclkSourceId for DpllId {
+    const DYN: DynGclkSourceId = DynGclkSourceId::Dpll;
+    type Resource = ();
+}
+impl GclkSourceId for Gclk1Id {
+    const DYN: DynGclkSourceId = DynGclkSourceId::Gclk1;
+    type Resource = ();
+}
+impl<I: GclkIo> GclkSourceId for I {
+    const DYN: DynGclkSourceId = DynGclkSourceId::GclkIn;
+    type Resource = Pin<I, AlternateH>;
+}
+impl GclkSourceId for OscUlp32kId {
+    const DYN: DynGclkSourceId = DynGclkSourceId::OscUlp32k;
+    type Resource = ();
+}
+impl GclkSourceId for Osc32kId {
+    const DYN: DynGclkSourceId = DynGclkSourceId::Osc32k;
+    type Resource = ();
+}
+impl GclkSourceId for Osc8mId {
+    const DYN: DynGclkSourceId = DynGclkSourceId::Osc8m;
+    type Resource = ();
+}
+impl GclkSourceId for XoscId {
+    const DYN: DynGclkSourceId = DynGclkSourceId::Xosc;
+    type Resource = ();
+}
+#[cfg(all(feature = "has-pa00", feature = "has-pa01"))]
+impl GclkSourceId for Xosc32kId {
+    const DYN: DynGclkSourceId = DynGclkSourceId::Xosc32k;
+    type Resource = ();
+}
+
+//==============================================================================
+// NotGclkIo
+//==============================================================================
+
+/// Type-level enum of [`GclkSourceId`] types that are not a [`GclkIo`]
+///
+/// The datasheet notes that a [`Gclk`] can use a GPIO [`Pin`] as either input
+/// or output, but not both. Stated differently, you cannot create a [`GclkOut`]
+/// from a `Gclk` where the [`GclkSourceId`] is a [`PinId`].
+///
+/// This trait acts as a [type-level enum] narrowing [`GclkSourceId`] to exclude
+/// any types which implement [`GclkIo`].
+///
+/// [type-level enum]: crate::typelevel#type-level-enums
+pub trait NotGclkIo: GclkSourceId<Resource = ()> {}
+
+impl<I: GclkSourceId<Resource = ()>> NotGclkIo for I {}
+
+//==============================================================================
+// Settings
+//==============================================================================
+
+/// Collection of [`Gclk`] settings to configure on enable
+struct Settings<G: GclkId> {
+    div: G::Divider,
+    output_off_value: bool,
+    improve_duty_cycle: bool,
+    output_enable: bool,
+    run_standby: bool,
+}
+
+impl<G: GclkId> Clone for Settings<G> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<G: GclkId> Copy for Settings<G> {}
+
+impl<G: GclkId> Default for Settings<G> {
+    fn default() -> Self {
+        Settings {
+            div: G::Divider::default(),
+            output_off_value: false,
+            improve_duty_cycle: false,
+            output_enable: false,
+            run_standby: false,
+        }
+    }
+}
+
+//==============================================================================
+// Gclk
+//==============================================================================
+
+/// Generic clock generator used to distribute clocks to various peripherals
+///
+/// A generic clock generator acts as a branch in the clock tree. It can connect
+/// a root or branch clock to other branch or leaf clocks. In particular, all
+/// peripheral [`Pclk`]s must be derived from a `Gclk`.
+///
+/// The type parameter `G` is a [`GclkId`] that determines which of the
+/// generators this [`Gclk`] represents. The type parameter `I` represents the
+/// `Id` type for the clock [`Source`] driving this `Gclk`. It must be one of
+/// the valid [`GclkSourceId`]s. Alternatively, if the `Gclk` is driven by a
+/// [GPIO](gpio) [`Pin`], then `I` is a [`PinId`] implementing [`GclkIo`]. See
+/// the [`clock` module documentation](super) for more detail on `Id` types.
+///
+/// On its own, an instance of `Gclk` does not represent an enabled clock
+/// generator. Instead, it must first be wrapped with [`Enabled`], which
+/// implements compile-time safety of the clock tree.
+///
+/// Because the terminal call to [`enable`] consumes the `Gclk` and returns an
+/// [`EnabledGclk`], the remaining API uses the builder pattern, where each
+/// method takes and returns `self` by value, allowing them to be easily
+/// chained.
+///
+/// See the [module-level documentation](self) for an example of creating,
+/// configuring and using a `Gclk`.
+///
+/// [`Pclk`]: super::pclk::Pclk
+/// [`enable`]: Gclk::enable
+pub struct Gclk<G, I>
+where
+    G: GclkId,
+    I: GclkSourceId,
+{
+    token: GclkToken<G>,
+    resource: I::Resource,
+    src_freq: Hertz,
+    settings: Settings<G>,
+}
+
+/// An [`Enabled`] [`Gclk`]
+///
+/// As described in the [`clock` module documentation](super), the [`Enabled`]
+/// wrapper implements compile-time clock tree safety by tracking the number of
+/// clocks consuming this [`Gclk`] and restricts access to the underlying
+/// [`Gclk`] to prevent misuse.
+///
+/// As with [`Enabled`], the default value for `N` is `U0`; if left unspecified,
+/// the counter is assumed to be zero.
+pub type EnabledGclk<G, I, N = U0> = Enabled<Gclk<G, I>, N>;
+
+/// Type alias for the corresponding [`Gclk`]
+///
+/// As mentioned in the [module-level documentation](self), `Gclk0` is special,
+/// because it provides the processor main clock. We represent this by
+/// permanently [`Increment`]ing the counter for [`EnabledGclk0`], which
+/// prevents it from ever being disabled. Accordingly, we also provide a few
+/// special methods on [`EnabledGclk0`] to configure the `Gclk` while it is
+/// actively running.
+pub type Gclk0<I> = Gclk<Gclk0Id, I>;
+
+/// Type alias for the corresponding [`EnabledGclk`]
+///
+/// As mentioned in the [module-level documentation](self), `Gclk0` is special,
+/// because it provides the processor main clock. We represent this by
+/// permanently [`Increment`]ing the counter for [`EnabledGclk0`], which
+/// prevents it from ever being disabled. Thus, the default value for `N` is
+/// [`U1`] instead of [`U0`]. Accordingly, we also provide a few special methods
+/// on [`EnabledGclk0`] to configure the `Gclk` while it is actively running.
+pub type EnabledGclk0<I, N = U1> = EnabledGclk<Gclk0Id, I, N>;
+
+macro_rules! gclk_aliases {
+    ($($G:literal),+) => {
+        paste! {
+            $(
+                /// Type alias for the corresponding [`Gclk`]
+                pub type [<Gclk $G>]<I> = Gclk<[<Gclk $G Id>], I>;
+
+                /// Type alias for the corresponding [`EnabledGclk`]
+                pub type [<EnabledGclk $G>]<I, N = U0> =
+                    EnabledGclk<[<Gclk $G Id>], I, N>;
+            )+
+        }
+    };
+}
+
+gclk_aliases!(1, 2, 3, 4, 5);
+#[cfg(feature = "samd21")]
+gclk_aliases!(6, 7, 8);
+
+impl<G, I> Gclk<G, I>
+where
+    G: GclkId,
+    I: GclkIo<GclkId = G>,
+{
+    /// Create a new [`Gclk`] from a GPIO [`Pin`]
+    ///
+    /// Creating a [`Gclk`] does not modify any of the hardware registers. It
+    /// only serves to consume the [`Pin`] and create a struct to track the GCLK
+    /// configuration.
+    ///
+    /// The configuration data is stored until the user calls [`enable`]. At
+    /// that point, all of the registers are written according to the
+    /// initialization procedures specified in the datasheet, and an
+    /// [`EnabledGclk`] is returned. The `Gclk` is not active or useful until
+    /// that point.
+    ///
+    /// [`enable`]: Gclk::enable
+    pub fn from_pin<P>(token: GclkToken<G>, pin: P, freq: impl Into<Hertz>) -> Self
+    where
+        P: AnyPin<Id = I>,
+    {
+        Gclk {
+            token,
+            resource: pin.into().into_mode(),
+            src_freq: freq.into(),
+            settings: Settings::default(),
+        }
+    }
+
+    /// Consume the [`Gclk`] and free its corresponding resources
+    ///
+    /// Freeing a [`Gclk`] returns the corresponding [`GclkToken`] and GPIO
+    /// [`Pin`].
+    pub fn free_pin(self) -> (GclkToken<G>, Pin<I, AlternateH>) {
+        (self.token, self.resource)
+    }
+}
+
+impl<G, I> Gclk<G, I>
+where
+    G: GclkId,
+    I: NotGclkIo,
+{
+    /// Create a new [`Gclk`] from a clock [`Source`]
+    ///
+    /// Creating a [`Gclk`] does not modify any of the hardware registers. It
+    /// only serves to [`Increment`] the [`Source`]'s [`Enabled`] counter
+    /// and create a struct to track the GCLK configuration.
+    ///
+    /// The configuration data is stored until the user calls [`enable`]. At
+    /// that point, all of the registers are written according to the
+    /// initialization procedures specified in the datasheet, and an
+    /// [`EnabledGclk`] is returned. The `Gclk` is not active or useful until
+    /// that point.
+    ///
+    /// [`enable`]: Gclk::enable
+    #[inline]
+    pub fn from_source<S>(token: GclkToken<G>, source: S) -> (Gclk<G, I>, S::Inc)
+    where
+        S: Source<Id = I> + Increment,
+    {
+        let config = Gclk {
+            token,
+            resource: (),
+            src_freq: source.freq(),
+            settings: Settings::default(),
+        };
+        (config, source.inc())
+    }
+
+    /// Consume the [`Gclk`] and free its corresponding resources
+    ///
+    /// Freeing a [`Gclk`] returns the corresponding [`GclkToken`] and
+    /// [`Decrement`]s the [`Source`]'s [`Enabled`] counter.
+    #[inline]
+    pub fn free_source<S>(self, source: S) -> (GclkToken<G>, S::Dec)
+    where
+        S: Source<Id = I> + Decrement,
+    {
+        (self.token, source.dec())
+    }
+}
+
+impl<G, I> Gclk<G, I>
+where
+    G: GclkId,
+    I: GclkSourceId,
+{
+    /// Modify the source of an enabled clock
+    ///
+    /// This is a helper function for swapping Gclk0 to different clock sources.
+    fn change_source<N: GclkSourceId>(
+        mut self,
+        resource: N::Resource,
+        freq: Hertz,
+    ) -> (Gclk<G, N>, I::Resource) {
+        self.token.configure(N::DYN, self.settings, true);
+        let gclk = Gclk {
+            token: self.token,
+            resource,
+            src_freq: freq,
+            settings: self.settings,
+        };
+        (gclk, self.resource)
+    }
+
+    /// Set the [`GclkDivider`] value
+    ///
+    /// Set the clock division factor from input to output. This takes either a
+    /// [`GclkDiv8`], [`GclkDiv16`] or [`GclkDiv5`] enum, restricting the
+    /// possible division factors to only the valid ones for the given
+    /// [`Gclk`]. See the [`GclkDivider`] trait for more details.
+    #[inline]
+    pub fn div(mut self, div: G::Divider) -> Self {
+        self.settings.div = div;
+        self
+    }
+
+    /// Output a 50-50 duty cycle clock when using an odd [`GclkDivider`]
+    #[inline]
+    pub fn improve_duty_cycle(mut self, flag: bool) -> Self {
+        self.settings.improve_duty_cycle = flag;
+        self
+    }
+
+    /// Keep the [`Gclk`] running in standby sleep mode
+    ///
+    /// By default, all generators are stopped in standby. Peripherals that must
+    /// remain active in standby need their [`Gclk`] to keep running as well.
+    #[inline]
+    pub fn run_standby(mut self, flag: bool) -> Self {
+        self.settings.run_standby = flag;
+        self
+    }
+
+    /// Return the [`Gclk`] ouput frequency
+    ///
+    /// This is the input frequency divided by the [`GclkDivider`].
+    #[inline]
+    pub fn freq(&self) -> Hertz {
+        let div = max(1, self.settings.div.divider());
+        self.src_freq / div
+    }
+
+    /// Set the state of [`GclkOut`] pins when [`GclkIo`] output is disabled
+    ///
+    /// The output off value (OOV) determines the logic level of a [GPIO](gpio)
+    /// [`Pin`] (configured as a [`GclkIo`] output) when the [`Gclk`] is
+    /// disabled **OR** the [`GclkOut`] is disabled.
+    ///
+    /// As mentioned in the [`Gclk`] documentation, configuration options are
+    /// not usually applied until the call to [`Gclk::enable`]. However, because
+    /// the OOV is relevant when the `Gclk` is *disabled*, we make an exception.
+    /// When calling this function, the new OOV will take effect immediately.
+    ///
+    /// However, remember that the `Pin` is not controlled by the `Gclk` unless
+    /// the `Pin` is configured in [`AlternateH`] mode. `Pin`s are automatically
+    /// set to `AlternateH` mode when calling [`enable_gclk_out`], but by that
+    /// point, the OOV is irrelevant. If you need the `Pin` to be set to its
+    /// OOV, you must *manually* set it to `AlternateH` mode before constructing
+    /// the `GclkOut`.
+    ///
+    /// [`enable_gclk_out`]: EnabledGclk::enable_gclk_out
+    #[inline]
+    pub fn output_off_value(mut self, high: bool) -> Self {
+        self.settings.output_off_value = high;
+        self.token.configure(I::DYN, self.settings, false);
+        self
+    }
+
+    /// Enable the [`Gclk`], so that it can be used as a clock [`Source`]
+    ///
+    /// As mentioned in the [`Gclk`] documentation, no hardware registers are
+    /// actually modified until this call. Rather, the desired configuration is
+    /// stored internally, and the [`Gclk`] is initialized and configured here
+    /// according to the datasheet.
+    ///
+    /// The returned value is an [`EnabledGclk`] that can be used as a clock
+    /// [`Source`] for other clocks.
+    #[inline]
+    pub fn enable(mut self) -> EnabledGclk<G, I> {
+        self.token.configure(I::DYN, self.settings, true);
+        Enabled::new(self)
+    }
+}
+
+impl<G, I> EnabledGclk<G, I>
+where
+    G: GclkId,
+    I: GclkSourceId,
+{
+    /// Disable the [`Gclk`]
+    ///
+    /// This method is only implemented for `N = U0`, which means the clock can
+    /// only be disabled when no other clocks consume this [`Gclk`].
+    #[inline]
+    pub fn disable(mut self) -> Gclk<G, I> {
+        self.0.token.configure(I::DYN, self.0.settings, false);
+        self.0
+    }
+}
+
+/// Special methods for an [`Enabled`] [`Gclk0`]
+///
+/// [`Gclk0`] is special, because it drives the processor's main clock, which
+/// can never be disabled. As discussed in the [module-level documentation],
+/// this fact is represented by permanently [`Increment`]ing the counter for
+/// [`EnabledGclk0`]. Thus, the minimum value for `N` is `U1` and
+/// [`EnabledGclk0`] can never be disabled.
+///
+/// These methods represent actions that can be taken when `N = U1`, i.e. the
+/// [`Enabled`] counter is at its minimum value. This is the only time it's
+/// safe to change the [`Gclk0`] [`Source`] or change its [`GclkDivider`] value.
+///
+/// [module-level documentation]: self
+impl<I: GclkSourceId> EnabledGclk0<I, U1> {
+    /// Swap [`Gclk0`] from one clock [`Source`] to another
+    ///
+    /// `Gclk0` will remain fully enabled during the swap.
+    #[inline]
+    pub fn swap_sources<O, N>(self, old: O, new: N) -> (EnabledGclk0<N::Id, U1>, O::Dec, N::Inc)
+    where
+        O: Source<Id = I> + Decrement,
+        N: Source + Increment,
+        N::Id: NotGclkIo,
+    {
+        let (gclk, _) = self.0.change_source((), new.freq());
+        let enabled = Enabled::new(gclk);
+        (enabled, old.dec(), new.inc())
+    }
+
+    /// Swap [`Gclk0`] from one [`GclkIo`] [`Pin`] to another
+    ///
+    /// `Gclk0` will remain fully enabled during the swap.
+    #[inline]
+    pub fn swap_pins<P>(
+        self,
+        pin: P,
+        freq: impl Into<Hertz>,
+    ) -> (EnabledGclk0<P::Id, U1>, Pin<I, AlternateH>)
+    where
+        I: Gclk0Io,
+        P: AnyPin,
+        P::Id: Gclk0Io,
+    {
+        let pin = pin.into().into_mode();
+        let (gclk, pin) = self.0.change_source(pin, freq.into());
+        let enabled = Enabled::new(gclk);
+        (enabled, pin)
+    }
+
+    /// Swap [`Gclk0`] from a clock [`Source`] to a [`GclkIo`] [`Pin`]
+    ///
+    /// `Gclk0` will remain fully enabled during the swap.
+    #[inline]
+    pub fn swap_source_for_pin<S, P>(
+        self,
+        source: S,
+        pin: P,
+        freq: impl Into<Hertz>,
+    ) -> (EnabledGclk0<P::Id, U1>, S::Dec)
+    where
+        S: Source<Id = I> + Decrement,
+        P: AnyPin,
+        P::Id: Gclk0Io,
+    {
+        let pin = pin.into().into_mode();
+        let (gclk, _) = self.0.change_source(pin, freq.into());
+        let enabled = Enabled::new(gclk);
+        (enabled, source.dec())
+    }
+
+    /// Swap [`Gclk0`] from a [`GclkIo`] [`Pin`] to a clock [`Source`]
+    ///
+    /// `Gclk0` will remain fully enabled during the swap.
+    #[inline]
+    pub fn swap_pin_for_source<S>(
+        self,
+        source: S,
+    ) -> (EnabledGclk0<S::Id, U1>, Pin<I, AlternateH>, S::Inc)
+    where
+        I: Gclk0Io,
+        S: Source + Increment,
+        S::Id: NotGclkIo,
+    {
+        let (gclk, pin) = self.0.change_source((), source.freq());
+        let enabled = Enabled::new(gclk);
+        (enabled, pin, source.inc())
+    }
+
+    /// Set the [`GclkDivider`] value for [`Gclk0`]
+    ///
+    /// See [`Gclk::div`] documentation for more details.
+    #[inline]
+    pub fn div(&mut self, div: GclkDiv8) {
+        self.0.settings.div = div;
+        self.0.token.configure(I::DYN, self.0.settings, true);
+    }
+
+    /// Output a 50-50 duty cycle clock when using an odd [`GclkDivider`]
+    #[inline]
+    pub fn improve_duty_cycle(&mut self, flag: bool) {
+        self.0.settings.improve_duty_cycle = flag;
+        self.0.token.configure(I::DYN, self.0.settings, true);
+    }
+
+    /// Return the [`Gclk0`] frequency
+    ///
+    /// See [`Gclk::freq`] documentation for more details.
+    #[inline]
+    pub fn freq(&self) -> Hertz {
+        self.0.freq()
+    }
+
+    /// Set the state of [`GclkOut`] pins when [`GclkIo`] output is disabled
+    ///
+    /// See [`Gclk::output_off_value`] documentation for more details.
+    #[inline]
+    pub fn output_off_value(&mut self, high: bool) {
+        self.0.settings.output_off_value = high;
+        self.0.token.configure(I::DYN, self.0.settings, true);
+    }
+}
+
+//==============================================================================
+// Source
+//==============================================================================
+
+impl<G, I, N> Source for EnabledGclk<G, I, N>
+where
+    G: GclkId,
+    I: GclkSourceId,
+{
+    type Id = G;
+
+    #[inline]
+    fn freq(&self) -> Hertz {
+        self.0.freq()
+    }
+}
+
+//==============================================================================
+// Tokens
+//==============================================================================
+
+macro_rules! gclk_tokens {
+    ($($N:literal),+) => {
+        paste! {
+            /// Set of [`GclkToken`]s representing the disabled [`Gclk`]s at
+            /// power-on reset
+            pub struct GclkTokens {
+                $(
+                    /// [`GclkToken`] for
+                    #[doc = "[`Gclk" $N "`]"]
+                    pub [<gclk $N>]: GclkToken<[<Gclk $N Id>]>,
+                )+
+            }
+
+            impl GclkTokens {
+                /// Create the set of [`GclkToken`]s
+                ///
+                /// # Safety
+                ///
+                /// All of the invariants required by `GclkToken::new` must be
+                /// upheld here as well.
+                #[inline]
+                pub(super) unsafe fn new(nvmctrl: &mut NVMCTRL) -> Self {
+                    // Use one wait state, which is sufficient up to 48 MHz
+                    nvmctrl.ctrlb.modify(|_, w| w.rws().half());
+                    GclkTokens {
+                        $( [<gclk $N>]: GclkToken::new(), )+
+                    }
+                }
+            }
+        }
+    };
+}
+
+#[cfg(feature = "samd11")]
+gclk_tokens!(1, 2, 3, 4, 5);
+#[cfg(feature = "samd21")]
+gclk_tokens!(1, 2, 3, 4, 5, 6, 7, 8);
+
+//==============================================================================
+// GclkOut
+//==============================================================================
+
+/// A GPIO [`Pin`] configured as a [`Gclk`] output
+///
+/// The existence of this struct serves as proof that the corresponding [`Gclk`]
+/// is [`Enabled`] and that it has been output to [`PinId`] `I`.
+///
+/// See the [module-level documentation](self) for an example of creating a
+/// [`GclkOut`] from an [`EnabledGclk`].
+pub struct GclkOut<I: GclkIo> {
+    pin: Pin<I, AlternateH>,
+    freq: Hertz,
+}
+
+impl<G, I> GclkOut<I>
+where
+    G: GclkId,
+    I: GclkIo<GclkId = G>,
+{
+    /// Return the frequency of the corresponding [`Gclk`]
+    #[inline]
+    pub fn freq(&self) -> Hertz {
+        self.freq
+    }
+}
+
+impl<G, S, N> EnabledGclk<G, S, N>
+where
+    G: GclkId,
+    S: NotGclkIo,
+{
+    /// Create and enable a [`GclkOut`]
+    ///
+    /// Enabling [`GclkIo`] output will [`Increment`] the `EnabledGclk`
+    /// counter, which will prevent it from being disabled while the
+    /// `GclkOut` exists.
+    ///
+    /// Note that a given [`Gclk`] can only use [`GclkIo`] for input **or**
+    /// output, but not both simultaneously. The [`NotGclkIo`] trait exists to
+    /// enforce this requirement.
+    ///
+    /// Finally, when a [`GclkOut`] is disabled, but the [`Pin`] is still in
+    /// [`AlternateH`] mode, it takes the "output off value" of the `Gclk`. See
+    /// the [`Gclk::output_off_value`] documentation for more details.
+    #[inline]
+    pub fn enable_gclk_out<P>(mut self, pin: P) -> (EnabledGclk<G, S, N::Inc>, GclkOut<P::Id>)
+    where
+        N: Increment,
+        P: AnyPin,
+        P::Id: GclkIo<GclkId = G>,
+    {
+        let pin = pin.into().into_mode();
+        let freq = self.freq();
+        self.0.settings.output_enable = true;
+        self.0.token.configure(S::DYN, self.0.settings, true);
+        let gclk_out = GclkOut { pin, freq };
+        (self.inc(), gclk_out)
+    }
+
+    /// Disable a [`GclkOut`] and free its [`Pin`]
+    ///
+    /// Disabling [`GclkIo`] output will [`Decrement`] the [`EnabledGclk`]
+    /// counter. When a [`GclkOut`] is disabled, but the [`Pin`] is still in
+    /// [`AlternateH`] mode, it takes the "output off value" of the `Gclk`. See
+    /// the [`Gclk::output_off_value`] documentation for more details.
+    #[inline]
+    pub fn disable_gclk_out<I>(
+        mut self,
+        gclk_out: GclkOut<I>,
+    ) -> (EnabledGclk<G, S, N::Dec>, Pin<I, AlternateH>)
+    where
+        N: Decrement,
+        I: GclkIo<GclkId = G>,
+    {
+        self.0.settings.output_enable = false;
+        self.0.token.configure(S::DYN, self.0.settings, true);
+        (self.dec(), gclk_out.pin)
+    }
+}
@@ -0,0 +1,315 @@
+//! # Internal 32 kHz oscillator
+//!
+//! ## Overview
+//!
+//! The `osc32k` module provides access to the 32 kHz internal RC oscillator
+//! (OSC32K) within the `SYSCTRL` peripheral.
+//!
+//! The OSC32K is more accurate than the [`OscUlp32k`], but it consumes more
+//! power, and it is disabled at power-on reset. Once enabled, it can act as a
+//! [`Source`] for any of the [`Gclk`]s. It is typically used as a reference for
+//! the [`Dfll`] in closed-loop mode when no external crystal is available.
+//!
+//! The factory calibration value is read from the NVM software calibration
+//! area when the [`Osc32k`] is created. It can be overridden with
+//! [`Osc32k::calibration`].
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use atsamd_hal::{
+//!     clock::v2::{
+//!         clock_system_at_reset,
+//!         gclk::Gclk,
+//!         osc32k::{Osc32k, StartUpDelay},
+//!     },
+//!     pac::Peripherals,
+//! };
+//! let mut pac = Peripherals::take().unwrap();
+//! let (buses, clocks, tokens) = clock_system_at_reset(
+//!     pac.SYSCTRL,
+//!     pac.GCLK,
+//!     pac.PM,
+//!     &mut pac.NVMCTRL,
+//! );
+//! let osc32k = Osc32k::new(tokens.osc32k)
+//!     .start_up_delay(StartUpDelay::Delay2ms)
+//!     .enable();
+//! while !osc32k.is_ready() {}
+//! let (gclk1, osc32k) = Gclk::from_source(tokens.gclks.gclk1, osc32k);
+//! let gclk1 = gclk1.enable();
+//! ```
+//!
+//! [`OscUlp32k`]: super::osculp32k::OscUlp32k
+//! [`Gclk`]: super::gclk::Gclk
+//! [`Dfll`]: super::dfll::Dfll
+
+use fugit::RateExtU32;
+use typenum::U0;
+
+use crate::pac::sysctrl::OSC32K;
+
+use crate::time::Hertz;
+use crate::typelevel::Sealed;
+
+use super::{Enabled, Source};
+
+//==============================================================================
+// Osc32kToken
+//==============================================================================
+
+/// Singleton token that can be exchanged for the [`Osc32k`]
+///
+/// As explained in the [`clock` module documentation](super), instances of
+/// various `Token` types can be exchanged for actual clock types. They
+/// typically represent clocks that are disabled at power-on reset.
+///
+/// The [`Osc32k`] clock is disabled at power-on reset. To use it, you must
+/// first exchange the token for an actual clock with [`Osc32k::new`].
+pub struct Osc32kToken(());
+
+impl Osc32kToken {
+    /// Create a new [`Osc32kToken`]
+    ///
+    /// # Safety
+    ///
+    /// The `Osc32kToken` is a singleton. There must never be two simulatenous
+    /// instances of it. See the notes on `Token` types and memory safety in the
+    /// root of the `clock` module for more details.
+    #[inline]
+    pub(super) unsafe fn new() -> Self {
+        Self(())
+    }
+
+    #[inline]
+    fn osc32k(&self) -> &OSC32K {
+        // Safety: The `Osc32kToken` has exclusive access to the `OSC32K`
+        // register. See the notes on `Token` types and memory safety in the
+        // root of the `clock` module for more details.
+        unsafe { &(*crate::pac::SYSCTRL::ptr()).osc32k }
+    }
+
+    #[inline]
+    fn is_ready(&self) -> bool {
+        // Safety: We are only reading from the `PCLKSR` register, so there is
+        // no risk of memory corruption.
+        let sysctrl = unsafe { &*crate::pac::SYSCTRL::ptr() };
+        sysctrl.pclksr.read().osc32krdy().bit_is_set()
+    }
+
+    /// Write the complete configuration and enable the oscillator
+    #[inline]
+    fn enable(&mut self, settings: Settings) {
+        self.osc32k().write(|w| {
+            // Safety: The calibration value is masked to the 7-bit field width
+            // and the start up value comes from `StartUpDelay`, so both are
+            // valid.
+            unsafe {
+                w.calib().bits(settings.calibration & 0x7F);
+                w.startup().bits(settings.start_up as u8);
+            }
+            w.ondemand().bit(settings.on_demand);
+            w.runstdby().bit(settings.run_standby);
+            w.en32k().set_bit();
+            w.enable().set_bit()
+        });
+    }
+
+    #[inline]
+    fn disable(&mut self) {
+        self.osc32k().modify(|_, w| w.enable().clear_bit());
+    }
+}
+
+//==============================================================================
+// Osc32kId
+//==============================================================================
+
+/// [`Id` type](super#id-types) representing the identity of the OSC32K clock
+pub enum Osc32kId {}
+
+impl Sealed for Osc32kId {}
+
+//==============================================================================
+// StartUpDelay
+//==============================================================================
+
+/// Start up delay before the [`Osc32k`] output is enabled
+///
+/// The delay is counted in cycles of the OSC32K itself. The variants are named
+/// according to the nominal delay, which includes a fixed three cycle overhead.
+#[repr(u8)]
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum StartUpDelay {
+    #[default]
+    Delay92us,
+    Delay122us,
+    Delay183us,
+    Delay305us,
+    Delay549us,
+    Delay1ms,
+    Delay2ms,
+    Delay4ms,
+}
+
+//==============================================================================
+// Settings
+//==============================================================================
+
+// Collection of OSC32K register fields
+//
+// All of these fields are set in a single write to OSC32K during the call to
+// [`Osc32k::enable`].
+#[derive(Clone, Copy)]
+struct Settings {
+    calibration: u8,
+    start_up: StartUpDelay,
+    on_demand: bool,
+    run_standby: bool,
+}
+
+//==============================================================================
+// Osc32k
+//==============================================================================
+
+/// Internal 32 kHz RC oscillator
+///
+/// On its own, an instance of `Osc32k` does not represent an enabled OSC32K.
+/// Instead, it must first be wrapped with [`Enabled`], which implements
+/// compile-time safety of the clock tree.
+///
+/// Because the terminal call to [`enable`] consumes the `Osc32k` and returns an
+/// [`EnabledOsc32k`], the remaining API uses the builder pattern, where each
+/// method takes and returns `self` by value, allowing them to be easily
+/// chained.
+///
+/// See the [module-level documentation](self) for an example.
+///
+/// [`enable`]: Osc32k::enable
+pub struct Osc32k {
+    token: Osc32kToken,
+    settings: Settings,
+}
+
+/// An [`Enabled`] [`Osc32k`]
+///
+/// As described in the [`clock` module documentation](super), the [`Enabled`]
+/// wrapper implements compile-time clock tree safety by tracking the number of
+/// consumer clocks and restricting access to the underlying [`Osc32k`] to
+/// prevent modification while in use.
+///
+/// As with [`Enabled`], the default value for `N` is `U0`; if left unspecified,
+/// the counter is assumed to be zero.
+pub type EnabledOsc32k<N = U0> = Enabled<Osc32k, N>;
+
+impl Osc32k {
+    /// Create the [`Osc32k`] from its [`Osc32kToken`]
+    ///
+    /// The calibration value is loaded from the NVM software calibration area.
+    ///
+    /// Creating an [`Osc32k`] does not modify any of the hardware registers. It
+    /// only creates a struct to track the configuration. The configuration data
+    /// is stored until the user calls [`enable`]. At that point, all of the
+    /// registers are written, and an [`EnabledOsc32k`] is returned. The
+    /// `Osc32k` is not active or useful until that point.
+    ///
+    /// [`enable`]: Osc32k::enable
+    #[inline]
+    pub fn new(token: Osc32kToken) -> Self {
+        let settings = Settings {
+            calibration: crate::calibration::osc32k_cal(),
+            start_up: StartUpDelay::Delay92us,
+            on_demand: true,
+            run_standby: false,
+        };
+        Self { token, settings }
+    }
+
+    /// Consume the [`Osc32k`] and release the [`Osc32kToken`]
+    #[inline]
+    pub fn free(self) -> Osc32kToken {
+        self.token
+    }
+
+    /// Override the calibration value read from the NVM
+    ///
+    /// The calibration field is 7 bits wide; higher bits are ignored.
+    #[inline]
+    pub fn calibration(mut self, calib: u8) -> Self {
+        self.settings.calibration = calib;
+        self
+    }
+
+    /// Set the start up delay before the [`Osc32k`] output is enabled
+    #[inline]
+    pub fn start_up_delay(mut self, delay: StartUpDelay) -> Self {
+        self.settings.start_up = delay;
+        self
+    }
+
+    /// Control the [`Osc32k`] on-demand behavior
+    ///
+    /// When on-demand is enabled, the [`Osc32k`] will only run when requested
+    /// by a peripheral. Otherwise, it will always run, and its behavior in
+    /// standby sleep mode depends on the run-standby setting.
+    #[inline]
+    pub fn on_demand(mut self, on_demand: bool) -> Self {
+        self.settings.on_demand = on_demand;
+        self
+    }
+
+    /// Control the [`Osc32k`] behavior in standby sleep mode
+    #[inline]
+    pub fn run_standby(mut self, run_standby: bool) -> Self {
+        self.settings.run_standby = run_standby;
+        self
+    }
+
+    /// Enable the [`Osc32k`], so that it can be used as a clock [`Source`]
+    ///
+    /// As mentioned when creating a new `Osc32k`, no hardware registers are
+    /// actually modified until this call. Rather, the desired configuration is
+    /// stored internally, and the `Osc32k` is configured and enabled here.
+    ///
+    /// The returned value is an [`EnabledOsc32k`] that can be used as a clock
+    /// [`Source`] for other clocks.
+    #[inline]
+    pub fn enable(mut self) -> EnabledOsc32k {
+        self.token.enable(self.settings);
+        Enabled::new(self)
+    }
+}
+
+impl EnabledOsc32k {
+    /// Disable the [`Osc32k`]
+    ///
+    /// This method is only implemented for `N = U0`, which means the clock can
+    /// only be disabled when no other clocks consume this [`Osc32k`].
+    #[inline]
+    pub fn disable(mut self) -> Osc32k {
+        self.0.token.disable();
+        self.0
+    }
+}
+
+impl<N> EnabledOsc32k<N> {
+    /// Check whether the [`Osc32k`] is stable and ready to be used as a clock
+    /// [`Source`]
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        self.0.token.is_ready()
+    }
+}
+
+//==============================================================================
+// Source
+//==============================================================================
+
+impl<N> Source for EnabledOsc32k<N> {
+    type Id = Osc32kId;
+
+    #[inline]
+    fn freq(&self) -> Hertz {
+        32_768.Hz()
+    }
+}
@@ -0,0 +1,333 @@
+//! # Internal 8 MHz oscillator
+//!
+//! ## Overview
+//!
+//! The `osc8m` module provides access to the 8 MHz internal RC oscillator
+//! (OSC8M) within the `SYSCTRL` peripheral.
+//!
+//! The OSC8M is factory calibrated and drives the processor's main clock at
+//! power-on reset, through [`Gclk0`]. Its output passes through a prescaler,
+//! which can divide the 8 MHz oscillator by 1, 2, 4 or 8. At reset, the
+//! prescaler divides by 8, so the main clock runs at 1 MHz.
+//!
+//! ## The OSC8M at power-on reset
+//!
+//! Unlike most other clocks, the [`Osc8m`] is [`Enabled`] at reset, so it is
+//! found in the [`Clocks`] struct, rather than the [`Tokens`] struct. Because
+//! [`Gclk0`] consumes it, its complete type is `EnabledOsc8m<U1>`.
+//!
+//! To change the prescaler, the OSC8M must first be disabled, which requires
+//! that [`Gclk0`] first be moved to a different clock [`Source`]. Because the
+//! OSC8M can also be used directly as a [`Gclk`] source, it is rarely
+//! necessary to do so.
+//!
+//! ## Example
+//!
+//! Suppose we want to run the main clock at 8 MHz rather than 1 MHz. To change
+//! the prescaler, we temporarily swap [`Gclk0`] to the [`OscUlp32k`], which
+//! frees the OSC8M. Then we disable it, change the prescaler, re-enable it and
+//! swap [`Gclk0`] back.
+//!
+//! ```no_run
+//! use atsamd_hal::{
+//!     clock::v2::{clock_system_at_reset, osc8m::Osc8mDiv},
+//!     pac::Peripherals,
+//! };
+//! let mut pac = Peripherals::take().unwrap();
+//! let (buses, clocks, tokens) = clock_system_at_reset(
+//!     pac.SYSCTRL,
+//!     pac.GCLK,
+//!     pac.PM,
+//!     &mut pac.NVMCTRL,
+//! );
+//! let (gclk0, osc8m, osculp32k) = clocks.gclk0.swap_sources(clocks.osc8m, clocks.osculp32k);
+//! let osc8m = osc8m.disable().prescaler(Osc8mDiv::Div1).enable();
+//! while !osc8m.is_ready() {}
+//! let (gclk0, osculp32k, osc8m) = gclk0.swap_sources(osculp32k, osc8m);
+//! ```
+//!
+//! [`Clocks`]: super::Clocks
+//! [`Tokens`]: super::Tokens
+//! [`Gclk`]: super::gclk::Gclk
+//! [`Gclk0`]: super::gclk::Gclk0
+//! [`OscUlp32k`]: super::osculp32k::OscUlp32k
+
+use fugit::RateExtU32;
+use typenum::U0;
+
+use crate::pac::sysctrl::osc8m::PRESC_A;
+use crate::pac::sysctrl::OSC8M;
+
+use crate::time::Hertz;
+use crate::typelevel::Sealed;
+
+use super::{Enabled, Source};
+
+//==============================================================================
+// Osc8mToken
+//==============================================================================
+
+/// Singleton token that can be exchanged for the [`Osc8m`]
+///
+/// As explained in the [`clock` module documentation](super), instances of
+/// various `Token` types represent disabled clocks and can be exchanged for
+/// actual clock types. However, unlike most other clocks in the module, the
+/// [`Osc8m`] is [`Enabled`] at power-on reset. Thus, users will never deal with
+/// the `Osc8mToken` unless they first disable and free the [`EnabledOsc8m`].
+pub struct Osc8mToken(());
+
+impl Osc8mToken {
+    /// Create a new [`Osc8mToken`]
+    ///
+    /// # Safety
+    ///
+    /// The `Osc8mToken` is a singleton. There must never be two simulatenous
+    /// instances of it. See the notes on `Token` types and memory safety in the
+    /// root of the `clock` module for more details.
+    #[inline]
+    pub(super) unsafe fn new() -> Self {
+        Self(())
+    }
+
+    #[inline]
+    fn osc8m(&self) -> &OSC8M {
+        // Safety: The `Osc8mToken` has exclusive access to the `OSC8M`
+        // register. See the notes on `Token` types and memory safety in the
+        // root of the `clock` module for more details.
+        unsafe { &(*crate::pac::SYSCTRL::ptr()).osc8m }
+    }
+
+    #[inline]
+    fn is_ready(&self) -> bool {
+        // Safety: We are only reading from the `PCLKSR` register, so there is
+        // no risk of memory corruption.
+        let sysctrl = unsafe { &*crate::pac::SYSCTRL::ptr() };
+        sysctrl.pclksr.read().osc8mrdy().bit_is_set()
+    }
+
+    /// Write the prescaler and sleep settings, preserving the factory
+    /// calibration and frequency range
+    #[inline]
+    fn configure(&mut self, settings: Settings) {
+        self.osc8m().modify(|_, w| {
+            w.presc().variant(settings.div.into());
+            w.ondemand().bit(settings.on_demand);
+            w.runstdby().bit(settings.run_standby)
+        });
+    }
+
+    #[inline]
+    fn enable(&mut self) {
+        self.osc8m().modify(|_, w| w.enable().set_bit());
+    }
+
+    #[inline]
+    fn disable(&mut self) {
+        self.osc8m().modify(|_, w| w.enable().clear_bit());
+    }
+}
+
+//==============================================================================
+// Osc8mId
+//==============================================================================
+
+/// [`Id` type](super#id-types) representing the identity of the OSC8M clock
+pub enum Osc8mId {}
+
+impl Sealed for Osc8mId {}
+
+//==============================================================================
+// Osc8mDiv
+//==============================================================================
+
+/// Division factor of the [`Osc8m`] output prescaler
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum Osc8mDiv {
+    Div1,
+    Div2,
+    Div4,
+    #[default]
+    Div8,
+}
+
+impl Osc8mDiv {
+    #[inline]
+    fn divider(self) -> u32 {
+        match self {
+            Self::Div1 => 1,
+            Self::Div2 => 2,
+            Self::Div4 => 4,
+            Self::Div8 => 8,
+        }
+    }
+}
+
+impl From<Osc8mDiv> for PRESC_A {
+    #[inline]
+    fn from(div: Osc8mDiv) -> Self {
+        match div {
+            Osc8mDiv::Div1 => PRESC_A::_0,
+            Osc8mDiv::Div2 => PRESC_A::_1,
+            Osc8mDiv::Div4 => PRESC_A::_2,
+            Osc8mDiv::Div8 => PRESC_A::_3,
+        }
+    }
+}
+
+//==============================================================================
+// Settings
+//==============================================================================
+
+// Collection of OSC8M register fields
+//
+// The calibration and frequency range fields are programmed by the factory and
+// are never modified.
+#[derive(Clone, Copy)]
+struct Settings {
+    div: Osc8mDiv,
+    on_demand: bool,
+    run_standby: bool,
+}
+
+//==============================================================================
+// Osc8m
+//==============================================================================
+
+/// Internal 8 MHz RC oscillator
+///
+/// On its own, an instance of `Osc8m` does not represent an enabled OSC8M.
+/// Instead, it must first be wrapped with [`Enabled`], which implements
+/// compile-time safety of the clock tree.
+///
+/// Because the terminal call to [`enable`] consumes the `Osc8m` and returns an
+/// [`EnabledOsc8m`], the remaining API uses the builder pattern, where each
+/// method takes and returns `self` by value, allowing them to be easily
+/// chained.
+///
+/// See the [module-level documentation](self) for an example.
+///
+/// [`enable`]: Osc8m::enable
+pub struct Osc8m {
+    token: Osc8mToken,
+    settings: Settings,
+}
+
+/// An [`Enabled`] [`Osc8m`]
+///
+/// As described in the [`clock` module documentation](super), the [`Enabled`]
+/// wrapper implements compile-time clock tree safety by tracking the number of
+/// consumer clocks and restricting access to the underlying [`Osc8m`] to
+/// prevent modification while in use.
+///
+/// As with [`Enabled`], the default value for `N` is `U0`; if left unspecified,
+/// the counter is assumed to be zero.
+pub type EnabledOsc8m<N = U0> = Enabled<Osc8m, N>;
+
+impl Osc8m {
+    /// Create the [`Osc8m`] from its [`Osc8mToken`]
+    ///
+    /// The configuration matches the state of the OSC8M at power-on reset,
+    /// i.e. the prescaler divides by 8 and on-demand mode is enabled.
+    ///
+    /// Creating an [`Osc8m`] does not modify any of the hardware registers. It
+    /// only creates a struct to track the configuration. The configuration data
+    /// is stored until the user calls [`enable`]. At that point, all of the
+    /// registers are written, and an [`EnabledOsc8m`] is returned.
+    ///
+    /// [`enable`]: Osc8m::enable
+    #[inline]
+    pub fn new(token: Osc8mToken) -> Self {
+        let settings = Settings {
+            div: Osc8mDiv::Div8,
+            on_demand: true,
+            run_standby: false,
+        };
+        Self { token, settings }
+    }
+
+    /// Consume the [`Osc8m`] and release the [`Osc8mToken`]
+    #[inline]
+    pub fn free(self) -> Osc8mToken {
+        self.token
+    }
+
+    /// Set the [`Osc8mDiv`] prescaler
+    #[inline]
+    pub fn prescaler(mut self, div: Osc8mDiv) -> Self {
+        self.settings.div = div;
+        self
+    }
+
+    /// Control the [`Osc8m`] on-demand behavior
+    ///
+    /// When on-demand is enabled, the [`Osc8m`] will only run when requested
+    /// by a peripheral. Otherwise, it will always run, and its behavior in
+    /// standby sleep mode depends on the run-standby setting.
+    #[inline]
+    pub fn on_demand(mut self, on_demand: bool) -> Self {
+        self.settings.on_demand = on_demand;
+        self
+    }
+
+    /// Control the [`Osc8m`] behavior in standby sleep mode
+    #[inline]
+    pub fn run_standby(mut self, run_standby: bool) -> Self {
+        self.settings.run_standby = run_standby;
+        self
+    }
+
+    /// Return the [`Osc8m`] output frequency, after the prescaler
+    #[inline]
+    pub fn freq(&self) -> Hertz {
+        8_000_000.Hz() / self.settings.div.divider()
+    }
+
+    /// Enable the [`Osc8m`], so that it can be used as a clock [`Source`]
+    ///
+    /// As mentioned when creating a new `Osc8m`, no hardware registers are
+    /// actually modified until this call. Rather, the desired configuration is
+    /// stored internally, and the `Osc8m` is configured here.
+    ///
+    /// The returned value is an [`EnabledOsc8m`] that can be used as a clock
+    /// [`Source`] for other clocks.
+    #[inline]
+    pub fn enable(mut self) -> EnabledOsc8m {
+        self.token.configure(self.settings);
+        self.token.enable();
+        Enabled::new(self)
+    }
+}
+
+impl EnabledOsc8m {
+    /// Disable the [`Osc8m`]
+    ///
+    /// This method is only implemented for `N = U0`, which means the clock can
+    /// only be disabled when no other clocks consume this [`Osc8m`].
+    #[inline]
+    pub fn disable(mut self) -> Osc8m {
+        self.0.token.disable();
+        self.0
+    }
+}
+
+impl<N> EnabledOsc8m<N> {
+    /// Check whether the [`Osc8m`] is stable and ready to be used as a clock
+    /// [`Source`]
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        self.0.token.is_ready()
+    }
+}
+
+//==============================================================================
+// Source
+//==============================================================================
+
+impl<N> Source for EnabledOsc8m<N> {
+    type Id = Osc8mId;
+
+    #[inline]
+    fn freq(&self) -> Hertz {
+        self.0.freq()
+    }
+}
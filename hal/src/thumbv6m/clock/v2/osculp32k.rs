@@ -0,0 +1,194 @@
+//! # Internal, ultra low power, 32 kHz oscillator
+//!
+//! ## Overview
+//!
+//! The `osculp32k` module provides access to the 32 kHz ultra low power
+//! internal oscillator (OSCULP32K) within the `SYSCTRL` peripheral.
+//!
+//! The `OSCULP32K` clock is unlike most other clocks. It is an internal clock
+//! that is always enabled and can't be disabled. On the ATSAMD21 and ATSAMD11,
+//! it has a single 32 kHz output, which can act as a [`Source`] for any of the
+//! [`Gclk`]s. It is also used internally to time the start up of other
+//! oscillators and to clock the watchdog timer.
+//!
+//! Because the clock can never be disabled, users get access to the
+//! [`EnabledOscUlp32k`] directly in the [`Clocks`] struct at power-on reset.
+//! There is no corresponding token and no `disable` method.
+//!
+//! ## Write lock
+//!
+//! The `OSCULP32K` register has a dedicated write lock bit that will freeze its
+//! configuration until the next power-on reset. We implement this by simply
+//! dropping the [`OscUlp32k`] clock, which prevents any further access to the
+//! `OSCULP32K` register.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use atsamd_hal::{
+//!     clock::v2::{clock_system_at_reset, gclk::Gclk},
+//!     pac::Peripherals,
+//! };
+//! let mut pac = Peripherals::take().unwrap();
+//! let (buses, clocks, tokens) = clock_system_at_reset(
+//!     pac.SYSCTRL,
+//!     pac.GCLK,
+//!     pac.PM,
+//!     &mut pac.NVMCTRL,
+//! );
+//! let mut osculp32k = clocks.osculp32k;
+//! osculp32k.set_calibration(16);
+//! let (gclk3, osculp32k) = Gclk::from_source(tokens.gclks.gclk3, osculp32k);
+//! let gclk3 = gclk3.enable();
+//! ```
+//!
+//! Note that, once the [`EnabledOscUlp32k`] has been consumed by the
+//! [`Gclk`], its counter is no longer zero, but the calibration can still be
+//! changed. Calibration does not change the nominal frequency of the clock, so
+//! it is safe to perform while the clock is in use.
+//!
+//! [`Clocks`]: super::Clocks
+//! [`Gclk`]: super::gclk::Gclk
+
+use fugit::RateExtU32;
+use typenum::U0;
+
+use crate::pac::sysctrl::OSCULP32K;
+
+use crate::time::Hertz;
+use crate::typelevel::Sealed;
+
+use super::{Enabled, Source};
+
+//==============================================================================
+// OscUlp32kToken
+//==============================================================================
+
+/// Singleton token for the [`OscUlp32k`] clock
+//
+// There should never be more than one instance of `OscUlp32kToken`, because it
+// relies on that fact for memory safety.
+//
+// Users never see `OscUlp32kToken`, because the OSCULP32K oscillator is always
+// enabled. Internally, however, it is used as a register interface. The token
+// is zero-sized, so it can be carried by clock types without introducing any
+// memory bloat.
+//
+// As part of that register interface, the `OscUlp32kToken` can access the
+// `OSCULP32K` register. That the token is a singleton guarantees the register
+// is written from only one location. This allows the token to be `Sync`, even
+// though the PAC `SYSCTRL` struct is not.
+struct OscUlp32kToken(());
+
+impl OscUlp32kToken {
+    #[inline]
+    fn osculp32k(&self) -> &OSCULP32K {
+        // Safety: The `OscUlp32kToken` has exclusive access to the `OSCULP32K`
+        // register. See the notes on `Token` types and memory safety in the
+        // root of the `clock` module for more details.
+        unsafe { &(*crate::pac::SYSCTRL::ptr()).osculp32k }
+    }
+
+    /// Set the calibration
+    #[inline]
+    fn set_calibration(&mut self, calib: u8) {
+        // Safety: The value is masked to the 5-bit field width
+        self.osculp32k()
+            .modify(|_, w| unsafe { w.calib().bits(calib & 0x1F) });
+    }
+
+    /// Enable the write lock
+    #[inline]
+    fn write_lock(&mut self) {
+        self.osculp32k().modify(|_, w| w.wrtlock().set_bit());
+    }
+}
+
+//==============================================================================
+// OscUlp32kId
+//==============================================================================
+
+/// Type representing the identity of the [`OscUlp32k`] clock
+///
+/// See the discussion on [`Id` types](super#id-types) for more information.
+pub enum OscUlp32kId {}
+
+impl Sealed for OscUlp32kId {}
+
+//==============================================================================
+// OscUlp32k
+//==============================================================================
+
+/// Internal, ultra low power, 32 kHz oscillator
+///
+/// The OSCULP32K is always enabled and can never be disabled. Consequently,
+/// users only ever interact with the [`EnabledOscUlp32k`] type, which is
+/// provided in the [`Clocks`](super::Clocks) struct at power-on reset. See the
+/// [module-level documentation](self) for details and examples.
+pub struct OscUlp32k {
+    token: OscUlp32kToken,
+}
+
+/// The [`Enabled`] [`OscUlp32k`] clock
+///
+/// As described in the [`clock` module documentation](super), the [`Enabled`]
+/// wrapper implements compile-time clock tree safety by tracking the number of
+/// clocks consuming the [`OscUlp32k`] clock and restricts access to the
+/// underlying type to prevent misuse.
+///
+/// **NOTE:** The `OscUlp32k` clock is internal and can never be disabled, so we
+/// do not provide a `disable` method.
+///
+/// As with [`Enabled`], the default value for `N` is `U0`; if left unspecified,
+/// the counter is assumed to be zero.
+pub type EnabledOscUlp32k<N = U0> = Enabled<OscUlp32k, N>;
+
+impl OscUlp32k {
+    /// Create the ultra-low power oscillator
+    ///
+    /// # Safety
+    ///
+    /// Because an `OscUlp32k` contains an `OscUlp32kToken`, there must never be
+    /// more than one instance of this struct at any given time. See the notes
+    /// on `Token` types and memory safety in the root of the `clock` module for
+    /// more details.
+    #[inline]
+    pub(super) unsafe fn new() -> EnabledOscUlp32k {
+        let token = OscUlp32kToken(());
+        Enabled::new(Self { token })
+    }
+}
+
+impl<N> EnabledOscUlp32k<N> {
+    /// Override the factory-default calibration value
+    ///
+    /// The calibration field is 5 bits wide; higher bits are ignored.
+    #[inline]
+    pub fn set_calibration(&mut self, calib: u8) {
+        self.0.token.set_calibration(calib);
+    }
+
+    /// Freeze the OSCULP32K configuration until power-on reset
+    ///
+    /// This function sets the write-lock bit, which freezes the OSCULP32K
+    /// configuration at the hardware level until power-on reset. At the API
+    /// level, it also consumes and drops the [`OscUlp32k`] clock, which
+    /// prevents any further modifications.
+    #[inline]
+    pub fn write_lock(mut self) {
+        self.0.token.write_lock();
+    }
+}
+
+//==============================================================================
+// Source
+//==============================================================================
+
+impl<N> Source for EnabledOscUlp32k<N> {
+    type Id = OscUlp32kId;
+
+    #[inline]
+    fn freq(&self) -> Hertz {
+        32_768.Hz()
+    }
+}
@@ -0,0 +1,575 @@
+//! # Peripheral Channel Clocks
+//!
+//! ## Overview
+//!
+//! Peripheral channel clocks, or [`Pclk`]s, connect generic clock controllers
+//! ([`Gclk`]s) to various peripherals within the chip. Each [`Pclk`] maps 1:1
+//! with a corresponding peripheral.
+//!
+//! The possible [`Pclk`]s are distinguished by their corresponding [`PclkId`]
+//! types. Ideally, each [`PclkId`] type would be a relevant type from a
+//! corresponding HAL module. For example, each of the [`Sercom`] types
+//! implements [`PclkId`]. However, the HAL does not yet support all
+//! peripherals, nor have all existing HAL peripherals been integrated with
+//! `clock::v2`. In those cases, a dummy type is defined in the
+//! [`clock::v2::types`] module.
+//!
+//! Some peripherals share a single channel on the ATSAMD21 and ATSAMD11. For
+//! example, `TCC0` and `TCC1` are both clocked by the [`Tcc0Tcc1`] channel.
+//!
+//! [`Pclk`]s are typically leaves in the clock tree. The only exceptions are
+//! [`Pclk`]s used for the [`DFLL`] or [`DPLL`] peripherals. In those cases, the
+//! [`Pclk`] acts as a branch clock.
+//!
+//! Each [`Pclk`] powers only a single peripheral; they do not act as general
+//! purpose clock [`Source`]s for other clocks in the tree. As a result, they do
+//! not need to be wrapped with [`Enabled`].
+//!
+//! [`Pclk`]s also do not have any meaningful configuration beyond identifying
+//! which [`EnabledGclk`] is its [`Source`]. Consequently, [`PclkToken`]s can be
+//! directly converted into enabled [`Pclk`]s with [`Pclk::enable`].
+//!
+//! See the [`clock` module documentation] for a more thorough explanation of
+//! the various concepts discussed above.
+//!
+//! ## Example
+//!
+//! The following example shows how to enable the [`Pclk`] for [`Sercom0`]. It
+//! derives the [`Sercom0`] clock from [`EnabledGclk0`], which is already
+//! running at power-on reset. In doing so, the [`EnabledGclk0`] counter is
+//! [`Increment`]ed.
+//!
+//! ```no_run
+//! use atsamd_hal::{
+//!     clock::v2::{clock_system_at_reset, pclk::Pclk},
+//!     pac::Peripherals,
+//! };
+//! let mut pac = Peripherals::take().unwrap();
+//! let (buses, clocks, tokens) = clock_system_at_reset(
+//!     pac.SYSCTRL,
+//!     pac.GCLK,
+//!     pac.PM,
+//!     &mut pac.NVMCTRL,
+//! );
+//! let (pclk_sercom0, gclk0) = Pclk::enable(tokens.pclks.sercom0, clocks.gclk0);
+//! ```
+//!
+//! [`Gclk`]: super::gclk::Gclk
+//! [`DFLL`]: super::dfll
+//! [`DPLL`]: super::dpll
+//! [`Enabled`]: super::Enabled
+//! [`EnabledGclk`]: super::gclk::EnabledGclk
+//! [`EnabledGclk0`]: super::gclk::EnabledGclk0
+//! [`clock` module documentation]: super
+//! [`clock::v2::types`]: super::types
+//! [`Sercom`]: crate::sercom
+//! [`Tcc0Tcc1`]: super::types::Tcc0Tcc1
+
+use core::marker::PhantomData;
+
+use paste::paste;
+
+use crate::pac;
+use crate::pac::gclk::clkctrl::GEN_A;
+
+use crate::time::Hertz;
+use crate::typelevel::{Decrement, Increment, Sealed};
+
+use super::gclk::{DynGclkId, GclkId};
+use super::Source;
+
+//==============================================================================
+// PclkToken
+//==============================================================================
+
+/// Singleton token that can be exchanged for a [`Pclk`]
+///
+/// As explained in the [`clock` module documentation](super), instances of
+/// various `Token` types can be exchanged for actual clock types. They
+/// typically represent clocks that are disabled at power-on reset.
+///
+/// [`PclkToken`]s are no different. All [`Pclk`]s are disabled at power-on
+/// reset. To use a [`Pclk`], you must first exchange the token for an actual
+/// clock with the [`Pclk::enable`] function.
+///
+/// [`PclkToken`] is generic over the [`PclkId`], where each token represents a
+/// corresponding peripheral clock channel.
+pub struct PclkToken<P: PclkId> {
+    pclk: PhantomData<P>,
+}
+
+impl<P: PclkId> PclkToken<P> {
+    /// Create a new instance of [`PclkToken`]
+    ///
+    /// # Safety
+    ///
+    /// Each `PclkToken`s is a singleton. There must never be two simulatenous
+    /// instances with the same [`PclkId`]. See the notes on `Token` types and
+    /// memory safety in the root of the `clock` module for more details.
+    #[inline]
+    pub(super) unsafe fn new() -> Self {
+        PclkToken { pclk: PhantomData }
+    }
+
+    /// Write the `CLKCTRL` register for this channel
+    ///
+    /// `CLKCTRL` is an indirect register. Each write selects the target channel
+    /// with its `ID` field, so the source and enable state are always written
+    /// together.
+    #[inline]
+    fn write(&mut self, source: DynPclkSourceId, enable: bool) {
+        // Safety: Each write selects this `PclkId` with the `ID` field, so it
+        // only affects the channel owned by this `PclkToken`. We never read the
+        // register, which would require a separate write of the `ID` field. See
+        // the notes on `Token` types and memory safety in the root of the
+        // `clock` module for more details.
+        let gclk = unsafe { &*pac::GCLK::ptr() };
+        gclk.clkctrl.write(|w| {
+            // Safety: The `ID` is derived from `DynPclkId`, so it is valid
+            unsafe { w.id().bits(P::DYN as u8) };
+            w.gen().variant(source.into());
+            w.clken().bit(enable)
+        });
+        while gclk.status.read().syncbusy().bit_is_set() {}
+    }
+}
+
+//==============================================================================
+// PclkId types
+//==============================================================================
+
+/// Module containing only the types implementing [`PclkId`]
+///
+/// Because there are so many types that implement [`PclkId`], it is helpful to
+/// have them defined in a separate module, so that you can import all of them
+/// using a wildcard (`*`) without importing anything else, i.e.
+///
+/// ```
+/// use atsamd_hal::clock::v2::pclk::ids::*;
+/// ```
+pub mod ids {
+
+    pub use crate::sercom::{Sercom0, Sercom1};
+
+    #[cfg(feature = "has-sercom2")]
+    pub use crate::sercom::Sercom2;
+    #[cfg(feature = "has-sercom3")]
+    pub use crate::sercom::Sercom3;
+    #[cfg(feature = "has-sercom4")]
+    pub use crate::sercom::Sercom4;
+    #[cfg(feature = "has-sercom5")]
+    pub use crate::sercom::Sercom5;
+
+    pub use super::super::dfll::DfllId;
+    pub use super::super::dpll::DpllId;
+
+    pub use super::super::types::{
+        AcAna, AcDig, Adc, Dac, Dpll32k, Eic, EvSys0, EvSys1, EvSys2, EvSys3, EvSys4, EvSys5, Rtc,
+        SlowClk, Wdt,
+    };
+
+    #[cfg(all(feature = "has-tc6", feature = "has-tc7"))]
+    pub use super::super::types::Tc6Tc7;
+    #[cfg(feature = "has-usb")]
+    pub use super::super::types::Usb;
+    #[cfg(feature = "samd21")]
+    pub use super::super::types::{
+        EvSys10, EvSys11, EvSys6, EvSys7, EvSys8, EvSys9, Tc4Tc5, Tcc0Tcc1, Tcc2Tc3,
+    };
+    #[cfg(feature = "samd11")]
+    pub use super::super::types::{Tc1Tc2, Tcc0};
+    #[cfg(feature = "has-i2s")]
+    pub use super::super::types::{I2S0, I2S1};
+}
+
+use ids::*;
+
+/// Append the list of all [`PclkId`] types and `snake_case` id names to the
+/// arguments of a macro call
+///
+/// This macro will perform the embedded macro call with a list of tuples
+/// appended to the arguments. Each tuple contains a type implementing
+/// [`PclkId`], its corresponding `CLKCTRL.ID` value, and the `snake_case` name
+/// of the corresponding token in the [`PclkTokens`] struct.
+///
+/// **Note:** The entries within [`DynPclkId`] do not match the type names.
+/// Rather, they match the `snake_case` names converted to `CamelCase`.
+///
+/// An optional attribute is added just before each tuple. These are mainly used
+/// to declare the conditions under which the corresponding peripheral exists.
+///
+/// The example below shows the pattern that should be used to match against the
+/// appended tokens.
+///
+/// ```ignore
+/// macro_rules! some_macro {
+///     (
+///         $first_arg:tt,
+///         $second_arg:tt
+///         $(
+///             $( #[$cfg:meta] )?
+///             ($Type:ident = $N:literal, $Id:ident)
+///         )+
+///     ) =>
+///     {
+///         // implementation here ...
+///     }
+/// }
+///
+/// with_pclk_types_ids!(some_macro!(first, second));
+/// ```
+#[cfg(feature = "samd11")]
+macro_rules! with_pclk_types_ids {
+    ( $some_macro:ident ! ( $( $args:tt )* ) ) => {
+        $some_macro!(
+            $( $args )*
+            (DfllId = 0, dfll)
+            (DpllId = 1, dpll)
+            (Dpll32k = 2, dpll32k)
+            (Wdt = 3, wdt)
+            (Rtc = 4, rtc)
+            (Eic = 5, eic)
+            (EvSys0 = 7, ev_sys0)
+            (EvSys1 = 8, ev_sys1)
+            (EvSys2 = 9, ev_sys2)
+            (EvSys3 = 10, ev_sys3)
+            (EvSys4 = 11, ev_sys4)
+            (EvSys5 = 12, ev_sys5)
+            (SlowClk = 13, slow)
+            (Sercom0 = 14, sercom0)
+            (Sercom1 = 15, sercom1)
+            #[cfg(feature = "has-sercom2")]
+            (Sercom2 = 16, sercom2)
+            (Tcc0 = 17, tcc0)
+            (Tc1Tc2 = 18, tc1_tc2)
+            (Adc = 19, adc)
+            (AcDig = 20, ac_dig)
+            (AcAna = 21, ac_ana)
+            (Dac = 22, dac)
+        );
+    };
+}
+
+/// Append the list of all [`PclkId`] types and `snake_case` id names to the
+/// arguments of a macro call
+///
+/// See the ATSAMD11 version of this macro for more details.
+#[cfg(feature = "samd21")]
+macro_rules! with_pclk_types_ids {
+    ( $some_macro:ident ! ( $( $args:tt )* ) ) => {
+        $some_macro!(
+            $( $args )*
+            (DfllId = 0, dfll)
+            (DpllId = 1, dpll)
+            (Dpll32k = 2, dpll32k)
+            (Wdt = 3, wdt)
+            (Rtc = 4, rtc)
+            (Eic = 5, eic)
+            #[cfg(feature = "has-usb")]
+            (Usb = 6, usb)
+            (EvSys0 = 7, ev_sys0)
+            (EvSys1 = 8, ev_sys1)
+            (EvSys2 = 9, ev_sys2)
+            (EvSys3 = 10, ev_sys3)
+            (EvSys4 = 11, ev_sys4)
+            (EvSys5 = 12, ev_sys5)
+            (EvSys6 = 13, ev_sys6)
+            (EvSys7 = 14, ev_sys7)
+            (EvSys8 = 15, ev_sys8)
+            (EvSys9 = 16, ev_sys9)
+            (EvSys10 = 17, ev_sys10)
+            (EvSys11 = 18, ev_sys11)
+            (SlowClk = 19, slow)
+            (Sercom0 = 20, sercom0)
+            (Sercom1 = 21, sercom1)
+            #[cfg(feature = "has-sercom2")]
+            (Sercom2 = 22, sercom2)
+            #[cfg(feature = "has-sercom3")]
+            (Sercom3 = 23, sercom3)
+            #[cfg(feature = "has-sercom4")]
+            (Sercom4 = 24, sercom4)
+            #[cfg(feature = "has-sercom5")]
+            (Sercom5 = 25, sercom5)
+            (Tcc0Tcc1 = 26, tcc0_tcc1)
+            (Tcc2Tc3 = 27, tcc2_tc3)
+            (Tc4Tc5 = 28, tc4_tc5)
+            #[cfg(all(feature = "has-tc6", feature = "has-tc7"))]
+            (Tc6Tc7 = 29, tc6_tc7)
+            (Adc = 30, adc)
+            (AcDig = 31, ac_dig)
+            (AcAna = 32, ac_ana)
+            (Dac = 33, dac)
+            #[cfg(feature = "has-i2s")]
+            (I2S0 = 35, i2s0)
+            #[cfg(feature = "has-i2s")]
+            (I2S1 = 36, i2s1)
+        );
+    };
+}
+
+//==============================================================================
+// DynPclkId
+//==============================================================================
+
+macro_rules! dyn_pclk_id {
+    (
+        $(
+            $( #[$cfg:meta] )?
+            ($Type:ident = $N:literal, $id:ident)
+        )+
+    ) => {
+        paste! {
+            /// Value-level enum identifying one of the possible [`Pclk`]s
+            ///
+            /// The variants of this enum identify one of the possible
+            /// peripheral channel clocks. When cast to a `u8`, each variant
+            /// maps to its corresponding `CLKCTRL.ID` value.
+            ///
+            /// `DynPclkId` is the value-level equivalent of [`PclkId`].
+            #[repr(u8)]
+            pub enum DynPclkId {
+                $(
+                    $( #[$cfg] )?
+                    [<$id:camel>] = $N,
+                )+
+            }
+
+            $(
+                $( #[$cfg] )?
+                impl PclkId for $Type {
+                    const DYN: DynPclkId = DynPclkId::[<$id:camel>];
+                }
+            )+
+        }
+    };
+}
+
+with_pclk_types_ids!(dyn_pclk_id!());
+
+//==============================================================================
+// PclkId
+//==============================================================================
+
+/// Type-level enum identifying one of the possible [`Pclk`]s
+///
+/// The types implementing this trait, e.g. [`Sercom0`] or [`DfllId`], are
+/// type-level variants of `PclkId`, and they identify one of the possible
+/// peripheral channel clocks.
+///
+/// `PclkId` is the type-level equivalent of [`DynPclkId`]. See the
+/// documentation on [type-level programming] and specifically
+/// [type-level enums] for more details.
+///
+/// [type-level programming]: crate::typelevel
+/// [type-level enums]: crate::typelevel#type-level-enums
+pub trait PclkId: Sealed {
+    /// Corresponding variant of [`DynPclkId`]
+    const DYN: DynPclkId;
+}
+
+//==============================================================================
+// DynPclkSourceId
+//==============================================================================
+
+/// Value-level enum of possible clock sources for a [`Pclk`]
+///
+/// The variants of this enum identify the [`Gclk`] used as a clock source for
+/// a given [`Pclk`]. Because the variants are identical to [`DynGclkId`], we
+/// simply define it as a type alias.
+///
+/// `DynPclkSourceId` is the value-level equivalent of [`PclkSourceId`].
+///
+/// [`Gclk`]: super::gclk::Gclk
+pub type DynPclkSourceId = DynGclkId;
+
+/// Convert from [`DynPclkSourceId`] to the equivalent [PAC](crate::pac) type
+impl From<DynPclkSourceId> for GEN_A {
+    fn from(source: DynPclkSourceId) -> Self {
+        use DynGclkId::*;
+        match source {
+            Gclk0 => GEN_A::GCLK0,
+            Gclk1 => GEN_A::GCLK1,
+            Gclk2 => GEN_A::GCLK2,
+            Gclk3 => GEN_A::GCLK3,
+            Gclk4 => GEN_A::GCLK4,
+            Gclk5 => GEN_A::GCLK5,
+            #[cfg(feature = "samd21")]
+            Gclk6 => GEN_A::GCLK6,
+            #[cfg(feature = "samd21")]
+            Gclk7 => GEN_A::GCLK7,
+            #[cfg(feature = "samd21")]
+            Gclk8 => GEN_A::GCLK8,
+        }
+    }
+}
+
+//==============================================================================
+// PclkSourceId
+//==============================================================================
+
+/// Type-level enum of possible clock [`Source`]s for a [`Pclk`]
+///
+/// The types implementing this trait are type-level variants of `PclkSourceId`,
+/// and they identify the [`Gclk`] acting as a clock [`Source`] for a given
+/// [`Pclk`]. Accordingly, all implementers of this trait are [`GclkId`] types,
+/// and this trait is simply a trait alias for [`GclkId`]. `Id` types in general
+/// are described in more detail in the [`clock` module documentation](super).
+///
+/// `PclkSourceId` is the type-level equivalent of [`DynPclkSourceId`]. See the
+/// documentation on [type-level programming] and specifically
+/// [type-level enums] for more details.
+///
+/// [`Gclk`]: super::gclk::Gclk
+/// [type-level programming]: crate::typelevel
+/// [type-level enums]: crate::typelevel#type-level-enums
+pub trait PclkSourceId: GclkId {}
+
+impl<G: GclkId> PclkSourceId for G {}
+
+//==============================================================================
+// Pclk
+//==============================================================================
+
+/// Peripheral channel clock for a given peripheral
+///
+/// Peripheral channel clocks connect generic clock generators ([`Gclk`]s) to
+/// various peripherals. `Pclk`s usually act as leaves in the clock tree, except
+/// when they feed the [`DFLL`] and [`DPLL`] peripherals.
+///
+/// The type parameter `P` is a [`PclkId`] that determines which of the
+/// peripherals this [`Pclk`] feeds. The type parameter `I` represents the `Id`
+/// type for the [`EnabledGclk`] acting as the `Pclk`'s [`Source`]. It must be
+/// one of the valid [`PclkSourceId`]s, which is simply a trait alias for
+/// [`GclkId`]. See the [`clock` module documentation](super) for more detail on
+/// `Id` types.
+///
+/// `Pclk`s cannot act as general purpose clock [`Source`]s; rather, they map
+/// 1:1 with corresponding peripherals. Thus, enabled `Pclk`s do not need a
+/// compile-time counter of consumer clocks, so they are not wrapped with
+/// [`Enabled`]. Enabled `Pclk`s are created directly from [`PclkToken`]s with
+/// [`Pclk::enable`].
+///
+/// See the [module-level documentation](self) for an example.
+///
+/// [`Enabled`]: super::Enabled
+/// [`Gclk`]: super::gclk::Gclk
+/// [`EnabledGclk`]: super::gclk::EnabledGclk
+/// [`DFLL`]: super::dfll
+/// [`DPLL`]: super::dpll
+pub struct Pclk<P, I>
+where
+    P: PclkId,
+    I: PclkSourceId,
+{
+    token: PclkToken<P>,
+    src: PhantomData<I>,
+    freq: Hertz,
+}
+
+impl<P, I> Pclk<P, I>
+where
+    P: PclkId,
+    I: PclkSourceId,
+{
+    pub(super) fn new(token: PclkToken<P>, freq: Hertz) -> Self {
+        Self {
+            token,
+            src: PhantomData,
+            freq,
+        }
+    }
+
+    /// Create and enable a [`Pclk`]
+    ///
+    /// Creating a [`Pclk`] immediately enables the corresponding peripheral
+    /// channel clock. It also [`Increment`]s the [`Source`]'s [`Enabled`]
+    /// counter.
+    ///
+    /// Note that the [`Source`] will always be an [`EnabledGclk`].
+    ///
+    /// [`Enabled`]: super::Enabled
+    /// [`EnabledGclk`]: super::gclk::EnabledGclk
+    #[inline]
+    pub fn enable<S>(mut token: PclkToken<P>, gclk: S) -> (Self, S::Inc)
+    where
+        S: Source<Id = I> + Increment,
+    {
+        let freq = gclk.freq();
+        token.write(I::DYN, true);
+        let pclk = Pclk::new(token, freq);
+        (pclk, gclk.inc())
+    }
+
+    /// Disable and destroy a [`Pclk`]
+    ///
+    /// Consume the [`Pclk`], release the [`PclkToken`], and [`Decrement`] the
+    /// [`EnabledGclk`]'s counter
+    ///
+    /// [`Enabled`]: super::Enabled
+    /// [`EnabledGclk`]: super::gclk::EnabledGclk
+    #[inline]
+    pub fn disable<S>(mut self, gclk: S) -> (PclkToken<P>, S::Dec)
+    where
+        S: Source<Id = I> + Decrement,
+    {
+        self.token.write(I::DYN, false);
+        (self.token, gclk.dec())
+    }
+
+    /// Return the [`Pclk`] frequency
+    #[inline]
+    pub fn freq(&self) -> Hertz {
+        self.freq
+    }
+}
+
+impl<P, I> Sealed for Pclk<P, I>
+where
+    P: PclkId,
+    I: PclkSourceId,
+{
+}
+
+//==============================================================================
+// PclkTokens
+//==============================================================================
+
+macro_rules! define_pclk_tokens_struct {
+    (
+        $(
+            $( #[$cfg:meta] )?
+            ($Type:ident = $_:literal, $id:ident)
+        )+
+    ) =>
+    {
+        /// Set of [`PclkToken`]s representing the disabled [`Pclk`]s at
+        /// power-on reset
+        pub struct PclkTokens {
+            $(
+                $( #[$cfg] )?
+                pub $id: PclkToken<$Type>,
+            )+
+        }
+
+        impl PclkTokens {
+            /// Create the set of [`PclkToken`]s
+            ///
+            /// # Safety
+            ///
+            /// All invariants required by `PclkToken::new` must be upheld here
+            #[inline]
+            pub(super) fn new() -> Self {
+                unsafe {
+                    Self {
+                        $(
+                            $( #[$cfg] )?
+                            $id: PclkToken::new(),
+                        )+
+                    }
+                }
+            }
+        }
+    };
+}
+
+with_pclk_types_ids!(define_pclk_tokens_struct!());
@@ -0,0 +1,173 @@
+//! This module is intentionally private. Its contents are publicly exported
+//! from the `v2` module, which is where the corresponding documentation will
+//! appear.
+
+use typenum::U1;
+
+use crate::pac::{GCLK, NVMCTRL, PM, SYSCTRL};
+
+use super::*;
+
+/// Collection of low-level PAC structs
+///
+/// This struct serves to guard access to the low-level PAC structs. It places
+/// them behind an `unsafe` barrier.
+///
+/// Normally, users trade the low-level PAC structs for the higher-level
+/// `clock::v2` API. However, in some cases, the `clock::v2` API may not be
+/// sufficient. In these cases, users can access the registers directly by
+/// calling [`Pac::steal`] to recover the PAC structs.
+pub struct Pac {
+    sysctrl: SYSCTRL,
+    gclk: GCLK,
+    pm: PM,
+}
+
+impl Pac {
+    /// Escape hatch allowing to access low-level PAC structs
+    ///
+    /// Consume the [`Pac`] and return the low-level PAC structs. This is
+    /// useful when the `clock::v2` API does not provide a necessary feature, or
+    /// when dealing with the legacy `clock::v1` API. For example, the
+    /// [`GenericClockController`](crate::clock::GenericClockController)
+    /// requires all three PAC structs.
+    ///
+    /// # Safety
+    ///
+    /// Directly configuring clocks through the PAC API can invalidate the
+    /// type-level guarantees of the `clock` module API.
+    pub unsafe fn steal(self) -> (SYSCTRL, GCLK, PM) {
+        (self.sysctrl, self.gclk, self.pm)
+    }
+}
+
+/// Bus clock objects
+///
+/// This type is constructed using the [`clock_system_at_reset`] function, which
+/// consumes the PAC-level clocking structs and returns the HAL-level clocking
+/// structs in their reset state.
+///
+/// This type contains the [bus clocks](super#bus-clocks), which are a necessary
+/// to implement memory safety for the [`AhbClk`]s and [`ApbClk`]s.
+///
+/// [`AhbClk`]: super::ahb::AhbClk
+/// [`ApbClk`]: super::apb::ApbClk
+pub struct Buses {
+    pub ahb: ahb::Ahb,
+    pub apb: apb::Apb,
+}
+
+/// Enabled clocks at power-on reset
+///
+/// This type is constructed using the [`clock_system_at_reset`] function, which
+/// consumes the PAC-level clocking structs and returns the HAL-level clocking
+/// structs in their reset state.
+///
+/// This type represents the clocks as they are configured at power-on reset.
+/// The main clock, [`Gclk0`](gclk::Gclk0), runs at 1 MHz using the
+/// [`Osc8m`](osc8m::Osc8m) with its prescaler set to divide by 8. The
+/// [ultra-low power oscillator](osculp32k::OscUlp32k) is also enabled and
+/// running, as it can never be disabled.
+///
+/// As described in the [top-level](super::super) documentation for the `clock`
+/// module, only [`Enabled`] clocks can be used as a [`Source`] for downstream
+/// clocks. This struct contains all of the `Enabled` clocks at reset.
+///
+/// This struct also contains the [`Pac`] wrapper struct, which provides
+/// `unsafe` access to the low-level PAC structs.
+pub struct Clocks {
+    /// Wrapper providing `unsafe` access to low-level PAC structs
+    pub pac: Pac,
+    /// Enabled AHB clocks
+    pub ahbs: ahb::AhbClks,
+    /// Enabled APB clocks
+    pub apbs: apb::ApbClks,
+    /// Main system clock, driven at 1 MHz by the OSC8M
+    pub gclk0: Enabled<gclk::Gclk0<osc8m::Osc8mId>, U1>,
+    /// OSC8M, with its prescaler set to divide by 8
+    pub osc8m: Enabled<osc8m::Osc8m, U1>,
+    /// Always-enabled ultra-low power 32 kHz oscillator
+    pub osculp32k: Enabled<osculp32k::OscUlp32k>,
+}
+
+/// Type-level tokens for unused clocks at power-on reset
+///
+/// This type is constructed using the [`clock_system_at_reset`] function, which
+/// consumes the PAC-level clocking structs and returns the HAL-level clocking
+/// structs in their reset state.
+///
+/// As described in the [top-level](super::super) documentation for the `clock`
+/// module, token types are used to guanrantee the uniqueness of each clock. To
+/// configure or enable a clock, you must provide the corresponding token.
+pub struct Tokens {
+    /// Tokens to create [`apb::ApbClk`]s
+    pub apbs: apb::ApbTokens,
+    /// Token to create [`dfll::Dfll`]
+    pub dfll: dfll::DfllToken,
+    /// Token to create [`dpll::Dpll`]
+    pub dpll: dpll::DpllToken,
+    /// Tokens to create [`gclk::Gclk`]
+    ///
+    /// At power-on reset, the WDT peripheral channel is connected to GCLK2,
+    /// which is sourced by the OSCULP32K. GCLK2 itself is disabled.
+    pub gclks: gclk::GclkTokens,
+    /// Tokens to create [`pclk::Pclk`]s
+    pub pclks: pclk::PclkTokens,
+    /// Token to create [`osc32k::Osc32k`]
+    pub osc32k: osc32k::Osc32kToken,
+    /// Token to create [`xosc::Xosc`]
+    pub xosc: xosc::XoscToken,
+    /// Token to create [`xosc32k::Xosc32k`]
+    #[cfg(all(feature = "has-pa00", feature = "has-pa01"))]
+    pub xosc32k: xosc32k::Xosc32kToken,
+}
+
+/// Consume the PAC clocking structs and return a HAL-level
+/// representation of the clocks at power-on reset
+///
+/// This function consumes the [`SYSCTRL`], [`GCLK`] and [`PM`] PAC structs
+/// and returns the [`Buses`], [`Clocks`] and [`Tokens`].
+///
+/// It also sets one flash wait state, so that the main clock can be raised to
+/// 48 MHz without further configuration.
+///
+/// See the [module-level documentation](super) for more details.
+#[inline]
+pub fn clock_system_at_reset(
+    sysctrl: SYSCTRL,
+    gclk: GCLK,
+    pm: PM,
+    nvmctrl: &mut NVMCTRL,
+) -> (Buses, Clocks, Tokens) {
+    // Safety: No bus, clock or token is instantiated more than once
+    unsafe {
+        let buses = Buses {
+            ahb: ahb::Ahb::new(),
+            apb: apb::Apb::new(),
+        };
+        let pac = Pac { sysctrl, gclk, pm };
+        let osc8m = Enabled::<_>::new(osc8m::Osc8m::new(osc8m::Osc8mToken::new()));
+        let (gclk0, osc8m) = gclk::Gclk0::from_source(gclk::GclkToken::new(), osc8m);
+        let gclk0 = Enabled::new(gclk0);
+        let clocks = Clocks {
+            pac,
+            ahbs: ahb::AhbClks::new(),
+            apbs: apb::ApbClks::new(),
+            gclk0,
+            osc8m,
+            osculp32k: osculp32k::OscUlp32k::new(),
+        };
+        let tokens = Tokens {
+            apbs: apb::ApbTokens::new(),
+            dfll: dfll::DfllToken::new(),
+            dpll: dpll::DpllToken::new(),
+            gclks: gclk::GclkTokens::new(nvmctrl),
+            pclks: pclk::PclkTokens::new(),
+            osc32k: osc32k::Osc32kToken::new(),
+            xosc: xosc::XoscToken::new(),
+            #[cfg(all(feature = "has-pa00", feature = "has-pa01"))]
+            xosc32k: xosc32k::Xosc32kToken::new(),
+        };
+        (buses, clocks, tokens)
+    }
+}
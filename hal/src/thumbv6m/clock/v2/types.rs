@@ -0,0 +1,85 @@
+//! Module defining or exporting peripheral types for the ['ahb'], ['apb'] and
+//! ['pclk'] modules
+//!
+//! The `ahb`, `apb` and `pclk` modules each define structs that are
+//! generic over a type parameter representing a peripheral. Some peripheral
+//! modules already define suitable types for this purpose. For example,
+//! [`sercom`] defines the [`Sercom0`], [`Sercom1`], etc. types. But other
+//! peripherals are either not yet implemented in the HAL or do not define a
+//! suitable type. This module defines a type for such peripherals. If/when a
+//! suitable type is added for a given peripheral, the type defined here should
+//! be deprecated or removed.
+//!
+//! [`ahb`]: super::ahb
+//! [`apb`]: super::apb
+//! [`pclk`]: super::pclk
+//! [`sercom`]: crate::sercom
+//! [`Sercom0`]: crate::sercom::Sercom0
+//! [`Sercom1`]: crate::sercom::Sercom1
+
+use paste::paste;
+use seq_macro::seq;
+
+use crate::typelevel::Sealed;
+
+seq!(N in 0..=5 {
+    paste! {
+        #[cfg(feature = "has-" sercom~N)]
+        pub use crate::sercom::Sercom~N;
+    }
+});
+
+macro_rules! create_types {
+    (
+        $(
+            $Type:ident
+        ),+
+    ) => {
+        $(
+            /// Marker type representing the corresponding peripheral
+            ///
+            /// This type is defined by and used within the [`clock`](super)
+            /// module. See the the [`types`](self) module documentation for
+            /// more details.
+            pub enum $Type {}
+            impl Sealed for $Type {}
+        )+
+    };
+}
+
+create_types!(Ac, AcAna, AcDig);
+create_types!(Adc);
+create_types!(Dac);
+create_types!(Dmac);
+create_types!(Dpll32k);
+create_types!(Dsu);
+create_types!(Eic);
+#[cfg(feature = "samd11")]
+create_types!(EvSys, EvSys0, EvSys1, EvSys2, EvSys3, EvSys4, EvSys5);
+#[cfg(feature = "samd21")]
+create_types!(
+    EvSys, EvSys0, EvSys1, EvSys2, EvSys3, EvSys4, EvSys5, EvSys6, EvSys7, EvSys8, EvSys9, EvSys10,
+    EvSys11
+);
+create_types!(Gclk);
+create_types!(HMatrix);
+create_types!(Hpb0, Hpb1, Hpb2);
+#[cfg(feature = "has-i2s")]
+create_types!(I2S, I2S0, I2S1);
+create_types!(NvmCtrl);
+create_types!(Pac0, Pac1, Pac2);
+create_types!(Pm);
+create_types!(Port);
+create_types!(Ptc);
+create_types!(Rtc);
+create_types!(SlowClk);
+create_types!(SysCtrl);
+#[cfg(feature = "samd11")]
+create_types!(Tcc0, Tc1Tc2, Tc1, Tc2);
+#[cfg(feature = "samd21")]
+create_types!(Tcc0Tcc1, Tcc0, Tcc1, Tcc2Tc3, Tcc2, Tc3, Tc4Tc5, Tc4, Tc5);
+#[cfg(all(feature = "has-tc6", feature = "has-tc7"))]
+create_types!(Tc6Tc7, Tc6, Tc7);
+#[cfg(feature = "has-usb")]
+create_types!(Usb);
+create_types!(Wdt);
@@ -0,0 +1,527 @@
+//! # External multipurpose crystal oscillator controller
+//!
+//! ## Overview
+//!
+//! The `xosc` module provides access to the external crystal oscillator
+//! controller (XOSC) within the `SYSCTRL` peripheral.
+//!
+//! The XOSC can operate in two [`Mode`]s. It can accept an external clock in
+//! the 0-32 MHz range or can interface with a crystal oscillator in the
+//! 0.4-32 MHz range.
+//!
+//! When used with an external clock, only one GPIO [`Pin`] is required, but
+//! when used with a crystal oscillator, two GPIO `Pin`s are required. The
+//! [`XIn`] `Pin` is used in both `Mode`s, while the [`XOut`] `Pin` is only
+//! used in [`CrystalMode`].
+//!
+//! In [`CrystalMode`], the oscillator gain is selected automatically from the
+//! crystal frequency. Automatic amplitude control can be enabled to reduce
+//! power consumption.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use atsamd_hal::{
+//!     clock::v2::{
+//!         clock_system_at_reset,
+//!         gclk::Gclk,
+//!         xosc::{StartUpDelay, Xosc},
+//!     },
+//!     fugit::RateExtU32,
+//!     gpio::Pins,
+//!     pac::Peripherals,
+//! };
+//! let mut pac = Peripherals::take().unwrap();
+//! let pins = Pins::new(pac.PORT);
+//! let (buses, clocks, tokens) = clock_system_at_reset(
+//!     pac.SYSCTRL,
+//!     pac.GCLK,
+//!     pac.PM,
+//!     &mut pac.NVMCTRL,
+//! );
+//! let xosc = Xosc::from_crystal(tokens.xosc, pins.pa14, pins.pa15, 16.MHz())
+//!     .loop_control(true)
+//!     .start_up_delay(StartUpDelay::Delay8ms)
+//!     .enable();
+//! while !xosc.is_ready() {}
+//! let (gclk1, xosc) = Gclk::from_source(tokens.gclks.gclk1, xosc);
+//! let gclk1 = gclk1.enable();
+//! ```
+
+use typenum::U0;
+
+use crate::pac::sysctrl::xosc::GAIN_A;
+use crate::pac::sysctrl::XOSC;
+
+use crate::gpio::{FloatingDisabled, Pin};
+#[cfg(feature = "samd11")]
+use crate::gpio::{PA08, PA09};
+#[cfg(feature = "samd21")]
+use crate::gpio::{PA14, PA15};
+use crate::time::Hertz;
+use crate::typelevel::Sealed;
+
+use super::{Enabled, Source};
+
+//==============================================================================
+// XoscToken
+//==============================================================================
+
+/// Singleton token that can be exchanged for the [`Xosc`]
+///
+/// As explained in the [`clock` module documentation](super), instances of
+/// various `Token` types can be exchanged for actual clock types. They
+/// typically represent clocks that are disabled at power-on reset.
+///
+/// The [`Xosc`] is disabled at power-on reset. To use it, you must first
+/// exchange the token for an actual clock with [`Xosc::from_clock`] or
+/// [`Xosc::from_crystal`].
+pub struct XoscToken(());
+
+impl XoscToken {
+    /// Create a new [`XoscToken`]
+    ///
+    /// # Safety
+    ///
+    /// The `XoscToken` is a singleton. There must never be two simulatenous
+    /// instances of it. See the notes on `Token` types and memory safety in the
+    /// root of the `clock` module for more details.
+    #[inline]
+    pub(super) unsafe fn new() -> Self {
+        Self(())
+    }
+
+    #[inline]
+    fn xosc(&self) -> &XOSC {
+        // Safety: The `XoscToken` has exclusive access to the `XOSC` register.
+        // See the notes on `Token` types and memory safety in the root of the
+        // `clock` module for more details.
+        unsafe { &(*crate::pac::SYSCTRL::ptr()).xosc }
+    }
+
+    #[inline]
+    fn is_ready(&self) -> bool {
+        // Safety: We are only reading from the `PCLKSR` register, so there is
+        // no risk of memory corruption.
+        let sysctrl = unsafe { &*crate::pac::SYSCTRL::ptr() };
+        sysctrl.pclksr.read().xoscrdy().bit_is_set()
+    }
+
+    /// Write the complete configuration and enable the oscillator
+    #[inline]
+    fn enable(&mut self, settings: Settings) {
+        let xtalen = settings.mode == DynMode::CrystalMode;
+        self.xosc().write(|w| {
+            // Safety: The start up value comes from `StartUpDelay`, so it is
+            // guaranteed to be valid.
+            unsafe { w.startup().bits(settings.start_up as u8) };
+            w.gain().variant(settings.gain);
+            w.ampgc().bit(settings.loop_control);
+            w.ondemand().bit(settings.on_demand);
+            w.runstdby().bit(settings.run_standby);
+            w.xtalen().bit(xtalen);
+            w.enable().set_bit()
+        });
+    }
+
+    #[inline]
+    fn disable(&mut self) {
+        self.xosc().modify(|_, w| w.enable().clear_bit());
+    }
+}
+
+//==============================================================================
+// XoscId
+//==============================================================================
+
+/// [`Id` type](super#id-types) representing the identity of the XOSC clock
+pub enum XoscId {}
+
+impl Sealed for XoscId {}
+
+//==============================================================================
+// Pins
+//==============================================================================
+
+/// Type alias for the [`Xosc`] input [`Pin`]
+#[cfg(feature = "samd21")]
+pub type XIn = Pin<PA14, FloatingDisabled>;
+
+/// Type alias for the [`Xosc`] output [`Pin`]
+#[cfg(feature = "samd21")]
+pub type XOut = Pin<PA15, FloatingDisabled>;
+
+/// Type alias for the [`Xosc`] input [`Pin`]
+#[cfg(feature = "samd11")]
+pub type XIn = Pin<PA08, FloatingDisabled>;
+
+/// Type alias for the [`Xosc`] output [`Pin`]
+#[cfg(feature = "samd11")]
+pub type XOut = Pin<PA09, FloatingDisabled>;
+
+//==============================================================================
+// StartUpDelay
+//==============================================================================
+
+/// Start up delay before the [`Xosc`] output is enabled
+///
+/// The delay is counted in cycles of the [`OscUlp32k`] clock. The variants are
+/// named according to the nominal delay.
+///
+/// [`OscUlp32k`]: super::osculp32k::OscUlp32k
+#[repr(u8)]
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum StartUpDelay {
+    #[default]
+    Delay31us,
+    Delay61us,
+    Delay122us,
+    Delay244us,
+    Delay488us,
+    Delay977us,
+    Delay2ms,
+    Delay4ms,
+    Delay8ms,
+    Delay16ms,
+    Delay31ms,
+    Delay63ms,
+    Delay125ms,
+    Delay250ms,
+    Delay500ms,
+    Delay1s,
+}
+
+//==============================================================================
+// DynMode
+//==============================================================================
+
+/// Value-level enum identifying one of two possible [`Xosc`] operating modes
+///
+/// An [`Xosc`] can be sourced from either an external clock or crystal
+/// oscillator. The variants of this enum identify one of these two possible
+/// operating modes.
+///
+/// `DynMode` is the value-level equivalent of [`Mode`].
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum DynMode {
+    #[default]
+    ClockMode,
+    CrystalMode,
+}
+
+//==============================================================================
+// Mode
+//==============================================================================
+
+/// Type-level `enum` for the [`Xosc`] operating mode
+///
+/// An [`Xosc`] can be sourced from either an external clock or a cyrstal
+/// oscillator. This type-level `enum` provides two type-level variants,
+/// [`ClockMode`] and [`CrystalMode`], representing these operating modes.
+///
+/// `Mode` is the type-level equivalent of [`DynMode`]. See the documentation on
+/// [type-level programming] and specifically [type-level enums] for more
+/// details.
+///
+/// [type-level programming]: crate::typelevel
+/// [type-level enums]: crate::typelevel#type-level-enums
+pub trait Mode: Sealed {
+    /// Corresponding variant of [`DynMode`]
+    const DYN: DynMode;
+    #[doc(hidden)]
+    type Pins;
+}
+
+//==============================================================================
+// ClockMode
+//==============================================================================
+
+/// Type-level variant of the [`Xosc`] operating [`Mode`]
+///
+/// Represents the [`Xosc`] configured to use an externally provided clock.
+///
+/// See the documentation on [type-level programming] and specifically
+/// [type-level enums] for more details.
+///
+/// [type-level programming]: crate::typelevel
+/// [type-level enums]: crate::typelevel#type-level-enums
+pub enum ClockMode {}
+
+impl Sealed for ClockMode {}
+
+impl Mode for ClockMode {
+    const DYN: DynMode = DynMode::ClockMode;
+    type Pins = XIn;
+}
+
+//==============================================================================
+// CrystalMode
+//==============================================================================
+
+/// Type-level variant of the [`Xosc`] operating [`Mode`]
+///
+/// Represents the [`Xosc`] configured to use an external crystal oscillator.
+///
+/// See the documentation on [type-level programming] and specifically
+/// [type-level enums] for more details.
+///
+/// [type-level programming]: crate::typelevel
+/// [type-level enums]: crate::typelevel#type-level-enums
+pub enum CrystalMode {}
+
+impl Sealed for CrystalMode {}
+
+impl Mode for CrystalMode {
+    const DYN: DynMode = DynMode::CrystalMode;
+    type Pins = (XIn, XOut);
+}
+
+//==============================================================================
+// Settings
+//==============================================================================
+
+// Collection of XOSC register fields
+//
+// All of these fields are set in a single write to XOSC during the call to
+// [`Xosc::enable`].
+#[derive(Clone, Copy)]
+struct Settings {
+    start_up: StartUpDelay,
+    gain: GAIN_A,
+    loop_control: bool,
+    on_demand: bool,
+    run_standby: bool,
+    mode: DynMode,
+}
+
+//==============================================================================
+// Xosc
+//==============================================================================
+
+/// An external multipurpose crystal oscillator controller
+///
+/// An `Xosc` interfaces with either an external clock or external crystal
+/// oscillator and delivers the resulting clock to the rest of the clock system.
+///
+/// The type parameter `M` represents the operating [`Mode`], either
+/// [`ClockMode`] or [`CrystalMode`].
+///
+/// On its own, an instance of `Xosc` does not represent an enabled XOSC.
+/// Instead, it must first be wrapped with [`Enabled`], which implements
+/// compile-time safety of the clock tree.
+///
+/// Because the terminal call to [`enable`] consumes the `Xosc` and returns an
+/// [`EnabledXosc`], the remaining API uses the builder pattern, where each
+/// method takes and returns `self` by value, allowing them to be easily
+/// chained.
+///
+/// See the [module-level documentation](self) for an example of creating,
+/// configuring and using an `Xosc`.
+///
+/// [`enable`]: Xosc::enable
+pub struct Xosc<M: Mode> {
+    token: XoscToken,
+    pins: M::Pins,
+    freq: Hertz,
+    settings: Settings,
+}
+
+/// An [`Enabled`] [`Xosc`]
+///
+/// As described in the [`clock` module documentation](super), the [`Enabled`]
+/// wrapper implements compile-time clock tree safety by tracking the number of
+/// consumer clocks and restricting access to the underlying [`Xosc`] to prevent
+/// modification while in use.
+///
+/// As with [`Enabled`], the default value for `N` is `U0`; if left unspecified,
+/// the counter is assumed to be zero.
+pub type EnabledXosc<M, N = U0> = Enabled<Xosc<M>, N>;
+
+impl Xosc<ClockMode> {
+    /// Create an [`Xosc`] from an external clock, taking ownership of the
+    /// [`XIn`] [`Pin`]
+    ///
+    /// Creating a [`Xosc`] does not modify any of the hardware registers. It
+    /// only creates a struct to track the configuration. The configuration data
+    /// is stored until the user calls [`enable`]. At that point, all of the
+    /// registers are written, and an [`EnabledXosc`] is returned. The `Xosc` is
+    /// not active or useful until that point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the clock frequency is above 32 MHz.
+    ///
+    /// [`enable`]: Xosc::enable
+    #[inline]
+    pub fn from_clock(token: XoscToken, xin: impl Into<XIn>, freq: impl Into<Hertz>) -> Self {
+        let freq = freq.into();
+        if freq.to_Hz() > 32_000_000 {
+            panic!("The XOSC clock frequency must be at most 32 MHz");
+        }
+        Xosc::new(token, xin.into(), freq)
+    }
+
+    /// Consume the [`Xosc`] and release the [`XoscToken`] and [`XIn`] [`Pin`]
+    #[inline]
+    pub fn free(self) -> (XoscToken, XIn) {
+        (self.token, self.pins)
+    }
+}
+
+impl Xosc<CrystalMode> {
+    /// Create an [`Xosc`] from an external crystal oscillator, taking ownership
+    /// of the [`XIn`] and [`XOut`] [`Pin`]s.
+    ///
+    /// Creating a [`Xosc`] does not modify any of the hardware registers. It
+    /// only creates a struct to track the configuration. The configuration data
+    /// is stored until the user calls [`enable`]. At that point, all of the
+    /// registers are written, and an [`EnabledXosc`] is returned. The `Xosc` is
+    /// not active or useful until that point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the crystal frequency is outside the 0.4-32 MHz range.
+    ///
+    /// [`enable`]: Xosc::enable
+    #[inline]
+    pub fn from_crystal(
+        token: XoscToken,
+        xin: impl Into<XIn>,
+        xout: impl Into<XOut>,
+        freq: impl Into<Hertz>,
+    ) -> Self {
+        let freq = freq.into();
+        let gain = match freq.to_Hz() {
+            400_000..=2_000_000 => GAIN_A::_0,
+            2_000_001..=4_000_000 => GAIN_A::_1,
+            4_000_001..=8_000_000 => GAIN_A::_2,
+            8_000_001..=16_000_000 => GAIN_A::_3,
+            16_000_001..=32_000_000 => GAIN_A::_4,
+            _ => panic!("The XOSC crystal frequency must be 0.4-32 MHz"),
+        };
+        let mut xosc = Xosc::new(token, (xin.into(), xout.into()), freq);
+        xosc.settings.gain = gain;
+        xosc
+    }
+
+    /// Consume the [`Xosc`] and release the [`XoscToken`], [`XIn`] and [`XOut`]
+    /// [`Pin`]s
+    #[inline]
+    pub fn free(self) -> (XoscToken, XIn, XOut) {
+        let (xin, xout) = self.pins;
+        (self.token, xin, xout)
+    }
+
+    /// Toggle automatic amplitude gain control
+    ///
+    /// If enabled, the hardware will automatically adjust the oscillator
+    /// amplitude once it is stable. In most cases, this will lower power
+    /// consumption.
+    #[inline]
+    pub fn loop_control(mut self, loop_control: bool) -> Self {
+        self.settings.loop_control = loop_control;
+        self
+    }
+}
+
+impl<M: Mode> Xosc<M> {
+    #[inline]
+    fn new(token: XoscToken, pins: M::Pins, freq: Hertz) -> Self {
+        let settings = Settings {
+            start_up: StartUpDelay::Delay31us,
+            gain: GAIN_A::_0,
+            loop_control: false,
+            on_demand: true,
+            run_standby: false,
+            mode: M::DYN,
+        };
+        Self {
+            token,
+            pins,
+            freq,
+            settings,
+        }
+    }
+
+    /// Return the clock or crystal frequency
+    #[inline]
+    pub fn freq(&self) -> Hertz {
+        self.freq
+    }
+
+    /// Set the start up delay before the [`Xosc`] output is enabled
+    ///
+    /// During the start up period, the [`Xosc`] is masked to prevent clock
+    /// instability from propagating to the digital logic.
+    #[inline]
+    pub fn start_up_delay(mut self, delay: StartUpDelay) -> Self {
+        self.settings.start_up = delay;
+        self
+    }
+
+    /// Control the [`Xosc`] on-demand behavior
+    ///
+    /// When on-demand is enabled, the [`Xosc`] will only run when requested by
+    /// a peripheral. Otherwise, it will always run, and its behavior in
+    /// standby sleep mode depends on the run-standby setting.
+    #[inline]
+    pub fn on_demand(mut self, on_demand: bool) -> Self {
+        self.settings.on_demand = on_demand;
+        self
+    }
+
+    /// Control the [`Xosc`] behavior in standby sleep mode
+    #[inline]
+    pub fn run_standby(mut self, run_standby: bool) -> Self {
+        self.settings.run_standby = run_standby;
+        self
+    }
+
+    /// Enable the [`Xosc`], so that it can be used as a clock [`Source`]
+    ///
+    /// As mentioned when creating a new `Xosc`, no hardware registers are
+    /// actually modified until this call. Rather, the desired configuration is
+    /// stored internally, and the `Xosc` is configured and enabled here.
+    ///
+    /// The returned value is an [`EnabledXosc`] that can be used as a clock
+    /// [`Source`] for other clocks.
+    #[inline]
+    pub fn enable(mut self) -> EnabledXosc<M> {
+        self.token.enable(self.settings);
+        Enabled::new(self)
+    }
+}
+
+impl<M: Mode> EnabledXosc<M> {
+    /// Disable the [`Xosc`]
+    ///
+    /// This method is only implemented for `N = U0`, which means the clock can
+    /// only be disabled when no other clocks consume this [`Xosc`].
+    #[inline]
+    pub fn disable(mut self) -> Xosc<M> {
+        self.0.token.disable();
+        self.0
+    }
+}
+
+impl<M: Mode, N> EnabledXosc<M, N> {
+    /// Check whether the [`Xosc`] is stable and ready to be used as a clock
+    /// [`Source`]
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        self.0.token.is_ready()
+    }
+}
+
+//==============================================================================
+// Source
+//==============================================================================
+
+impl<M: Mode, N> Source for EnabledXosc<M, N> {
+    type Id = XoscId;
+
+    #[inline]
+    fn freq(&self) -> Hertz {
+        self.0.freq()
+    }
+}
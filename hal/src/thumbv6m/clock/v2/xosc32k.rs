@@ -0,0 +1,488 @@
+//! # External 32 kHz crystal oscillator controller
+//!
+//! ## Overview
+//!
+//! The `xosc32k` module provides access to the 32 kHz external crystal
+//! oscillator controller (XOSC32K) within the `SYSCTRL` peripheral.
+//!
+//! The XOSC32K can operate in two [`Mode`]s. It can accept an external clock
+//! or can interface with a 32.768 kHz crystal oscillator. When used with an
+//! external clock, only the [`XIn32`] [`Pin`] is required. When used with a
+//! crystal oscillator, both the [`XIn32`] and [`XOut32`] `Pin`s are required.
+//!
+//! Once enabled, the XOSC32K can act as a [`Source`] for any of the [`Gclk`]s,
+//! and it can directly drive the reference of the [`Dpll`]. It is the most
+//! common reference for the [`Dfll`] in closed-loop mode.
+//!
+//! ## Write lock
+//!
+//! The `XOSC32K` register has a write lock bit that will freeze its
+//! configuration until the next power-on reset. We implement this by simply
+//! dropping the [`Xosc32k`] clock, which prevents any further access to the
+//! `XOSC32K` register.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use atsamd_hal::{
+//!     clock::v2::{
+//!         clock_system_at_reset,
+//!         gclk::Gclk,
+//!         xosc32k::{StartUpDelay, Xosc32k},
+//!     },
+//!     gpio::Pins,
+//!     pac::Peripherals,
+//! };
+//! let mut pac = Peripherals::take().unwrap();
+//! let pins = Pins::new(pac.PORT);
+//! let (buses, clocks, tokens) = clock_system_at_reset(
+//!     pac.SYSCTRL,
+//!     pac.GCLK,
+//!     pac.PM,
+//!     &mut pac.NVMCTRL,
+//! );
+//! let xosc32k = Xosc32k::from_crystal(tokens.xosc32k, pins.pa00, pins.pa01)
+//!     .control_gain(true)
+//!     .start_up_delay(StartUpDelay::Delay1s)
+//!     .enable();
+//! while !xosc32k.is_ready() {}
+//! let (gclk1, xosc32k) = Gclk::from_source(tokens.gclks.gclk1, xosc32k);
+//! let gclk1 = gclk1.enable();
+//! ```
+//!
+//! [`Gclk`]: super::gclk::Gclk
+//! [`Dfll`]: super::dfll::Dfll
+//! [`Dpll`]: super::dpll::Dpll
+
+use fugit::RateExtU32;
+use typenum::U0;
+
+use crate::pac::sysctrl::XOSC32K;
+
+use crate::gpio::{FloatingDisabled, Pin, PA00, PA01};
+use crate::time::Hertz;
+use crate::typelevel::Sealed;
+
+use super::{Enabled, Source};
+
+//==============================================================================
+// Xosc32kToken
+//==============================================================================
+
+/// Singleton token that can be exchanged for the [`Xosc32k`]
+///
+/// As explained in the [`clock` module documentation](super), instances of
+/// various `Token` types can be exchanged for actual clock types. They
+/// typically represent clocks that are disabled at power-on reset.
+///
+/// The [`Xosc32k`] is disabled at power-on reset. To use it, you must first
+/// exchange the token for an actual clock with [`Xosc32k::from_clock`] or
+/// [`Xosc32k::from_crystal`].
+pub struct Xosc32kToken(());
+
+impl Xosc32kToken {
+    /// Create a new [`Xosc32kToken`]
+    ///
+    /// # Safety
+    ///
+    /// The `Xosc32kToken` is a singleton. There must never be two simulatenous
+    /// instances of it. See the notes on `Token` types and memory safety in the
+    /// root of the `clock` module for more details.
+    #[inline]
+    pub(super) unsafe fn new() -> Self {
+        Self(())
+    }
+
+    #[inline]
+    fn xosc32k(&self) -> &XOSC32K {
+        // Safety: The `Xosc32kToken` has exclusive access to the `XOSC32K`
+        // register. See the notes on `Token` types and memory safety in the
+        // root of the `clock` module for more details.
+        unsafe { &(*crate::pac::SYSCTRL::ptr()).xosc32k }
+    }
+
+    #[inline]
+    fn is_ready(&self) -> bool {
+        // Safety: We are only reading from the `PCLKSR` register, so there is
+        // no risk of memory corruption.
+        let sysctrl = unsafe { &*crate::pac::SYSCTRL::ptr() };
+        sysctrl.pclksr.read().xosc32krdy().bit_is_set()
+    }
+
+    /// Write the complete configuration and enable the oscillator
+    #[inline]
+    fn enable(&mut self, settings: Settings) {
+        let xtalen = settings.mode == DynMode::CrystalMode;
+        self.xosc32k().write(|w| {
+            // Safety: The start up value comes from `StartUpDelay`, so it is
+            // guaranteed to be valid.
+            unsafe { w.startup().bits(settings.start_up as u8) };
+            w.aampen().bit(settings.control_gain);
+            w.en1k().bit(settings.output_1k);
+            w.en32k().set_bit();
+            w.ondemand().bit(settings.on_demand);
+            w.runstdby().bit(settings.run_standby);
+            w.xtalen().bit(xtalen);
+            w.enable().set_bit()
+        });
+    }
+
+    #[inline]
+    fn disable(&mut self) {
+        self.xosc32k().modify(|_, w| w.enable().clear_bit());
+    }
+
+    #[inline]
+    fn write_lock(&mut self) {
+        self.xosc32k().modify(|_, w| w.wrtlock().set_bit());
+    }
+}
+
+//==============================================================================
+// Xosc32kId
+//==============================================================================
+
+/// [`Id` type](super#id-types) representing the identity of the XOSC32K clock
+pub enum Xosc32kId {}
+
+impl Sealed for Xosc32kId {}
+
+//==============================================================================
+// Pins
+//==============================================================================
+
+/// Type alias for the [`Xosc32k`] input [`Pin`]
+pub type XIn32 = Pin<PA00, FloatingDisabled>;
+
+/// Type alias for the [`Xosc32k`] output [`Pin`]
+pub type XOut32 = Pin<PA01, FloatingDisabled>;
+
+//==============================================================================
+// StartUpDelay
+//==============================================================================
+
+/// Start up delay before the [`Xosc32k`] output is enabled
+///
+/// The delay is counted in cycles of the [`OscUlp32k`] clock. The variants are
+/// named according to the nominal delay.
+///
+/// [`OscUlp32k`]: super::osculp32k::OscUlp32k
+#[repr(u8)]
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum StartUpDelay {
+    #[default]
+    Delay122us,
+    Delay1ms,
+    Delay63ms,
+    Delay125ms,
+    Delay500ms,
+    Delay1s,
+    Delay2s,
+    Delay4s,
+}
+
+//==============================================================================
+// DynMode
+//==============================================================================
+
+/// Value-level enum identifying one of two possible [`Xosc32k`] operating
+/// modes
+///
+/// `DynMode` is the value-level equivalent of [`Mode`].
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum DynMode {
+    #[default]
+    ClockMode,
+    CrystalMode,
+}
+
+//==============================================================================
+// Mode
+//==============================================================================
+
+/// Type-level `enum` for the [`Xosc32k`] operating mode
+///
+/// An [`Xosc32k`] can be sourced from either an external clock or a cyrstal
+/// oscillator. This type-level `enum` provides two type-level variants,
+/// [`ClockMode`] and [`CrystalMode`], representing these operating modes.
+///
+/// `Mode` is the type-level equivalent of [`DynMode`]. See the documentation on
+/// [type-level programming] and specifically [type-level enums] for more
+/// details.
+///
+/// [type-level programming]: crate::typelevel
+/// [type-level enums]: crate::typelevel#type-level-enums
+pub trait Mode: Sealed {
+    /// Corresponding variant of [`DynMode`]
+    const DYN: DynMode;
+    #[doc(hidden)]
+    type Pins;
+}
+
+//==============================================================================
+// ClockMode
+//==============================================================================
+
+/// Type-level variant of the [`Xosc32k`] operating [`Mode`]
+///
+/// Represents the [`Xosc32k`] configured to use an externally provided clock.
+///
+/// See the documentation on [type-level programming] and specifically
+/// [type-level enums] for more details.
+///
+/// [type-level programming]: crate::typelevel
+/// [type-level enums]: crate::typelevel#type-level-enums
+pub enum ClockMode {}
+
+impl Sealed for ClockMode {}
+
+impl Mode for ClockMode {
+    const DYN: DynMode = DynMode::ClockMode;
+    type Pins = XIn32;
+}
+
+//==============================================================================
+// CrystalMode
+//==============================================================================
+
+/// Type-level variant of the [`Xosc32k`] operating [`Mode`]
+///
+/// Represents the [`Xosc32k`] configured to use an external crystal
+/// oscillator.
+///
+/// See the documentation on [type-level programming] and specifically
+/// [type-level enums] for more details.
+///
+/// [type-level programming]: crate::typelevel
+/// [type-level enums]: crate::typelevel#type-level-enums
+pub enum CrystalMode {}
+
+impl Sealed for CrystalMode {}
+
+impl Mode for CrystalMode {
+    const DYN: DynMode = DynMode::CrystalMode;
+    type Pins = (XIn32, XOut32);
+}
+
+//==============================================================================
+// Settings
+//==============================================================================
+
+// Collection of XOSC32K register fields
+//
+// All of these fields are set in a single write to XOSC32K during the call to
+// [`Xosc32k::enable`].
+#[derive(Clone, Copy)]
+struct Settings {
+    start_up: StartUpDelay,
+    control_gain: bool,
+    output_1k: bool,
+    on_demand: bool,
+    run_standby: bool,
+    mode: DynMode,
+}
+
+//==============================================================================
+// Xosc32k
+//==============================================================================
+
+/// External 32 kHz crystal oscillator controller
+///
+/// The type parameter `M` represents the operating [`Mode`], either
+/// [`ClockMode`] or [`CrystalMode`].
+///
+/// On its own, an instance of `Xosc32k` does not represent an enabled
+/// XOSC32K. Instead, it must first be wrapped with [`Enabled`], which
+/// implements compile-time safety of the clock tree.
+///
+/// Because the terminal call to [`enable`] consumes the `Xosc32k` and returns
+/// an [`EnabledXosc32k`], the remaining API uses the builder pattern, where
+/// each method takes and returns `self` by value, allowing them to be easily
+/// chained.
+///
+/// See the [module-level documentation](self) for an example.
+///
+/// [`enable`]: Xosc32k::enable
+pub struct Xosc32k<M: Mode> {
+    token: Xosc32kToken,
+    pins: M::Pins,
+    settings: Settings,
+}
+
+/// An [`Enabled`] [`Xosc32k`]
+///
+/// As described in the [`clock` module documentation](super), the [`Enabled`]
+/// wrapper implements compile-time clock tree safety by tracking the number of
+/// consumer clocks and restricting access to the underlying [`Xosc32k`] to
+/// prevent modification while in use.
+///
+/// As with [`Enabled`], the default value for `N` is `U0`; if left unspecified,
+/// the counter is assumed to be zero.
+pub type EnabledXosc32k<M, N = U0> = Enabled<Xosc32k<M>, N>;
+
+impl Xosc32k<ClockMode> {
+    /// Create an [`Xosc32k`] from an external clock, taking ownership of the
+    /// [`XIn32`] [`Pin`]
+    ///
+    /// Creating an [`Xosc32k`] does not modify any of the hardware registers.
+    /// The configuration is stored until the user calls [`enable`].
+    ///
+    /// [`enable`]: Xosc32k::enable
+    #[inline]
+    pub fn from_clock(token: Xosc32kToken, xin32: impl Into<XIn32>) -> Self {
+        Xosc32k::new(token, xin32.into())
+    }
+
+    /// Consume the [`Xosc32k`] and release the [`Xosc32kToken`] and [`XIn32`]
+    /// [`Pin`]
+    #[inline]
+    pub fn free(self) -> (Xosc32kToken, XIn32) {
+        (self.token, self.pins)
+    }
+}
+
+impl Xosc32k<CrystalMode> {
+    /// Create an [`Xosc32k`] from a 32.768 kHz crystal oscillator, taking
+    /// ownership of the [`XIn32`] and [`XOut32`] [`Pin`]s
+    ///
+    /// Creating an [`Xosc32k`] does not modify any of the hardware registers.
+    /// The configuration is stored until the user calls [`enable`].
+    ///
+    /// [`enable`]: Xosc32k::enable
+    #[inline]
+    pub fn from_crystal(
+        token: Xosc32kToken,
+        xin32: impl Into<XIn32>,
+        xout32: impl Into<XOut32>,
+    ) -> Self {
+        Xosc32k::new(token, (xin32.into(), xout32.into()))
+    }
+
+    /// Consume the [`Xosc32k`] and release the [`Xosc32kToken`], [`XIn32`] and
+    /// [`XOut32`] [`Pin`]s
+    #[inline]
+    pub fn free(self) -> (Xosc32kToken, XIn32, XOut32) {
+        let (xin32, xout32) = self.pins;
+        (self.token, xin32, xout32)
+    }
+
+    /// Toggle automatic amplitude control of the crystal oscillator
+    #[inline]
+    pub fn control_gain(mut self, control_gain: bool) -> Self {
+        self.settings.control_gain = control_gain;
+        self
+    }
+}
+
+impl<M: Mode> Xosc32k<M> {
+    #[inline]
+    fn new(token: Xosc32kToken, pins: M::Pins) -> Self {
+        let settings = Settings {
+            start_up: StartUpDelay::Delay122us,
+            control_gain: false,
+            output_1k: false,
+            on_demand: true,
+            run_standby: false,
+            mode: M::DYN,
+        };
+        Self {
+            token,
+            pins,
+            settings,
+        }
+    }
+
+    /// Set the start up delay before the [`Xosc32k`] output is enabled
+    #[inline]
+    pub fn start_up_delay(mut self, delay: StartUpDelay) -> Self {
+        self.settings.start_up = delay;
+        self
+    }
+
+    /// Toggle the 1 kHz output
+    ///
+    /// The 1 kHz output is only used by the RTC. The 32 kHz output is always
+    /// enabled.
+    #[inline]
+    pub fn output_1k(mut self, output_1k: bool) -> Self {
+        self.settings.output_1k = output_1k;
+        self
+    }
+
+    /// Control the [`Xosc32k`] on-demand behavior
+    ///
+    /// When on-demand is enabled, the [`Xosc32k`] will only run when requested
+    /// by a peripheral. Otherwise, it will always run, and its behavior in
+    /// standby sleep mode depends on the run-standby setting.
+    #[inline]
+    pub fn on_demand(mut self, on_demand: bool) -> Self {
+        self.settings.on_demand = on_demand;
+        self
+    }
+
+    /// Control the [`Xosc32k`] behavior in standby sleep mode
+    #[inline]
+    pub fn run_standby(mut self, run_standby: bool) -> Self {
+        self.settings.run_standby = run_standby;
+        self
+    }
+
+    /// Enable the [`Xosc32k`], so that it can be used as a clock [`Source`]
+    ///
+    /// As mentioned when creating a new `Xosc32k`, no hardware registers are
+    /// actually modified until this call. Rather, the desired configuration is
+    /// stored internally, and the `Xosc32k` is configured and enabled here.
+    ///
+    /// The returned value is an [`EnabledXosc32k`] that can be used as a clock
+    /// [`Source`] for other clocks.
+    #[inline]
+    pub fn enable(mut self) -> EnabledXosc32k<M> {
+        self.token.enable(self.settings);
+        Enabled::new(self)
+    }
+}
+
+impl<M: Mode> EnabledXosc32k<M> {
+    /// Disable the [`Xosc32k`]
+    ///
+    /// This method is only implemented for `N = U0`, which means the clock can
+    /// only be disabled when no other clocks consume this [`Xosc32k`].
+    #[inline]
+    pub fn disable(mut self) -> Xosc32k<M> {
+        self.0.token.disable();
+        self.0
+    }
+}
+
+impl<M: Mode, N> EnabledXosc32k<M, N> {
+    /// Check whether the [`Xosc32k`] is stable and ready to be used as a clock
+    /// [`Source`]
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        self.0.token.is_ready()
+    }
+
+    /// Freeze the XOSC32K configuration until power-on reset
+    ///
+    /// This function sets the write-lock bit, which freezes the XOSC32K
+    /// configuration at the hardware level until power-on reset. At the API
+    /// level, it also consumes and drops the [`Xosc32k`] clock, which
+    /// prevents any further modifications.
+    #[inline]
+    pub fn write_lock(mut self) {
+        self.0.token.write_lock();
+    }
+}
+
+//==============================================================================
+// Source
+//==============================================================================
+
+impl<M: Mode, N> Source for EnabledXosc32k<M, N> {
+    type Id = Xosc32kId;
+
+    #[inline]
+    fn freq(&self) -> Hertz {
+        32_768.Hz()
+    }
+}
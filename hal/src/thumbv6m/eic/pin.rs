@@ -1,5 +1,6 @@
-#[cfg(feature = "unproven")]
-use crate::ehal::digital::v2::InputPin;
+use crate::ehal_1::digital::{ErrorType, InputPin};
+#[cfg(feature = "ehal-02")]
+use crate::ehal_02::digital::v2 as ehal_02;
 use crate::gpio::{
     self, pin::*, AnyPin, FloatingInterrupt, PinMode, PullDownInterrupt, PullUpInterrupt,
 };
@@ -156,23 +157,46 @@ crate::paste::item! {
         }
     }
 
-    #[cfg(feature = "unproven")]
-    impl<GPIO, C> InputPin for [<$PadType $num>]<GPIO>
+    impl<GPIO, C> ErrorType for [<$PadType $num>]<GPIO>
     where
         GPIO: AnyPin<Mode = Interrupt<C>>,
         C: InterruptConfig,
     {
         type Error = core::convert::Infallible;
+    }
+
+    impl<GPIO, C> InputPin for [<$PadType $num>]<GPIO>
+    where
+        GPIO: AnyPin<Mode = Interrupt<C>>,
+        C: InterruptConfig,
+    {
         #[inline]
-        fn is_high(&self) -> Result<bool, Self::Error> {
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
             self._pin.is_high()
         }
         #[inline]
-        fn is_low(&self) -> Result<bool, Self::Error> {
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
             self._pin.is_low()
         }
     }
 
+    #[cfg(feature = "ehal-02")]
+    impl<GPIO, C> ehal_02::InputPin for [<$PadType $num>]<GPIO>
+    where
+        GPIO: AnyPin<Mode = Interrupt<C>>,
+        C: InterruptConfig,
+    {
+        type Error = core::convert::Infallible;
+        #[inline]
+        fn is_high(&self) -> Result<bool, core::convert::Infallible> {
+            Ok(self._pin._is_high())
+        }
+        #[inline]
+        fn is_low(&self) -> Result<bool, core::convert::Infallible> {
+            Ok(self._pin._is_low())
+        }
+    }
+
     $(
         $(#[$attr])*
         impl<M: PinMode> EicPin for Pin<gpio::$PinType, M> {
@@ -1,8 +1,12 @@
+use core::convert::Infallible;
+
 use paste::paste;
 use seq_macro::seq;
 
 use crate::clock;
-use crate::ehal::{Pwm, PwmPin};
+#[cfg(feature = "ehal-02")]
+use crate::ehal_02;
+use crate::ehal_1::pwm::{ErrorType, SetDutyCycle};
 use crate::pac::PM;
 use crate::time::Hertz;
 use crate::timer_params::TimerParams;
@@ -99,39 +103,75 @@ impl $TYPE {
         let top = count.cc[0].read().cc().bits();
         self.clock_freq / divisor as u32 / (top + 1) as u32
     }
-}
 
-impl PwmPin for $TYPE {
-    type Duty = u16;
-
-    fn disable(&mut self) {
+    pub fn disable(&mut self) {
         let count = self.tc.count16();
         count.ctrla.modify(|_, w| w.enable().clear_bit());
     }
 
-    fn enable(&mut self) {
+    pub fn enable(&mut self) {
         let count = self.tc.count16();
         count.ctrla.modify(|_, w| w.enable().set_bit());
     }
 
-    fn get_duty(&self) -> Self::Duty {
+    pub fn get_duty(&self) -> u16 {
         let count = self.tc.count16();
         let duty: u16 = count.cc[1].read().cc().bits();
         duty
     }
 
-    fn get_max_duty(&self) -> Self::Duty {
+    pub fn get_max_duty(&self) -> u16 {
         let count = self.tc.count16();
         let top = count.cc[0].read().cc().bits();
         top
     }
 
-    fn set_duty(&mut self, duty: Self::Duty) {
+    pub fn set_duty(&mut self, duty: u16) {
         let count = self.tc.count16();
         count.cc[1].write(|w| unsafe { w.cc().bits(duty) });
     }
 }
 
+impl ErrorType for $TYPE {
+    type Error = Infallible;
+}
+
+impl SetDutyCycle for $TYPE {
+    fn max_duty_cycle(&self) -> u16 {
+        self.get_max_duty()
+    }
+
+    fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Infallible> {
+        self.set_duty(duty);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ehal-02")]
+impl ehal_02::PwmPin for $TYPE {
+    type Duty = u16;
+
+    fn disable(&mut self) {
+        $TYPE::disable(self);
+    }
+
+    fn enable(&mut self) {
+        $TYPE::enable(self);
+    }
+
+    fn get_duty(&self) -> u16 {
+        $TYPE::get_duty(self)
+    }
+
+    fn get_max_duty(&self) -> u16 {
+        $TYPE::get_max_duty(self)
+    }
+
+    fn set_duty(&mut self, duty: u16) {
+        $TYPE::set_duty(self, duty);
+    }
+}
+
 )+}}
 
 #[cfg(feature = "has-tc1")]
@@ -160,6 +200,14 @@ pub enum Channel {
     _3,
 }
 
+/// A single channel of a TCC PWM
+///
+/// Create this struct by calling the `channel` method of a TCC PWM.
+pub struct PwmChannel<'a, P> {
+    pwm: &'a mut P,
+    channel: Channel,
+}
+
 macro_rules! pwm_tcc {
     ($($TYPE:ident: ($TCC:ident, $clock:ident, $apmask:ident, $apbits:ident, $wrapper:ident)),+) => {
         $(
@@ -212,48 +260,42 @@ impl $TYPE {
             tcc,
         }
     }
-}
-
-impl Pwm for $TYPE {
-    type Channel = Channel;
-    type Time = Hertz;
-    type Duty = u32;
 
-    fn disable(&mut self, _channel: Self::Channel) {
+    pub fn disable(&mut self, _channel: Channel) {
         self.tcc.ctrla.modify(|_, w| w.enable().clear_bit());
     }
 
-    fn enable(&mut self, _channel: Self::Channel) {
+    pub fn enable(&mut self, _channel: Channel) {
         self.tcc.ctrla.modify(|_, w| w.enable().set_bit());
     }
 
-    fn get_period(&self) -> Self::Time {
+    pub fn get_period(&self) -> Hertz {
         let divisor = self.tcc.ctrla.read().prescaler().bits();
         let top = self.tcc.per().read().bits();
         self.clock_freq / divisor as u32 / (top + 1) as u32
     }
 
-    fn get_duty(&self, channel: Self::Channel) -> Self::Duty {
+    pub fn get_duty(&self, channel: Channel) -> u32 {
         let cc = self.tcc.cc();
         let duty: u32 = cc[channel as usize].read().cc().bits();
         duty
     }
 
-    fn get_max_duty(&self) -> Self::Duty {
+    pub fn get_max_duty(&self) -> u32 {
         let top = self.tcc.per().read().bits();
         top
     }
 
-    fn set_duty(&mut self, channel: Self::Channel, duty: Self::Duty) {
+    pub fn set_duty(&mut self, channel: Channel, duty: u32) {
         let cc = self.tcc.cc();
         cc[channel as usize].write(|w| unsafe { w.cc().bits(duty) });
     }
 
-    fn set_period<P>(&mut self, period: P)
+    pub fn set_period<P>(&mut self, period: P)
     where
-        P: Into<Self::Time>,
+        P: Into<Hertz>,
     {
-        let period = period.into();
+        let period: Hertz = period.into();
         let params = TimerParams::new(period, self.clock_freq);
         self.tcc.ctrla.modify(|_, w| w.enable().clear_bit());
         self.tcc.ctrla.modify(|_, w| {
@@ -273,6 +315,72 @@ impl Pwm for $TYPE {
         self.tcc.per().write(|w| unsafe { w.bits(params.cycles as u32) });
         while self.tcc.syncbusy.read().per().bit() {}
     }
+
+    /// Borrow a single channel, which implements [`SetDutyCycle`]
+    pub fn channel(&mut self, channel: Channel) -> PwmChannel<'_, Self> {
+        PwmChannel { pwm: self, channel }
+    }
+}
+
+impl ErrorType for PwmChannel<'_, $TYPE> {
+    type Error = Infallible;
+}
+
+impl SetDutyCycle for PwmChannel<'_, $TYPE> {
+    fn max_duty_cycle(&self) -> u16 {
+        self.pwm.get_max_duty().min(u16::MAX as u32) as u16
+    }
+
+    fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Infallible> {
+        let top = self.pwm.get_max_duty();
+        let max = self.max_duty_cycle() as u32;
+        // Scale the duty cycle if the period doesn't fit in 16 bits
+        let duty = if top > max {
+            (duty as u64 * top as u64 / max as u64) as u32
+        } else {
+            duty as u32
+        };
+        self.pwm.set_duty(self.channel, duty);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ehal-02")]
+impl ehal_02::Pwm for $TYPE {
+    type Channel = Channel;
+    type Time = Hertz;
+    type Duty = u32;
+
+    fn disable(&mut self, channel: Channel) {
+        $TYPE::disable(self, channel);
+    }
+
+    fn enable(&mut self, channel: Channel) {
+        $TYPE::enable(self, channel);
+    }
+
+    fn get_period(&self) -> Hertz {
+        $TYPE::get_period(self)
+    }
+
+    fn get_duty(&self, channel: Channel) -> u32 {
+        $TYPE::get_duty(self, channel)
+    }
+
+    fn get_max_duty(&self) -> u32 {
+        $TYPE::get_max_duty(self)
+    }
+
+    fn set_duty(&mut self, channel: Channel, duty: u32) {
+        $TYPE::set_duty(self, channel, duty);
+    }
+
+    fn set_period<P>(&mut self, period: P)
+    where
+        P: Into<Hertz>,
+    {
+        $TYPE::set_period(self, period);
+    }
 }
 
 )+}}
@@ -0,0 +1,181 @@
+//! # Die temperature measurement
+//!
+//! The SAMD5x/E5x have two temperature sensors, `PTAT` (proportional to
+//! absolute temperature) and `CTAT` (complementary to absolute temperature),
+//! which can be converted by either ADC instance. Each device is calibrated in
+//! the factory at two temperatures, and the calibration values are stored in
+//! the NVM software calibration area, see
+//! [`Nvm::temperatures_calibration_area`].
+//!
+//! A [`TemperatureSensor`] enables the sensors, converts both of them against
+//! the internal bandgap reference and applies the calibration to return the
+//! temperature in °C.
+//!
+//! ```no_run
+//! use atsamd_hal::adc::temperature::TemperatureSensor;
+//! # use atsamd_hal::adc::v2::Adc;
+//! # use atsamd_hal::clock::v2::{gclk::Gclk0Id, types::Adc0};
+//! # use atsamd_hal::nvm::Nvm;
+//! # fn example(adc: &mut Adc<Adc0, Gclk0Id>, nvm: &Nvm) {
+//! # let mut pac = unsafe { atsamd_hal::pac::Peripherals::steal() };
+//! let mut sensor = TemperatureSensor::new(&mut pac.SUPC, nvm.temperatures_calibration_area());
+//! let celsius = sensor.read(adc);
+//! # }
+//! ```
+//!
+//! [`Nvm::temperatures_calibration_area`]: crate::nvm::Nvm::temperatures_calibration_area
+
+use crate::clock::v2::pclk::PclkSourceId;
+use crate::nvm::TemperaturesCalibrationArea;
+use crate::pac::SUPC;
+
+use super::v2::{Adc, AdcId, Input, Internal, Reference};
+
+//==============================================================================
+// Calibration
+//==============================================================================
+
+/// Factory calibration of the temperature sensors
+///
+/// The sensor values are 12-bit ADC results, measured against the internal
+/// bandgap reference.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Calibration {
+    /// Low calibration temperature, in °C
+    pub tl: f32,
+    /// High calibration temperature, in °C
+    pub th: f32,
+    /// `PTAT` result at the low temperature
+    pub vpl: u16,
+    /// `PTAT` result at the high temperature
+    pub vph: u16,
+    /// `CTAT` result at the low temperature
+    pub vcl: u16,
+    /// `CTAT` result at the high temperature
+    pub vch: u16,
+}
+
+impl Calibration {
+    /// Compute the temperature, in °C, from 12-bit `PTAT` and `CTAT` results
+    ///
+    /// This is the equation of the "Device Temperature Measurement" section of
+    /// the datasheet.
+    #[inline]
+    pub fn celsius(&self, tp: u16, tc: u16) -> f32 {
+        let (tl, th) = (self.tl, self.th);
+        let vpl = f32::from(self.vpl);
+        let vph = f32::from(self.vph);
+        let vcl = f32::from(self.vcl);
+        let vch = f32::from(self.vch);
+        let tp = f32::from(tp);
+        let tc = f32::from(tc);
+
+        (tl * vph * tc - vpl * th * tc - tl * vch * tp + th * vcl * tp)
+            / (vcl * tp - vch * tp - vpl * tc + vph * tc)
+    }
+}
+
+impl From<TemperaturesCalibrationArea> for Calibration {
+    #[inline]
+    fn from(area: TemperaturesCalibrationArea) -> Self {
+        Self {
+            tl: area.tli() as f32 + area.tld() as f32 / 16.0,
+            th: area.thi() as f32 + area.thd() as f32 / 16.0,
+            vpl: area.vpl() as u16,
+            vph: area.vph() as u16,
+            vcl: area.vcl() as u16,
+            vch: area.vch() as u16,
+        }
+    }
+}
+
+//==============================================================================
+// TemperatureSensor
+//==============================================================================
+
+/// Calibrated die temperature sensor
+pub struct TemperatureSensor {
+    calibration: Calibration,
+}
+
+impl TemperatureSensor {
+    /// Enable the temperature sensors
+    ///
+    /// This sets `SUPC.VREF.TSEN`. The calibration is usually obtained from
+    /// [`Nvm::temperatures_calibration_area`].
+    ///
+    /// [`Nvm::temperatures_calibration_area`]: crate::nvm::Nvm::temperatures_calibration_area
+    #[inline]
+    pub fn new(supc: &mut SUPC, calibration: impl Into<Calibration>) -> Self {
+        supc.vref.modify(|_, w| w.tsen().set_bit());
+        Self {
+            calibration: calibration.into(),
+        }
+    }
+
+    /// Factory calibration used by this sensor
+    #[inline]
+    pub fn calibration(&self) -> &Calibration {
+        &self.calibration
+    }
+
+    /// Measure the die temperature, in °C
+    ///
+    /// Both sensors are converted against the internal bandgap reference, and
+    /// the previous reference is restored afterwards. The ADC must produce
+    /// 12-bit results, ie it must not be configured to oversample, nor to
+    /// average more than 16 samples.
+    #[inline]
+    pub fn read<I: AdcId, S: PclkSourceId>(&mut self, adc: &mut Adc<I, S>) -> f32 {
+        let (tp, tc) = adc.with_reference(Reference::INTREF, |adc| {
+            (
+                adc.read(Input::internal(Internal::Ptat)),
+                adc.read(Input::internal(Internal::Ctat)),
+            )
+        });
+        self.calibration.celsius(tp, tc)
+    }
+
+    /// Disable the temperature sensors
+    #[inline]
+    pub fn free(self, supc: &mut SUPC) {
+        supc.vref.modify(|_, w| w.tsen().clear_bit());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CALIBRATION: Calibration = Calibration {
+        tl: 25.5,
+        th: 85.25,
+        vpl: 1200,
+        vph: 1500,
+        vcl: 2300,
+        vch: 1900,
+    };
+
+    fn assert_close(actual: f32, expected: f32) {
+        let error = actual - expected;
+        assert!(
+            error < 0.01 && error > -0.01,
+            "{} is not close to {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn calibration_points() {
+        assert_close(CALIBRATION.celsius(1200, 2300), 25.5);
+        assert_close(CALIBRATION.celsius(1500, 1900), 85.25);
+    }
+
+    #[test]
+    fn between_calibration_points() {
+        let celsius = CALIBRATION.celsius(1350, 2100);
+        assert!(celsius > 25.5 && celsius < 85.25);
+    }
+}
@@ -0,0 +1,825 @@
+//! # Analog-to-Digital Converter, clocked by [`clock::v2`]
+//!
+//! Each ADC instance is identified at the type level by an [`AdcId`], which is
+//! one of the [`clock::v2`] types [`Adc0`] and [`Adc1`]. An [`Adc`] is created
+//! from its PAC struct, its [`ApbClk`] and its [`Pclk`], and a [`Config`].
+//!
+//! # Inputs
+//!
+//! Conversions are made on an [`Input`], which can be
+//!
+//! - a single-ended pin, checked at compile time by [`Input::pin`],
+//! - a differential pair of pins, created by [`Input::differential`],
+//! - one of the [`Internal`] signals, such as the scaled supplies, the bandgap
+//!   or the temperature sensors.
+//!
+//! Differential results are signed and should be interpreted as `i16`.
+//!
+//! ```no_run
+//! use atsamd_hal::adc::v2::{Adc, Config, Input, Internal};
+//! use atsamd_hal::clock::v2::{clock_system_at_reset, pclk::Pclk};
+//! use atsamd_hal::gpio::{Pins, B};
+//! # let mut pac = atsamd_hal::pac::Peripherals::take().unwrap();
+//! let (mut buses, clocks, tokens) = clock_system_at_reset(
+//!     pac.OSCCTRL,
+//!     pac.OSC32KCTRL,
+//!     pac.GCLK,
+//!     pac.MCLK,
+//!     &mut pac.NVMCTRL,
+//! );
+//! let apb = buses.apb.enable(tokens.apbs.adc0);
+//! let (pclk, _gclk0) = Pclk::enable(tokens.pclks.adc0, clocks.gclk0);
+//! let pins = Pins::new(pac.PORT);
+//! let pot = pins.pa02.into_alternate::<B>();
+//!
+//! let mut adc = Adc::new(pac.ADC0, apb, pclk, Config::default());
+//! let raw = adc.read(Input::pin(&pot));
+//! let core = adc.read(Input::internal(Internal::ScaledCoreVcc));
+//! ```
+//!
+//! # Averaging and oversampling
+//!
+//! [`Accumulation`] selects whether each result comes from a single
+//! conversion, from the average of several conversions, or from oversampling
+//! and decimation, which yields results of up to 16 bits.
+//!
+//! # DMA sequencing
+//!
+//! [`Adc::scan_with_dma`] converts a list of inputs in a row, without CPU
+//! intervention. One DMA channel writes the `INPUTCTRL` value of each input,
+//! given by [`Input::sequence_word`], to the ADC, which automatically starts a
+//! conversion for each of them. A second DMA channel moves each result into a
+//! buffer.
+//!
+//! [`clock::v2`]: crate::clock::v2
+//! [`Adc0`]: crate::clock::v2::types::Adc0
+//! [`Adc1`]: crate::clock::v2::types::Adc1
+
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+use bitflags::bitflags;
+
+use crate::calibration;
+use crate::clock::v2::{
+    apb::{ApbClk, ApbId},
+    pclk::{Pclk, PclkId, PclkSourceId},
+    types::{Adc0, Adc1},
+};
+use crate::gpio::*;
+use crate::pac::{adc0, ADC0, ADC1, SUPC};
+
+#[cfg(feature = "dma")]
+use crate::dmac::{
+    self,
+    channel::{AnyChannel, Busy, Channel, Ready},
+    dma_controller::ChId,
+    transfer::BufferPair,
+    Buffer, Transfer, TriggerAction, TriggerSource,
+};
+
+/// Number of samples accumulated per result
+pub use adc0::avgctrl::SAMPLENUM_A as SampleCount;
+/// Clock frequency relative to the peripheral clock
+pub use adc0::ctrla::PRESCALER_A as Prescaler;
+/// Window monitor mode
+pub use adc0::ctrlb::WINMODE_A as WindowMode;
+/// Reference voltage (or its source)
+pub use adc0::refctrl::REFSEL_A as Reference;
+
+/// Maximum frequency of the ADC clock, after the prescaler
+const MAX_CLK_ADC: u32 = 16_000_000;
+
+//==============================================================================
+// Ids
+//==============================================================================
+
+/// Type-level ADC instance
+///
+/// This trait is implemented by the [`clock::v2`](crate::clock::v2) types
+/// [`Adc0`] and [`Adc1`].
+pub trait AdcId: PclkId + ApbId {
+    /// PAC struct of the instance
+    type Instance: Deref<Target = adc0::RegisterBlock>;
+    /// DMA trigger of the result ready condition
+    #[cfg(feature = "dma")]
+    const DMA_RESRDY: TriggerSource;
+    /// DMA trigger of the DMA sequencing request
+    #[cfg(feature = "dma")]
+    const DMA_SEQ: TriggerSource;
+    /// `BIASCOMP`, `BIASREFBUF` and `BIASR2R` calibration values
+    fn calibration() -> (u8, u8, u8);
+}
+
+impl AdcId for Adc0 {
+    type Instance = ADC0;
+    #[cfg(feature = "dma")]
+    const DMA_RESRDY: TriggerSource = TriggerSource::ADC0_RESRDY;
+    #[cfg(feature = "dma")]
+    const DMA_SEQ: TriggerSource = TriggerSource::ADC0_SEQ;
+
+    #[inline]
+    fn calibration() -> (u8, u8, u8) {
+        (
+            calibration::adc0_biascomp_scale_cal(),
+            calibration::adc0_biasref_scale_cal(),
+            calibration::adc0_biasr2r_scale_cal(),
+        )
+    }
+}
+
+impl AdcId for Adc1 {
+    type Instance = ADC1;
+    #[cfg(feature = "dma")]
+    const DMA_RESRDY: TriggerSource = TriggerSource::ADC1_RESRDY;
+    #[cfg(feature = "dma")]
+    const DMA_SEQ: TriggerSource = TriggerSource::ADC1_SEQ;
+
+    #[inline]
+    fn calibration() -> (u8, u8, u8) {
+        (
+            calibration::adc1_biascomp_scale_cal(),
+            calibration::adc1_biasref_scale_cal(),
+            calibration::adc1_biasr2r_scale_cal(),
+        )
+    }
+}
+
+//==============================================================================
+// Pins
+//==============================================================================
+
+/// [`PinId`]s that can be used as an input of ADC instance `I`
+///
+/// Input pins must be in [`AlternateB`] mode.
+pub trait AdcPin<I: AdcId>: PinId {
+    /// Index of the `AIN` input
+    const CHANNEL: u8;
+}
+
+macro_rules! adc_pins {
+    (
+        $(
+            $PinId:ident: ($Id:ident, $CHAN:literal),
+        )+
+    ) => {
+        $(
+            impl AdcPin<$Id> for $PinId {
+                const CHANNEL: u8 = $CHAN;
+            }
+        )+
+    }
+}
+
+adc_pins! {
+    PA02: (Adc0, 0),
+    PA03: (Adc0, 1),
+    PB08: (Adc0, 2),
+    PB09: (Adc0, 3),
+    PA04: (Adc0, 4),
+    PA05: (Adc0, 5),
+    PA06: (Adc0, 6),
+    PA07: (Adc0, 7),
+    PA08: (Adc0, 8),
+    PA09: (Adc0, 9),
+    PA10: (Adc0, 10),
+    PA11: (Adc0, 11),
+    PB02: (Adc0, 14),
+    PB03: (Adc0, 15),
+
+    PB08: (Adc1, 0),
+    PB09: (Adc1, 1),
+    PA08: (Adc1, 2),
+    PA09: (Adc1, 3),
+}
+
+#[cfg(feature = "pins-64")]
+adc_pins! {
+    PB00: (Adc0, 12),
+    PB01: (Adc0, 13),
+    PB04: (Adc1, 6),
+    PB05: (Adc1, 7),
+    PB06: (Adc1, 8),
+    PB07: (Adc1, 9),
+}
+
+#[cfg(feature = "pins-100")]
+adc_pins! {
+    PC02: (Adc1, 4),
+    PC03: (Adc1, 5),
+    PC00: (Adc1, 10),
+    PC01: (Adc1, 11),
+}
+
+#[cfg(feature = "pins-128")]
+adc_pins! {
+    PC30: (Adc1, 12),
+    PC31: (Adc1, 13),
+    PD00: (Adc1, 14),
+    PD01: (Adc1, 15),
+}
+
+//==============================================================================
+// Inputs
+//==============================================================================
+
+/// Internal signals that can be converted by the ADC
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Internal {
+    /// Core supply, scaled by 1/4
+    ScaledCoreVcc,
+    /// Backup battery supply, scaled by 1/4
+    ScaledVbat,
+    /// I/O supply, scaled by 1/4
+    ScaledIoVcc,
+    /// Bandgap voltage, see [`enable_internal_sensors`]
+    Bandgap,
+    /// PTAT temperature sensor, see [`enable_internal_sensors`]
+    Ptat,
+    /// CTAT temperature sensor, see [`enable_internal_sensors`]
+    Ctat,
+    /// DAC output
+    Dac,
+}
+
+impl Internal {
+    #[inline]
+    fn muxpos(self) -> u8 {
+        use adc0::inputctrl::MUXPOS_A;
+        let muxpos = match self {
+            Internal::ScaledCoreVcc => MUXPOS_A::SCALEDCOREVCC,
+            Internal::ScaledVbat => MUXPOS_A::SCALEDVBAT,
+            Internal::ScaledIoVcc => MUXPOS_A::SCALEDIOVCC,
+            Internal::Bandgap => MUXPOS_A::BANDGAP,
+            Internal::Ptat => MUXPOS_A::PTAT,
+            Internal::Ctat => MUXPOS_A::CTAT,
+            Internal::Dac => MUXPOS_A::DAC,
+        };
+        muxpos as u8
+    }
+}
+
+/// Input of a conversion of ADC instance `I`
+pub struct Input<I: AdcId> {
+    muxpos: u8,
+    muxneg: Option<u8>,
+    adc: PhantomData<I>,
+}
+
+impl<I: AdcId> Clone for Input<I> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I: AdcId> Copy for Input<I> {}
+
+impl<I: AdcId> Input<I> {
+    #[inline]
+    fn new(muxpos: u8, muxneg: Option<u8>) -> Self {
+        Self {
+            muxpos,
+            muxneg,
+            adc: PhantomData,
+        }
+    }
+
+    /// Single-ended conversion of a pin, relative to ground
+    #[inline]
+    pub fn pin<P: AdcPin<I>>(_pin: &Pin<P, AlternateB>) -> Self {
+        Self::new(P::CHANNEL, None)
+    }
+
+    /// Differential conversion between two pins
+    ///
+    /// # Panics
+    ///
+    /// Panics if the negative pin is not one of the `AIN0` to `AIN7` inputs.
+    #[inline]
+    pub fn differential<P: AdcPin<I>, N: AdcPin<I>>(
+        _pos: &Pin<P, AlternateB>,
+        _neg: &Pin<N, AlternateB>,
+    ) -> Self {
+        assert!(N::CHANNEL < 8, "Invalid negative ADC input");
+        Self::new(P::CHANNEL, Some(N::CHANNEL))
+    }
+
+    /// Single-ended conversion of an internal signal, relative to ground
+    #[inline]
+    pub fn internal(internal: Internal) -> Self {
+        Self::new(internal.muxpos(), None)
+    }
+
+    /// Value of the `INPUTCTRL` register selecting this input
+    ///
+    /// This is the word to write to the ADC to convert this input during DMA
+    /// sequencing, see [`Adc::scan_with_dma`].
+    #[inline]
+    pub fn sequence_word(&self) -> u32 {
+        use adc0::inputctrl::MUXNEG_A;
+        match self.muxneg {
+            Some(muxneg) => u32::from(self.muxpos) | 1 << 7 | u32::from(muxneg) << 8,
+            None => u32::from(self.muxpos) | (MUXNEG_A::GND as u32) << 8,
+        }
+    }
+}
+
+/// Enable the temperature sensors and the bandgap output, so that the
+/// [`Internal::Ptat`], [`Internal::Ctat`] and [`Internal::Bandgap`] inputs can
+/// be converted
+///
+/// To measure the die temperature, use a
+/// [`TemperatureSensor`](super::temperature::TemperatureSensor) instead.
+#[inline]
+pub fn enable_internal_sensors(supc: &mut SUPC) {
+    supc.vref
+        .modify(|_, w| w.tsen().set_bit().vrefoe().set_bit());
+}
+
+//==============================================================================
+// Configuration
+//==============================================================================
+
+/// Resolution of a single conversion
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Resolution {
+    Bits8,
+    Bits10,
+    Bits12,
+}
+
+/// Resolution of an oversampled result
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Oversampled {
+    /// 13-bit result, from 4 conversions
+    Bits13,
+    /// 14-bit result, from 16 conversions
+    Bits14,
+    /// 15-bit result, from 64 conversions
+    Bits15,
+    /// 16-bit result, from 256 conversions
+    Bits16,
+}
+
+/// How each result is accumulated from conversions
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Accumulation {
+    /// Each result comes from a single conversion
+    Single(Resolution),
+    /// Each result is the 12-bit average of several conversions
+    Average(SampleCount),
+    /// Each result is computed by oversampling and decimation, which increases
+    /// the resolution beyond 12 bits
+    Oversample(Oversampled),
+}
+
+impl Accumulation {
+    /// `SAMPLENUM` and `ADJRES` values, see tables 45-3 and 45-4 of the
+    /// datasheet
+    #[inline]
+    fn avgctrl(self) -> (SampleCount, u8) {
+        match self {
+            Accumulation::Single(_) => (SampleCount::_1, 0),
+            Accumulation::Average(samples) => (samples, (samples as u8).min(4)),
+            Accumulation::Oversample(Oversampled::Bits13) => (SampleCount::_4, 1),
+            Accumulation::Oversample(Oversampled::Bits14) => (SampleCount::_16, 2),
+            Accumulation::Oversample(Oversampled::Bits15) => (SampleCount::_64, 1),
+            Accumulation::Oversample(Oversampled::Bits16) => (SampleCount::_256, 0),
+        }
+    }
+}
+
+/// Digital gain and offset correction
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Correction {
+    /// 12-bit gain correction factor, where `0x800` is a gain of 1
+    pub gain: u16,
+    /// 12-bit two's complement offset, subtracted from the result before the
+    /// gain correction
+    pub offset: i16,
+}
+
+/// ADC configuration
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    /// Division of the peripheral clock. The resulting ADC clock must not
+    /// exceed 16 MHz.
+    pub prescaler: Prescaler,
+    /// Voltage reference
+    pub reference: Reference,
+    /// How results are accumulated from conversions
+    pub accumulation: Accumulation,
+    /// Sampling time, in ADC clock cycles, minus one, in the range `0..64`
+    pub sample_length: u8,
+    /// Digital gain and offset correction
+    pub correction: Option<Correction>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            prescaler: Prescaler::DIV32,
+            reference: Reference::INTVCC1,
+            accumulation: Accumulation::Single(Resolution::Bits12),
+            sample_length: 5,
+            correction: None,
+        }
+    }
+}
+
+//==============================================================================
+// Flags
+//==============================================================================
+
+bitflags! {
+    /// Interrupt bit flags
+    ///
+    /// The binary format of the underlying bits exactly matches the INTFLAG
+    /// bits.
+    pub struct Flags: u8 {
+        const RESRDY = 0x01;
+        const OVERRUN = 0x02;
+        const WINMON = 0x04;
+    }
+}
+
+//==============================================================================
+// Adc
+//==============================================================================
+
+/// Analog-to-Digital Converter instance `I`, clocked by a [`Pclk`] sourced
+/// from `S`
+pub struct Adc<I: AdcId, S: PclkSourceId> {
+    adc: I::Instance,
+    apb: ApbClk<I>,
+    pclk: Pclk<I, S>,
+}
+
+impl<I: AdcId, S: PclkSourceId> Adc<I, S> {
+    /// Reset, calibrate, configure and enable the ADC
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ADC clock, ie the [`Pclk`] frequency divided by the
+    /// prescaler, exceeds 16 MHz, or if the sample length is out of range.
+    #[inline]
+    pub fn new(adc: I::Instance, apb: ApbClk<I>, pclk: Pclk<I, S>, config: Config) -> Self {
+        let div = 2 << (config.prescaler as u32);
+        assert!(
+            pclk.freq().to_Hz() / div <= MAX_CLK_ADC,
+            "ADC clock frequency too high"
+        );
+        assert!(config.sample_length < 64, "Invalid ADC sample length");
+
+        let mut adc = Self { adc, apb, pclk };
+        adc.adc.ctrla.write(|w| w.swrst().set_bit());
+        adc.sync();
+
+        let (biascomp, biasrefbuf, biasr2r) = I::calibration();
+        adc.adc.calib.write(|w| unsafe {
+            w.biascomp().bits(biascomp);
+            w.biasrefbuf().bits(biasrefbuf);
+            w.biasr2r().bits(biasr2r)
+        });
+
+        adc.adc
+            .ctrla
+            .modify(|_, w| w.prescaler().variant(config.prescaler));
+        adc.adc.ctrlb.modify(|_, w| {
+            match config.accumulation {
+                Accumulation::Single(Resolution::Bits8) => w.ressel()._8bit(),
+                Accumulation::Single(Resolution::Bits10) => w.ressel()._10bit(),
+                Accumulation::Single(Resolution::Bits12) => w.ressel()._12bit(),
+                // Accumulated results always use the 16-bit result register
+                _ => w.ressel()._16bit(),
+            };
+            w.corren().bit(config.correction.is_some())
+        });
+        let (samples, adjres) = config.accumulation.avgctrl();
+        adc.adc.avgctrl.write(|w| {
+            w.samplenum().variant(samples);
+            // Safety: ADJRES is at most 4
+            unsafe { w.adjres().bits(adjres) }
+        });
+        // Safety: The sample length was checked above
+        adc.adc
+            .sampctrl
+            .write(|w| unsafe { w.samplen().bits(config.sample_length) });
+        adc.adc
+            .refctrl
+            .write(|w| w.refsel().variant(config.reference));
+        if let Some(correction) = config.correction {
+            // Safety: Both registers are 12 bits wide, extra bits are ignored
+            adc.adc
+                .gaincorr
+                .write(|w| unsafe { w.gaincorr().bits(correction.gain) });
+            adc.adc
+                .offsetcorr
+                .write(|w| unsafe { w.offsetcorr().bits(correction.offset as u16) });
+        }
+        adc.sync();
+
+        adc.enable();
+        adc
+    }
+
+    #[inline]
+    fn sync(&self) {
+        while self.adc.syncbusy.read().bits() != 0 {}
+    }
+
+    #[inline]
+    fn enable(&mut self) {
+        self.adc.ctrla.modify(|_, w| w.enable().set_bit());
+        self.sync();
+    }
+
+    #[inline]
+    fn disable(&mut self) {
+        self.adc.ctrla.modify(|_, w| w.enable().clear_bit());
+        self.sync();
+    }
+
+    #[inline]
+    fn select(&mut self, input: Input<I>) {
+        // Safety: `sequence_word` only sets valid MUXPOS, MUXNEG and DIFFMODE
+        // values
+        self.adc
+            .inputctrl
+            .write(|w| unsafe { w.bits(input.sequence_word() as u16) });
+        self.sync();
+    }
+
+    /// Convert an input, blocking until the result is ready
+    ///
+    /// Differential results are two's complement and should be cast to
+    /// `i16`.
+    #[inline]
+    pub fn read(&mut self, input: Input<I>) -> u16 {
+        self.select(input);
+        self.adc.intflag.write(|w| w.resrdy().set_bit());
+        self.adc.swtrig.write(|w| w.start().set_bit());
+        while self.adc.intflag.read().resrdy().bit_is_clear() {}
+        self.adc.result.read().result().bits()
+    }
+
+    /// Run `f` with the voltage reference temporarily set to `reference`
+    #[inline]
+    pub(super) fn with_reference<R>(
+        &mut self,
+        reference: Reference,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let previous = self.adc.refctrl.read().refsel().variant();
+        self.set_reference(reference);
+        let result = f(self);
+        if let Some(previous) = previous {
+            self.set_reference(previous);
+        }
+        result
+    }
+
+    /// Set the voltage reference
+    #[inline]
+    pub fn set_reference(&mut self, reference: Reference) {
+        self.adc.refctrl.write(|w| w.refsel().variant(reference));
+        self.sync();
+    }
+
+    /// Configure the window monitor
+    ///
+    /// The window monitor compares each result to the `lower` and `upper`
+    /// thresholds, and sets the [`Flags::WINMON`] flag when the condition
+    /// selected by `mode` is met. Thresholds are two's complement for
+    /// differential inputs.
+    #[inline]
+    pub fn set_window(&mut self, mode: WindowMode, lower: i16, upper: i16) {
+        // Safety: Any 16-bit threshold is valid
+        self.adc
+            .winlt
+            .write(|w| unsafe { w.winlt().bits(lower as u16) });
+        self.adc
+            .winut
+            .write(|w| unsafe { w.winut().bits(upper as u16) });
+        self.adc.ctrlb.modify(|_, w| w.winmode().variant(mode));
+        self.sync();
+    }
+
+    /// Enable interrupts for the specified flags
+    #[inline]
+    pub fn enable_interrupts(&mut self, flags: Flags) {
+        // Safety: `Flags` bits match the INTENSET bits
+        self.adc.intenset.write(|w| unsafe { w.bits(flags.bits()) });
+    }
+
+    /// Disable interrupts for the specified flags
+    #[inline]
+    pub fn disable_interrupts(&mut self, flags: Flags) {
+        // Safety: `Flags` bits match the INTENCLR bits
+        self.adc.intenclr.write(|w| unsafe { w.bits(flags.bits()) });
+    }
+
+    /// Read the interrupt flags
+    #[inline]
+    pub fn read_flags(&self) -> Flags {
+        Flags::from_bits_truncate(self.adc.intflag.read().bits())
+    }
+
+    /// Clear the specified interrupt flags
+    #[inline]
+    pub fn clear_flags(&mut self, flags: Flags) {
+        // Safety: `Flags` bits match the INTFLAG bits
+        self.adc.intflag.write(|w| unsafe { w.bits(flags.bits()) });
+    }
+
+    /// Disable and reset the ADC, and return its resources
+    #[inline]
+    pub fn free(mut self) -> (I::Instance, ApbClk<I>, Pclk<I, S>) {
+        self.disable();
+        self.adc.ctrla.write(|w| w.swrst().set_bit());
+        self.sync();
+        (self.adc, self.apb, self.pclk)
+    }
+}
+
+//==============================================================================
+// DMA
+//==============================================================================
+
+/// [`Adc`] used as the source of the result DMA transfer of a [`Scan`]
+#[cfg(feature = "dma")]
+pub struct DmaResult<I: AdcId, S: PclkSourceId> {
+    adc: Adc<I, S>,
+}
+
+#[cfg(feature = "dma")]
+unsafe impl<I: AdcId, S: PclkSourceId> Buffer for DmaResult<I, S> {
+    type Beat = u16;
+
+    #[inline]
+    fn dma_ptr(&mut self) -> *mut Self::Beat {
+        self.adc.adc.result.as_ptr()
+    }
+
+    #[inline]
+    fn incrementing(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn buffer_len(&self) -> usize {
+        1
+    }
+}
+
+/// `DSEQDATA` register, used as the destination of the sequence DMA transfer
+/// of a [`Scan`]
+#[cfg(feature = "dma")]
+pub struct DmaSequence<I: AdcId> {
+    adc: PhantomData<I>,
+    ptr: *mut u32,
+}
+
+#[cfg(feature = "dma")]
+unsafe impl<I: AdcId> Buffer for DmaSequence<I> {
+    type Beat = u32;
+
+    #[inline]
+    fn dma_ptr(&mut self) -> *mut Self::Beat {
+        self.ptr
+    }
+
+    #[inline]
+    fn incrementing(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn buffer_len(&self) -> usize {
+        1
+    }
+}
+
+/// Conversion of a sequence of inputs by DMA, started by
+/// [`Adc::scan_with_dma`]
+///
+/// `SeqId` and `ResId` are the [`ChId`]s of the DMA channels writing the
+/// sequence and reading the results.
+#[cfg(feature = "dma")]
+pub struct Scan<I, S, SeqId, ResId, B, R>
+where
+    I: AdcId,
+    S: PclkSourceId,
+    SeqId: ChId,
+    ResId: ChId,
+    B: Buffer<Beat = u32>,
+    R: Buffer<Beat = u16>,
+{
+    sequence: Transfer<Channel<SeqId, Busy>, BufferPair<B, DmaSequence<I>>>,
+    results: Transfer<Channel<ResId, Busy>, BufferPair<DmaResult<I, S>, R>>,
+}
+
+/// Resources released by [`Scan::wait`]
+///
+/// The [`Adc`], the sequence and result buffers, and the two DMA channels.
+#[cfg(feature = "dma")]
+pub type ScanParts<I, S, SeqId, ResId, B, R> = (
+    Adc<I, S>,
+    B,
+    R,
+    Channel<SeqId, Ready>,
+    Channel<ResId, Ready>,
+);
+
+#[cfg(feature = "dma")]
+impl<I, S, SeqId, ResId, B, R> Scan<I, S, SeqId, ResId, B, R>
+where
+    I: AdcId,
+    S: PclkSourceId,
+    SeqId: ChId,
+    ResId: ChId,
+    B: Buffer<Beat = u32>,
+    R: Buffer<Beat = u16>,
+{
+    /// Check whether all the results have been written to the result buffer
+    #[inline]
+    pub fn complete(&mut self) -> bool {
+        self.results.complete()
+    }
+
+    /// Wait for the scan to complete, disable DMA sequencing and release all
+    /// owned resources
+    ///
+    /// # Blocking: This method may block
+    #[inline]
+    pub fn wait(self) -> ScanParts<I, S, SeqId, ResId, B, R> {
+        let (res_channel, source, results) = self.results.wait();
+        let (seq_channel, sequence, _) = self.sequence.wait();
+        let adc = source.adc;
+        // Safety: Writing zero disables DMA sequencing
+        adc.adc.dseqctrl.write(|w| unsafe { w.bits(0) });
+        (adc, sequence, results, seq_channel, res_channel)
+    }
+}
+
+#[cfg(feature = "dma")]
+impl<I: AdcId, S: PclkSourceId> Adc<I, S> {
+    /// Convert each input of `sequence` in a row, storing the results in
+    /// `results`
+    ///
+    /// `sequence` holds one [`Input::sequence_word`] per conversion. It is
+    /// written to the ADC by `seq_channel`, which starts each conversion, and
+    /// `res_channel` moves each result to `results`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sequence` and `results` are not of equal length.
+    #[inline]
+    pub fn scan_with_dma<SeqCh, ResCh, B, R>(
+        self,
+        sequence: B,
+        results: R,
+        seq_channel: SeqCh,
+        res_channel: ResCh,
+    ) -> Scan<I, S, SeqCh::Id, ResCh::Id, B, R>
+    where
+        SeqCh: AnyChannel<Status = Ready>,
+        ResCh: AnyChannel<Status = Ready>,
+        B: Buffer<Beat = u32> + 'static,
+        R: Buffer<Beat = u16> + 'static,
+    {
+        assert_eq!(
+            sequence.buffer_len(),
+            results.buffer_len(),
+            "ADC sequence and result buffers must be of equal length"
+        );
+
+        // Only INPUTCTRL is written by the sequence, and each write starts a
+        // conversion
+        self.adc
+            .dseqctrl
+            .write(|w| w.inputctrl().set_bit().autostart().set_bit());
+
+        let dest = DmaSequence {
+            adc: PhantomData,
+            ptr: self.adc.dseqdata.as_ptr(),
+        };
+        let source = DmaResult { adc: self };
+
+        // SAFETY: This is safe because the of the `'static` bound check
+        // for `B` and `R`, and the fact that the buffer length of `DmaResult`
+        // and `DmaSequence` is always 1.
+        let results = unsafe { dmac::Transfer::new_unchecked(res_channel, source, results, false) }
+            .begin(I::DMA_RESRDY, TriggerAction::BURST);
+        let sequence = unsafe { dmac::Transfer::new_unchecked(seq_channel, sequence, dest, false) }
+            .begin(I::DMA_SEQ, TriggerAction::BURST);
+
+        Scan { sequence, results }
+    }
+}
@@ -98,6 +98,31 @@
 //!     cipher.decrypt_block(&mut block);
 //!     assert_eq!(block, block_copy);
 //! ```
+//!
+//! # CMAC, key wrap and key derivation
+//!
+//! [`Cmac`] (NIST SP 800-38B), [`KeyWrap`] (RFC 3394 and RFC 5649) and
+//! [`CounterKdf`] (NIST SP 800-108, counter mode) are built on an
+//! [`AesBackend`]:
+//!
+//! * [`HardwareAes`] borrows the [`Aes`] peripheral and keeps the key loaded
+//!   across blocks. [`Cmac`] runs the peripheral in CBC mode.
+//! * [`SoftwareAes`] wraps any RustCrypto block cipher, such as the ones of the
+//!   `aes` crate, and can be used without the peripheral.
+//!
+//! ```no_run
+//! use atsamd_hal::aes::{Aes, Cmac, HardwareAes, KeyWrap};
+//!
+//! let mut aes = Aes::new(peripherals.AES);
+//!
+//! let mut cmac = Cmac::new(HardwareAes::new(&mut aes, &network_key));
+//! cmac.update(&frame);
+//! let mic = cmac.finalize();
+//!
+//! let mut wrap = KeyWrap::new(HardwareAes::new(&mut aes, &key_encryption_key));
+//! let mut wrapped = [0; 24];
+//! wrap.wrap(&session_key, &mut wrapped)?;
+//! ```
 
 // Re-exports
 pub use crate::pac::aes::ctrla::{
@@ -112,6 +137,18 @@ mod rustcrypto;
 #[cfg(feature = "enable_unsafe_aes_newblock_cipher")]
 pub use rustcrypto::{Aes128, Aes192, Aes256};
 
+mod backend;
+pub use backend::{AesBackend, HardwareAes, SoftwareAes};
+
+mod cmac;
+pub use cmac::{Cmac, MacError};
+
+mod keywrap;
+pub use keywrap::{KeyWrap, KeyWrapError};
+
+mod kdf;
+pub use kdf::{CounterKdf, CounterLocation, KdfError};
+
 #[cfg(feature = "enable_unsafe_aes_newblock_cipher")]
 pub use cipher::{
     consts::{U1, U16, U24, U32, U8},
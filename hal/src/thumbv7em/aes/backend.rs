@@ -0,0 +1,228 @@
+//! Block cipher backends for the AES constructions
+//!
+//! The [`Cmac`](super::Cmac), [`KeyWrap`](super::KeyWrap) and
+//! [`CounterKdf`](super::CounterKdf) constructions are generic over an
+//! [`AesBackend`]. [`HardwareAes`] runs them on the AES peripheral, while
+//! [`SoftwareAes`] runs them on any RustCrypto block cipher, which allows
+//! testing them on the host.
+
+use super::{Aes, AESMODE_A, CIPHER_A, KEYSIZE_A};
+use cipher::{consts::U16, BlockCipher, BlockDecrypt, BlockEncrypt};
+
+/// A 128-bit block cipher, keyed once for the lifetime of the backend
+pub trait AesBackend {
+    /// Encrypt a single block in ECB mode
+    fn encrypt_block(&mut self, block: &mut [u8; 16]);
+
+    /// Decrypt a single block in ECB mode
+    fn decrypt_block(&mut self, block: &mut [u8; 16]);
+
+    /// Encrypt a single block in CBC mode, with an all-zero IV
+    ///
+    /// If `first` is `true`, a new message is started. Otherwise, `block` is
+    /// chained to the output of the previous call.
+    fn encrypt_cbc_block(&mut self, block: &mut [u8; 16], first: bool);
+}
+
+//=============================================================================
+// HardwareAes
+//=============================================================================
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Encrypt,
+    Decrypt,
+    Cbc,
+}
+
+/// [`AesBackend`] running on the AES peripheral
+///
+/// The key is loaded into the peripheral once, and kept loaded for as long as
+/// consecutive blocks use the same mode of operation. Switching between ECB
+/// encryption, ECB decryption and CBC encryption resets the peripheral and
+/// reloads the key.
+///
+/// `N` is the key length in bytes, and must be 16, 24 or 32.
+pub struct HardwareAes<'a, const N: usize> {
+    aes: &'a mut Aes,
+    key: [u8; N],
+    mode: Option<Mode>,
+}
+
+impl<'a, const N: usize> HardwareAes<'a, N> {
+    /// Create a new [`HardwareAes`] backend from the AES peripheral and a key
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is not 16, 24 or 32.
+    #[inline]
+    pub fn new(aes: &'a mut Aes, key: &[u8; N]) -> Self {
+        assert!(
+            matches!(N, 16 | 24 | 32),
+            "Invalid AES keysize, expected 16, 24 or 32 bytes"
+        );
+        Self {
+            aes,
+            key: *key,
+            mode: None,
+        }
+    }
+
+    /// Reset the AES peripheral and release it
+    #[inline]
+    pub fn free(self) {
+        self.aes.swrst();
+        while self.aes.ctrla().read().swrst().bit_is_set() {}
+    }
+
+    /// Reset the peripheral and reload the key, unless it is already
+    /// configured for `mode`
+    fn configure(&mut self, mode: Mode) {
+        if self.mode == Some(mode) {
+            return;
+        }
+
+        self.aes.swrst();
+        while self.aes.ctrla().read().swrst().bit_is_set() {}
+
+        let keysize = match N {
+            16 => KEYSIZE_A::_128BIT,
+            24 => KEYSIZE_A::_192BIT,
+            _ => KEYSIZE_A::_256BIT,
+        };
+        let (aesmode, cipher) = match mode {
+            Mode::Encrypt => (AESMODE_A::ECB, CIPHER_A::ENC),
+            Mode::Decrypt => (AESMODE_A::ECB, CIPHER_A::DEC),
+            Mode::Cbc => (AESMODE_A::CBC, CIPHER_A::ENC),
+        };
+        self.aes.ctrla().write(|w| {
+            w.aesmode()
+                .variant(aesmode)
+                .cipher()
+                .variant(cipher)
+                .keysize()
+                .variant(keysize)
+                .enable()
+                .set_bit()
+        });
+
+        for (index, word) in self.key.chunks_exact(4).enumerate() {
+            let data = u32::from_le_bytes([word[0], word[1], word[2], word[3]]);
+            self.aes.aes().keyword[index].write(|w| unsafe { w.bits(data) });
+        }
+        if mode == Mode::Cbc {
+            self.aes.set_initialization_vector([0; 4]);
+        }
+
+        self.mode = Some(mode);
+    }
+
+    /// Process a single block with the current configuration
+    fn process(&mut self, block: &mut [u8; 16], newmsg: bool) {
+        self.aes.set_databufptr(0);
+        for word in block.chunks_exact(4) {
+            self.aes
+                .set_data(u32::from_le_bytes([word[0], word[1], word[2], word[3]]));
+        }
+
+        self.aes
+            .ctrlb()
+            .write(|w| w.start().set_bit().newmsg().bit(newmsg));
+        while !self.aes.read_enccmp() {}
+
+        for word in block.chunks_exact_mut(4) {
+            word.copy_from_slice(&self.aes.get_data().to_le_bytes());
+        }
+    }
+}
+
+impl<const N: usize> AesBackend for HardwareAes<'_, N> {
+    #[inline]
+    fn encrypt_block(&mut self, block: &mut [u8; 16]) {
+        self.configure(Mode::Encrypt);
+        self.process(block, false);
+    }
+
+    #[inline]
+    fn decrypt_block(&mut self, block: &mut [u8; 16]) {
+        self.configure(Mode::Decrypt);
+        self.process(block, false);
+    }
+
+    #[inline]
+    fn encrypt_cbc_block(&mut self, block: &mut [u8; 16], first: bool) {
+        // Chaining is lost when switching modes, so only a new message can
+        // reconfigure the peripheral
+        debug_assert!(first || self.mode == Some(Mode::Cbc));
+        self.configure(Mode::Cbc);
+        self.process(block, first);
+    }
+}
+
+//=============================================================================
+// SoftwareAes
+//=============================================================================
+
+/// [`AesBackend`] running on a RustCrypto block cipher
+///
+/// This backend does not need the AES peripheral, so it can be used on the
+/// host, for instance with the `aes` crate:
+///
+/// ```no_run
+/// use aes::{Aes128, NewBlockCipher};
+/// use atsamd_hal::aes::{Cmac, SoftwareAes};
+///
+/// let key = [0; 16];
+/// let mut cmac = Cmac::new(SoftwareAes::new(Aes128::new(&key.into())));
+/// ```
+pub struct SoftwareAes<C> {
+    cipher: C,
+    chain: [u8; 16],
+}
+
+impl<C> SoftwareAes<C>
+where
+    C: BlockCipher<BlockSize = U16> + BlockEncrypt + BlockDecrypt,
+{
+    /// Create a new [`SoftwareAes`] backend from a keyed block cipher
+    #[inline]
+    pub fn new(cipher: C) -> Self {
+        Self {
+            cipher,
+            chain: [0; 16],
+        }
+    }
+
+    /// Return the underlying block cipher
+    #[inline]
+    pub fn free(self) -> C {
+        self.cipher
+    }
+}
+
+impl<C> AesBackend for SoftwareAes<C>
+where
+    C: BlockCipher<BlockSize = U16> + BlockEncrypt + BlockDecrypt,
+{
+    #[inline]
+    fn encrypt_block(&mut self, block: &mut [u8; 16]) {
+        self.cipher.encrypt_block(block.into());
+    }
+
+    #[inline]
+    fn decrypt_block(&mut self, block: &mut [u8; 16]) {
+        self.cipher.decrypt_block(block.into());
+    }
+
+    #[inline]
+    fn encrypt_cbc_block(&mut self, block: &mut [u8; 16], first: bool) {
+        if first {
+            self.chain = [0; 16];
+        }
+        for (byte, chain) in block.iter_mut().zip(self.chain.iter()) {
+            *byte ^= chain;
+        }
+        self.encrypt_block(block);
+        self.chain = *block;
+    }
+}
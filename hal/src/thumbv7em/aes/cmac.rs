@@ -0,0 +1,338 @@
+//! AES-CMAC message authentication, as specified in NIST SP 800-38B and
+//! RFC 4493
+
+use cipher::{consts::U16, BlockCipher, BlockDecrypt, BlockEncrypt, NewBlockCipher};
+use digest::crypto_common::KeySizeUser;
+use digest::{
+    FixedOutput, FixedOutputReset, Key, KeyInit, Mac, MacMarker, Output, OutputSizeUser, Reset,
+    Update,
+};
+
+use super::{AesBackend, SoftwareAes};
+
+pub use digest::MacError;
+
+/// Compare two slices in constant time
+pub(super) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+/// Multiply a block by `x` in GF(2^128)
+fn double(block: &[u8; 16]) -> [u8; 16] {
+    let value = u128::from_be_bytes(*block);
+    let reduce = if value >> 127 == 1 { 0x87 } else { 0 };
+    ((value << 1) ^ reduce).to_be_bytes()
+}
+
+/// AES-CMAC message authentication code
+///
+/// The MAC is computed as a CBC-MAC, using
+/// [`AesBackend::encrypt_cbc_block`], so the key stays loaded in the AES
+/// peripheral for the whole message. The last block is held back until
+/// [`finalize`](Self::finalize) is called, since it must be combined with
+/// one of the subkeys.
+///
+/// [`Cmac`] implements the RustCrypto [`Mac`] traits, so it can be used by
+/// any code generic over them. With a [`SoftwareAes`] backend, it also
+/// implements [`KeyInit`]. The inherent methods are shortcuts for the trait
+/// methods, returning plain arrays.
+pub struct Cmac<B: AesBackend> {
+    backend: B,
+    k1: [u8; 16],
+    k2: [u8; 16],
+    buffer: [u8; 16],
+    position: usize,
+    first: bool,
+}
+
+impl<B: AesBackend> Cmac<B> {
+    /// Create a new [`Cmac`], deriving the subkeys from the backend key
+    #[inline]
+    pub fn new(mut backend: B) -> Self {
+        let mut l = [0; 16];
+        backend.encrypt_block(&mut l);
+        let k1 = double(&l);
+        let k2 = double(&k1);
+        Self {
+            backend,
+            k1,
+            k2,
+            buffer: [0; 16],
+            position: 0,
+            first: true,
+        }
+    }
+
+    /// Return the underlying [`AesBackend`]
+    #[inline]
+    pub fn free(self) -> B {
+        self.backend
+    }
+
+    /// Process the buffered block
+    fn process_buffer(&mut self) {
+        let mut block = self.buffer;
+        self.backend.encrypt_cbc_block(&mut block, self.first);
+        self.first = false;
+        self.position = 0;
+    }
+
+    /// Append `data` to the message
+    #[inline]
+    pub fn update(&mut self, data: &[u8]) {
+        Update::update(self, data);
+    }
+
+    /// Compute the tag of the message, and reset the [`Cmac`] for a new one
+    #[inline]
+    pub fn finalize_reset(&mut self) -> [u8; 16] {
+        self.finalize_fixed_reset().into()
+    }
+
+    /// Compute the tag of the message
+    #[inline]
+    pub fn finalize(self) -> [u8; 16] {
+        self.finalize_fixed().into()
+    }
+
+    /// Discard the message processed so far
+    #[inline]
+    pub fn reset(&mut self) {
+        Reset::reset(self);
+    }
+
+    /// Check the tag of the message, in constant time
+    #[inline]
+    pub fn verify(self, tag: &[u8; 16]) -> Result<(), MacError> {
+        Mac::verify_slice(self, tag)
+    }
+
+    /// Check a tag truncated to its leftmost bytes, in constant time
+    ///
+    /// This is used by protocols such as LoRaWAN, which only transmit the
+    /// first 4 bytes of the tag. Empty tags are rejected.
+    #[inline]
+    pub fn verify_truncated(self, tag: &[u8]) -> Result<(), MacError> {
+        if tag.is_empty() {
+            return Err(MacError);
+        }
+        Mac::verify_truncated_left(self, tag)
+    }
+}
+
+impl<B: AesBackend> MacMarker for Cmac<B> {}
+
+impl<B: AesBackend> OutputSizeUser for Cmac<B> {
+    type OutputSize = U16;
+}
+
+impl<B: AesBackend> Update for Cmac<B> {
+    fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            if self.position == 16 {
+                self.process_buffer();
+            }
+            let count = data.len().min(16 - self.position);
+            self.buffer[self.position..self.position + count].copy_from_slice(&data[..count]);
+            self.position += count;
+            data = &data[count..];
+        }
+    }
+}
+
+impl<B: AesBackend> FixedOutput for Cmac<B> {
+    #[inline]
+    fn finalize_into(mut self, out: &mut Output<Self>) {
+        self.finalize_into_reset(out);
+    }
+}
+
+impl<B: AesBackend> FixedOutputReset for Cmac<B> {
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        let mut block = self.buffer;
+        let subkey = if self.position == 16 {
+            &self.k1
+        } else {
+            block[self.position] = 0x80;
+            block[self.position + 1..].fill(0);
+            &self.k2
+        };
+        for (byte, key) in block.iter_mut().zip(subkey.iter()) {
+            *byte ^= key;
+        }
+        self.backend.encrypt_cbc_block(&mut block, self.first);
+        Reset::reset(self);
+        out.copy_from_slice(&block);
+    }
+}
+
+impl<B: AesBackend> Reset for Cmac<B> {
+    #[inline]
+    fn reset(&mut self) {
+        self.position = 0;
+        self.first = true;
+    }
+}
+
+impl<C> KeySizeUser for Cmac<SoftwareAes<C>>
+where
+    C: BlockCipher<BlockSize = U16> + BlockEncrypt + BlockDecrypt + NewBlockCipher,
+    C::KeySize: 'static,
+{
+    type KeySize = C::KeySize;
+}
+
+impl<C> KeyInit for Cmac<SoftwareAes<C>>
+where
+    C: BlockCipher<BlockSize = U16> + BlockEncrypt + BlockDecrypt + NewBlockCipher,
+    C::KeySize: 'static,
+{
+    #[inline]
+    fn new(key: &Key<Self>) -> Self {
+        Self::new(SoftwareAes::new(C::new(key)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aes::SoftwareAes;
+    use aes::{Aes128, Aes256, NewBlockCipher};
+
+    const KEY: [u8; 16] = [
+        0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f,
+        0x3c,
+    ];
+
+    const MESSAGE: [u8; 64] = [
+        0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17,
+        0x2a, 0xae, 0x2d, 0x8a, 0x57, 0x1e, 0x03, 0xac, 0x9c, 0x9e, 0xb7, 0x6f, 0xac, 0x45, 0xaf,
+        0x8e, 0x51, 0x30, 0xc8, 0x1c, 0x46, 0xa3, 0x5c, 0xe4, 0x11, 0xe5, 0xfb, 0xc1, 0x19, 0x1a,
+        0x0a, 0x52, 0xef, 0xf6, 0x9f, 0x24, 0x45, 0xdf, 0x4f, 0x9b, 0x17, 0xad, 0x2b, 0x41, 0x7b,
+        0xe6, 0x6c, 0x37, 0x10,
+    ];
+
+    fn new_cmac() -> Cmac<SoftwareAes<Aes128>> {
+        Cmac::new(SoftwareAes::new(Aes128::new(&KEY.into())))
+    }
+
+    #[test]
+    fn rfc4493_subkeys() {
+        let cmac = new_cmac();
+        assert_eq!(
+            cmac.k1,
+            [
+                0xfb, 0xee, 0xd6, 0x18, 0x35, 0x71, 0x33, 0x66, 0x7c, 0x85, 0xe0, 0x8f, 0x72, 0x36,
+                0xa8, 0xde
+            ]
+        );
+        assert_eq!(
+            cmac.k2,
+            [
+                0xf7, 0xdd, 0xac, 0x30, 0x6a, 0xe2, 0x66, 0xcc, 0xf9, 0x0b, 0xc1, 0x1e, 0xe4, 0x6d,
+                0x51, 0x3b
+            ]
+        );
+    }
+
+    #[test]
+    fn rfc4493_examples() {
+        let expected: [(usize, [u8; 16]); 4] = [
+            (
+                0,
+                [
+                    0xbb, 0x1d, 0x69, 0x29, 0xe9, 0x59, 0x37, 0x28, 0x7f, 0xa3, 0x7d, 0x12, 0x9b,
+                    0x75, 0x67, 0x46,
+                ],
+            ),
+            (
+                16,
+                [
+                    0x07, 0x0a, 0x16, 0xb4, 0x6b, 0x4d, 0x41, 0x44, 0xf7, 0x9b, 0xdd, 0x9d, 0xd0,
+                    0x4a, 0x28, 0x7c,
+                ],
+            ),
+            (
+                40,
+                [
+                    0xdf, 0xa6, 0x67, 0x47, 0xde, 0x9a, 0xe6, 0x30, 0x30, 0xca, 0x32, 0x61, 0x14,
+                    0x97, 0xc8, 0x27,
+                ],
+            ),
+            (
+                64,
+                [
+                    0x51, 0xf0, 0xbe, 0xbf, 0x7e, 0x3b, 0x9d, 0x92, 0xfc, 0x49, 0x74, 0x17, 0x79,
+                    0x36, 0x3c, 0xfe,
+                ],
+            ),
+        ];
+
+        let mut cmac = new_cmac();
+        for (len, tag) in expected.iter() {
+            cmac.update(&MESSAGE[..*len]);
+            assert_eq!(&cmac.finalize_reset(), tag);
+
+            // Split updates must give the same result
+            for chunk in MESSAGE[..*len].chunks(7) {
+                cmac.update(chunk);
+            }
+            assert_eq!(&cmac.finalize_reset(), tag);
+        }
+    }
+
+    #[test]
+    fn sp800_38b_aes256() {
+        let key = [
+            0x60, 0x3d, 0xeb, 0x10, 0x15, 0xca, 0x71, 0xbe, 0x2b, 0x73, 0xae, 0xf0, 0x85, 0x7d,
+            0x77, 0x81, 0x1f, 0x35, 0x2c, 0x07, 0x3b, 0x61, 0x08, 0xd7, 0x2d, 0x98, 0x10, 0xa3,
+            0x09, 0x14, 0xdf, 0xf4,
+        ];
+        let mut cmac = Cmac::new(SoftwareAes::new(Aes256::new(&key.into())));
+        cmac.update(&MESSAGE);
+        assert_eq!(
+            cmac.finalize(),
+            [
+                0xe1, 0x99, 0x21, 0x90, 0x54, 0x9f, 0x6e, 0xd5, 0x69, 0x6a, 0x2c, 0x05, 0x6c, 0x31,
+                0x54, 0x10
+            ]
+        );
+    }
+
+    #[test]
+    fn mac_trait() {
+        let mut cmac = <Cmac<SoftwareAes<Aes128>> as Mac>::new_from_slice(&KEY).unwrap();
+        Mac::update(&mut cmac, &MESSAGE[..40]);
+        assert_eq!(
+            cmac.finalize_fixed()[..],
+            [
+                0xdf, 0xa6, 0x67, 0x47, 0xde, 0x9a, 0xe6, 0x30, 0x30, 0xca, 0x32, 0x61, 0x14, 0x97,
+                0xc8, 0x27
+            ]
+        );
+    }
+
+    #[test]
+    fn verify() {
+        let tag = [
+            0x07, 0x0a, 0x16, 0xb4, 0x6b, 0x4d, 0x41, 0x44, 0xf7, 0x9b, 0xdd, 0x9d, 0xd0, 0x4a,
+            0x28, 0x7c,
+        ];
+        let mut cmac = new_cmac();
+        cmac.update(&MESSAGE[..16]);
+        assert_eq!(cmac.verify(&tag), Ok(()));
+
+        let mut cmac = new_cmac();
+        cmac.update(&MESSAGE[..16]);
+        assert_eq!(cmac.verify_truncated(&tag[..4]), Ok(()));
+
+        let mut cmac = new_cmac();
+        cmac.update(&MESSAGE[..15]);
+        assert_eq!(cmac.verify_truncated(&tag[..4]), Err(MacError));
+
+        assert_eq!(new_cmac().verify_truncated(&[]), Err(MacError));
+    }
+}
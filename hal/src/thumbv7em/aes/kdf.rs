@@ -0,0 +1,236 @@
+//! Key derivation in counter mode, as specified in NIST SP 800-108, with
+//! AES-CMAC as the pseudorandom function
+
+use super::{AesBackend, Cmac};
+use core::convert::TryFrom;
+
+/// Error returned when the requested key material is too long for the
+/// counter length
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct KdfError;
+
+/// Location of the counter in the input of the pseudorandom function
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CounterLocation {
+    /// The counter precedes the fixed input data
+    BeforeFixed,
+    /// The counter follows the fixed input data
+    AfterFixed,
+}
+
+/// SP 800-108 key derivation function in counter mode, using AES-CMAC
+///
+/// Each 16-byte block of key material is the [`Cmac`] of the block counter,
+/// starting at 1, and of the fixed input data. By default, the counter is 4
+/// bytes long and precedes the fixed input data.
+pub struct CounterKdf<B: AesBackend> {
+    cmac: Cmac<B>,
+    counter_len: usize,
+    location: CounterLocation,
+}
+
+impl<B: AesBackend> CounterKdf<B> {
+    /// Create a new [`CounterKdf`] from a backend holding the key derivation
+    /// key
+    #[inline]
+    pub fn new(backend: B) -> Self {
+        Self {
+            cmac: Cmac::new(backend),
+            counter_len: 4,
+            location: CounterLocation::BeforeFixed,
+        }
+    }
+
+    /// Return the underlying [`AesBackend`]
+    #[inline]
+    pub fn free(self) -> B {
+        self.cmac.free()
+    }
+
+    /// Set the length of the counter, in bytes (builder pattern version)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is not between 1 and 4.
+    #[inline]
+    pub fn counter_len(mut self, bytes: usize) -> Self {
+        self.set_counter_len(bytes);
+        self
+    }
+
+    /// Set the length of the counter, in bytes (setter version)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is not between 1 and 4.
+    #[inline]
+    pub fn set_counter_len(&mut self, bytes: usize) {
+        assert!(
+            (1..=4).contains(&bytes),
+            "Counter must be 1 to 4 bytes long"
+        );
+        self.counter_len = bytes;
+    }
+
+    /// Get the length of the counter, in bytes
+    #[inline]
+    pub fn get_counter_len(&self) -> usize {
+        self.counter_len
+    }
+
+    /// Set the location of the counter (builder pattern version)
+    #[inline]
+    pub fn counter_location(mut self, location: CounterLocation) -> Self {
+        self.set_counter_location(location);
+        self
+    }
+
+    /// Set the location of the counter (setter version)
+    #[inline]
+    pub fn set_counter_location(&mut self, location: CounterLocation) {
+        self.location = location;
+    }
+
+    /// Get the location of the counter
+    #[inline]
+    pub fn get_counter_location(&self) -> CounterLocation {
+        self.location
+    }
+
+    /// Fill `out` with key material derived from `fixed_input`
+    ///
+    /// Returns an error if `out` needs more blocks than the counter can
+    /// count.
+    #[inline]
+    pub fn derive(&mut self, fixed_input: &[u8], out: &mut [u8]) -> Result<(), KdfError> {
+        self.derive_with(out, |cmac| cmac.update(fixed_input))
+    }
+
+    /// Fill `out` with key material derived from a `label` and a `context`
+    ///
+    /// The fixed input data is formatted as recommended by SP 800-108, i.e.
+    /// `label || 0x00 || context || L`, where `L` is the length of `out` in
+    /// bits, as a 4-byte big endian integer.
+    pub fn derive_with_label(
+        &mut self,
+        label: &[u8],
+        context: &[u8],
+        out: &mut [u8],
+    ) -> Result<(), KdfError> {
+        let bits = u32::try_from(out.len())
+            .ok()
+            .and_then(|len| len.checked_mul(8))
+            .ok_or(KdfError)?;
+        self.derive_with(out, |cmac| {
+            cmac.update(label);
+            cmac.update(&[0]);
+            cmac.update(context);
+            cmac.update(&bits.to_be_bytes());
+        })
+    }
+
+    /// Fill `out`, feeding the fixed input data to the [`Cmac`] with `fixed`
+    fn derive_with(
+        &mut self,
+        out: &mut [u8],
+        mut fixed: impl FnMut(&mut Cmac<B>),
+    ) -> Result<(), KdfError> {
+        let max_blocks = u32::MAX >> (8 * (4 - self.counter_len));
+        let blocks = out.len().div_ceil(16);
+        if u32::try_from(blocks).map_or(true, |blocks| blocks > max_blocks) {
+            return Err(KdfError);
+        }
+
+        for (index, chunk) in out.chunks_mut(16).enumerate() {
+            let counter = (index as u32 + 1).to_be_bytes();
+            let counter = &counter[4 - self.counter_len..];
+
+            self.cmac.reset();
+            if self.location == CounterLocation::BeforeFixed {
+                self.cmac.update(counter);
+            }
+            fixed(&mut self.cmac);
+            if self.location == CounterLocation::AfterFixed {
+                self.cmac.update(counter);
+            }
+            let block = self.cmac.finalize_reset();
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aes::SoftwareAes;
+    use aes::{Aes128, NewBlockCipher};
+
+    #[test]
+    fn sp800_108_cavp_before_fixed_8bit_counter() {
+        let key = [
+            0xdf, 0xf1, 0xe5, 0x0a, 0xc0, 0xb6, 0x9d, 0xc4, 0x0f, 0x10, 0x51, 0xd4, 0x6c, 0x2b,
+            0x06, 0x9c,
+        ];
+        let fixed_input = [
+            0xc1, 0x6e, 0x6e, 0x02, 0xc5, 0xa3, 0xdc, 0xc8, 0xd7, 0x8b, 0x9a, 0xc1, 0x30, 0x68,
+            0x77, 0x76, 0x13, 0x10, 0x45, 0x5b, 0x4e, 0x41, 0x46, 0x99, 0x51, 0xd9, 0xe6, 0xc2,
+            0x24, 0x5a, 0x06, 0x4b, 0x33, 0xfd, 0x8c, 0x3b, 0x01, 0x20, 0x3a, 0x78, 0x24, 0x48,
+            0x5b, 0xf0, 0xa6, 0x40, 0x60, 0xc4, 0x64, 0x8b, 0x70, 0x7d, 0x26, 0x07, 0x93, 0x56,
+            0x99, 0x31, 0x6e, 0xa5,
+        ];
+        let mut kdf = CounterKdf::new(SoftwareAes::new(Aes128::new(&key.into()))).counter_len(1);
+
+        let mut out = [0; 16];
+        kdf.derive(&fixed_input, &mut out).unwrap();
+        assert_eq!(
+            out,
+            [
+                0x8b, 0xe8, 0xf0, 0x86, 0x9b, 0x3c, 0x0b, 0xa9, 0x7b, 0x71, 0x86, 0x3d, 0x1b, 0x9f,
+                0x78, 0x13
+            ]
+        );
+
+        // 256 blocks cannot be counted with a single byte
+        let mut out = [0; 16 * 256];
+        assert_eq!(kdf.derive(&fixed_input, &mut out), Err(KdfError));
+    }
+
+    #[test]
+    fn label_and_context() {
+        // Cross-checked against the `KBKDFCMAC` implementation of the Python
+        // `cryptography` package
+        let key = [
+            0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf,
+            0x4f, 0x3c,
+        ];
+        let context = [0, 1, 2, 3, 4, 5, 6, 7];
+        let mut kdf = CounterKdf::new(SoftwareAes::new(Aes128::new(&key.into())));
+
+        let mut out = [0; 40];
+        kdf.derive_with_label(b"SmartEeprom", &context, &mut out)
+            .unwrap();
+        assert_eq!(
+            out,
+            [
+                0x34, 0x86, 0x84, 0xdc, 0xe5, 0x3e, 0x7a, 0xb4, 0xdc, 0x00, 0x63, 0x99, 0x35, 0x56,
+                0xfc, 0xcd, 0xc3, 0xe4, 0xfe, 0x29, 0xad, 0x90, 0x80, 0x19, 0x37, 0x83, 0x89, 0x0b,
+                0x29, 0x23, 0x87, 0x46, 0x6e, 0x11, 0x32, 0xc1, 0x8d, 0x2e, 0x66, 0x3f
+            ]
+        );
+
+        kdf.set_counter_location(CounterLocation::AfterFixed);
+        kdf.derive_with_label(b"SmartEeprom", &context, &mut out)
+            .unwrap();
+        assert_eq!(
+            out,
+            [
+                0x3d, 0xdb, 0x26, 0x9d, 0x6b, 0x96, 0xf8, 0xa9, 0x01, 0x6f, 0xde, 0x77, 0x2f, 0x4a,
+                0x90, 0xa8, 0x4e, 0x4b, 0xd1, 0x53, 0x52, 0x2b, 0xc4, 0x7b, 0xbd, 0xb4, 0x6d, 0x5f,
+                0x25, 0x0c, 0x5b, 0xfc, 0xbc, 0x38, 0x73, 0x74, 0x9d, 0xa9, 0x5a, 0x14
+            ]
+        );
+    }
+}
@@ -0,0 +1,328 @@
+//! AES key wrap, as specified in RFC 3394, and AES key wrap with padding, as
+//! specified in RFC 5649
+
+use super::{cmac::constant_time_eq, AesBackend};
+use core::convert::TryFrom;
+
+/// Default initial value of RFC 3394
+const IV: [u8; 8] = [0xa6; 8];
+
+/// Constant part of the alternative initial value of RFC 5649
+const AIV: [u8; 4] = [0xa6, 0x59, 0x59, 0xa6];
+
+/// Errors returned by [`KeyWrap`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum KeyWrapError {
+    /// The length of the input is not supported
+    InvalidLength,
+    /// The output buffer is too small
+    BufferTooSmall,
+    /// The integrity check of the wrapped key failed
+    IntegrityCheck,
+}
+
+/// AES key wrap
+///
+/// Keys are wrapped with a key-encryption key, i.e. the key of the
+/// [`AesBackend`]. The wrapped keys are authenticated, so they can be stored
+/// in untrusted memory, such as the `SmartEeprom`.
+pub struct KeyWrap<B: AesBackend> {
+    backend: B,
+}
+
+impl<B: AesBackend> KeyWrap<B> {
+    /// Create a new [`KeyWrap`] from a backend holding the key-encryption key
+    #[inline]
+    pub fn new(backend: B) -> Self {
+        Self { backend }
+    }
+
+    /// Return the underlying [`AesBackend`]
+    #[inline]
+    pub fn free(self) -> B {
+        self.backend
+    }
+
+    /// Wrap `key` into `out`, as specified in RFC 3394
+    ///
+    /// `key` must be a multiple of 8 bytes long, and at least 16 bytes long.
+    /// The wrapped key is 8 bytes longer than `key`. Returns the length of the
+    /// wrapped key.
+    pub fn wrap(&mut self, key: &[u8], out: &mut [u8]) -> Result<usize, KeyWrapError> {
+        if key.len() < 16 || key.len() % 8 != 0 {
+            return Err(KeyWrapError::InvalidLength);
+        }
+        let len = key.len() + 8;
+        let out = out.get_mut(..len).ok_or(KeyWrapError::BufferTooSmall)?;
+
+        out[8..].copy_from_slice(key);
+        let a = self.wrap_semiblocks(IV, &mut out[8..]);
+        out[..8].copy_from_slice(&a);
+        Ok(len)
+    }
+
+    /// Unwrap `wrapped` into `out`, as specified in RFC 3394
+    ///
+    /// The key is 8 bytes shorter than `wrapped`. Returns the length of the
+    /// key. If the integrity check fails, `out` is cleared.
+    pub fn unwrap(&mut self, wrapped: &[u8], out: &mut [u8]) -> Result<usize, KeyWrapError> {
+        if wrapped.len() < 24 || wrapped.len() % 8 != 0 {
+            return Err(KeyWrapError::InvalidLength);
+        }
+        let len = wrapped.len() - 8;
+        let out = out.get_mut(..len).ok_or(KeyWrapError::BufferTooSmall)?;
+
+        out.copy_from_slice(&wrapped[8..]);
+        let a = self.unwrap_semiblocks(semiblock(&wrapped[..8]), out);
+        if constant_time_eq(&a, &IV) {
+            Ok(len)
+        } else {
+            out.fill(0);
+            Err(KeyWrapError::IntegrityCheck)
+        }
+    }
+
+    /// Wrap `key` into `out`, as specified in RFC 5649
+    ///
+    /// `key` can have any non-zero length. It is padded to a multiple of 8
+    /// bytes, and the wrapped key is 8 bytes longer than the padded key.
+    /// Returns the length of the wrapped key.
+    pub fn wrap_with_padding(&mut self, key: &[u8], out: &mut [u8]) -> Result<usize, KeyWrapError> {
+        let mli = u32::try_from(key.len()).map_err(|_| KeyWrapError::InvalidLength)?;
+        if key.is_empty() {
+            return Err(KeyWrapError::InvalidLength);
+        }
+        let padded = key.len().div_ceil(8) * 8;
+        let out = out
+            .get_mut(..padded + 8)
+            .ok_or(KeyWrapError::BufferTooSmall)?;
+
+        let mut aiv = [0; 8];
+        aiv[..4].copy_from_slice(&AIV);
+        aiv[4..].copy_from_slice(&mli.to_be_bytes());
+
+        out[8..8 + key.len()].copy_from_slice(key);
+        out[8 + key.len()..].fill(0);
+        if padded == 8 {
+            let mut block = [0; 16];
+            block[..8].copy_from_slice(&aiv);
+            block[8..].copy_from_slice(&out[8..]);
+            self.backend.encrypt_block(&mut block);
+            out.copy_from_slice(&block);
+        } else {
+            let a = self.wrap_semiblocks(aiv, &mut out[8..]);
+            out[..8].copy_from_slice(&a);
+        }
+        Ok(padded + 8)
+    }
+
+    /// Unwrap `wrapped` into `out`, as specified in RFC 5649
+    ///
+    /// `out` must be able to hold the padded key, which is 8 bytes shorter
+    /// than `wrapped`. Returns the length of the key, without padding. If the
+    /// integrity check fails, `out` is cleared.
+    pub fn unwrap_with_padding(
+        &mut self,
+        wrapped: &[u8],
+        out: &mut [u8],
+    ) -> Result<usize, KeyWrapError> {
+        if wrapped.len() < 16 || wrapped.len() % 8 != 0 {
+            return Err(KeyWrapError::InvalidLength);
+        }
+        let padded = wrapped.len() - 8;
+        let out = out.get_mut(..padded).ok_or(KeyWrapError::BufferTooSmall)?;
+
+        let a = if padded == 8 {
+            let mut block = [0; 16];
+            block.copy_from_slice(wrapped);
+            self.backend.decrypt_block(&mut block);
+            out.copy_from_slice(&block[8..]);
+            semiblock(&block[..8])
+        } else {
+            out.copy_from_slice(&wrapped[8..]);
+            self.unwrap_semiblocks(semiblock(&wrapped[..8]), out)
+        };
+
+        let mli = u32::from_be_bytes([a[4], a[5], a[6], a[7]]) as usize;
+        let valid = constant_time_eq(&a[..4], &AIV)
+            && mli + 8 > padded
+            && mli <= padded
+            && out[mli..].iter().all(|&byte| byte == 0);
+        if valid {
+            Ok(mli)
+        } else {
+            out.fill(0);
+            Err(KeyWrapError::IntegrityCheck)
+        }
+    }
+
+    /// Apply the wrapping function W to the semiblocks `r`, with the initial
+    /// value `a`, and return the resulting integrity check register
+    fn wrap_semiblocks(&mut self, mut a: [u8; 8], r: &mut [u8]) -> [u8; 8] {
+        let n = r.len() / 8;
+        for j in 0..6 {
+            for (i, r) in r.chunks_exact_mut(8).enumerate() {
+                let mut block = [0; 16];
+                block[..8].copy_from_slice(&a);
+                block[8..].copy_from_slice(r);
+                self.backend.encrypt_block(&mut block);
+
+                let t = (n * j + i + 1) as u64;
+                a = (u64::from_be_bytes(semiblock(&block[..8])) ^ t).to_be_bytes();
+                r.copy_from_slice(&block[8..]);
+            }
+        }
+        a
+    }
+
+    /// Apply the unwrapping function W⁻¹ to the semiblocks `r`, with the
+    /// integrity check register `a`, and return the recovered initial value
+    fn unwrap_semiblocks(&mut self, mut a: [u8; 8], r: &mut [u8]) -> [u8; 8] {
+        let n = r.len() / 8;
+        for j in (0..6).rev() {
+            for (i, r) in r.chunks_exact_mut(8).enumerate().rev() {
+                let t = (n * j + i + 1) as u64;
+                let mut block = [0; 16];
+                block[..8].copy_from_slice(&(u64::from_be_bytes(a) ^ t).to_be_bytes());
+                block[8..].copy_from_slice(r);
+                self.backend.decrypt_block(&mut block);
+
+                a = semiblock(&block[..8]);
+                r.copy_from_slice(&block[8..]);
+            }
+        }
+        a
+    }
+}
+
+/// Copy an 8-byte slice into a semiblock
+#[inline]
+fn semiblock(slice: &[u8]) -> [u8; 8] {
+    let mut semiblock = [0; 8];
+    semiblock.copy_from_slice(slice);
+    semiblock
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aes::SoftwareAes;
+    use aes::{Aes128, Aes192, Aes256, NewBlockCipher};
+
+    const KEY_DATA: [u8; 32] = [
+        0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee,
+        0xff, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d,
+        0x0e, 0x0f,
+    ];
+
+    fn kek<const N: usize>() -> [u8; N] {
+        let mut kek = [0; N];
+        for (i, byte) in kek.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        kek
+    }
+
+    #[test]
+    fn rfc3394_128bit_kek() {
+        let mut wrap = KeyWrap::new(SoftwareAes::new(Aes128::new(&kek::<16>().into())));
+        let expected = [
+            0x1f, 0xa6, 0x8b, 0x0a, 0x81, 0x12, 0xb4, 0x47, 0xae, 0xf3, 0x4b, 0xd8, 0xfb, 0x5a,
+            0x7b, 0x82, 0x9d, 0x3e, 0x86, 0x23, 0x71, 0xd2, 0xcf, 0xe5,
+        ];
+
+        let mut wrapped = [0; 32];
+        assert_eq!(wrap.wrap(&KEY_DATA[..16], &mut wrapped), Ok(24));
+        assert_eq!(wrapped[..24], expected);
+
+        let mut key = [0; 16];
+        assert_eq!(wrap.unwrap(&expected, &mut key), Ok(16));
+        assert_eq!(key, KEY_DATA[..16]);
+    }
+
+    #[test]
+    fn rfc3394_256bit_kek() {
+        let mut wrap = KeyWrap::new(SoftwareAes::new(Aes256::new(&kek::<32>().into())));
+        let expected = [
+            0x28, 0xc9, 0xf4, 0x04, 0xc4, 0xb8, 0x10, 0xf4, 0xcb, 0xcc, 0xb3, 0x5c, 0xfb, 0x87,
+            0xf8, 0x26, 0x3f, 0x57, 0x86, 0xe2, 0xd8, 0x0e, 0xd3, 0x26, 0xcb, 0xc7, 0xf0, 0xe7,
+            0x1a, 0x99, 0xf4, 0x3b, 0xfb, 0x98, 0x8b, 0x9b, 0x7a, 0x02, 0xdd, 0x21,
+        ];
+
+        let mut wrapped = [0; 40];
+        assert_eq!(wrap.wrap(&KEY_DATA, &mut wrapped), Ok(40));
+        assert_eq!(wrapped, expected);
+
+        let mut key = [0; 32];
+        assert_eq!(wrap.unwrap(&expected, &mut key), Ok(32));
+        assert_eq!(key, KEY_DATA);
+    }
+
+    #[test]
+    fn rfc3394_integrity_check() {
+        let mut wrap = KeyWrap::new(SoftwareAes::new(Aes128::new(&kek::<16>().into())));
+        let mut wrapped = [0; 24];
+        wrap.wrap(&KEY_DATA[..16], &mut wrapped).unwrap();
+        wrapped[10] ^= 1;
+
+        let mut key = [0; 16];
+        assert_eq!(
+            wrap.unwrap(&wrapped, &mut key),
+            Err(KeyWrapError::IntegrityCheck)
+        );
+        assert_eq!(key, [0; 16]);
+        assert_eq!(
+            wrap.wrap(&KEY_DATA[..12], &mut wrapped),
+            Err(KeyWrapError::InvalidLength)
+        );
+        assert_eq!(
+            wrap.wrap(&KEY_DATA[..24], &mut wrapped),
+            Err(KeyWrapError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn rfc5649_examples() {
+        let kek = [
+            0x58, 0x40, 0xdf, 0x6e, 0x29, 0xb0, 0x2a, 0xf1, 0xab, 0x49, 0x3b, 0x70, 0x5b, 0xf1,
+            0x6e, 0xa1, 0xae, 0x83, 0x38, 0xf4, 0xdc, 0xc1, 0x76, 0xa8,
+        ];
+        let mut wrap = KeyWrap::new(SoftwareAes::new(Aes192::new(&kek.into())));
+
+        let key = [
+            0xc3, 0x7b, 0x7e, 0x64, 0x92, 0x58, 0x43, 0x40, 0xbe, 0xd1, 0x22, 0x07, 0x80, 0x89,
+            0x41, 0x15, 0x50, 0x68, 0xf7, 0x38,
+        ];
+        let expected = [
+            0x13, 0x8b, 0xde, 0xaa, 0x9b, 0x8f, 0xa7, 0xfc, 0x61, 0xf9, 0x77, 0x42, 0xe7, 0x22,
+            0x48, 0xee, 0x5a, 0xe6, 0xae, 0x53, 0x60, 0xd1, 0xae, 0x6a, 0x5f, 0x54, 0xf3, 0x73,
+            0xfa, 0x54, 0x3b, 0x6a,
+        ];
+        let mut wrapped = [0; 32];
+        assert_eq!(wrap.wrap_with_padding(&key, &mut wrapped), Ok(32));
+        assert_eq!(wrapped, expected);
+        let mut unwrapped = [0; 24];
+        assert_eq!(wrap.unwrap_with_padding(&expected, &mut unwrapped), Ok(20));
+        assert_eq!(unwrapped[..20], key);
+
+        let key = [0x46, 0x6f, 0x72, 0x50, 0x61, 0x73, 0x69];
+        let expected = [
+            0xaf, 0xbe, 0xb0, 0xf0, 0x7d, 0xfb, 0xf5, 0x41, 0x92, 0x00, 0xf2, 0xcc, 0xb5, 0x0b,
+            0xb2, 0x4f,
+        ];
+        let mut wrapped = [0; 16];
+        assert_eq!(wrap.wrap_with_padding(&key, &mut wrapped), Ok(16));
+        assert_eq!(wrapped, expected);
+        let mut unwrapped = [0; 8];
+        assert_eq!(wrap.unwrap_with_padding(&expected, &mut unwrapped), Ok(7));
+        assert_eq!(unwrapped[..7], key);
+
+        let mut tampered = expected;
+        tampered[0] ^= 1;
+        assert_eq!(
+            wrap.unwrap_with_padding(&tampered, &mut unwrapped),
+            Err(KeyWrapError::IntegrityCheck)
+        );
+    }
+}
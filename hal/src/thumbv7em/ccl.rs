@@ -0,0 +1,452 @@
+//! # Configurable Custom Logic
+//!
+//! The Configurable Custom Logic (CCL) peripheral provides four programmable
+//! look-up tables (LUTs). Each LUT combines three inputs through a truth table,
+//! and pairs of LUTs can feed a sequential element, i.e. a flip-flop or a
+//! latch. The LUT outputs can drive I/O pins, generate events or be fed back
+//! into other LUTs, which allows simple glue logic to be implemented without
+//! external gates.
+//!
+//! # Initializing
+//!
+//! Create a [`Ccl`] with [`Ccl::new`], which enables the CCL APB clock and
+//! resets the peripheral. The filters, edge detectors and sequential elements
+//! are clocked by the CCL generic clock, which must be configured separately.
+//!
+//! # LUT configuration
+//!
+//! Each LUT is configured from a [`LutConfig`], which is parametrized by the
+//! [`LutId`] of the LUT it applies to. The truth table is indexed by the three
+//! inputs, with input 0 as the least significant bit. For example, `0x80` is a
+//! three-input AND gate and `0xFE` a three-input OR gate.
+//!
+//! I/O pins must be in [`AlternateN`] mode. [`LutConfig::io_input`] and
+//! [`LutConfig::io_output`] only accept pins connected to the right LUT, and
+//! for inputs, the right input index.
+//!
+//! ```no_run
+//! use atsamd_hal::ccl::{Ccl, Filter, Input, Lut0, LutConfig};
+//! use atsamd_hal::gpio::{Pins, N};
+//! # let mut peripherals = atsamd_hal::pac::Peripherals::take().unwrap();
+//! let pins = Pins::new(peripherals.PORT);
+//! let in0 = pins.pa04.into_alternate::<N>();
+//! let out = pins.pa07.into_alternate::<N>();
+//!
+//! let mut ccl = Ccl::new(peripherals.CCL, &mut peripherals.MCLK);
+//! // Gate a TCC0 waveform output with PA04
+//! let config = LutConfig::<Lut0>::new(0x88)
+//!     .io_input(&in0)
+//!     .input(1, Input::Tcc)
+//!     .filter(Filter::Synchronizer)
+//!     .io_output(&out);
+//! ccl.configure_lut(config);
+//! ccl.enable(false);
+//! ```
+//!
+//! The CCL must be disabled while the LUTs and sequential elements are
+//! configured. [`Ccl::configure_lut`] and [`Ccl::set_sequential`] disable the
+//! CCL while writing the registers and restore its previous state.
+
+use core::marker::PhantomData;
+
+use crate::gpio::*;
+use crate::pac::{CCL, MCLK};
+use crate::typelevel::Sealed;
+
+//==============================================================================
+// Ids
+//==============================================================================
+
+/// Type-level LUT number
+pub trait LutId: Sealed {
+    const NUM: usize;
+}
+
+/// Type-level sequential element number
+///
+/// Sequential element `n` takes the outputs of LUTs `2n` and `2n + 1`.
+pub trait SeqId: Sealed {
+    const NUM: usize;
+}
+
+macro_rules! ids {
+    ($Trait:ident, $desc:literal: $( $Id:ident = $num:literal ),+) => {
+        $(
+            #[doc = concat!("Type-level ", $desc, " ", stringify!($num))]
+            pub enum $Id {}
+            impl Sealed for $Id {}
+            impl $Trait for $Id {
+                const NUM: usize = $num;
+            }
+        )+
+    };
+}
+
+ids!(LutId, "LUT": Lut0 = 0, Lut1 = 1, Lut2 = 2, Lut3 = 3);
+ids!(SeqId, "sequential element": Seq0 = 0, Seq1 = 1);
+
+//==============================================================================
+// Pins
+//==============================================================================
+
+/// [`PinId`]s that can be used as a LUT input
+pub trait InputPin: PinId {
+    /// LUT connected to this pin
+    type Lut: LutId;
+    /// Index of the LUT input connected to this pin
+    const INDEX: usize;
+}
+
+/// [`PinId`]s that can be used as a LUT output
+pub trait OutputPin: PinId {
+    /// LUT connected to this pin
+    type Lut: LutId;
+}
+
+macro_rules! pins {
+    (
+        $(
+            $( #[$cfg:meta] )?
+            $PinId:ident: $kind:ident($Lut:ident $(, $index:literal)?),
+        )+
+    ) => {
+        $(
+            pins!(@impl $( #[$cfg] )? $PinId, $kind, $Lut $(, $index)?);
+        )+
+    };
+    (@impl $( #[$cfg:meta] )? $PinId:ident, In, $Lut:ident, $index:literal) => {
+        $( #[$cfg] )?
+        impl InputPin for $PinId {
+            type Lut = $Lut;
+            const INDEX: usize = $index;
+        }
+    };
+    (@impl $( #[$cfg:meta] )? $PinId:ident, Out, $Lut:ident) => {
+        $( #[$cfg] )?
+        impl OutputPin for $PinId {
+            type Lut = $Lut;
+        }
+    };
+}
+
+pins!(
+    PA04: In(Lut0, 0),
+    PA05: In(Lut0, 1),
+    PA06: In(Lut0, 2),
+    PA07: Out(Lut0),
+    PA08: In(Lut1, 0),
+    PA09: In(Lut1, 1),
+    PA10: In(Lut1, 2),
+    PA11: Out(Lut1),
+    PA16: In(Lut0, 0),
+    PA17: In(Lut0, 1),
+    PA18: In(Lut0, 2),
+    PA19: Out(Lut0),
+    PA22: In(Lut2, 0),
+    PA23: In(Lut2, 1),
+    PA24: In(Lut2, 2),
+    PA25: Out(Lut2),
+    PA30: In(Lut1, 0),
+    PA31: Out(Lut1),
+    #[cfg(feature = "has-pb00")]
+    PB00: In(Lut0, 1),
+    #[cfg(feature = "has-pb01")]
+    PB01: In(Lut0, 2),
+    PB02: Out(Lut0),
+    #[cfg(feature = "pins-64")]
+    PB06: In(Lut2, 0),
+    #[cfg(feature = "pins-64")]
+    PB07: In(Lut2, 1),
+    PB08: In(Lut2, 2),
+    PB09: Out(Lut2),
+    #[cfg(feature = "pins-64")]
+    PB14: In(Lut3, 0),
+    #[cfg(feature = "pins-64")]
+    PB15: In(Lut3, 1),
+    #[cfg(feature = "pins-64")]
+    PB16: In(Lut3, 2),
+    #[cfg(feature = "pins-64")]
+    PB17: Out(Lut3),
+    PB22: In(Lut0, 0),
+    PB23: Out(Lut0),
+);
+
+//==============================================================================
+// Configuration
+//==============================================================================
+
+/// LUT input source
+///
+/// The meaning of [`Input::Tc`], [`Input::AltTc`], [`Input::Tcc`] and
+/// [`Input::Sercom`] depends on the LUT and input index. Refer to the datasheet
+/// for the peripheral instance and output connected to each input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum Input {
+    /// The input is masked, i.e. always low
+    Mask = 0,
+    /// Output of the sequential element or LUT
+    Feedback = 1,
+    /// Output of the next LUT, i.e. LUT `(n + 1) % 4`
+    Link = 2,
+    /// Event input of the LUT
+    Event = 3,
+    /// I/O pin
+    ///
+    /// Prefer [`LutConfig::io_input`], which checks the pin at compile time.
+    Io = 4,
+    /// Analog comparator output
+    Ac = 5,
+    /// TC waveform output
+    Tc = 6,
+    /// Alternate TC waveform output
+    AltTc = 7,
+    /// TCC waveform output
+    Tcc = 8,
+    /// SERCOM output
+    Sercom = 9,
+}
+
+/// Input filter of a LUT output
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum Filter {
+    /// No filter
+    Disabled = 0,
+    /// Synchronize the output to the CCL clock
+    Synchronizer = 1,
+    /// Filter out pulses shorter than the CCL clock period
+    Filter = 2,
+}
+
+/// Sequential element
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum Sequential {
+    /// Sequential element is disabled, the LUT outputs are used directly
+    Disabled = 0,
+    /// D flip-flop, with the even LUT as D and the odd LUT as reset
+    DFlipFlop = 1,
+    /// JK flip-flop, with the even LUT as J and the odd LUT as K
+    JkFlipFlop = 2,
+    /// D latch, with the even LUT as D and the odd LUT as gate
+    DLatch = 3,
+    /// SR latch, with the even LUT as set and the odd LUT as reset
+    SrLatch = 4,
+}
+
+/// Configuration of a LUT
+///
+/// A `LutConfig` is created with a truth table, every input masked and all
+/// other options disabled. Apply it with [`Ccl::configure_lut`].
+pub struct LutConfig<Id: LutId> {
+    truth: u8,
+    inputs: [Input; 3],
+    filter: Filter,
+    edge_detector: bool,
+    event_input: Option<bool>,
+    event_output: bool,
+    enable: bool,
+    id: PhantomData<Id>,
+}
+
+impl<Id: LutId> LutConfig<Id> {
+    /// Create a new configuration with the given truth table
+    ///
+    /// The LUT is enabled when the configuration is applied.
+    #[inline]
+    pub fn new(truth: u8) -> Self {
+        Self {
+            truth,
+            inputs: [Input::Mask; 3],
+            filter: Filter::Disabled,
+            edge_detector: false,
+            event_input: None,
+            event_output: false,
+            enable: true,
+            id: PhantomData,
+        }
+    }
+
+    /// Set the truth table
+    #[inline]
+    pub fn truth(mut self, truth: u8) -> Self {
+        self.truth = truth;
+        self
+    }
+
+    /// Select the source of input `index`, in the range `0..3`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of range.
+    #[inline]
+    pub fn input(mut self, index: usize, input: Input) -> Self {
+        self.inputs[index] = input;
+        self
+    }
+
+    /// Use an I/O pin as an input
+    ///
+    /// The pin must be connected to this LUT. Its input index is determined by
+    /// the pin.
+    #[inline]
+    pub fn io_input<I>(self, _pin: &Pin<I, AlternateN>) -> Self
+    where
+        I: InputPin<Lut = Id>,
+    {
+        self.input(I::INDEX, Input::Io)
+    }
+
+    /// Check that an I/O pin is connected to the output of this LUT
+    ///
+    /// The output is driven on any pin in [`AlternateN`] mode connected to the
+    /// LUT, so this method has no effect at run-time.
+    #[inline]
+    pub fn io_output<I>(self, _pin: &Pin<I, AlternateN>) -> Self
+    where
+        I: OutputPin<Lut = Id>,
+    {
+        self
+    }
+
+    /// Select the filter of the LUT output
+    #[inline]
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Enable the edge detector, which outputs a pulse of one CCL clock cycle
+    /// on the rising edge of the filter output
+    #[inline]
+    pub fn edge_detector(mut self, enable: bool) -> Self {
+        self.edge_detector = enable;
+        self
+    }
+
+    /// Enable the event input, optionally inverted
+    ///
+    /// Use [`Input::Event`] to select it as a LUT input.
+    #[inline]
+    pub fn event_input(mut self, invert: bool) -> Self {
+        self.event_input = Some(invert);
+        self
+    }
+
+    /// Enable the event output
+    #[inline]
+    pub fn event_output(mut self, enable: bool) -> Self {
+        self.event_output = enable;
+        self
+    }
+
+    /// Enable or disable the LUT when the configuration is applied
+    #[inline]
+    pub fn enable(mut self, enable: bool) -> Self {
+        self.enable = enable;
+        self
+    }
+}
+
+//==============================================================================
+// Ccl
+//==============================================================================
+
+/// Configurable Custom Logic peripheral
+pub struct Ccl {
+    ccl: CCL,
+}
+
+impl Ccl {
+    /// Enable the CCL APB clock and reset the peripheral
+    #[inline]
+    pub fn new(ccl: CCL, mclk: &mut MCLK) -> Self {
+        mclk.apbcmask.modify(|_, w| w.ccl_().set_bit());
+        ccl.ctrl.write(|w| w.swrst().set_bit());
+        Self { ccl }
+    }
+
+    /// Enable the CCL, optionally running in standby sleep mode
+    #[inline]
+    pub fn enable(&mut self, run_standby: bool) {
+        self.ccl.ctrl.write(|w| {
+            w.runstdby().bit(run_standby);
+            w.enable().set_bit()
+        });
+    }
+
+    /// Disable the CCL
+    #[inline]
+    pub fn disable(&mut self) {
+        self.ccl.ctrl.modify(|_, w| w.enable().clear_bit());
+    }
+
+    /// Return `true` if the CCL is enabled
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.ccl.ctrl.read().enable().bit_is_set()
+    }
+
+    /// Apply a [`LutConfig`]
+    #[inline]
+    pub fn configure_lut<Id: LutId>(&mut self, config: LutConfig<Id>) {
+        self.while_disabled(|ccl| {
+            ccl.lutctrl[Id::NUM].write(|w| {
+                // Safety: All `Input`, `Filter` and truth table values are valid
+                unsafe {
+                    w.truth().bits(config.truth);
+                    w.insel0().bits(config.inputs[0] as u8);
+                    w.insel1().bits(config.inputs[1] as u8);
+                    w.insel2().bits(config.inputs[2] as u8);
+                    w.filtsel().bits(config.filter as u8);
+                }
+                w.edgesel().bit(config.edge_detector);
+                w.lutei().bit(config.event_input.is_some());
+                w.invei().bit(config.event_input.unwrap_or(false));
+                w.luteo().bit(config.event_output);
+                w.enable().bit(config.enable)
+            });
+        });
+    }
+
+    /// Enable or disable a LUT without changing its configuration
+    #[inline]
+    pub fn enable_lut<Id: LutId>(&mut self, enable: bool) {
+        self.while_disabled(|ccl| {
+            ccl.lutctrl[Id::NUM].modify(|_, w| w.enable().bit(enable));
+        });
+    }
+
+    /// Select the sequential element taking the outputs of LUTs `2n` and
+    /// `2n + 1`
+    #[inline]
+    pub fn set_sequential<Id: SeqId>(&mut self, seq: Sequential) {
+        self.while_disabled(|ccl| {
+            // Safety: All `Sequential` values are valid
+            ccl.seqctrl[Id::NUM].write(|w| unsafe { w.seqsel().bits(seq as u8) });
+        });
+    }
+
+    /// Reset the CCL and return the register block
+    #[inline]
+    pub fn free(self) -> CCL {
+        self.ccl.ctrl.write(|w| w.swrst().set_bit());
+        self.ccl
+    }
+
+    /// The `LUTCTRL` and `SEQCTRL` registers are enable-protected, so disable
+    /// the CCL while they are written, then restore its state
+    #[inline]
+    fn while_disabled(&mut self, f: impl FnOnce(&CCL)) {
+        let enabled = self.is_enabled();
+        self.disable();
+        f(&self.ccl);
+        if enabled {
+            self.ccl.ctrl.modify(|_, w| w.enable().set_bit());
+        }
+    }
+}
@@ -14,8 +14,9 @@
 //! compile-time. This is exceedingly reasonable for most clocking
 //! configurations, because most users set up their clocks once and never change
 //! them again. However, if you need to dynamically change the clocking
-//! configuration at run-time, you may find this API less ergonomic. A future,
-//! fully dynamic API has been discussed, but nothing has been developed so far.
+//! configuration at run-time, you may find this API less ergonomic. In that
+//! case, see the [`dynamic`] module, which tracks the [`Gclk`]s and [`Pclk`]s
+//! at run-time instead.
 //! </p>
 //!
 //! The sections that follow provide an explanation of key concepts in the
@@ -803,6 +804,7 @@
 //! [`DpllToken`]: dpll::DpllToken
 //! [`EnabledDpll0`]: dpll::EnabledDpll0
 //!
+//! [`Gclk`]: gclk::Gclk
 //! [`Gclk0`]: gclk::Gclk0
 //! [`GclkId`]: gclk::GclkId
 //! [`EnabledGclk0`]: gclk::EnabledGclk0
@@ -862,6 +864,7 @@ pub mod ahb;
 pub mod apb;
 pub mod dfll;
 pub mod dpll;
+pub mod dynamic;
 pub mod gclk;
 pub mod osculp32k;
 pub mod pclk;
@@ -0,0 +1,662 @@
+//! # Dynamic clock tree
+//!
+//! ## Overview
+//!
+//! The rest of the [`clock` module](super) tracks the state of the clock tree
+//! at compile-time. That is ideal for clocks that are configured once at
+//! start-up, but it is cumbersome for applications that must reconfigure their
+//! clocks at run-time, e.g. to drop the CPU from [`Dpll0`] at 120 MHz to the
+//! [`Dfll`] at 48 MHz, or to the [`OscUlp32k`] in a low-power state. Each
+//! configuration has a different type, so the application ends up storing its
+//! clock tree in a hand-written `enum` and matching on it everywhere.
+//!
+//! This module provides [`DynClocks`], a clock tree manager that tracks the
+//! same information at run-time instead. It owns [`Gclk0`], any other [`Gclk`]
+//! and [`Pclk`] handed to it, and keeps a run-time count of the consumers of
+//! each clock. Operations that would break the clock tree, like disabling a
+//! [`Gclk`] that still feeds a [`Pclk`], are refused with an [`Error`] rather
+//! than rejected at compile-time.
+//!
+//! ## Conversion to and from the type-level API
+//!
+//! [`DynClocks`] only manages [`Gclk`]s and [`Pclk`]s. Clock [`Source`]s are
+//! still created, configured and enabled with the type-level API, then
+//! registered with [`DynClocks::register_source`]. Registration [`Increment`]s
+//! the [`Enabled`] counter of the `Source`, so it cannot be disabled while
+//! [`DynClocks`] may be using it. [`DynClocks::unregister_source`] is refused
+//! while any managed [`Gclk`] still uses the `Source`; otherwise, it returns
+//! the [`Decrement`]ed `Source`, which can then be disabled as usual.
+//!
+//! [`Gclk`]s and [`Pclk`]s move between the two APIs as [`GclkToken`]s and
+//! [`PclkToken`]s. [`DynClocks::adopt_gclk`] and [`DynClocks::enable_pclk`]
+//! take tokens, while [`DynClocks::release_gclk`] and
+//! [`DynClocks::disable_pclk`] return them. Finally, [`DynClocks::new`] takes
+//! [`EnabledGclk0`], and [`DynClocks::free`] returns it once every other clock
+//! has been released, which [`DynClocks::check_free`] verifies beforehand.
+//!
+//! ## Notifying peripherals
+//!
+//! Peripherals derive their baud rates, periods, etc. from the frequency of
+//! their [`Pclk`]. When a [`Gclk`] changes frequency, the affected peripherals
+//! must be reconfigured. Functions that change a frequency take a slice of
+//! [`ClockConsumer`]s. After the change, each consumer clocked by an affected
+//! [`Pclk`] receives the new frequency through
+//! [`ClockConsumer::set_clock_freq`].
+//!
+//! [`DynClocks`] does not keep track of the peripherals themselves, so only
+//! the consumers in the slice are notified. The caller is responsible for
+//! passing every peripheral clocked by an affected [`Pclk`]. Any peripheral
+//! left out keeps running with settings derived from the old frequency.
+//!
+//! ## Example
+//!
+//! Starting from the [`clock_system_at_reset`], derive [`Dpll0`] at 120 MHz
+//! from the [`Dfll`], as shown in the [`dpll` module documentation]. Then hand
+//! [`Gclk0`] over to [`DynClocks`], clock a UART from [`Gclk2`] and switch the
+//! CPU to `Dpll0`.
+//!
+//! ```no_run
+//! use atsamd_hal::{
+//!     clock::v2::{
+//!         clock_system_at_reset,
+//!         dpll::Dpll,
+//!         dynamic::{ClockConsumer, DynClocks},
+//!         gclk::{DynGclkId, DynGclkSourceId, Gclk, GclkDiv16},
+//!         pclk::Pclk,
+//!     },
+//!     pac::Peripherals,
+//! };
+//! let mut pac = Peripherals::take().unwrap();
+//! let (buses, clocks, tokens) = clock_system_at_reset(
+//!     pac.OSCCTRL,
+//!     pac.OSC32KCTRL,
+//!     pac.GCLK,
+//!     pac.MCLK,
+//!     &mut pac.NVMCTRL,
+//! );
+//! let (gclk1, dfll) = Gclk::from_source(tokens.gclks.gclk1, clocks.dfll);
+//! let gclk1 = gclk1.div(GclkDiv16::Div(24)).enable();
+//! let (pclk_dpll0, gclk1) = Pclk::enable(tokens.pclks.dpll0, gclk1);
+//! let dpll0 = Dpll::from_pclk(tokens.dpll0, pclk_dpll0)
+//!     .loop_div(60, 0)
+//!     .enable();
+//! while !dpll0.is_ready() {}
+//! let mut dyn_clocks = DynClocks::new(clocks.gclk0);
+//! let dpll0 = dyn_clocks.register_source(dpll0).ok().unwrap();
+//! dyn_clocks.adopt_gclk(tokens.gclks.gclk2);
+//! dyn_clocks
+//!     .enable_gclk(DynGclkId::Gclk2, DynGclkSourceId::Dfll, 1)
+//!     .unwrap();
+//! let freq = dyn_clocks
+//!     .enable_pclk(tokens.pclks.sercom0, DynGclkId::Gclk2)
+//!     .ok()
+//!     .unwrap();
+//! // Create the UART from `freq`
+//! # let uart: &mut dyn ClockConsumer = unimplemented!();
+//! dyn_clocks
+//!     .configure_gclk(DynGclkId::Gclk0, DynGclkSourceId::Dpll0, 1, &mut [])
+//!     .unwrap();
+//! // Slow down `Gclk2`, which recomputes the UART baud rate
+//! dyn_clocks
+//!     .configure_gclk(DynGclkId::Gclk2, DynGclkSourceId::Dfll, 4, &mut [uart])
+//!     .unwrap();
+//! ```
+//!
+//! [`Dpll0`]: super::dpll::Dpll0
+//! [`Dfll`]: super::dfll::Dfll
+//! [`OscUlp32k`]: super::osculp32k::OscUlp32k
+//! [`Gclk`]: super::gclk::Gclk
+//! [`Gclk0`]: super::gclk::Gclk0
+//! [`Gclk2`]: super::gclk::Gclk2
+//! [`dpll` module documentation]: super::dpll
+//! [`Pclk`]: super::pclk::Pclk
+//! [`Enabled`]: super::Enabled
+//! [`clock_system_at_reset`]: super::clock_system_at_reset
+
+use crate::pac;
+use crate::pac::gclk::genctrl::DIVSEL_A;
+use crate::pac::gclk::{GENCTRL, PCHCTRL};
+use crate::time::Hertz;
+use crate::typelevel::{Decrement, Increment};
+
+use super::gclk::{
+    DynGclkId, DynGclkSourceId, EnabledGclk0, Gclk0Id, GclkDiv8, GclkId, GclkSourceId, GclkToken,
+    NotGclkIo,
+};
+use super::pclk::{DynPclkId, PclkId, PclkToken};
+use super::Source;
+
+/// Number of possible [`Gclk`](super::gclk::Gclk) sources
+const NUM_SOURCES: usize = 9;
+
+/// Number of [`Gclk`](super::gclk::Gclk)s
+const NUM_GCLKS: usize = 12;
+
+/// Number of [`Pclk`](super::pclk::Pclk) channels
+const NUM_PCLKS: usize = 48;
+
+//==============================================================================
+// Error
+//==============================================================================
+
+/// Errors returned by [`DynClocks`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// The clock still has consumers, so it cannot be disabled or released
+    InUse,
+    /// The clock is not owned by [`DynClocks`]
+    NotOwned,
+    /// The clock is already enabled or registered
+    AlreadyEnabled,
+    /// The clock, or its source, is not enabled or registered
+    NotEnabled,
+    /// The division factor is not valid for the
+    /// [`Gclk`](super::gclk::Gclk)
+    InvalidDivider,
+    /// The [`Gclk`](super::gclk::Gclk) cannot use the requested source
+    InvalidSource,
+}
+
+//==============================================================================
+// ClockConsumer
+//==============================================================================
+
+/// Peripheral that must be notified when its [`Pclk`](super::pclk::Pclk)
+/// frequency changes
+///
+/// Peripherals store the frequency of their clock to derive baud rates,
+/// periods, etc. Implementers of this trait recompute those settings when
+/// [`DynClocks`] changes the frequency of their clock.
+pub trait ClockConsumer {
+    /// Return the [`DynPclkId`] of the clock feeding this peripheral
+    fn pclk_id(&self) -> DynPclkId;
+
+    /// Reconfigure the peripheral for a new clock frequency
+    fn set_clock_freq(&mut self, freq: Hertz);
+}
+
+//==============================================================================
+// Nodes
+//==============================================================================
+
+/// Run-time state of a clock [`Source`]
+#[derive(Clone, Copy)]
+struct SourceNode {
+    /// Frequency of the source, if registered
+    freq: Option<Hertz>,
+    /// Number of managed [`Gclk`](super::gclk::Gclk)s using the source
+    consumers: u8,
+}
+
+impl SourceNode {
+    const EMPTY: Self = Self {
+        freq: None,
+        consumers: 0,
+    };
+}
+
+/// Run-time state of a [`Gclk`](super::gclk::Gclk)
+#[derive(Clone, Copy)]
+struct GclkNode {
+    /// The corresponding [`GclkToken`] is owned by [`DynClocks`]
+    owned: bool,
+    /// Source of the `Gclk`, if enabled
+    source: Option<DynGclkSourceId>,
+    /// Actual division factor
+    div: u32,
+    /// Number of managed [`Pclk`](super::pclk::Pclk)s using the `Gclk`
+    consumers: u8,
+}
+
+impl GclkNode {
+    const EMPTY: Self = Self {
+        owned: false,
+        source: None,
+        div: 1,
+        consumers: 0,
+    };
+}
+
+//==============================================================================
+// Registers
+//==============================================================================
+
+/// Access the `GENCTRL` register of a [`Gclk`](super::gclk::Gclk)
+#[inline]
+fn genctrl(id: DynGclkId) -> &'static GENCTRL {
+    // Safety: `DynClocks` only accesses the registers of the `Gclk`s whose
+    // `GclkToken` it owns. See the notes on `Token` types and memory safety in
+    // the root of the `clock` module for more details.
+    unsafe { &(*pac::GCLK::PTR).genctrl[id as usize] }
+}
+
+/// Block until the `GENCTRL` register of a [`Gclk`](super::gclk::Gclk) is
+/// synchronized
+#[inline]
+fn wait_syncbusy(id: DynGclkId) {
+    // Safety: We are only reading from the `SYNCBUSY` register, and we are
+    // only observing the bit corresponding to this particular `Gclk`, so there
+    // is no risk of memory corruption.
+    let syncbusy = unsafe { &(*pac::GCLK::PTR).syncbusy };
+    let mask = 1 << id as u16;
+    while syncbusy.read().genctrl().bits() & mask != 0 {}
+}
+
+/// Access the `PCHCTRL` register of a [`Pclk`](super::pclk::Pclk)
+#[inline]
+fn pchctrl(id: DynPclkId) -> &'static PCHCTRL {
+    // Safety: `DynClocks` only accesses the registers of the `Pclk`s whose
+    // `PclkToken` it owns. See the notes on `Token` types and memory safety in
+    // the root of the `clock` module for more details.
+    unsafe { &(*pac::GCLK::PTR).pchctrl[id as usize] }
+}
+
+/// Convert a division factor into the `DIVSEL` and `DIV` register fields
+///
+/// [`Gclk1`](super::gclk::Gclk1) accepts 1-65535, 65536 and 131072. All other
+/// `Gclk`s accept 1-255, 256 and 512.
+fn divsel_div(id: DynGclkId, div: u32) -> Result<(DIVSEL_A, u16), Error> {
+    let bits = if id == DynGclkId::Gclk1 { 16 } else { 8 };
+    if (1..1 << bits).contains(&div) {
+        Ok((DIVSEL_A::DIV1, div as u16))
+    } else if div == 1 << bits {
+        Ok((DIVSEL_A::DIV2, bits - 1))
+    } else if div == 1 << (bits + 1) {
+        Ok((DIVSEL_A::DIV2, bits))
+    } else {
+        Err(Error::InvalidDivider)
+    }
+}
+
+//==============================================================================
+// DynClocks
+//==============================================================================
+
+/// Run-time manager of [`Gclk`]s and [`Pclk`]s
+///
+/// See the [module-level documentation](self) for more details.
+///
+/// [`Gclk`]: super::gclk::Gclk
+/// [`Pclk`]: super::pclk::Pclk
+pub struct DynClocks {
+    gclk0: GclkToken<Gclk0Id>,
+    sources: [SourceNode; NUM_SOURCES],
+    gclks: [GclkNode; NUM_GCLKS],
+    pclks: [Option<DynGclkId>; NUM_PCLKS],
+}
+
+impl DynClocks {
+    /// Create a new [`DynClocks`] from [`EnabledGclk0`]
+    ///
+    /// The [`Source`] of [`Gclk0`](super::gclk::Gclk0) is registered
+    /// automatically. Its [`Enabled`](super::Enabled) counter already accounts
+    /// for `Gclk0`, so it keeps the same type.
+    #[inline]
+    pub fn new<I: NotGclkIo>(gclk0: EnabledGclk0<I>) -> Self {
+        let (gclk0, freq, div) = gclk0.into_dynamic();
+        let mut sources = [SourceNode::EMPTY; NUM_SOURCES];
+        sources[I::DYN as usize] = SourceNode {
+            freq: Some(freq),
+            consumers: 1,
+        };
+        let mut gclks = [GclkNode::EMPTY; NUM_GCLKS];
+        gclks[0] = GclkNode {
+            owned: true,
+            source: Some(I::DYN),
+            div,
+            consumers: 0,
+        };
+        Self {
+            gclk0,
+            sources,
+            gclks,
+            pclks: [None; NUM_PCLKS],
+        }
+    }
+
+    /// Check whether [`DynClocks::free`] would succeed
+    ///
+    /// This is only possible when every other [`Gclk`](super::gclk::Gclk) and
+    /// [`Pclk`](super::pclk::Pclk) has been released, and when every [`Source`]
+    /// other than the one driving `Gclk0` has been unregistered. The type
+    /// parameter `I` must match the current source of `Gclk0`.
+    #[inline]
+    pub fn check_free<I: NotGclkIo>(&self) -> Result<(), Error> {
+        if self.gclks[0].source != Some(I::DYN) {
+            return Err(Error::InvalidSource);
+        }
+        let in_use = self.gclks[1..].iter().any(|g| g.owned)
+            || self.pclks.iter().any(Option::is_some)
+            || self
+                .sources
+                .iter()
+                .enumerate()
+                .any(|(i, s)| i != I::DYN as usize && s.freq.is_some());
+        if in_use {
+            return Err(Error::InUse);
+        }
+        if self.sources[I::DYN as usize].freq.is_none() {
+            return Err(Error::NotEnabled);
+        }
+        Ok(())
+    }
+
+    /// Consume the [`DynClocks`] and return [`EnabledGclk0`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`DynClocks::check_free`] returns an [`Error`]. Call it first
+    /// to keep the [`DynClocks`] when the clock tree is still in use.
+    #[inline]
+    pub fn free<I: NotGclkIo>(self) -> EnabledGclk0<I> {
+        if let Err(err) = self.check_free::<I>() {
+            panic!("cannot free DynClocks: {:?}", err);
+        }
+        let freq = self.sources[I::DYN as usize].freq.unwrap();
+        let div = match self.gclks[0].div {
+            256 => GclkDiv8::Div2Pow8,
+            512 => GclkDiv8::Div2Pow9,
+            div => GclkDiv8::Div(div as u8),
+        };
+        EnabledGclk0::from_dynamic(self.gclk0, freq, div)
+    }
+
+    /// Register an [`Enabled`](super::Enabled) clock [`Source`]
+    ///
+    /// Registered sources can drive the managed [`Gclk`](super::gclk::Gclk)s.
+    /// Registration [`Increment`]s the [`Enabled`](super::Enabled) counter of
+    /// the `Source`.
+    #[inline]
+    pub fn register_source<S>(&mut self, source: S) -> Result<S::Inc, (S, Error)>
+    where
+        S: Source + Increment,
+        S::Id: GclkSourceId + NotGclkIo,
+    {
+        let id = S::Id::DYN;
+        if self.source_freq(id).is_some() {
+            return Err((source, Error::AlreadyEnabled));
+        }
+        self.sources[id as usize].freq = Some(source.freq());
+        Ok(source.inc())
+    }
+
+    /// Unregister a clock [`Source`]
+    ///
+    /// This is refused while any managed [`Gclk`](super::gclk::Gclk) uses the
+    /// `Source`. On success, the [`Enabled`](super::Enabled) counter of the
+    /// `Source` is [`Decrement`]ed.
+    #[inline]
+    pub fn unregister_source<S>(&mut self, source: S) -> Result<S::Dec, (S, Error)>
+    where
+        S: Source + Decrement,
+        S::Id: GclkSourceId + NotGclkIo,
+    {
+        let node = &mut self.sources[S::Id::DYN as usize];
+        if node.freq.is_none() {
+            return Err((source, Error::NotEnabled));
+        }
+        if node.consumers > 0 {
+            return Err((source, Error::InUse));
+        }
+        node.freq = None;
+        Ok(source.dec())
+    }
+
+    /// Take ownership of a disabled [`Gclk`](super::gclk::Gclk)
+    #[inline]
+    pub fn adopt_gclk<G: GclkId>(&mut self, token: GclkToken<G>) {
+        // `GclkToken`s are zero-sized singletons, so we only need to record
+        // the fact that we own it
+        let _ = token;
+        self.gclks[G::NUM].owned = true;
+    }
+
+    /// Release a managed [`Gclk`](super::gclk::Gclk) and return its
+    /// [`GclkToken`]
+    ///
+    /// The `Gclk` is disabled first, which is refused if it has any consumers.
+    #[inline]
+    pub fn release_gclk<G: GclkId>(&mut self) -> Result<GclkToken<G>, Error> {
+        if G::NUM == 0 || !self.gclks[G::NUM].owned {
+            return Err(Error::NotOwned);
+        }
+        if self.gclks[G::NUM].source.is_some() {
+            self.disable_gclk(G::DYN)?;
+        }
+        self.gclks[G::NUM].owned = false;
+        // Safety: We just gave up ownership of the token
+        Ok(unsafe { GclkToken::new() })
+    }
+
+    /// Enable a managed [`Gclk`](super::gclk::Gclk)
+    ///
+    /// The `source` must be registered, and `div` must be a valid division
+    /// factor for the `Gclk`. Return the resulting `Gclk` frequency.
+    #[inline]
+    pub fn enable_gclk(
+        &mut self,
+        id: DynGclkId,
+        source: DynGclkSourceId,
+        div: u32,
+    ) -> Result<Hertz, Error> {
+        let node = self.gclks[id as usize];
+        if !node.owned {
+            return Err(Error::NotOwned);
+        }
+        if node.source.is_some() {
+            return Err(Error::AlreadyEnabled);
+        }
+        let (divsel, div_bits) = self.check_source(id, source, div)?;
+        genctrl(id).modify(|_, w| {
+            // Safety: The `DIVSEL` and `DIV` values were validated above
+            unsafe {
+                w.divsel().variant(divsel);
+                w.div().bits(div_bits);
+            };
+            w.src().variant(source.into());
+            w.genen().set_bit()
+        });
+        wait_syncbusy(id);
+        self.sources[source as usize].consumers += 1;
+        self.gclks[id as usize].source = Some(source);
+        self.gclks[id as usize].div = div;
+        self.gclk_freq(id).ok_or(Error::NotEnabled)
+    }
+
+    /// Disable a managed [`Gclk`](super::gclk::Gclk)
+    ///
+    /// This is refused if any [`Pclk`](super::pclk::Pclk) or other `Gclk` uses
+    /// it. [`Gclk0`](super::gclk::Gclk0) drives the processor, so it can never
+    /// be disabled.
+    #[inline]
+    pub fn disable_gclk(&mut self, id: DynGclkId) -> Result<(), Error> {
+        let node = self.gclks[id as usize];
+        if id == DynGclkId::Gclk0 {
+            return Err(Error::InUse);
+        }
+        if !node.owned {
+            return Err(Error::NotOwned);
+        }
+        let source = node.source.ok_or(Error::NotEnabled)?;
+        let gclk1_in_use =
+            id == DynGclkId::Gclk1 && self.sources[DynGclkSourceId::Gclk1 as usize].consumers > 0;
+        if node.consumers > 0 || gclk1_in_use {
+            return Err(Error::InUse);
+        }
+        genctrl(id).modify(|_, w| w.genen().clear_bit());
+        wait_syncbusy(id);
+        self.sources[source as usize].consumers -= 1;
+        self.gclks[id as usize].source = None;
+        Ok(())
+    }
+
+    /// Change the source and division factor of an enabled
+    /// [`Gclk`](super::gclk::Gclk)
+    ///
+    /// The `Gclk` remains enabled during the change. Afterwards, every
+    /// [`ClockConsumer`] in `consumers` clocked by an affected
+    /// [`Pclk`](super::pclk::Pclk) is notified of its new frequency. Return the
+    /// resulting `Gclk` frequency.
+    ///
+    /// Only the given `consumers` are notified. The caller must include every
+    /// peripheral clocked by the `Gclk`, or by a `Gclk` derived from
+    /// [`Gclk1`](super::gclk::Gclk1) when changing `Gclk1`.
+    pub fn configure_gclk(
+        &mut self,
+        id: DynGclkId,
+        source: DynGclkSourceId,
+        div: u32,
+        consumers: &mut [&mut dyn ClockConsumer],
+    ) -> Result<Hertz, Error> {
+        let node = self.gclks[id as usize];
+        if !node.owned {
+            return Err(Error::NotOwned);
+        }
+        let old = node.source.ok_or(Error::NotEnabled)?;
+        let (divsel, div_bits) = self.check_source(id, source, div)?;
+        genctrl(id).modify(|_, w| {
+            // Safety: The `DIVSEL` and `DIV` values were validated above
+            unsafe {
+                w.divsel().variant(divsel);
+                w.div().bits(div_bits);
+            };
+            w.src().variant(source.into())
+        });
+        wait_syncbusy(id);
+        self.sources[old as usize].consumers -= 1;
+        self.sources[source as usize].consumers += 1;
+        self.gclks[id as usize].source = Some(source);
+        self.gclks[id as usize].div = div;
+        for consumer in consumers.iter_mut() {
+            let gclk = match self.pclks[consumer.pclk_id() as usize] {
+                Some(gclk) => gclk,
+                None => continue,
+            };
+            let affected = gclk == id
+                || (id == DynGclkId::Gclk1
+                    && self.gclks[gclk as usize].source == Some(DynGclkSourceId::Gclk1));
+            if let (true, Some(freq)) = (affected, self.gclk_freq(gclk)) {
+                consumer.set_clock_freq(freq);
+            }
+        }
+        self.gclk_freq(id).ok_or(Error::NotEnabled)
+    }
+
+    /// Enable a [`Pclk`](super::pclk::Pclk) from a managed, enabled
+    /// [`Gclk`](super::gclk::Gclk)
+    ///
+    /// Return the `Pclk` frequency.
+    #[inline]
+    pub fn enable_pclk<P: PclkId>(
+        &mut self,
+        token: PclkToken<P>,
+        gclk: DynGclkId,
+    ) -> Result<Hertz, (PclkToken<P>, Error)> {
+        let freq = match self.gclk_freq(gclk) {
+            Some(freq) => freq,
+            None => return Err((token, Error::NotEnabled)),
+        };
+        // `PclkToken`s are zero-sized singletons, so we only need to record
+        // the fact that we own it
+        let _ = token;
+        pchctrl(P::DYN).modify(|_, w| {
+            w.gen().variant(gclk.into());
+            w.chen().set_bit()
+        });
+        self.gclks[gclk as usize].consumers += 1;
+        self.pclks[P::DYN as usize] = Some(gclk);
+        Ok(freq)
+    }
+
+    /// Disable a managed [`Pclk`](super::pclk::Pclk) and return its
+    /// [`PclkToken`]
+    #[inline]
+    pub fn disable_pclk<P: PclkId>(&mut self) -> Result<PclkToken<P>, Error> {
+        let gclk = self.pclks[P::DYN as usize].ok_or(Error::NotOwned)?;
+        pchctrl(P::DYN).modify(|_, w| w.chen().clear_bit());
+        while pchctrl(P::DYN).read().chen().bit_is_set() {}
+        self.gclks[gclk as usize].consumers -= 1;
+        self.pclks[P::DYN as usize] = None;
+        // Safety: We just gave up ownership of the token
+        Ok(unsafe { PclkToken::new() })
+    }
+
+    /// Move a managed [`Pclk`](super::pclk::Pclk) to a different
+    /// [`Gclk`](super::gclk::Gclk)
+    ///
+    /// The `Pclk` is briefly disabled during the change. Afterwards, every
+    /// [`ClockConsumer`] in `consumers` clocked by the `Pclk` is notified of
+    /// its new frequency. Return the new `Pclk` frequency.
+    ///
+    /// Only the given `consumers` are notified. The caller must include every
+    /// peripheral clocked by the `Pclk`.
+    pub fn set_pclk_source(
+        &mut self,
+        id: DynPclkId,
+        gclk: DynGclkId,
+        consumers: &mut [&mut dyn ClockConsumer],
+    ) -> Result<Hertz, Error> {
+        let old = self.pclks[id as usize].ok_or(Error::NotOwned)?;
+        let freq = self.gclk_freq(gclk).ok_or(Error::NotEnabled)?;
+        // The `GEN` field can only be changed while the channel is disabled
+        pchctrl(id).modify(|_, w| w.chen().clear_bit());
+        while pchctrl(id).read().chen().bit_is_set() {}
+        pchctrl(id).modify(|_, w| {
+            w.gen().variant(gclk.into());
+            w.chen().set_bit()
+        });
+        self.gclks[old as usize].consumers -= 1;
+        self.gclks[gclk as usize].consumers += 1;
+        self.pclks[id as usize] = Some(gclk);
+        for consumer in consumers.iter_mut() {
+            if consumer.pclk_id() == id {
+                consumer.set_clock_freq(freq);
+            }
+        }
+        Ok(freq)
+    }
+
+    /// Return the frequency of a registered [`Source`], if any
+    #[inline]
+    pub fn source_freq(&self, source: DynGclkSourceId) -> Option<Hertz> {
+        if source == DynGclkSourceId::Gclk1 && self.gclks[1].owned {
+            self.gclk_freq(DynGclkId::Gclk1)
+        } else {
+            self.sources[source as usize].freq
+        }
+    }
+
+    /// Return the frequency of a managed, enabled [`Gclk`](super::gclk::Gclk)
+    #[inline]
+    pub fn gclk_freq(&self, id: DynGclkId) -> Option<Hertz> {
+        let node = self.gclks[id as usize];
+        let freq = self.source_freq(node.source?)?;
+        Some(freq / node.div)
+    }
+
+    /// Return the frequency of a managed [`Pclk`](super::pclk::Pclk)
+    #[inline]
+    pub fn pclk_freq(&self, id: DynPclkId) -> Option<Hertz> {
+        self.gclk_freq(self.pclks[id as usize]?)
+    }
+
+    /// Validate a new source and division factor for a
+    /// [`Gclk`](super::gclk::Gclk)
+    fn check_source(
+        &self,
+        id: DynGclkId,
+        source: DynGclkSourceId,
+        div: u32,
+    ) -> Result<(DIVSEL_A, u16), Error> {
+        let invalid = source == DynGclkSourceId::GclkIn
+            || (id == DynGclkId::Gclk1 && source == DynGclkSourceId::Gclk1);
+        if invalid {
+            return Err(Error::InvalidSource);
+        }
+        if self.source_freq(source).is_none() {
+            return Err(Error::NotEnabled);
+        }
+        divsel_div(id, div)
+    }
+}
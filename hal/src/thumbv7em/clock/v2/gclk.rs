@@ -514,6 +514,7 @@ impl<G: GclkId> GclkToken<G> {
 /// generators.
 ///
 /// `DynGclkId` is the value-level equivalent of [`GclkId`].
+#[derive(Copy, Clone, PartialEq, Eq)]
 pub enum DynGclkId {
     Gclk0,
     Gclk1,
@@ -1385,6 +1386,44 @@ where
     }
 }
 
+//==============================================================================
+// Dynamic API conversions
+//==============================================================================
+
+impl<I: NotGclkIo> EnabledGclk0<I, U1> {
+    /// Hand [`Gclk0`] over to the [`dynamic`](super::dynamic) API
+    ///
+    /// Return the [`GclkToken`], the [`Source`] frequency and the actual
+    /// division factor.
+    #[inline]
+    pub(super) fn into_dynamic(self) -> (GclkToken<Gclk0Id>, Hertz, u32) {
+        let gclk = self.0;
+        let div = max(1, gclk.settings.div.divider());
+        (gclk.token, gclk.src_freq, div)
+    }
+
+    /// Recover [`EnabledGclk0`] from the [`dynamic`](super::dynamic) API
+    ///
+    /// The caller must guarantee that `Gclk0` is currently driven by `I`, with
+    /// the given [`Source`] frequency and divider.
+    #[inline]
+    pub(super) fn from_dynamic(token: GclkToken<Gclk0Id>, src_freq: Hertz, div: GclkDiv8) -> Self {
+        let genctrl = token.genctrl().read();
+        let settings = Settings {
+            div,
+            output_off_value: genctrl.oov().bit(),
+            improve_duty_cycle: genctrl.idc().bit(),
+        };
+        let gclk = Gclk {
+            token,
+            resource: (),
+            src_freq,
+            settings,
+        };
+        Enabled::new(gclk)
+    }
+}
+
 //==============================================================================
 // Tokens
 //==============================================================================
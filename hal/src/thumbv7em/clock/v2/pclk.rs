@@ -307,6 +307,7 @@ macro_rules! dyn_pclk_id {
             ///
             /// `DynPclkId` is the value-level equivalent of [`PclkId`].
             #[repr(u8)]
+            #[derive(Copy, Clone, PartialEq, Eq)]
             pub enum DynPclkId {
                 $(
                     $( #[$cfg] )?
@@ -2,6 +2,7 @@ pub mod aes;
 pub mod calibration;
 #[cfg(all(any(feature = "has-can0", feature = "has-can1"), feature = "can"))]
 pub mod can;
+pub mod ccl;
 pub mod clock;
 pub mod eic;
 pub mod pukcc;
@@ -18,17 +19,12 @@ pub use reset_cause::*;
 mod serial_number;
 pub use serial_number::*;
 
-#[cfg(feature = "unproven")]
 pub mod adc;
-
-#[cfg(feature = "unproven")]
+pub mod icm;
+pub mod pdec;
 pub mod pwm;
-
-#[cfg(feature = "unproven")]
+pub mod tcc;
 pub mod watchdog;
 
-#[cfg(feature = "unproven")]
-pub mod icm;
-
 pub mod dsu;
 pub mod nvm;
@@ -932,7 +932,7 @@ bitfield! {
     /// Access the `vph` field. Setter is not provided.
     pub vph, _: 63, 52;
     /// Access the `vcl` field. Setter is not provided.
-    pub vcl, _: 75, 63;
+    pub vcl, _: 75, 64;
     /// Access the `vch` field. Setter is not provided.
     pub vch, _: 87, 76;
 }
@@ -0,0 +1,757 @@
+//! # Position Decoder
+//!
+//! The PDEC decodes the signals of a quadrature encoder or of Hall effect
+//! sensors in hardware. It has three inputs, `QDI0` to `QDI2`, which are
+//! either muxed to pins or driven by the [`evsys`](crate::evsys) module:
+//!
+//! | Input  | QDEC      | HALL          | COUNTER       |
+//! | ------ | --------- | ------------- | ------------- |
+//! | `QDI0` | Phase A   | Hall sensor 0 | Count input   |
+//! | `QDI1` | Phase B   | Hall sensor 1 | -             |
+//! | `QDI2` | Index     | Hall sensor 2 | -             |
+//!
+//! The pins are checked at compile time with the [`PhaseAId`], [`PhaseBId`]
+//! and [`IndexId`] traits, and collected in a [`Pins`] struct. The index pin
+//! is optional.
+//!
+//! In [`Mode::Qdec`], the counter is split in an angular position, counting
+//! the edges of the phases, and a revolution count, incremented or
+//! decremented by the index. The number of bits of the angular position is
+//! set by [`Config::angular_bits`], and the counter is read as a
+//! [`Position`].
+//!
+//! ```no_run
+//! use atsamd_hal::gpio::Pins;
+//! use atsamd_hal::pdec::{self, Config, Decoder, Flags, Mode, Pdec};
+//! # let mut peripherals = atsamd_hal::pac::Peripherals::take().unwrap();
+//! let pins = Pins::new(peripherals.PORT);
+//! let pins = pdec::Pins::new(pins.pa24.into_alternate(), pins.pa25.into_alternate())
+//!     .index(pins.pb22.into_alternate());
+//!
+//! let config = Config {
+//!     mode: Mode::Qdec(Decoder::X4),
+//!     // 1024 counts per revolution
+//!     angular_bits: 10,
+//!     filter: 8,
+//!     ..Default::default()
+//! };
+//! let mut pdec = Pdec::new(peripherals.PDEC, &mut peripherals.MCLK, pins, config);
+//! pdec.enable_interrupts(Flags::ERR | Flags::OVF);
+//! pdec.enable();
+//! let position = pdec.position();
+//! ```
+
+use bitflags::bitflags;
+
+use crate::gpio::*;
+use crate::pac::pdec::ctrlbset::CMD_A;
+use crate::pac::{pdec, MCLK, PDEC};
+use crate::typelevel::{NoneT, Sealed};
+
+//==============================================================================
+// Pins
+//==============================================================================
+
+/// Pin that can be muxed to `QDI0`, the phase A input
+pub trait PhaseAId: PinId {}
+
+/// Pin that can be muxed to `QDI1`, the phase B input
+pub trait PhaseBId: PinId {}
+
+/// Pin that can be muxed to `QDI2`, the index input
+pub trait IndexId: PinId {}
+
+macro_rules! qdi_pins {
+    ($Trait:ident: $( $( #[$attr:meta] )* $Id:ident, )+) => {
+        $(
+            $( #[$attr] )*
+            impl $Trait for $Id {}
+        )+
+    };
+}
+
+qdi_pins!(PhaseAId:
+    PA24,
+    #[cfg(feature = "has-pb23")]
+    PB23,
+    #[cfg(feature = "pins-100")]
+    PB18,
+    #[cfg(feature = "pins-100")]
+    PC16,
+);
+
+qdi_pins!(PhaseBId:
+    PA25,
+    #[cfg(feature = "pins-100")]
+    PB19,
+    #[cfg(feature = "pins-100")]
+    PC17,
+);
+
+qdi_pins!(IndexId:
+    #[cfg(feature = "has-pb22")]
+    PB22,
+    #[cfg(feature = "pins-100")]
+    PB20,
+    #[cfg(feature = "pins-100")]
+    PC18,
+);
+
+/// Type-level equivalent of `Option<Pin<I, AlternateG>>` for the index input
+pub trait OptionalIndex: Sealed {
+    /// Whether the index input is muxed to a pin
+    const ENABLED: bool;
+}
+
+impl OptionalIndex for NoneT {
+    const ENABLED: bool = false;
+}
+
+impl<I: IndexId> OptionalIndex for Pin<I, AlternateG> {
+    const ENABLED: bool = true;
+}
+
+/// Pins of the PDEC
+///
+/// `I` is either [`NoneT`], when the index input is not used or is driven by
+/// an event, or a `Pin<I, AlternateG>` set with [`Pins::index`].
+pub struct Pins<A: PhaseAId, B: PhaseBId, I: OptionalIndex = NoneT> {
+    phase_a: Pin<A, AlternateG>,
+    phase_b: Pin<B, AlternateG>,
+    index: I,
+}
+
+impl<A: PhaseAId, B: PhaseBId> Pins<A, B> {
+    /// Collect the phase pins, without an index pin
+    #[inline]
+    pub fn new(phase_a: Pin<A, AlternateG>, phase_b: Pin<B, AlternateG>) -> Self {
+        Self {
+            phase_a,
+            phase_b,
+            index: NoneT,
+        }
+    }
+
+    /// Add the index pin
+    #[inline]
+    pub fn index<I: IndexId>(self, index: Pin<I, AlternateG>) -> Pins<A, B, Pin<I, AlternateG>> {
+        Pins {
+            phase_a: self.phase_a,
+            phase_b: self.phase_b,
+            index,
+        }
+    }
+}
+
+impl<A: PhaseAId, B: PhaseBId, I: OptionalIndex> Pins<A, B, I> {
+    /// Return the pins
+    #[inline]
+    pub fn free(self) -> (Pin<A, AlternateG>, Pin<B, AlternateG>, I) {
+        (self.phase_a, self.phase_b, self.index)
+    }
+}
+
+//==============================================================================
+// Configuration
+//==============================================================================
+
+/// Quadrature decoder configuration
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Decoder {
+    /// Count the edges of both phases, with the direction given by the phase
+    /// relationship
+    #[default]
+    X4,
+    /// Like [`Decoder::X4`], and also check the consistency of the phases and
+    /// the index
+    X4Secure,
+    /// Count the edges of phase A only, with the direction given by phase B
+    X2,
+    /// Like [`Decoder::X2`], and also check the consistency of the phases and
+    /// the index
+    X2Secure,
+    /// Like [`Decoder::X4`], correcting the angular position at each index
+    AutoCorrect,
+}
+
+/// Operating mode
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Mode {
+    /// Quadrature decoder
+    Qdec(Decoder),
+    /// Hall effect sensors decoder
+    ///
+    /// An invalid sensor pattern sets [`Errors::HALL`]. The three inputs must
+    /// be used, either as pins or as event inputs.
+    Hall,
+    /// Counter of the `QDI0` input
+    Counter,
+}
+
+impl Default for Mode {
+    #[inline]
+    fn default() -> Self {
+        Mode::Qdec(Decoder::default())
+    }
+}
+
+/// Division of the generic clock used to sample the inputs
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Prescaler {
+    #[default]
+    Div1,
+    Div2,
+    Div4,
+    Div8,
+    Div16,
+    Div32,
+    Div64,
+    Div128,
+    Div256,
+    Div512,
+    Div1024,
+}
+
+/// Action on the counter of the input events
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EventAction {
+    /// Events are decoded like the pin inputs
+    #[default]
+    Off,
+    /// Start, restart or retrigger the counter
+    Retrigger,
+    /// Count the events
+    Count,
+}
+
+/// PDEC configuration, written while the PDEC is disabled
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Config {
+    /// Operating mode
+    pub mode: Mode,
+    /// Division of the generic clock used to sample the inputs
+    pub prescaler: Prescaler,
+    /// Number of consecutive samples for an input change to be detected
+    pub filter: u8,
+    /// Number of bits of the angular position, between 10 and 16, in
+    /// [`Mode::Qdec`]
+    ///
+    /// The remaining bits of the 16-bit counter hold the revolution count.
+    pub angular_bits: u8,
+    /// Wrap the counter at the period, see [`Pdec::set_period`]
+    pub period: bool,
+    /// Swap the phase A and phase B inputs
+    pub swap: bool,
+    /// Invert the `QDI0`, `QDI1` and `QDI2` pin inputs
+    pub invert: [bool; 3],
+    /// Number of consecutive missing pulses for a missing pulse error, up to
+    /// 15. Zero disables the detection.
+    pub max_missing_pulses: u8,
+    /// Lock the buffered registers update when an error occurs
+    pub auto_lock: bool,
+    /// Action of the input events
+    pub event_action: EventAction,
+    /// Keep the PDEC running in standby sleep mode
+    pub run_in_standby: bool,
+}
+
+impl Default for Config {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            mode: Mode::default(),
+            prescaler: Prescaler::default(),
+            filter: 0,
+            angular_bits: 16,
+            period: false,
+            swap: false,
+            invert: [false; 3],
+            max_missing_pulses: 0,
+            auto_lock: false,
+            event_action: EventAction::default(),
+            run_in_standby: false,
+        }
+    }
+}
+
+//==============================================================================
+// Position
+//==============================================================================
+
+/// Value of the counter in [`Mode::Qdec`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Position {
+    /// Angular position, in counts
+    pub angle: u16,
+    /// Revolution count, modulo the number of revolution bits
+    pub revolutions: u16,
+}
+
+impl Position {
+    /// Split a counter value holding `angular_bits` bits of angular position
+    #[inline]
+    pub fn from_count(count: u16, angular_bits: u8) -> Self {
+        let (angle, revolutions) = match angular_bits {
+            16 => (count, 0),
+            bits => (count & ((1 << bits) - 1), count >> bits),
+        };
+        Self { angle, revolutions }
+    }
+
+    /// Join the angular position and revolution count in a counter value
+    /// holding `angular_bits` bits of angular position
+    ///
+    /// The bits of each field that do not fit in the counter are discarded.
+    #[inline]
+    pub fn to_count(self, angular_bits: u8) -> u16 {
+        match angular_bits {
+            16 => self.angle,
+            bits => self.angle & ((1 << bits) - 1) | self.revolutions << bits,
+        }
+    }
+}
+
+/// Counting direction
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Direction {
+    Up,
+    Down,
+}
+
+//==============================================================================
+// Flags
+//==============================================================================
+
+bitflags! {
+    /// Interrupt flags
+    ///
+    /// The binary format of the underlying bits exactly matches the INTFLAG
+    /// bits.
+    pub struct Flags: u8 {
+        /// Counter overflow or underflow
+        const OVF = 1 << 0;
+        /// Error, see [`Pdec::errors`]
+        const ERR = 1 << 1;
+        /// Direction change
+        const DIR = 1 << 2;
+        /// Velocity
+        const VLC = 1 << 3;
+        /// Compare channel 0 match
+        const MC0 = 1 << 4;
+        /// Compare channel 1 match
+        const MC1 = 1 << 5;
+    }
+}
+
+bitflags! {
+    /// Error flags
+    ///
+    /// The binary format of the underlying bits exactly matches the error bits
+    /// of the STATUS register.
+    pub struct Errors: u16 {
+        /// Invalid sequence of the phases
+        const QUADRATURE = 1 << 0;
+        /// Index detected at an unexpected angular position
+        const INDEX = 1 << 1;
+        /// Too many consecutive missing pulses
+        const MISSING_PULSE = 1 << 2;
+        /// Hall sensor transition outside of the compare window
+        const WINDOW = 1 << 4;
+        /// Invalid Hall sensor pattern
+        const HALL = 1 << 5;
+    }
+}
+
+//==============================================================================
+// Pdec
+//==============================================================================
+
+/// Position decoder with pins `A`, `B` and optional index pin `I`
+pub struct Pdec<A: PhaseAId, B: PhaseBId, I: OptionalIndex = NoneT> {
+    pdec: PDEC,
+    pins: Pins<A, B, I>,
+    angular_bits: u8,
+}
+
+impl<A: PhaseAId, B: PhaseBId, I: OptionalIndex> Pdec<A, B, I> {
+    /// Enable the APB clock, reset and configure the PDEC
+    ///
+    /// The PDEC generic clock must be enabled. The PDEC is left disabled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the angular length is not between 10 and 16 bits, or if the
+    /// maximum number of missing pulses exceeds 15.
+    #[inline]
+    pub fn new(pdec: PDEC, mclk: &mut MCLK, pins: Pins<A, B, I>, config: Config) -> Self {
+        assert!(
+            (10..=16).contains(&config.angular_bits),
+            "Invalid angular counter length"
+        );
+        assert!(
+            config.max_missing_pulses < 16,
+            "Invalid missing pulses count"
+        );
+        mclk.apbcmask.modify(|_, w| w.pdec_().set_bit());
+
+        pdec.ctrla.write(|w| w.swrst().set_bit());
+        while pdec.syncbusy.read().swrst().bit_is_set() {}
+
+        pdec.ctrla.write(|w| {
+            match config.mode {
+                Mode::Qdec(decoder) => {
+                    w.mode().qdec();
+                    match decoder {
+                        Decoder::X4 => w.conf().x4(),
+                        Decoder::X4Secure => w.conf().x4s(),
+                        Decoder::X2 => w.conf().x2(),
+                        Decoder::X2Secure => w.conf().x2s(),
+                        Decoder::AutoCorrect => w.conf().autoc(),
+                    };
+                }
+                Mode::Hall => {
+                    w.mode().hall();
+                }
+                Mode::Counter => {
+                    w.mode().counter();
+                }
+            }
+            w.pinen0().set_bit();
+            w.pinen1().set_bit();
+            w.pinen2().bit(I::ENABLED);
+            w.pinven0().bit(config.invert[0]);
+            w.pinven1().bit(config.invert[1]);
+            w.pinven2().bit(config.invert[2]);
+            w.swap().bit(config.swap);
+            w.peren().bit(config.period);
+            w.alock().bit(config.auto_lock);
+            w.runstdby().bit(config.run_in_standby);
+            // Safety: The values are checked above
+            unsafe {
+                w.angular().bits(config.angular_bits - 10);
+                w.maxcmp().bits(config.max_missing_pulses)
+            }
+        });
+        pdec.evctrl.write(|w| match config.event_action {
+            EventAction::Off => w.evact().off(),
+            EventAction::Retrigger => w.evact().retrigger(),
+            EventAction::Count => w.evact().count(),
+        });
+        // Safety: The prescaler variants are the PRESC values
+        pdec.presc
+            .write(|w| unsafe { w.presc().bits(config.prescaler as u8) });
+        // Safety: Every filter value is valid
+        pdec.filter
+            .write(|w| unsafe { w.filter().bits(config.filter) });
+
+        let pdec = Self {
+            pdec,
+            pins,
+            angular_bits: config.angular_bits,
+        };
+        pdec.sync();
+        pdec
+    }
+
+    #[inline]
+    fn sync(&self) {
+        while self.pdec.syncbusy.read().bits() != 0 {}
+    }
+
+    /// Enable the PDEC and start decoding
+    #[inline]
+    pub fn enable(&mut self) {
+        self.pdec.ctrla.modify(|_, w| w.enable().set_bit());
+        self.sync();
+    }
+
+    /// Disable the PDEC
+    #[inline]
+    pub fn disable(&mut self) {
+        self.pdec.ctrla.modify(|_, w| w.enable().clear_bit());
+        self.sync();
+    }
+
+    /// Write enable-protected registers
+    #[inline]
+    fn while_disabled(&mut self, f: impl FnOnce(&pdec::RegisterBlock)) {
+        let enabled = self.pdec.ctrla.read().enable().bit_is_set();
+        self.disable();
+        f(&self.pdec);
+        if enabled {
+            self.enable();
+        }
+    }
+
+    #[inline]
+    fn command(&mut self, cmd: CMD_A) {
+        self.pdec.ctrlbset.write(|w| w.cmd().variant(cmd));
+        self.sync();
+    }
+
+    /// Resume decoding after [`Pdec::stop`], in [`Mode::Qdec`] and
+    /// [`Mode::Hall`]
+    #[inline]
+    pub fn start(&mut self) {
+        self.command(CMD_A::START);
+    }
+
+    /// Stop decoding, in [`Mode::Qdec`] and [`Mode::Hall`]
+    #[inline]
+    pub fn stop(&mut self) {
+        self.command(CMD_A::STOP);
+    }
+
+    /// Restart the counter
+    #[inline]
+    pub fn retrigger(&mut self) {
+        self.command(CMD_A::RETRIGGER);
+    }
+
+    //==========================================================================
+    // Counter
+    //==========================================================================
+
+    /// Read the counter
+    #[inline]
+    pub fn count(&mut self) -> u16 {
+        self.command(CMD_A::READSYNC);
+        self.pdec.count.read().count().bits()
+    }
+
+    /// Write the counter
+    #[inline]
+    pub fn set_count(&mut self, count: u16) {
+        // Safety: Every counter value is valid
+        self.pdec.count.write(|w| unsafe { w.count().bits(count) });
+        self.sync();
+    }
+
+    /// Read the counter as an angular position and a revolution count
+    #[inline]
+    pub fn position(&mut self) -> Position {
+        Position::from_count(self.count(), self.angular_bits)
+    }
+
+    /// Write the counter as an angular position and a revolution count
+    #[inline]
+    pub fn set_position(&mut self, position: Position) {
+        self.set_count(position.to_count(self.angular_bits));
+    }
+
+    /// Set the period of the counter
+    ///
+    /// When [`Config::period`] is set, the angular position and the revolution
+    /// count wrap at the values of `period`. The period is held by compare
+    /// channel 0.
+    #[inline]
+    pub fn set_period(&mut self, period: Position) {
+        self.set_compare(0, period.to_count(self.angular_bits));
+    }
+
+    /// Set the value of compare channel `channel`, immediately
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel` is greater than 1.
+    #[inline]
+    pub fn set_compare(&mut self, channel: u8, value: u16) {
+        assert!(channel < 2, "Invalid compare channel");
+        // Safety: Every compare value is valid
+        self.pdec.cc[channel as usize].write(|w| unsafe { w.cc().bits(value) });
+        self.sync();
+    }
+
+    /// Set the value of compare channel `channel` at the next update condition
+    ///
+    /// See [`Pdec::lock_update`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel` is greater than 1.
+    #[inline]
+    pub fn set_compare_buffered(&mut self, channel: u8, value: u16) {
+        assert!(channel < 2, "Invalid compare channel");
+        // Safety: Every compare value is valid
+        self.pdec.ccbuf[channel as usize].write(|w| unsafe { w.ccbuf().bits(value) });
+    }
+
+    /// Set the input filter length at the next update condition
+    #[inline]
+    pub fn set_filter_buffered(&mut self, filter: u8) {
+        // Safety: Every filter value is valid
+        self.pdec
+            .filterbuf
+            .write(|w| unsafe { w.filterbuf().bits(filter) });
+    }
+
+    /// Lock or unlock the update of the buffered registers
+    #[inline]
+    pub fn lock_update(&mut self, lock: bool) {
+        if lock {
+            self.pdec.ctrlbset.write(|w| w.lupd().set_bit());
+        } else {
+            self.pdec.ctrlbclr.write(|w| w.lupd().set_bit());
+        }
+        self.sync();
+    }
+
+    /// Copy the buffered registers immediately
+    #[inline]
+    pub fn force_update(&mut self) {
+        self.command(CMD_A::UPDATE);
+    }
+
+    /// Current counting direction
+    #[inline]
+    pub fn direction(&self) -> Direction {
+        if self.pdec.status.read().dir().bit_is_set() {
+            Direction::Down
+        } else {
+            Direction::Up
+        }
+    }
+
+    /// Whether decoding is stopped
+    #[inline]
+    pub fn is_stopped(&self) -> bool {
+        self.pdec.status.read().stop().bit_is_set()
+    }
+
+    //==========================================================================
+    // Events
+    //==========================================================================
+
+    /// Enable or disable event input `input`, between 0 and 2, optionally
+    /// inverted
+    ///
+    /// An enabled event input replaces the corresponding `QDIn` pin input.
+    /// The PDEC is disabled while the setting is changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `input` is greater than 2.
+    #[inline]
+    pub fn set_event_input(&mut self, input: u8, enable: bool, invert: bool) {
+        assert!(input < 3, "Invalid event input");
+        let evinv = 1 << (2 + input);
+        let evei = 1 << (5 + input);
+        self.while_disabled(|pdec| {
+            pdec.evctrl.modify(|r, w| {
+                let mut bits = r.bits() & !(evinv | evei);
+                if enable {
+                    bits |= evei;
+                    if invert {
+                        bits |= evinv;
+                    }
+                }
+                // Safety: Only the event input bits are changed
+                unsafe { w.bits(bits) }
+            });
+        });
+    }
+
+    /// Select the flags generating output events
+    ///
+    /// The PDEC is disabled while the setting is changed.
+    #[inline]
+    pub fn set_event_outputs(&mut self, flags: Flags) {
+        let bits = (flags.bits() as u16) << 8;
+        self.while_disabled(|pdec| {
+            pdec.evctrl.modify(|r, w| {
+                // Safety: The event output enable bits have the same order as
+                // the INTFLAG bits
+                unsafe { w.bits(r.bits() & 0xff | bits) }
+            });
+        });
+    }
+
+    //==========================================================================
+    // Interrupts
+    //==========================================================================
+
+    /// Enable interrupts for the specified flags
+    #[inline]
+    pub fn enable_interrupts(&mut self, flags: Flags) {
+        // Safety: `Flags` bits match the INTENSET bits
+        self.pdec
+            .intenset
+            .write(|w| unsafe { w.bits(flags.bits()) });
+    }
+
+    /// Disable interrupts for the specified flags
+    #[inline]
+    pub fn disable_interrupts(&mut self, flags: Flags) {
+        // Safety: `Flags` bits match the INTENCLR bits
+        self.pdec
+            .intenclr
+            .write(|w| unsafe { w.bits(flags.bits()) });
+    }
+
+    /// Read the interrupt flags
+    #[inline]
+    pub fn read_flags(&self) -> Flags {
+        Flags::from_bits_truncate(self.pdec.intflag.read().bits())
+    }
+
+    /// Clear the specified interrupt flags
+    #[inline]
+    pub fn clear_flags(&mut self, flags: Flags) {
+        // Safety: `Flags` bits match the INTFLAG bits
+        self.pdec.intflag.write(|w| unsafe { w.bits(flags.bits()) });
+    }
+
+    /// Read the error flags
+    #[inline]
+    pub fn errors(&self) -> Errors {
+        Errors::from_bits_truncate(self.pdec.status.read().bits())
+    }
+
+    /// Clear the specified error flags
+    #[inline]
+    pub fn clear_errors(&mut self, errors: Errors) {
+        // Safety: `Errors` bits match the STATUS bits, and writing zeros has
+        // no effect
+        self.pdec.status.write(|w| unsafe { w.bits(errors.bits()) });
+        self.sync();
+    }
+
+    /// Reset the PDEC and return it with its pins
+    #[inline]
+    pub fn free(self) -> (PDEC, Pins<A, B, I>) {
+        self.pdec.ctrla.write(|w| w.swrst().set_bit());
+        while self.pdec.syncbusy.read().swrst().bit_is_set() {}
+        (self.pdec, self.pins)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn position_round_trip() {
+        let position = Position {
+            angle: 1000,
+            revolutions: 37,
+        };
+        let count = position.to_count(10);
+        assert_eq!(count, 1000 | 37 << 10);
+        assert_eq!(Position::from_count(count, 10), position);
+    }
+
+    #[test]
+    fn angular_only() {
+        let position = Position::from_count(0xabcd, 16);
+        assert_eq!(position.angle, 0xabcd);
+        assert_eq!(position.revolutions, 0);
+        assert_eq!(position.to_count(16), 0xabcd);
+    }
+}
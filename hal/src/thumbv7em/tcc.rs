@@ -0,0 +1,1147 @@
+//! # Timer/Counter for Control Applications
+//!
+//! The [`pwm`](crate::pwm) module only exposes the TCC as a basic PWM. This
+//! module gives access to the features of the TCC meant for motor control and
+//! power conversion:
+//!
+//! * complementary outputs with dead-time insertion, see
+//!   [`Tcc::set_complementary`],
+//! * recoverable and non-recoverable fault inputs, driven by the
+//!   [`evsys`](crate::evsys) module, see [`Tcc::set_recoverable_fault`] and
+//!   [`Tcc::set_non_recoverable_fault`],
+//! * the output matrix, see [`OutputMatrix`],
+//! * pattern generation, see [`Tcc::set_pattern`],
+//! * dithering, see [`Resolution`],
+//! * circular buffers and double-buffered updates, see
+//!   [`Tcc::set_period_buffered`], [`Tcc::set_duty_buffered`] and
+//!   [`Tcc::lock_update`].
+//!
+//! Dead-time insertion, output swapping and pattern generation are only
+//! available on `TCC0` and `TCC1`, which implement [`ExtendedTccId`].
+//!
+//! # Outputs
+//!
+//! The `WO[x]` waveform outputs are checked at compile time with the
+//! [`WoPin`] trait, implemented for each [`PinId`] that can be muxed to a TCC
+//! output. With the default [`OutputMatrix`], `WO[x]` and `WO[x + 4]` are
+//! both driven by compare channel `x % 4`. When dead-time insertion is enabled
+//! for channel `x`, `WO[x]` is the low side and `WO[x + 4]` the high side of a
+//! complementary pair.
+//!
+//! ```no_run
+//! use atsamd_hal::gpio::Pins;
+//! use atsamd_hal::tcc::{Config, DeadTime, Tcc, Waveform};
+//! use atsamd_hal::fugit::RateExtU32;
+//! # let mut peripherals = atsamd_hal::pac::Peripherals::take().unwrap();
+//! let pins = Pins::new(peripherals.PORT);
+//! // WO[0] and WO[4] of TCC0
+//! let low = pins.pa08.into_alternate();
+//! let high = pins.pa16.into_alternate();
+//!
+//! let config = Config {
+//!     waveform: Waveform::DualSlopeBottom,
+//!     ..Default::default()
+//! };
+//! let mut tcc = Tcc::new(peripherals.TCC0, &mut peripherals.MCLK, 120_000_000.Hz(), config);
+//! tcc.set_complementary(&low, &high, Some(DeadTime { low_side: 60, high_side: 60 }));
+//! tcc.set_period(3000);
+//! tcc.set_duty(0, 1500);
+//! tcc.enable();
+//! ```
+
+use core::ops::Deref;
+
+use bitflags::bitflags;
+
+use crate::gpio::*;
+use crate::pac::{tcc0, MCLK};
+use crate::time::Hertz;
+use crate::typelevel::Sealed;
+
+#[cfg(feature = "has-tcc0")]
+use crate::pac::TCC0;
+#[cfg(feature = "has-tcc1")]
+use crate::pac::TCC1;
+#[cfg(feature = "has-tcc2")]
+use crate::pac::TCC2;
+#[cfg(feature = "has-tcc3")]
+use crate::pac::TCC3;
+
+pub use crate::capture::Prescaler;
+
+//==============================================================================
+// Instances
+//==============================================================================
+
+/// TCC instance
+///
+/// `Sealed` is implemented for the TCC instances by the
+/// [`capture`](crate::capture) module.
+pub trait TccId: Deref<Target = tcc0::RegisterBlock> + Sealed {
+    /// Number of compare/capture channels
+    const CC_NUM: u8;
+    /// Number of waveform outputs
+    const WO_NUM: u8;
+    /// Maximum value of the period and compare registers
+    const MAX: u32;
+
+    #[doc(hidden)]
+    fn enable_apb(mclk: &mut MCLK);
+}
+
+/// TCC instance with dead-time insertion, output swapping and pattern
+/// generation
+pub trait ExtendedTccId: TccId {}
+
+macro_rules! tcc_ids {
+    ($( $( #[$attr:meta] )* $Tcc:ident: ($cc:literal, $wo:literal, $max:literal, $apbmask:ident, $apbbit:ident), )+) => {
+        $(
+            $( #[$attr] )*
+            impl TccId for $Tcc {
+                const CC_NUM: u8 = $cc;
+                const WO_NUM: u8 = $wo;
+                const MAX: u32 = $max;
+
+                #[inline]
+                fn enable_apb(mclk: &mut MCLK) {
+                    mclk.$apbmask.modify(|_, w| w.$apbbit().set_bit());
+                }
+            }
+        )+
+    };
+}
+
+tcc_ids!(
+    #[cfg(feature = "has-tcc0")]
+    TCC0: (6, 8, 0xff_ffff, apbbmask, tcc0_),
+    #[cfg(feature = "has-tcc1")]
+    TCC1: (4, 8, 0xff_ffff, apbbmask, tcc1_),
+    #[cfg(feature = "has-tcc2")]
+    TCC2: (3, 3, 0xffff, apbcmask, tcc2_),
+    #[cfg(feature = "has-tcc3")]
+    TCC3: (2, 2, 0xffff, apbcmask, tcc3_),
+);
+
+#[cfg(feature = "has-tcc0")]
+impl ExtendedTccId for TCC0 {}
+#[cfg(feature = "has-tcc1")]
+impl ExtendedTccId for TCC1 {}
+
+//==============================================================================
+// Pins
+//==============================================================================
+
+/// Pin that can be muxed to waveform output `WO` of TCC instance `T`
+pub trait WoPin<T: TccId>: PinId {
+    /// Index of the waveform output
+    const WO: u8;
+    /// Alternate mode muxing the pin to the TCC
+    type Mode: PinMode;
+}
+
+macro_rules! wo_pins {
+    ($Tcc:ident: $( $( #[$attr:meta] )* $Id:ident: ($wo:literal, $Mode:ident), )+) => {
+        $(
+            $( #[$attr] )*
+            impl WoPin<$Tcc> for $Id {
+                const WO: u8 = $wo;
+                type Mode = $Mode;
+            }
+        )+
+    };
+}
+
+#[cfg(feature = "has-tcc0")]
+wo_pins!(TCC0:
+    PA08: (0, AlternateF),
+    PA09: (1, AlternateF),
+    PA10: (2, AlternateF),
+    PA11: (3, AlternateF),
+    PA12: (6, AlternateF),
+    PA13: (7, AlternateF),
+    PA16: (4, AlternateG),
+    PA17: (5, AlternateG),
+    PA18: (6, AlternateG),
+    PA19: (7, AlternateG),
+    PA20: (0, AlternateG),
+    PA21: (1, AlternateG),
+    PA22: (2, AlternateG),
+    PA23: (3, AlternateG),
+    PB10: (4, AlternateF),
+    PB11: (5, AlternateF),
+    #[cfg(feature = "pins-64")]
+    PB12: (0, AlternateG),
+    #[cfg(feature = "pins-64")]
+    PB13: (1, AlternateG),
+    #[cfg(feature = "pins-64")]
+    PB14: (2, AlternateG),
+    #[cfg(feature = "pins-64")]
+    PB15: (3, AlternateG),
+    #[cfg(feature = "pins-64")]
+    PB16: (4, AlternateG),
+    #[cfg(feature = "pins-64")]
+    PB17: (5, AlternateG),
+    #[cfg(feature = "pins-64")]
+    PB30: (6, AlternateG),
+    #[cfg(feature = "pins-64")]
+    PB31: (7, AlternateG),
+    #[cfg(feature = "pins-100")]
+    PC10: (0, AlternateF),
+    #[cfg(feature = "pins-100")]
+    PC11: (1, AlternateF),
+    #[cfg(feature = "pins-100")]
+    PC12: (2, AlternateF),
+    #[cfg(feature = "pins-100")]
+    PC13: (3, AlternateF),
+    #[cfg(feature = "pins-100")]
+    PC14: (4, AlternateF),
+    #[cfg(feature = "pins-100")]
+    PC15: (5, AlternateF),
+    #[cfg(feature = "pins-100")]
+    PC16: (0, AlternateF),
+    #[cfg(feature = "pins-100")]
+    PC17: (1, AlternateF),
+    #[cfg(feature = "pins-100")]
+    PC18: (2, AlternateF),
+    #[cfg(feature = "pins-100")]
+    PC19: (3, AlternateF),
+    #[cfg(feature = "pins-100")]
+    PC20: (4, AlternateF),
+    #[cfg(feature = "pins-100")]
+    PC21: (5, AlternateF),
+    #[cfg(feature = "pins-128")]
+    PC04: (0, AlternateF),
+    #[cfg(feature = "pins-128")]
+    PC22: (6, AlternateF),
+    #[cfg(feature = "pins-128")]
+    PC23: (7, AlternateF),
+    #[cfg(feature = "pins-128")]
+    PD08: (1, AlternateF),
+    #[cfg(feature = "pins-128")]
+    PD09: (2, AlternateF),
+    #[cfg(feature = "pins-128")]
+    PD10: (3, AlternateF),
+    #[cfg(feature = "pins-128")]
+    PD11: (4, AlternateF),
+    #[cfg(feature = "pins-128")]
+    PD12: (5, AlternateF),
+);
+
+#[cfg(feature = "has-tcc1")]
+wo_pins!(TCC1:
+    PA08: (4, AlternateG),
+    PA09: (5, AlternateG),
+    PA10: (6, AlternateG),
+    PA11: (7, AlternateG),
+    PA12: (2, AlternateG),
+    PA13: (3, AlternateG),
+    PA14: (2, AlternateG),
+    PA15: (3, AlternateG),
+    PA16: (0, AlternateF),
+    PA17: (1, AlternateF),
+    PA18: (2, AlternateF),
+    PA19: (3, AlternateF),
+    PA20: (4, AlternateF),
+    PA21: (5, AlternateF),
+    PA22: (6, AlternateF),
+    PA23: (7, AlternateF),
+    PB10: (0, AlternateG),
+    PB11: (1, AlternateG),
+    #[cfg(feature = "pins-100")]
+    PB18: (0, AlternateF),
+    #[cfg(feature = "pins-100")]
+    PB19: (1, AlternateF),
+    #[cfg(feature = "pins-100")]
+    PB20: (2, AlternateF),
+    #[cfg(feature = "pins-100")]
+    PB21: (3, AlternateF),
+    #[cfg(feature = "pins-100")]
+    PC10: (4, AlternateG),
+    #[cfg(feature = "pins-100")]
+    PC11: (5, AlternateG),
+    #[cfg(feature = "pins-100")]
+    PC12: (6, AlternateG),
+    #[cfg(feature = "pins-100")]
+    PC13: (7, AlternateG),
+    #[cfg(feature = "pins-100")]
+    PC14: (0, AlternateG),
+    #[cfg(feature = "pins-100")]
+    PC15: (1, AlternateG),
+    #[cfg(feature = "pins-128")]
+    PB26: (2, AlternateF),
+    #[cfg(feature = "pins-128")]
+    PB27: (3, AlternateF),
+    #[cfg(feature = "pins-128")]
+    PB28: (4, AlternateF),
+    #[cfg(feature = "pins-128")]
+    PB29: (5, AlternateF),
+    #[cfg(feature = "pins-128")]
+    PD20: (0, AlternateF),
+    #[cfg(feature = "pins-128")]
+    PD21: (1, AlternateF),
+);
+
+#[cfg(feature = "has-tcc2")]
+wo_pins!(TCC2:
+    PA14: (0, AlternateF),
+    PA15: (1, AlternateF),
+    PA24: (2, AlternateF),
+    PA30: (0, AlternateF),
+    PA31: (1, AlternateF),
+    #[cfg(feature = "has-pb02")]
+    PB02: (2, AlternateF),
+);
+
+#[cfg(feature = "has-tcc3")]
+wo_pins!(TCC3:
+    #[cfg(feature = "pins-64")]
+    PB12: (0, AlternateF),
+    #[cfg(feature = "pins-64")]
+    PB13: (1, AlternateF),
+    #[cfg(feature = "pins-64")]
+    PB16: (0, AlternateF),
+    #[cfg(feature = "pins-64")]
+    PB17: (1, AlternateF),
+);
+
+//==============================================================================
+// Configuration
+//==============================================================================
+
+/// Waveform generation operation
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Waveform {
+    /// Single-slope PWM
+    #[default]
+    NormalPwm,
+    /// Dual-slope PWM, with the period and compare values updated at the
+    /// bottom. `CC[0]` is the critical value of the other channels.
+    DualSlopeCritical,
+    /// Dual-slope PWM, with updates and the overflow flag at the bottom
+    DualSlopeBottom,
+    /// Dual-slope PWM, with updates at the bottom and the overflow flag at the
+    /// top and bottom
+    DualSlopeBoth,
+    /// Dual-slope PWM, with updates at the bottom and the overflow flag at the
+    /// top
+    DualSlopeTop,
+}
+
+impl Waveform {
+    #[inline]
+    fn bits(self) -> u32 {
+        match self {
+            Waveform::NormalPwm => 2,
+            Waveform::DualSlopeCritical => 4,
+            Waveform::DualSlopeBottom => 5,
+            Waveform::DualSlopeBoth => 6,
+            Waveform::DualSlopeTop => 7,
+        }
+    }
+}
+
+/// Dithering resolution
+///
+/// With dithering, the period and compare values are extended by `n`
+/// fractional bits, which add one counter cycle to that many periods out of
+/// every `2^n` periods. Use [`Resolution::encode`] to format the values
+/// written to the period and compare registers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Resolution {
+    /// No dithering
+    #[default]
+    None,
+    /// 4 fractional bits, over 16 periods
+    Dith4,
+    /// 5 fractional bits, over 32 periods
+    Dith5,
+    /// 6 fractional bits, over 64 periods
+    Dith6,
+}
+
+impl Resolution {
+    /// Number of fractional bits
+    #[inline]
+    pub fn fractional_bits(self) -> u32 {
+        match self {
+            Resolution::None => 0,
+            Resolution::Dith4 => 4,
+            Resolution::Dith5 => 5,
+            Resolution::Dith6 => 6,
+        }
+    }
+
+    /// Format a period or compare register value from its integer part and
+    /// the number of dithered cycles
+    ///
+    /// The integer part is shifted to make room for the fractional bits, so
+    /// its maximum is reduced accordingly.
+    #[inline]
+    pub fn encode(self, value: u32, dither: u8) -> u32 {
+        let bits = self.fractional_bits();
+        let mask = (1 << bits) - 1;
+        value << bits | (dither as u32 & mask)
+    }
+}
+
+/// Selection of the compare channel driving each waveform output
+///
+/// `CCx` denotes the output of compare channel `x`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum OutputMatrix {
+    /// `WO[0..8]` are driven by `CC0 CC1 CC2 CC3 CC0 CC1 CC2 CC3`
+    #[default]
+    Default,
+    /// `WO[0..8]` are driven by `CC0 CC1 CC0 CC1 CC0 CC1 CC0 CC1`
+    Paired,
+    /// All outputs are driven by `CC0`
+    Cc0,
+    /// `WO[0..8]` are driven by `CC0 CC1 CC1 CC1 CC1 CC1 CC1 CC1`
+    Cc0Cc1,
+}
+
+/// TCC configuration, written while the TCC is disabled
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Config {
+    /// Division of the generic clock to obtain the counter clock
+    pub prescaler: Prescaler,
+    /// Waveform generation operation
+    pub waveform: Waveform,
+    /// Dithering resolution
+    pub resolution: Resolution,
+    /// Output matrix
+    pub output_matrix: OutputMatrix,
+    /// Keep the TCC running in standby sleep mode
+    pub run_in_standby: bool,
+}
+
+/// Dead time inserted in a complementary pair, in cycles of the TCC generic
+/// clock
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DeadTime {
+    /// Dead time before the low side output is activated
+    pub low_side: u8,
+    /// Dead time before the high side output is activated
+    pub high_side: u8,
+}
+
+/// Pattern generator state
+///
+/// Bit `x` of each field applies to waveform output `WO[x]`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Pattern {
+    /// Outputs overridden by the pattern generator
+    pub enable: u8,
+    /// Levels of the overridden outputs
+    pub value: u8,
+}
+
+impl Pattern {
+    #[inline]
+    fn bits(self) -> u16 {
+        self.enable as u16 | (self.value as u16) << 8
+    }
+}
+
+//==============================================================================
+// Faults
+//==============================================================================
+
+/// Recoverable fault input
+///
+/// Fault `A` is driven by the match/capture 0 event input (`MCEI0`), and fault
+/// `B` by the match/capture 1 event input (`MCEI1`). Connect them with the
+/// `TccnMc0` and `TccnMc1` [`evsys`](crate::evsys) users.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RecoverableFaultId {
+    A,
+    B,
+}
+
+/// Action taken by a recoverable fault on the outputs
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Halt {
+    /// The outputs are not halted
+    #[default]
+    Disabled,
+    /// The outputs are halted while the fault is active
+    Hardware,
+    /// The outputs are halted until the fault is cleared with
+    /// [`Tcc::clear_faults`]
+    Software,
+    /// The outputs are driven to their non-recoverable fault state, see
+    /// [`Tcc::set_fault_state`]
+    NonRecoverable,
+}
+
+/// Start of the blanking window of a recoverable fault
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Blank {
+    /// Blanking applied from the start of the ramp
+    #[default]
+    Start,
+    /// Blanking applied from the rising edge of the output
+    Rise,
+    /// Blanking applied from the falling edge of the output
+    Fall,
+    /// Blanking applied from each edge of the output
+    Both,
+}
+
+/// Recoverable fault configuration
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RecoverableFault {
+    /// Trigger the fault on a low event level instead of a high one
+    pub invert: bool,
+    /// Action on the outputs
+    pub halt: Halt,
+    /// Restart the counter at the end of the period once the fault is gone
+    pub restart: bool,
+    /// Keep the outputs halted until the end of the period, even if the fault
+    /// is gone
+    pub keep: bool,
+    /// Only consider the fault while the output of the selected compare
+    /// channel is inactive
+    pub qualify: bool,
+    /// Compare channel used by `qualify` and `blank`, between 0 and 3
+    pub channel: u8,
+    /// Start of the blanking window
+    pub blank: Blank,
+    /// Duration of the blanking window, in counter clock cycles
+    pub blank_cycles: u8,
+    /// Number of consecutive samples for the fault to be detected, up to 15
+    pub filter: u8,
+}
+
+impl RecoverableFault {
+    /// Value of the `FCTRLA` or `FCTRLB` register
+    #[inline]
+    fn bits(&self) -> u32 {
+        assert!(self.channel < 4 && self.filter < 16);
+        let src = if self.invert { 2 } else { 1 };
+        let halt = self.halt as u32;
+        src | (self.keep as u32) << 3
+            | (self.qualify as u32) << 4
+            | (self.blank as u32) << 5
+            | (self.restart as u32) << 7
+            | halt << 8
+            | (self.channel as u32) << 10
+            | (self.blank_cycles as u32) << 16
+            | (self.filter as u32) << 24
+    }
+}
+
+/// Non-recoverable fault input
+///
+/// Each input is driven by the corresponding TCC event input. Connect them
+/// with the `TccnEv0` and `TccnEv1` [`evsys`](crate::evsys) users.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum NonRecoverableFaultId {
+    Fault0,
+    Fault1,
+}
+
+/// Non-recoverable fault configuration
+///
+/// While the fault is active, each output is driven to the state selected by
+/// [`Tcc::set_fault_state`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NonRecoverableFault {
+    /// Trigger the fault on a low event level instead of a high one
+    pub invert: bool,
+    /// Number of consecutive samples for the fault to be detected, up to 15
+    pub filter: u8,
+}
+
+bitflags! {
+    /// Fault state flags
+    ///
+    /// The binary format of the underlying bits exactly matches the fault bits
+    /// of the STATUS register.
+    pub struct Faults: u32 {
+        /// Recoverable fault A input is active
+        const FAULTA_IN = 1 << 8;
+        /// Recoverable fault B input is active
+        const FAULTB_IN = 1 << 9;
+        /// Non-recoverable fault 0 input is active
+        const FAULT0_IN = 1 << 10;
+        /// Non-recoverable fault 1 input is active
+        const FAULT1_IN = 1 << 11;
+        /// Recoverable fault A occurred
+        const FAULTA = 1 << 12;
+        /// Recoverable fault B occurred
+        const FAULTB = 1 << 13;
+        /// Non-recoverable fault 0 occurred
+        const FAULT0 = 1 << 14;
+        /// Non-recoverable fault 1 occurred
+        const FAULT1 = 1 << 15;
+    }
+}
+
+bitflags! {
+    /// Interrupt flags
+    ///
+    /// The binary format of the underlying bits exactly matches the INTFLAG
+    /// bits.
+    pub struct Flags: u32 {
+        const OVF = 1 << 0;
+        const TRG = 1 << 1;
+        const CNT = 1 << 2;
+        const ERR = 1 << 3;
+        const UFS = 1 << 10;
+        const DFS = 1 << 11;
+        const FAULTA = 1 << 12;
+        const FAULTB = 1 << 13;
+        const FAULT0 = 1 << 14;
+        const FAULT1 = 1 << 15;
+        const MC0 = 1 << 16;
+        const MC1 = 1 << 17;
+        const MC2 = 1 << 18;
+        const MC3 = 1 << 19;
+        const MC4 = 1 << 20;
+        const MC5 = 1 << 21;
+    }
+}
+
+//==============================================================================
+// Tcc
+//==============================================================================
+
+/// Timer/Counter for Control Applications instance `T`
+pub struct Tcc<T: TccId> {
+    tcc: T,
+    freq: Hertz,
+}
+
+impl<T: TccId> Tcc<T> {
+    /// Enable the APB clock, reset and configure the TCC
+    ///
+    /// `clock_freq` is the frequency of the generic clock of the TCC. The TCC is
+    /// left disabled, with all the outputs driven by the waveform generator.
+    #[inline]
+    pub fn new(tcc: T, mclk: &mut MCLK, clock_freq: Hertz, config: Config) -> Self {
+        T::enable_apb(mclk);
+        let tcc = Self {
+            tcc,
+            freq: clock_freq / config.prescaler.divider(),
+        };
+        let regs = &tcc.tcc;
+
+        regs.ctrla.write(|w| w.swrst().set_bit());
+        while regs.syncbusy.read().swrst().bit_is_set() {}
+
+        regs.ctrla.write(|w| {
+            match config.resolution {
+                Resolution::None => w.resolution().none(),
+                Resolution::Dith4 => w.resolution().dith4(),
+                Resolution::Dith5 => w.resolution().dith5(),
+                Resolution::Dith6 => w.resolution().dith6(),
+            };
+            match config.prescaler {
+                Prescaler::Div1 => w.prescaler().div1(),
+                Prescaler::Div2 => w.prescaler().div2(),
+                Prescaler::Div4 => w.prescaler().div4(),
+                Prescaler::Div8 => w.prescaler().div8(),
+                Prescaler::Div16 => w.prescaler().div16(),
+                Prescaler::Div64 => w.prescaler().div64(),
+                Prescaler::Div256 => w.prescaler().div256(),
+                Prescaler::Div1024 => w.prescaler().div1024(),
+            };
+            w.runstdby().bit(config.run_in_standby)
+        });
+        // Safety: OTMX is a 2-bit field
+        regs.wexctrl
+            .write(|w| unsafe { w.otmx().bits(config.output_matrix as u8) });
+        // Safety: WAVEGEN is a 3-bit field and the value is a valid operation
+        regs.wave
+            .write(|w| unsafe { w.bits(config.waveform.bits()) });
+        tcc.sync();
+        tcc
+    }
+
+    #[inline]
+    fn sync(&self) {
+        while self.tcc.syncbusy.read().bits() != 0 {}
+    }
+
+    /// Frequency of the counter
+    #[inline]
+    pub fn tick_freq(&self) -> Hertz {
+        self.freq
+    }
+
+    /// Enable the TCC
+    #[inline]
+    pub fn enable(&mut self) {
+        self.tcc.ctrla.modify(|_, w| w.enable().set_bit());
+        self.sync();
+    }
+
+    /// Disable the TCC
+    #[inline]
+    pub fn disable(&mut self) {
+        self.tcc.ctrla.modify(|_, w| w.enable().clear_bit());
+        self.sync();
+    }
+
+    /// Write enable-protected registers
+    #[inline]
+    fn while_disabled(&mut self, f: impl FnOnce(&tcc0::RegisterBlock)) {
+        let enabled = self.tcc.ctrla.read().enable().bit_is_set();
+        self.disable();
+        f(&self.tcc);
+        if enabled {
+            self.enable();
+        }
+    }
+
+    //==========================================================================
+    // Period and compare values
+    //==========================================================================
+
+    /// Set the period, immediately
+    ///
+    /// With dithering, the value must be formatted with
+    /// [`Resolution::encode`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the period exceeds [`TccId::MAX`].
+    #[inline]
+    pub fn set_period(&mut self, period: u32) {
+        assert!(period <= T::MAX, "Period out of range");
+        // Safety: The value fits in the PER register
+        self.tcc.per().write(|w| unsafe { w.bits(period) });
+        self.sync();
+    }
+
+    /// Set the period at the next update condition
+    ///
+    /// The value is written to `PERBUF`, and copied to `PER` at the end of the
+    /// current period unless updates are locked, see [`Tcc::lock_update`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the period exceeds [`TccId::MAX`].
+    #[inline]
+    pub fn set_period_buffered(&mut self, period: u32) {
+        assert!(period <= T::MAX, "Period out of range");
+        // Safety: The value fits in the PERBUF register
+        self.tcc.perbuf().write(|w| unsafe { w.bits(period) });
+    }
+
+    /// Current period
+    #[inline]
+    pub fn period(&self) -> u32 {
+        self.tcc.per().read().bits()
+    }
+
+    /// Set the compare value of `channel`, immediately
+    ///
+    /// With dithering, the value must be formatted with
+    /// [`Resolution::encode`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the channel does not exist on this instance, or if the value
+    /// exceeds [`TccId::MAX`].
+    #[inline]
+    pub fn set_duty(&mut self, channel: u8, duty: u32) {
+        assert!(channel < T::CC_NUM, "Invalid compare channel");
+        assert!(duty <= T::MAX, "Compare value out of range");
+        // Safety: The value fits in the CC register
+        self.tcc.cc()[channel as usize].write(|w| unsafe { w.bits(duty) });
+        self.sync();
+    }
+
+    /// Set the compare value of `channel` at the next update condition
+    ///
+    /// The value is written to `CCBUF`, and copied to `CC` at the end of the
+    /// current period unless updates are locked, see [`Tcc::lock_update`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the channel does not exist on this instance, or if the value
+    /// exceeds [`TccId::MAX`].
+    #[inline]
+    pub fn set_duty_buffered(&mut self, channel: u8, duty: u32) {
+        assert!(channel < T::CC_NUM, "Invalid compare channel");
+        assert!(duty <= T::MAX, "Compare value out of range");
+        // Safety: The value fits in the CCBUF register
+        self.tcc.ccbuf()[channel as usize].write(|w| unsafe { w.bits(duty) });
+    }
+
+    /// Current compare value of `channel`
+    #[inline]
+    pub fn duty(&self, channel: u8) -> u32 {
+        self.tcc.cc()[channel as usize].read().bits()
+    }
+
+    /// Lock or unlock the update of the buffered registers
+    ///
+    /// While locked, the values written with the buffered methods are held in
+    /// the buffer registers, so that several of them can be applied at the
+    /// same update condition.
+    #[inline]
+    pub fn lock_update(&mut self, lock: bool) {
+        if lock {
+            self.tcc.ctrlbset.write(|w| w.lupd().set_bit());
+        } else {
+            self.tcc.ctrlbclr.write(|w| w.lupd().set_bit());
+        }
+        self.sync();
+    }
+
+    /// Copy the buffer registers immediately, without waiting for the update
+    /// condition
+    #[inline]
+    pub fn force_update(&mut self) {
+        self.tcc.ctrlbset.write(|w| w.cmd().update());
+        self.sync();
+    }
+
+    /// Enable the circular buffer of the period
+    ///
+    /// At each update condition, `PER` and `PERBUF` are swapped, so that the
+    /// period alternates between two values.
+    #[inline]
+    pub fn set_circular_period(&mut self, enable: bool) {
+        self.tcc.wave.modify(|_, w| w.ciperen().bit(enable));
+        self.sync();
+    }
+
+    /// Enable the circular buffer of compare channel `channel`
+    ///
+    /// At each update condition, `CC` and `CCBUF` are swapped, so that the
+    /// compare value alternates between two values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel` is not between 0 and 3, or does not exist on this
+    /// instance.
+    #[inline]
+    pub fn set_circular_duty(&mut self, channel: u8, enable: bool) {
+        assert!(
+            channel < 4 && channel < T::CC_NUM,
+            "Invalid compare channel"
+        );
+        self.tcc.wave.modify(|r, w| {
+            let bits = r.bits() & !(1 << (8 + channel)) | (enable as u32) << (8 + channel);
+            // Safety: Only the CICCEN bit of the channel is changed
+            unsafe { w.bits(bits) }
+        });
+        self.sync();
+    }
+
+    /// Invert the output of compare channel `channel`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the channel does not exist on this instance.
+    #[inline]
+    pub fn set_polarity(&mut self, channel: u8, inverted: bool) {
+        assert!(channel < T::CC_NUM, "Invalid compare channel");
+        self.tcc.wave.modify(|r, w| {
+            let bits = r.bits() & !(1 << (16 + channel)) | (inverted as u32) << (16 + channel);
+            // Safety: Only the POL bit of the channel is changed
+            unsafe { w.bits(bits) }
+        });
+        self.sync();
+    }
+
+    //==========================================================================
+    // Outputs
+    //==========================================================================
+
+    /// Modify the bits of the `DRVCTRL` register selected by `mask`
+    #[inline]
+    fn modify_drvctrl(&mut self, mask: u32, bits: u32) {
+        self.while_disabled(|tcc| {
+            tcc.drvctrl.modify(|r, w| {
+                // Safety: Every combination of DRVCTRL bits is valid
+                unsafe { w.bits(r.bits() & !mask | bits & mask) }
+            });
+        });
+    }
+
+    /// Invert the waveform output muxed to `pin`
+    ///
+    /// The TCC is disabled while the setting is changed.
+    #[inline]
+    pub fn invert_output<P: WoPin<T>>(&mut self, _pin: &Pin<P, P::Mode>, invert: bool) {
+        let bit = 1 << (16 + P::WO);
+        self.modify_drvctrl(bit, if invert { bit } else { 0 });
+    }
+
+    /// Select the state of the waveform output muxed to `pin` while a
+    /// non-recoverable fault is active
+    ///
+    /// `Some(true)` drives the output high, `Some(false)` low, and `None`
+    /// leaves it driven by the waveform generator. The TCC is disabled while
+    /// the setting is changed.
+    #[inline]
+    pub fn set_fault_state<P: WoPin<T>>(&mut self, _pin: &Pin<P, P::Mode>, state: Option<bool>) {
+        let nre = 1 << P::WO;
+        let nrv = 1 << (8 + P::WO);
+        let bits = match state {
+            None => 0,
+            Some(false) => nre,
+            Some(true) => nre | nrv,
+        };
+        self.modify_drvctrl(nre | nrv, bits);
+    }
+
+    //==========================================================================
+    // Faults
+    //==========================================================================
+
+    /// Configure or disable a recoverable fault input
+    ///
+    /// This also enables or disables the corresponding event input. The TCC is
+    /// disabled while the setting is changed.
+    #[inline]
+    pub fn set_recoverable_fault(
+        &mut self,
+        fault: RecoverableFaultId,
+        config: Option<RecoverableFault>,
+    ) {
+        let bits = config.as_ref().map_or(0, RecoverableFault::bits);
+        let enable = config.is_some();
+        self.while_disabled(|tcc| match fault {
+            RecoverableFaultId::A => {
+                // Safety: The value is built from the FCTRLA fields
+                tcc.fctrla.write(|w| unsafe { w.bits(bits) });
+                tcc.evctrl.modify(|_, w| w.mcei0().bit(enable));
+            }
+            RecoverableFaultId::B => {
+                // Safety: FCTRLB has the same layout as FCTRLA
+                tcc.fctrlb.write(|w| unsafe { w.bits(bits) });
+                tcc.evctrl.modify(|_, w| w.mcei1().bit(enable));
+            }
+        });
+    }
+
+    /// Configure or disable a non-recoverable fault input
+    ///
+    /// This sets the action of the corresponding event input. The TCC is
+    /// disabled while the setting is changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the filter length exceeds 15.
+    #[inline]
+    pub fn set_non_recoverable_fault(
+        &mut self,
+        fault: NonRecoverableFaultId,
+        config: Option<NonRecoverableFault>,
+    ) {
+        let NonRecoverableFault { invert, filter } = config.unwrap_or_default();
+        assert!(filter < 16, "Invalid fault filter length");
+        let enable = config.is_some();
+        let (shift, evact) = match fault {
+            NonRecoverableFaultId::Fault0 => (24, 0),
+            NonRecoverableFaultId::Fault1 => (28, 3),
+        };
+        self.while_disabled(|tcc| {
+            tcc.drvctrl.modify(|r, w| {
+                let bits = r.bits() & !(0xf << shift) | (filter as u32) << shift;
+                // Safety: Only the FILTERVAL field of the fault is changed
+                unsafe { w.bits(bits) }
+            });
+            tcc.evctrl.modify(|r, w| {
+                // EVACT0 is bits 0..3 and EVACT1 bits 3..6, FAULT is 7
+                let action = if enable { 7 } else { 0 };
+                let mut bits = r.bits() & !(0x7 << evact) | action << evact;
+                let (tcinv, tcei) = match fault {
+                    NonRecoverableFaultId::Fault0 => (12, 14),
+                    NonRecoverableFaultId::Fault1 => (13, 15),
+                };
+                bits = bits & !(1 << tcinv | 1 << tcei)
+                    | ((invert && enable) as u32) << tcinv
+                    | (enable as u32) << tcei;
+                // Safety: Only the event input fields of the fault are changed
+                unsafe { w.bits(bits) }
+            });
+        });
+    }
+
+    /// Read the fault state
+    #[inline]
+    pub fn faults(&self) -> Faults {
+        Faults::from_bits_truncate(self.tcc.status.read().bits())
+    }
+
+    /// Clear the specified faults
+    ///
+    /// A fault can only be cleared once its input is inactive. This resumes
+    /// the outputs halted by a [`Halt::Software`] recoverable fault or by a
+    /// non-recoverable fault.
+    #[inline]
+    pub fn clear_faults(&mut self, faults: Faults) {
+        let bits =
+            (faults & (Faults::FAULTA | Faults::FAULTB | Faults::FAULT0 | Faults::FAULT1)).bits();
+        // Safety: The fault bits of INTFLAG and STATUS are at the same
+        // positions, and writing zeros has no effect
+        self.tcc.intflag.write(|w| unsafe { w.bits(bits) });
+        self.tcc.status.write(|w| unsafe { w.bits(bits) });
+    }
+
+    //==========================================================================
+    // Interrupts
+    //==========================================================================
+
+    /// Enable interrupts for the specified flags
+    #[inline]
+    pub fn enable_interrupts(&mut self, flags: Flags) {
+        // Safety: `Flags` bits match the INTENSET bits
+        self.tcc.intenset.write(|w| unsafe { w.bits(flags.bits()) });
+    }
+
+    /// Disable interrupts for the specified flags
+    #[inline]
+    pub fn disable_interrupts(&mut self, flags: Flags) {
+        // Safety: `Flags` bits match the INTENCLR bits
+        self.tcc.intenclr.write(|w| unsafe { w.bits(flags.bits()) });
+    }
+
+    /// Read the interrupt flags
+    #[inline]
+    pub fn read_flags(&self) -> Flags {
+        Flags::from_bits_truncate(self.tcc.intflag.read().bits())
+    }
+
+    /// Clear the specified interrupt flags
+    #[inline]
+    pub fn clear_flags(&mut self, flags: Flags) {
+        // Safety: `Flags` bits match the INTFLAG bits
+        self.tcc.intflag.write(|w| unsafe { w.bits(flags.bits()) });
+    }
+
+    /// Reset the TCC and return it
+    #[inline]
+    pub fn free(self) -> T {
+        self.tcc.ctrla.write(|w| w.swrst().set_bit());
+        while self.tcc.syncbusy.read().swrst().bit_is_set() {}
+        self.tcc
+    }
+}
+
+impl<T: ExtendedTccId> Tcc<T> {
+    /// Drive `low` and `high` as a complementary pair, with dead-time
+    /// insertion, or disable the dead-time insertion of the pair with `None`
+    ///
+    /// `low` must be `WO[x]` and `high` must be `WO[x + 4]`, with `x` between
+    /// 0 and 3. Both are driven by compare channel `x`. The dead times are
+    /// shared by all the pairs. The TCC is disabled while the setting is
+    /// changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pins do not form a complementary pair.
+    #[inline]
+    pub fn set_complementary<L: WoPin<T>, H: WoPin<T>>(
+        &mut self,
+        _low: &Pin<L, L::Mode>,
+        _high: &Pin<H, H::Mode>,
+        dead_time: Option<DeadTime>,
+    ) {
+        assert!(
+            L::WO < 4 && H::WO == L::WO + 4,
+            "Pins are not a complementary pair"
+        );
+        let dtien = 1 << (8 + L::WO);
+        self.while_disabled(|tcc| {
+            tcc.wexctrl.modify(|r, w| {
+                let mut bits = r.bits() & !dtien;
+                if let Some(dead_time) = dead_time {
+                    bits = bits & 0xffff
+                        | dtien
+                        | (dead_time.low_side as u32) << 16
+                        | (dead_time.high_side as u32) << 24;
+                }
+                // Safety: Only the DTIEN bit of the pair and the shared dead
+                // times are changed
+                unsafe { w.bits(bits) }
+            });
+        });
+    }
+
+    /// Swap the low side and high side outputs of complementary pair
+    /// `channel`, between 0 and 3
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel` is greater than 3.
+    #[inline]
+    pub fn set_swap(&mut self, channel: u8, swap: bool) {
+        assert!(channel < 4, "Invalid complementary pair");
+        self.tcc.wave.modify(|r, w| {
+            let bits = r.bits() & !(1 << (24 + channel)) | (swap as u32) << (24 + channel);
+            // Safety: Only the SWAP bit of the pair is changed
+            unsafe { w.bits(bits) }
+        });
+        self.sync();
+    }
+
+    /// Override the waveform outputs with the pattern generator, immediately
+    #[inline]
+    pub fn set_pattern(&mut self, pattern: Pattern) {
+        // Safety: Every pattern is valid
+        self.tcc.patt.write(|w| unsafe { w.bits(pattern.bits()) });
+        self.sync();
+    }
+
+    /// Override the waveform outputs with the pattern generator, at the next
+    /// update condition
+    ///
+    /// See [`Tcc::lock_update`].
+    #[inline]
+    pub fn set_pattern_buffered(&mut self, pattern: Pattern) {
+        // Safety: Every pattern is valid
+        self.tcc
+            .pattbuf
+            .write(|w| unsafe { w.bits(pattern.bits()) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dithered_values() {
+        assert_eq!(Resolution::None.encode(1000, 3), 1000);
+        assert_eq!(Resolution::Dith4.encode(1000, 3), 1000 << 4 | 3);
+        assert_eq!(Resolution::Dith6.encode(1000, 0x7f), 1000 << 6 | 0x3f);
+    }
+
+    #[test]
+    fn recoverable_fault_bits() {
+        let fault = RecoverableFault {
+            invert: true,
+            halt: Halt::Hardware,
+            restart: true,
+            channel: 2,
+            blank: Blank::Rise,
+            blank_cycles: 10,
+            filter: 3,
+            ..Default::default()
+        };
+        assert_eq!(
+            fault.bits(),
+            2 | 1 << 5 | 1 << 7 | 1 << 8 | 2 << 10 | 10 << 16 | 3 << 24
+        );
+    }
+}
@@ -1,5 +1,5 @@
 //! Working with timer counter hardware
-use crate::ehal::timer::{CountDown, Periodic};
+use crate::ehal_02::timer::{CountDown, Periodic};
 use crate::pac::tc0::COUNT16;
 use crate::pac::{MCLK, TC2, TC3};
 #[cfg(all(feature = "has-tc4", feature = "has-tc5"))]
@@ -8,6 +8,8 @@ use crate::timer_params::TimerParams;
 use crate::timer_traits::InterruptDrivenTimer;
 
 use crate::clock;
+use crate::clock::v2::dynamic::ClockConsumer;
+use crate::clock::v2::pclk::DynPclkId;
 use crate::time::{Hertz, Nanoseconds};
 use void::Void;
 
@@ -28,6 +30,8 @@ use void::Void;
 pub struct TimerCounter<TC> {
     freq: Hertz,
     tc: TC,
+    /// Last started timeout, used to recompute the period when `freq` changes
+    timeout: Option<Nanoseconds>,
 }
 
 /// This is a helper trait to make it easier to make most of the
@@ -48,7 +52,9 @@ where
     where
         T: Into<Self::Time>,
     {
-        let params = TimerParams::new_us(timeout.into(), self.freq);
+        let timeout = timeout.into();
+        self.timeout = Some(timeout);
+        let params = TimerParams::new_us(timeout, self.freq);
         let divider = params.divider;
         let cycles = params.cycles;
         let count = self.tc.count_16();
@@ -130,7 +136,7 @@ where
 }
 
 macro_rules! tc {
-    ($($TYPE:ident: ($TC:ident, $mclk:ident, $clock:ident, $apmask:ident),)+) => {
+    ($($TYPE:ident: ($TC:ident, $mclk:ident, $clock:ident, $apmask:ident, $pclk:ident),)+) => {
         $(
 pub type $TYPE = TimerCounter<$TC>;
 
@@ -161,6 +167,23 @@ impl TimerCounter<$TC>
         Self {
             freq: clock.freq(),
             tc,
+            timeout: None,
+        }
+    }
+}
+
+impl ClockConsumer for TimerCounter<$TC> {
+    fn pclk_id(&self) -> DynPclkId {
+        DynPclkId::$pclk
+    }
+
+    /// Restart a running timer with a period recomputed for the new clock
+    /// frequency
+    fn set_clock_freq(&mut self, freq: Hertz) {
+        self.freq = freq;
+        let running = self.tc.count_16().ctrla.read().enable().bit_is_set();
+        if let (true, Some(timeout)) = (running, self.timeout) {
+            self.start(timeout);
         }
     }
 }
@@ -169,12 +192,12 @@ impl TimerCounter<$TC>
 }
 
 tc! {
-    TimerCounter2: (TC2, tc2_, Tc2Tc3Clock, apbbmask),
-    TimerCounter3: (TC3, tc3_, Tc2Tc3Clock, apbbmask),
+    TimerCounter2: (TC2, tc2_, Tc2Tc3Clock, apbbmask, Tc2Tc3),
+    TimerCounter3: (TC3, tc3_, Tc2Tc3Clock, apbbmask, Tc2Tc3),
 }
 
 #[cfg(all(feature = "has-tc4", feature = "has-tc5"))]
 tc! {
-    TimerCounter4: (TC4, tc4_, Tc4Tc5Clock, apbcmask),
-    TimerCounter5: (TC5, tc5_, Tc4Tc5Clock, apbcmask),
+    TimerCounter4: (TC4, tc4_, Tc4Tc5Clock, apbcmask, Tc4Tc5),
+    TimerCounter5: (TC5, tc5_, Tc4Tc5Clock, apbcmask, Tc4Tc5),
 }
@@ -1,4 +1,4 @@
-use crate::ehal::timer::{CountDown, Periodic};
+use crate::ehal_02::timer::{CountDown, Periodic};
 use crate::time;
 
 /// Trait for timers that can enable & disable an interrupt that fires